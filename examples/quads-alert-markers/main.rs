@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const ALERTS: usize = 40;
+const WARNINGS: usize = 400;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.02, 0.02, 0.04)))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-alert-markers",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_animations)
+        .run();
+}
+
+/// Alerts pulse and flash while their color cycles from red to orange, starting over every second
+fn alert_animation(start_time: f32) -> QuadsAnimation {
+    QuadsAnimation {
+        scale: Some(QuadCurve::new([(0.0, 1.0), (0.15, 1.6), (0.5, 1.0)]).unwrap()),
+        alpha: Some(QuadCurve::new([(0.0, 1.0), (0.5, 0.2), (1.0, 1.0)]).unwrap()),
+        hue_shift: Some(QuadCurve::new([(0.0, 0.0), (0.5, 30.0), (1.0, 0.0)]).unwrap()),
+        dissolve: None,
+        mode: QuadsAnimationMode::Loop,
+        start_time,
+    }
+}
+
+/// Warnings slowly fade in and out
+fn warning_animation(start_time: f32) -> QuadsAnimation {
+    QuadsAnimation {
+        alpha: Some(QuadCurve::new([(0.0, 0.1), (1.5, 1.0)]).unwrap()),
+        mode: QuadsAnimationMode::PingPong,
+        start_time,
+        ..default()
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 25.0, 40.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut position = || Vec3::new(rng.gen_range(-30.0..30.0), 0.0, rng.gen_range(-30.0..30.0));
+
+    // NOTE: With premultiplied alpha the fading markers darken towards the background, as the
+    // quads are not blended
+    let mut alerts = Quads {
+        alpha_mode: QuadsAlphaMode::PremultiplyOnUpload,
+        animation: Some(alert_animation(0.0)),
+        ..default()
+    };
+    for _ in 0..ALERTS {
+        alerts.insert(Quad {
+            color: Color::rgb(1.0, 0.1, 0.05),
+            center: position(),
+            half_extents: Vec3::splat(6.0),
+            billboard: Billboard::FixedScreenSize,
+            ..default()
+        });
+    }
+    commands.insert_resource(alerts);
+
+    // The warnings are generated on the GPU and animated independently of the alerts
+    commands.insert_resource(QuadsPoints {
+        points: (0..WARNINGS)
+            .map(|_| QuadPoint {
+                position: position(),
+                kind: 0,
+            })
+            .collect(),
+        kinds: vec![Quad {
+            color: Color::rgb(1.0, 0.8, 0.1),
+            half_extents: Vec3::splat(0.3),
+            billboard: Billboard::ViewY,
+            ..default()
+        }],
+        animation: Some(warning_animation(0.0)),
+        ..default()
+    });
+
+    info!("Press 1 to toggle the alert animation and 2 to toggle the warning animation");
+}
+
+fn toggle_animations(
+    keys: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut alerts: ResMut<Quads>,
+    mut warnings: ResMut<QuadsPoints>,
+) {
+    // NOTE: The shader animates against the wrapped elapsed time
+    let now = time.elapsed_seconds_wrapped();
+    if keys.just_pressed(KeyCode::Key1) {
+        alerts.animation = match alerts.animation {
+            Some(_) => None,
+            None => Some(alert_animation(now)),
+        };
+    }
+    if keys.just_pressed(KeyCode::Key2) {
+        warnings.animation = match warnings.animation {
+            Some(_) => None,
+            None => Some(warning_animation(now)),
+        };
+    }
+}
@@ -0,0 +1,188 @@
+use bevy::{
+    input::mouse::MouseWheel,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_vertex_pulling::prelude::*;
+use bevy_vertex_pulling::quads::{QuadsDepthOfField, QuadsDepthOfFieldMode};
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const TEXTURE_SIZE: u32 = 128;
+const LIGHTS: usize = 4_000;
+/// Nearest and farthest distance of the lights from the camera
+const DEPTH_RANGE: std::ops::Range<f32> = 1.0..80.0;
+/// Factor the focal distance changes by per scroll line
+const FOCUS_STEP: f32 = 1.1;
+
+#[derive(Resource)]
+struct BokehImages {
+    bokeh: Handle<Image>,
+    gaussian: Handle<Image>,
+}
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::BLACK))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-bokeh",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (pull_focus, change_lens))
+        .run();
+}
+
+/// A round sprite of `TEXTURE_SIZE` pixels, `intensity` maps the distance from the center, 1 at
+/// the edge of the inscribed circle, to the brightness
+fn round_image(intensity: impl Fn(f32) -> f32) -> Image {
+    let mut data = Vec::with_capacity((TEXTURE_SIZE * TEXTURE_SIZE * 4) as usize);
+    for y in 0..TEXTURE_SIZE {
+        for x in 0..TEXTURE_SIZE {
+            let p = (Vec2::new(x as f32, y as f32) + 0.5) / TEXTURE_SIZE as f32 * 2.0 - 1.0;
+            let value = (255.0 * intensity(p.length()).clamp(0.0, 1.0)) as u8;
+            data.extend_from_slice(&[value, value, value, 255]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: TEXTURE_SIZE,
+            height: TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.spawn((
+        Camera3dBundle::default(),
+        CameraController::default(),
+        QuadsDepthOfField {
+            aperture_f_stops: 0.5,
+            max_circle_of_confusion: 96.0,
+            ..default()
+        },
+    ));
+
+    // NOTE: A bokeh disc has a bright rim like the bokeh of a real lens. The Gaussian quad
+    // reaches out to three standard deviations.
+    let bokeh_images = BokehImages {
+        bokeh: images.add(round_image(|r| {
+            if r > 1.0 {
+                0.0
+            } else {
+                0.6 + 0.4 * r.powi(4)
+            }
+        })),
+        gaussian: images.add(round_image(|r| (-4.5 * r * r).exp())),
+    };
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut quads = Quads {
+        image: Some(bokeh_images.bokeh.clone()),
+        ..default()
+    };
+    quads.data.extend((0..LIGHTS).map(|_| {
+        // NOTE: Scattered evenly over the view frustum, so there are as many lights near as far
+        let depth = rng.gen_range(DEPTH_RANGE);
+        let spread = Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0));
+        Quad {
+            color: Color::hsl(rng.gen_range(20.0..60.0), 0.9, rng.gen_range(0.5..0.8)),
+            center: (spread * Vec2::new(0.75, 0.42) * depth).extend(-depth),
+            // NOTE: In pixels, the size of the light in focus
+            half_extents: Vec3::splat(rng.gen_range(1.5..3.0)),
+            billboard: Billboard::CocScaled,
+            ..default()
+        }
+    }));
+    commands.insert_resource(quads);
+    commands.insert_resource(bokeh_images);
+
+    info!("Scroll or press Up and Down to pull focus");
+    info!("Press [ and ] to close and open the aperture");
+    info!("Press M to switch between bokeh and Gaussian blur");
+    info!("Press F to toggle the depth of field");
+}
+
+fn pull_focus(
+    keys: Res<Input<KeyCode>>,
+    mut wheel: EventReader<MouseWheel>,
+    mut depths_of_field: Query<&mut QuadsDepthOfField>,
+) {
+    let mut steps: f32 = wheel.iter().map(|event| event.y.signum()).sum();
+    if keys.just_pressed(KeyCode::Up) {
+        steps += 1.0;
+    }
+    if keys.just_pressed(KeyCode::Down) {
+        steps -= 1.0;
+    }
+    if steps == 0.0 {
+        return;
+    }
+    for mut depth_of_field in &mut depths_of_field {
+        depth_of_field.focal_distance = (depth_of_field.focal_distance * FOCUS_STEP.powf(steps))
+            .clamp(DEPTH_RANGE.start, DEPTH_RANGE.end);
+        info!("Focused at {:.1}m", depth_of_field.focal_distance);
+    }
+}
+
+fn change_lens(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    bokeh_images: Res<BokehImages>,
+    mut quads: ResMut<Quads>,
+    mut cameras: Query<(Entity, Option<&mut QuadsDepthOfField>), With<Camera3d>>,
+    mut removed: Local<Option<QuadsDepthOfField>>,
+) {
+    let (camera, depth_of_field) = cameras.single_mut();
+    if keys.just_pressed(KeyCode::F) {
+        match depth_of_field {
+            Some(depth_of_field) => {
+                *removed = Some(depth_of_field.clone());
+                commands.entity(camera).remove::<QuadsDepthOfField>();
+                info!("Depth of field off, every light is in focus");
+            }
+            None => {
+                commands
+                    .entity(camera)
+                    .insert(removed.take().unwrap_or_default());
+                info!("Depth of field on");
+            }
+        }
+        return;
+    }
+    let Some(mut depth_of_field) = depth_of_field else {
+        return;
+    };
+    if keys.just_pressed(KeyCode::BracketLeft) {
+        depth_of_field.aperture_f_stops *= 2f32.sqrt();
+        info!("Aperture f/{:.2}", depth_of_field.aperture_f_stops);
+    }
+    if keys.just_pressed(KeyCode::BracketRight) {
+        depth_of_field.aperture_f_stops /= 2f32.sqrt();
+        info!("Aperture f/{:.2}", depth_of_field.aperture_f_stops);
+    }
+    if keys.just_pressed(KeyCode::M) {
+        let (mode, image) = match depth_of_field.mode {
+            QuadsDepthOfFieldMode::Bokeh => {
+                (QuadsDepthOfFieldMode::Gaussian, &bokeh_images.gaussian)
+            }
+            _ => (QuadsDepthOfFieldMode::Bokeh, &bokeh_images.bokeh),
+        };
+        depth_of_field.mode = mode;
+        quads.image = Some(image.clone());
+        info!("{mode:?} blur");
+    }
+}
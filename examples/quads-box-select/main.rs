@@ -0,0 +1,176 @@
+use bevy::{prelude::*, utils::HashSet, window::PrimaryWindow};
+use bevy_vertex_pulling::prelude::*;
+use bevy_vertex_pulling::quads::QuadsScreenIndex;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+/// Quads per side of the square grid
+const QUADS_PER_SIDE: i32 = 100;
+const SELECTED_COLOR: Color = Color::rgb(1.0, 0.9, 0.1);
+const HOVERED_COLOR: Color = Color::WHITE;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-box-select",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .init_resource::<QuadsScreenIndex>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, (select, update_selection_box).chain())
+        .run();
+}
+
+#[derive(Resource)]
+struct Selection {
+    /// Colors of the quads while they are neither selected nor hovered
+    colors: Vec<Color>,
+    selected: HashSet<usize>,
+    hovered: Option<usize>,
+    /// Cursor position the drag started at
+    drag_start: Option<Vec2>,
+}
+
+/// Outline of the box while dragging
+#[derive(Component)]
+struct SelectionBox;
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 40.0, 70.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController {
+            // NOTE: The left mouse button selects
+            key_enable_mouse: MouseButton::Right,
+            ..default()
+        });
+
+    let mut quads = Quads::default();
+    let half = QUADS_PER_SIDE / 2;
+    for z in -half..half {
+        for x in -half..half {
+            quads.data.push(Quad {
+                color: Color::hsl((x + half) as f32 * 360.0 / QUADS_PER_SIDE as f32, 0.6, 0.4),
+                center: Vec3::new(x as f32, 0.5, z as f32),
+                half_extents: Vec3::new(0.3, 0.4, 0.0),
+                billboard: Billboard::ViewY,
+                ..default()
+            });
+        }
+    }
+    commands.insert_resource(Selection {
+        colors: quads.data.iter().map(|quad| quad.color).collect(),
+        selected: HashSet::default(),
+        hovered: None,
+        drag_start: None,
+    });
+    commands.insert_resource(quads);
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                border: UiRect::all(Val::Px(1.0)),
+                display: Display::None,
+                ..default()
+            },
+            background_color: Color::rgba(1.0, 1.0, 1.0, 0.1).into(),
+            border_color: Color::WHITE.into(),
+            ..default()
+        },
+        SelectionBox,
+    ));
+
+    info!("Drag with the left mouse button to select quads, hold Shift to add to the selection");
+    info!("Hold the right mouse button to look around");
+}
+
+fn select(
+    mouse: Res<Input<MouseButton>>,
+    keys: Res<Input<KeyCode>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<Entity, With<Camera3d>>,
+    index: Res<QuadsScreenIndex>,
+    mut selection: ResMut<Selection>,
+    mut quads: ResMut<Quads>,
+) {
+    let camera = cameras.single();
+    let Some(cursor) = windows.single().cursor_position() else {
+        return;
+    };
+    let selection = &mut *selection;
+    let mut changed = Vec::new();
+
+    // NOTE: The closest quad under the cursor is the one with the largest reverse-Z depth
+    let hovered = index
+        .query_point(camera, cursor)
+        .max_by(|a, b| a.depth.total_cmp(&b.depth))
+        .map(|quad| quad.index);
+    if hovered != selection.hovered {
+        changed.extend(selection.hovered);
+        changed.extend(hovered);
+        selection.hovered = hovered;
+    }
+
+    if mouse.just_pressed(MouseButton::Left) {
+        selection.drag_start = Some(cursor);
+    }
+    if let Some(start) = selection.drag_start {
+        if mouse.just_released(MouseButton::Left) {
+            selection.drag_start = None;
+            let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+            if !shift {
+                changed.extend(selection.selected.drain());
+            }
+            for quad in index.query_rect(camera, Rect::from_corners(start, cursor)) {
+                selection.selected.insert(quad.index);
+                changed.push(quad.index);
+            }
+            info!("{} quads selected", selection.selected.len());
+        }
+    }
+
+    // NOTE: Only recolor when something changed, as any change uploads all quads
+    if changed.is_empty() {
+        return;
+    }
+    for i in changed {
+        quads.data[i].color = if Some(i) == selection.hovered {
+            HOVERED_COLOR
+        } else if selection.selected.contains(&i) {
+            SELECTED_COLOR
+        } else {
+            selection.colors[i]
+        };
+    }
+}
+
+fn update_selection_box(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    selection: Res<Selection>,
+    mut selection_box: Query<&mut Style, With<SelectionBox>>,
+) {
+    let mut style = selection_box.single_mut();
+    let (Some(start), Some(cursor)) = (selection.drag_start, windows.single().cursor_position())
+    else {
+        style.display = Display::None;
+        return;
+    };
+    let rect = Rect::from_corners(start, cursor);
+    style.display = Display::Flex;
+    style.left = Val::Px(rect.min.x);
+    style.top = Val::Px(rect.min.y);
+    style.width = Val::Px(rect.width());
+    style.height = Val::Px(rect.height());
+}
@@ -0,0 +1,163 @@
+use bevy::{core_pipeline::bloom::BloomSettings, prelude::*};
+use bevy_vertex_pulling::prelude::*;
+use bevy_vertex_pulling::quads::QuadsDissolve;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const ENEMIES: usize = 40;
+/// Seconds the marker of a killed enemy takes to burn away
+const BURN_SECONDS: f32 = 1.2;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.05, 0.05, 0.08)))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-burn-away",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (kill_enemy, burn_markers))
+        .run();
+}
+
+struct Enemy {
+    body: Entity,
+    marker: QuadId,
+    /// Time the enemy was killed at, its marker burns away from then on
+    killed_at: Option<f32>,
+}
+
+#[derive(Resource)]
+struct Enemies {
+    enemies: Vec<Enemy>,
+    rng: StdRng,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands
+        .spawn(Camera3dBundle {
+            camera: Camera {
+                hdr: true,
+                ..default()
+            },
+            transform: Transform::from_translation(Vec3::new(0.0, 15.0, 30.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert((CameraController::default(), BloomSettings::default()));
+
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(shape::Plane::from_size(60.0).into()),
+        material: materials.add(Color::rgb(0.2, 0.2, 0.25).into()),
+        ..default()
+    });
+    commands.insert_resource(AmbientLight {
+        color: Color::WHITE,
+        brightness: 1.0,
+    });
+
+    let body_mesh = meshes.add(shape::Cube { size: 1.0 }.into());
+    let body_material = materials.add(Color::rgb(0.5, 0.15, 0.1).into());
+    let mut rng = StdRng::seed_from_u64(5);
+    // NOTE: The markers burn away in a glowing orange edge, which blooms as its color is
+    // brighter than 1
+    let mut quads = Quads {
+        dissolve: Some(QuadsDissolve {
+            noise_scale: 0.5,
+            edge_width: 0.15,
+            edge_color: Color::rgb(8.0, 2.5, 0.4),
+            ..default()
+        }),
+        ..default()
+    };
+    let mut enemies = Vec::with_capacity(ENEMIES);
+    for i in 0..ENEMIES {
+        let position = Vec3::new(rng.gen_range(-25.0..25.0), 0.5, rng.gen_range(-25.0..25.0));
+        let body = commands
+            .spawn(PbrBundle {
+                mesh: body_mesh.clone(),
+                material: body_material.clone(),
+                transform: Transform::from_translation(position),
+                ..default()
+            })
+            .id();
+        let marker = quads.insert(Quad {
+            color: Color::rgb(1.0, 0.2, 0.1),
+            center: position + Vec3::Y * 1.5,
+            half_extents: Vec3::new(0.5, 0.5, 0.0),
+            billboard: Billboard::ViewY,
+            // NOTE: Different seeds burn away in different patterns
+            seed: i as u32,
+            ..default()
+        });
+        enemies.push(Enemy {
+            body,
+            marker,
+            killed_at: None,
+        });
+    }
+    commands.insert_resource(quads);
+    commands.insert_resource(Enemies { enemies, rng });
+
+    info!("Press Space to kill an enemy, its marker burns away");
+}
+
+fn kill_enemy(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut enemies: ResMut<Enemies>,
+) {
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+    let Enemies { enemies, rng } = &mut *enemies;
+    let alive = enemies
+        .iter()
+        .filter(|enemy| enemy.killed_at.is_none())
+        .count();
+    if alive == 0 {
+        info!("All enemies are dead");
+        return;
+    }
+    let enemy = enemies
+        .iter_mut()
+        .filter(|enemy| enemy.killed_at.is_none())
+        .nth(rng.gen_range(0..alive))
+        .unwrap();
+    commands.entity(enemy.body).despawn();
+    enemy.killed_at = Some(time.elapsed_seconds());
+}
+
+/// Raises the dissolve threshold of the markers of killed enemies and removes the markers that
+/// burnt away
+fn burn_markers(time: Res<Time>, mut enemies: ResMut<Enemies>, mut quads: ResMut<Quads>) {
+    let now = time.elapsed_seconds();
+    enemies.enemies.retain(|enemy| {
+        let Some(killed_at) = enemy.killed_at else {
+            return true;
+        };
+        let dissolve = (now - killed_at) / BURN_SECONDS;
+        if dissolve >= 1.0 {
+            quads.remove(enemy.marker);
+            return false;
+        }
+        if let Some(marker) = quads.get_mut(enemy.marker) {
+            marker.dissolve = dissolve;
+        }
+        true
+    });
+}
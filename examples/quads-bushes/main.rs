@@ -0,0 +1,109 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Number of bushes stamped across the terrain
+const BUSHES: usize = 100_000;
+/// Number of bushes removed per press of R
+const BUSHES_PER_REMOVAL: usize = 1000;
+/// Half the width of the square area covered by bushes
+const TERRAIN_HALF_SIZE: f32 = 300.0;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-bushes",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, remove_bushes)
+        .run();
+}
+
+/// The stamps in the order they were made
+#[derive(Resource)]
+struct Bushes(Vec<QuadStampId>);
+
+/// Rolling hills for the bushes to sit on
+fn terrain_height(x: f32, z: f32) -> f32 {
+    4.0 * (x * 0.02).sin() * (z * 0.03).cos() + 1.5 * (x * 0.07 + z * 0.05).sin()
+}
+
+/// A bush of twelve leaf clumps around its base, in local space with the base at the origin
+fn bush(rng: &mut impl Rng) -> QuadCluster {
+    QuadCluster {
+        quads: (0..12)
+            .map(|i| {
+                let angle = i as f32 / 12.0 * TAU;
+                let radius = rng.gen_range(0.1..0.5);
+                let height = rng.gen_range(0.3..1.0);
+                Quad {
+                    color: Color::hsl(rng.gen_range(90.0..140.0), 0.5, rng.gen_range(0.2..0.4)),
+                    center: Vec3::new(radius * angle.cos(), height, radius * angle.sin()),
+                    half_extents: Vec3::splat(rng.gen_range(0.2..0.35)),
+                    billboard: Billboard::WorldY,
+                    ..default()
+                }
+            })
+            .collect(),
+    }
+}
+
+fn setup(mut commands: Commands) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let cluster = bush(&mut rng);
+
+    let mut quads = Quads::default();
+    let stamps = (0..BUSHES)
+        .map(|_| {
+            let x = rng.gen_range(-TERRAIN_HALF_SIZE..TERRAIN_HALF_SIZE);
+            let z = rng.gen_range(-TERRAIN_HALF_SIZE..TERRAIN_HALF_SIZE);
+            let transform = Transform::from_translation(Vec3::new(x, terrain_height(x, z), z))
+                .with_rotation(Quat::from_rotation_y(rng.gen_range(0.0..TAU)))
+                .with_scale(Vec3::splat(rng.gen_range(0.7..1.3)));
+            quads.stamp(&cluster, &transform)
+        })
+        .collect();
+    info!(
+        "Stamped {} bushes of {} quads, {} quads in total",
+        BUSHES,
+        cluster.quads.len(),
+        quads.data.len()
+    );
+    info!("Press R to remove {} bushes", BUSHES_PER_REMOVAL);
+    commands.insert_resource(quads);
+    commands.insert_resource(Bushes(stamps));
+
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 12.0, 30.0))
+                .looking_at(Vec3::new(0.0, 0.0, -20.0), Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+}
+
+fn remove_bushes(keys: Res<Input<KeyCode>>, mut bushes: ResMut<Bushes>, mut quads: ResMut<Quads>) {
+    if !keys.just_pressed(KeyCode::R) {
+        return;
+    }
+    let start = bushes.0.len().saturating_sub(BUSHES_PER_REMOVAL);
+    let removed: usize = bushes
+        .0
+        .drain(start..)
+        .map(|stamp| quads.remove_stamp(stamp))
+        .sum();
+    info!("Removed {} quads, {} bushes left", removed, bushes.0.len());
+}
@@ -0,0 +1,127 @@
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const QUADS: usize = 100_000;
+/// Fraction of the quads poisoned every frame in chaos mode
+const POISONED_FRACTION: f64 = 0.01;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-chaos",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((
+            CameraControllerPlugin,
+            FrameTimeDiagnosticsPlugin,
+            LogDiagnosticsPlugin::default(),
+            // NOTE: Hides or fixes the poisoned quads, and logs them and records the
+            // invalid_quads diagnostic
+            QuadsPlugin {
+                sanitize: true,
+                ..default()
+            },
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (toggle_chaos, poison_quads).chain())
+        .run();
+}
+
+#[derive(Resource)]
+struct Chaos {
+    enabled: bool,
+    /// The quads as they were before they were poisoned
+    valid: Vec<Quad>,
+    /// Indices of the quads poisoned last frame
+    poisoned: Vec<usize>,
+    rng: StdRng,
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(60.0 * Vec3::Z).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut quads = Quads::default();
+    for _ in 0..QUADS {
+        quads.data.push(Quad {
+            color: Color::hsl(rng.gen_range(0.0..360.0), 0.7, 0.5),
+            center: Vec3::new(
+                rng.gen_range(-20.0..20.0),
+                rng.gen_range(-20.0..20.0),
+                rng.gen_range(-20.0..20.0),
+            ),
+            half_extents: Vec3::new(0.1, 0.1, 0.0),
+            billboard: Billboard::ViewY,
+            ..default()
+        });
+    }
+    commands.insert_resource(Chaos {
+        enabled: false,
+        valid: quads.data.clone(),
+        poisoned: Vec::new(),
+        rng,
+    });
+    commands.insert_resource(quads);
+
+    info!("Press Space to toggle chaos mode, which poisons 1% of the quads with NaNs and infinities every frame");
+}
+
+fn toggle_chaos(keys: Res<Input<KeyCode>>, mut chaos: ResMut<Chaos>) {
+    if keys.just_pressed(KeyCode::Space) {
+        chaos.enabled = !chaos.enabled;
+        info!("Chaos mode {}", if chaos.enabled { "on" } else { "off" });
+    }
+}
+
+/// Restores the quads poisoned last frame and poisons others, like a physics simulation blowing
+/// up in different places every frame
+fn poison_quads(mut chaos: ResMut<Chaos>, mut quads: ResMut<Quads>) {
+    let Chaos {
+        enabled,
+        valid,
+        poisoned,
+        rng,
+    } = &mut *chaos;
+    if !*enabled && poisoned.is_empty() {
+        return;
+    }
+    for i in poisoned.drain(..) {
+        quads.data[i] = valid[i].clone();
+    }
+    if !*enabled {
+        return;
+    }
+    let count = (QUADS as f64 * POISONED_FRACTION) as usize;
+    for _ in 0..count {
+        let i = rng.gen_range(0..QUADS);
+        let bad = [f32::NAN, f32::INFINITY, f32::NEG_INFINITY][rng.gen_range(0..3)];
+        let quad = &mut quads.data[i];
+        match rng.gen_range(0..4) {
+            // NOTE: Quads without a finite position or size are hidden
+            0 => quad.center.x = bad,
+            1 => quad.half_extents.y = bad,
+            // NOTE: Other non-finite values are replaced, so these quads stay visible
+            2 => quad.color = Color::rgba(bad, 0.5, 0.5, 1.0),
+            _ => quad.spin = bad,
+        }
+        poisoned.push(i);
+    }
+}
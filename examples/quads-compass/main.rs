@@ -0,0 +1,106 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{camera_controller, CameraController, CameraControllerPlugin};
+
+/// Number of compass ticks around the full circle
+const TICKS: usize = 36;
+/// Distance of the compass strip from the camera, further than the near plane but close enough
+/// that it would poke through walls without the foreground depth range
+const COMPASS_DISTANCE: f32 = 0.8;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-compass",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, follow_camera.after(camera_controller))
+        .run();
+}
+
+/// The compass ticks, the first one points north (-z)
+#[derive(Resource)]
+struct Compass {
+    ticks: Vec<QuadId>,
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 1.0, 4.0)),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    // A corridor of walls to walk into
+    let mut quads = Quads::default();
+    for i in 0..8 {
+        let z = -4.0 * i as f32;
+        let shade = if i % 2 == 0 { 0.5 } else { 0.4 };
+        quads.insert(Quad {
+            color: Color::rgb(shade, shade, shade + 0.1),
+            center: Vec3::new(0.0, 1.0, z - 2.0),
+            half_extents: Vec3::new(1.5, 1.5, 0.0),
+            billboard: Billboard::None,
+            ..default()
+        });
+    }
+
+    // The compass is a ring of ticks around the camera below eye level. Only the ticks in front
+    // of the camera are in view, forming a strip at the bottom of the screen.
+    let ticks = (0..TICKS)
+        .map(|i| {
+            let cardinal = i % (TICKS / 4) == 0;
+            quads.insert(Quad {
+                color: if i == 0 {
+                    Color::RED
+                } else if cardinal {
+                    Color::WHITE
+                } else {
+                    Color::GRAY
+                },
+                half_extents: if cardinal {
+                    Vec3::new(0.008, 0.03, 0.0)
+                } else {
+                    Vec3::new(0.004, 0.015, 0.0)
+                },
+                billboard: Billboard::ViewY,
+                foreground: true,
+                ..default()
+            })
+        })
+        .collect();
+
+    commands.insert_resource(quads);
+    commands.insert_resource(Compass { ticks });
+}
+
+fn follow_camera(
+    compass: Res<Compass>,
+    cameras: Query<&Transform, With<Camera>>,
+    mut quads: QuadsWriter,
+) {
+    let Ok(camera) = cameras.get_single() else {
+        return;
+    };
+    let below = camera.translation - 0.3 * Vec3::Y;
+    for (i, &id) in compass.ticks.iter().enumerate() {
+        let heading = i as f32 / TICKS as f32 * TAU;
+        let direction = Vec3::new(heading.sin(), 0.0, -heading.cos());
+        quads.update(id, |tick| {
+            tick.center = below + COMPASS_DISTANCE * direction
+        });
+    }
+}
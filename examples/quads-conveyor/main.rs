@@ -0,0 +1,108 @@
+use std::f32::consts::TAU;
+
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+/// Size of the tileable texture, a multiple of the chevron period
+const TEXTURE_SIZE: u32 = 64;
+/// Width of one chevron stripe in texels
+const CHEVRON_WIDTH: u32 = 8;
+const BELTS: usize = 5;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.05, 0.05, 0.08)))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-conveyor",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+/// Chevrons pointing along +u over smooth noise, tileable in both directions so the scrolling
+/// texture wraps without seams
+fn conveyor_image() -> Image {
+    let mut data = Vec::with_capacity((TEXTURE_SIZE * TEXTURE_SIZE * 4) as usize);
+    for y in 0..TEXTURE_SIZE {
+        for x in 0..TEXTURE_SIZE {
+            let chevron = (x + TEXTURE_SIZE - (y as i32 - TEXTURE_SIZE as i32 / 2).unsigned_abs())
+                / CHEVRON_WIDTH
+                % 2;
+            // Whole periods across the texture keep the noise tileable
+            let (u, v) = (
+                x as f32 / TEXTURE_SIZE as f32 * TAU,
+                y as f32 / TEXTURE_SIZE as f32 * TAU,
+            );
+            let noise = 0.5 + 0.25 * ((3.0 * u + v).sin() + (2.0 * v - 5.0 * u).cos());
+            let value = 0.35 * chevron as f32 + 0.65 * noise;
+            let value = (255.0 * value.clamp(0.0, 1.0)) as u8;
+            data.extend_from_slice(&[value, value, value, 255]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: TEXTURE_SIZE,
+            height: TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 2.0, 14.0))
+                .looking_at(Vec3::new(0.0, 2.0, 0.0), Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    let mut quads = Quads {
+        image: Some(images.add(conveyor_image())),
+        ..default()
+    };
+
+    // Long belts with the texture repeated along them, alternating direction and speed
+    for i in 0..BELTS {
+        let direction = if i % 2 == 0 { 1.0 } else { -1.0 };
+        quads.insert(Quad {
+            color: Color::rgb(0.9, 0.8, 0.5),
+            center: Vec3::new(0.0, i as f32 * 1.2, -2.0),
+            half_extents: Vec3::new(8.0, 0.4, 0.0),
+            billboard: Billboard::None,
+            uv_tile: Vec2::new(10.0, 1.0),
+            uv_scroll: Vec2::new(-direction * (0.5 + 0.25 * i as f32), 0.0),
+            ..default()
+        });
+    }
+
+    // An energy shield drifting diagonally in front of the belts, with the texture tiled finer
+    quads.insert(Quad {
+        color: Color::rgb(0.3, 0.8, 1.0),
+        center: Vec3::new(3.0, 2.5, 1.0),
+        half_extents: Vec3::splat(2.5),
+        billboard: Billboard::ViewY,
+        uv_tile: Vec2::splat(3.0),
+        uv_scroll: Vec2::new(0.1, 0.25),
+        ..default()
+    });
+
+    commands.insert_resource(quads);
+}
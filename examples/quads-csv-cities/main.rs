@@ -0,0 +1,156 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use bevy::prelude::*;
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Number of cities in the generated table
+const CITIES: usize = 5_000_000;
+/// Number of countries the generated cities are clustered in
+const COUNTRIES: usize = 200;
+/// Smallest population of a generated city
+const MIN_POPULATION: f32 = 1_000.0;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.02, 0.03, 0.06)))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-csv-cities",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, highlight_country)
+        .run();
+}
+
+/// Writes a table of cities clustered around random country centers, with a few large and many
+/// small ones, unless it was generated before
+fn generate_cities(path: &Path) -> std::io::Result<()> {
+    if path.exists() {
+        return Ok(());
+    }
+    info!("Generating {CITIES} cities into {}", path.display());
+    let mut rng = StdRng::seed_from_u64(5);
+    let countries: Vec<Vec2> = (0..COUNTRIES)
+        .map(|_| Vec2::new(rng.gen_range(-170.0..170.0), rng.gen_range(-50.0..65.0)))
+        .collect();
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "lon,lat,population,radius,country")?;
+    for _ in 0..CITIES {
+        let country = rng.gen_range(0..COUNTRIES);
+        // NOTE: The sum of uniform offsets crowds the cities toward the center of their country
+        let offset = Vec2::new(
+            rng.gen_range(-6.0..6.0) + rng.gen_range(-6.0..6.0),
+            rng.gen_range(-4.0..4.0) + rng.gen_range(-4.0..4.0),
+        );
+        let position = countries[country] + offset;
+        // A Pareto distribution, so most cities are small
+        let population = MIN_POPULATION / rng.gen_range(1e-6f32..1.0).powf(0.9);
+        let radius = (population / MIN_POPULATION).sqrt();
+        writeln!(
+            writer,
+            "{:.4},{:.4},{:.0},{radius:.3},C{country:03}",
+            position.x, position.y, population
+        )?;
+    }
+    writer.flush()
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 10.0, 300.0))
+                .looking_at(Vec3::new(0.0, 10.0, 0.0), Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    // NOTE: Pass the path of a CSV file with the same columns to load it instead
+    let path = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            let path = std::env::temp_dir().join("bevy_vertex_pulling_cities.csv");
+            generate_cities(&path).expect("failed to generate the cities");
+            path
+        });
+
+    // Longitude and latitude in degrees on the xy plane, colored by a logarithmic population
+    // ramp. The cities are sized by their radius column but never smaller than a pixel or two
+    // on screen, so the small ones don't vanish when zoomed out.
+    let mapping = QuadsColumnMapping {
+        x: "lon".to_string(),
+        y: "lat".to_string(),
+        z: None,
+        value: Some("population".to_string()),
+        ramp: vec![
+            Color::rgb(0.1, 0.2, 0.6),
+            Color::rgb(0.2, 0.7, 0.9),
+            Color::rgb(1.0, 0.9, 0.4),
+            Color::rgb(1.0, 0.3, 0.1),
+        ],
+        value_range: MIN_POPULATION..1e7,
+        value_scale: QuadsValueScale::Logarithmic,
+        size: Some("radius".to_string()),
+        category: Some("country".to_string()),
+        quad: Quad {
+            half_extents: Vec3::new(0.01, 0.01, 0.0),
+            billboard: Billboard::ViewYMinScreenSize {
+                min_half_extent: 1.0,
+            },
+            ..default()
+        },
+    };
+    let start = Instant::now();
+    let file = File::open(&path).expect("failed to open the cities");
+    let quads = match Quads::from_csv(BufReader::new(file), &mapping) {
+        Ok(quads) => quads,
+        Err(err) => {
+            error!("Failed to import {}: {err}", path.display());
+            Quads::default()
+        }
+    };
+    info!(
+        "Imported {} cities in {:.2}s",
+        quads.data.len(),
+        start.elapsed().as_secs_f32()
+    );
+    commands.insert_resource(quads);
+
+    info!("Press Space to highlight a random country");
+}
+
+fn highlight_country(
+    keys: Res<Input<KeyCode>>,
+    mut recolors: EventWriter<QuadGroupRecolor>,
+    mut highlighted: Local<Option<u8>>,
+) {
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+    if let Some(group) = highlighted.take() {
+        recolors.send(QuadGroupRecolor { group, color: None });
+    }
+    // NOTE: Countries are numbered from 1 in the order they first appear in the table
+    let group = rand::thread_rng().gen_range(1..=COUNTRIES as u8);
+    recolors.send(QuadGroupRecolor {
+        group,
+        color: Some(Color::WHITE),
+    });
+    *highlighted = Some(group);
+}
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use bevy_vertex_pulling::prelude::*;
+use bevy_vertex_pulling::quads::{QuadsDeclutter, QuadsScreenIndex};
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const MARKERS: usize = 50_000;
+/// Half the side of the square the markers are scattered over, in world units
+const MAP_HALF_SIZE: f32 = 2000.0;
+/// Highest marker priority, for capitals. Every priority below has about four times as many
+/// markers as the one above it.
+const MAX_PRIORITY: u16 = 6;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.05, 0.07, 0.1)))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-declutter",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .init_resource::<QuadsScreenIndex>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_declutter)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    let camera = commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 600.0, 600.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController {
+            walk_speed: 200.0,
+            run_speed: 1000.0,
+            ..default()
+        })
+        .id();
+    commands.insert_resource(QuadsDeclutter::new(camera));
+
+    // NOTE: Markers stand in for the labels of places, the few large cities have the highest
+    // priority and bigger, brighter markers
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut quads = Quads::default();
+    for _ in 0..MARKERS {
+        let mut priority = 1;
+        while priority < MAX_PRIORITY && rng.gen_bool(0.25) {
+            priority += 1;
+        }
+        let importance = priority as f32 / MAX_PRIORITY as f32;
+        quads.data.push(Quad {
+            color: Color::hsl(200.0 - 160.0 * importance, 0.8, 0.3 + 0.4 * importance),
+            center: Vec3::new(
+                rng.gen_range(-MAP_HALF_SIZE..MAP_HALF_SIZE),
+                0.0,
+                rng.gen_range(-MAP_HALF_SIZE..MAP_HALF_SIZE),
+            ),
+            // NOTE: In pixels, shaped like a line of text
+            half_extents: Vec3::new(20.0 + 30.0 * importance, 5.0 + 4.0 * importance, 0.0),
+            billboard: Billboard::FixedScreenSize,
+            priority,
+            ..default()
+        });
+    }
+    commands.insert_resource(quads);
+
+    info!("Fly up to zoom out, only the most important markers stay visible");
+    info!("Press Space to toggle decluttering");
+}
+
+fn toggle_declutter(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    declutter: Option<Res<QuadsDeclutter>>,
+    cameras: Query<Entity, With<Camera3d>>,
+    mut quads: ResMut<Quads>,
+) {
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+    if let Some(declutter) = declutter {
+        info!(
+            "Decluttering off, it kept {} and hid {} markers on screen",
+            declutter.kept(),
+            declutter.hidden()
+        );
+        commands.remove_resource::<QuadsDeclutter>();
+        for quad in &mut quads.data {
+            quad.hidden = false;
+        }
+    } else {
+        info!("Decluttering on");
+        commands.insert_resource(QuadsDeclutter::new(cameras.single()));
+    }
+}
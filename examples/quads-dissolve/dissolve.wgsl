@@ -0,0 +1,39 @@
+#import bevy_vertex_pulling::quads::types FragmentInput, FragmentOutput
+#import bevy_vertex_pulling::quads::bindings quads_texture, quads_sampler
+#import bevy_vertex_pulling::quads::billboard camera_origin, position_to_view
+#import bevy_vertex_pulling::quads::shading texture_uv, wrap_texture_uv, finish_color, fragment_output
+
+// Quads dissolve between these distances from the camera, so they never block the view up close
+const DISSOLVE_START: f32 = 12.0;
+const DISSOLVE_END: f32 = 4.0;
+// Width of the glowing edge in noise values
+const EDGE_WIDTH: f32 = 0.06;
+
+fn hash(cell: vec2<f32>) -> f32 {
+    return fract(sin(dot(cell, vec2<f32>(12.9898, 78.233))) * 43758.5453);
+}
+
+// Value noise from 0 to 1
+fn noise(p: vec2<f32>) -> f32 {
+    let i = floor(p);
+    let f = smoothstep(vec2<f32>(0.0), vec2<f32>(1.0), fract(p));
+    let bottom = mix(hash(i), hash(i + vec2<f32>(1.0, 0.0)), f.x);
+    let top = mix(hash(i + vec2<f32>(0.0, 1.0)), hash(i + vec2<f32>(1.0, 1.0)), f.x);
+    return mix(bottom, top, f.y);
+}
+
+@fragment
+fn fragment(in: FragmentInput) -> FragmentOutput {
+    let uv = texture_uv(in);
+    var color = in.color * textureSampleGrad(quads_texture, quads_sampler, wrap_texture_uv(in.flags, uv), dpdx(uv), dpdy(uv));
+    let distance = -position_to_view(in.world_position.xyz - camera_origin()).z;
+    let dissolved = smoothstep(DISSOLVE_START, DISSOLVE_END, distance);
+    let value = noise(in.uv * 6.0);
+    if value < dissolved {
+        discard;
+    }
+    if value < dissolved + EDGE_WIDTH && dissolved > 0.0 {
+        color = vec4<f32>(1.0, 0.55, 0.1, color.a);
+    }
+    return fragment_output(in, finish_color(in, color));
+}
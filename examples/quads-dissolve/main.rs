@@ -0,0 +1,88 @@
+use bevy::{asset::load_internal_asset, prelude::*, reflect::TypeUuid};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const BUSHES: usize = 5_000;
+/// Half the size of the square the bushes grow on
+const FIELD_HALF_SIZE: f32 = 40.0;
+
+/// Fragment shader importing the quads' modules, which dissolves the quads close to the camera
+const DISSOLVE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3829104756201938471);
+
+fn main() {
+    let mut app = App::new();
+    app.insert_resource(ClearColor(Color::rgb(0.55, 0.7, 0.9)))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-dissolve",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((
+            CameraControllerPlugin,
+            QuadsPlugin {
+                fragment_shader: Some(DISSOLVE_SHADER_HANDLE.typed()),
+                ..default()
+            },
+        ))
+        .add_systems(Startup, setup);
+    load_internal_asset!(
+        app,
+        DISSOLVE_SHADER_HANDLE,
+        "dissolve.wgsl",
+        Shader::from_wgsl
+    );
+    app.run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 2.0, 30.0))
+                .looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(shape::Plane::from_size(2.0 * FIELD_HALF_SIZE).into()),
+        material: materials.add(Color::rgb(0.3, 0.25, 0.15).into()),
+        ..default()
+    });
+    commands.insert_resource(AmbientLight {
+        color: Color::WHITE,
+        brightness: 1.0,
+    });
+
+    let mut rng = StdRng::seed_from_u64(11);
+    let mut quads = Quads::default();
+    for _ in 0..BUSHES {
+        let half_height = rng.gen_range(0.5..1.5);
+        quads.insert(Quad {
+            color: Color::hsl(rng.gen_range(70.0..140.0), 0.6, rng.gen_range(0.25..0.45)),
+            center: Vec3::new(
+                rng.gen_range(-FIELD_HALF_SIZE..FIELD_HALF_SIZE),
+                half_height,
+                rng.gen_range(-FIELD_HALF_SIZE..FIELD_HALF_SIZE),
+            ),
+            half_extents: Vec3::new(half_height * rng.gen_range(0.6..1.2), half_height, 0.0),
+            billboard: Billboard::WorldY,
+            ..default()
+        });
+    }
+    commands.insert_resource(quads);
+
+    info!("Fly through the bushes, the ones close to the camera dissolve out of the way");
+}
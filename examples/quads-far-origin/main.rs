@@ -0,0 +1,75 @@
+use std::f32::consts::TAU;
+
+use bevy::{math::DVec3, prelude::*};
+use bevy_vertex_pulling::prelude::*;
+use bevy_vertex_pulling::quads::QuadsOrigin;
+
+/// Where the scene is placed, far enough from the world origin that single precision view
+/// transforms make the quads jitter visibly
+const FAR_AWAY: DVec3 = DVec3::new(1e6, 0.0, 1e6);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-far-origin",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins(QuadsPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (look_around, toggle_camera_relative))
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // The quad centers are relative to the origin, so they keep full precision
+    commands.insert_resource(QuadsOrigin {
+        translation: FAR_AWAY,
+        camera_relative: true,
+    });
+
+    let mut quads = Quads::default();
+    for x in -10..=10i32 {
+        for z in -10..=10 {
+            quads.insert(Quad {
+                color: Color::hsl((x * 10 + z * 5).rem_euclid(360) as f32, 0.6, 0.6),
+                center: Vec3::new(x as f32, 0.0, z as f32),
+                half_extents: Vec3::new(0.05, 0.3, 0.0),
+                billboard: Billboard::WorldY,
+                ..default()
+            });
+        }
+    }
+    commands.insert_resource(quads);
+
+    // The camera sits at the same spot, its own single precision translation only has to be
+    // accurate to a few centimeters
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_translation(FAR_AWAY.as_vec3() + Vec3::new(0.0, 1.5, 12.0)),
+        ..default()
+    });
+
+    info!("Press C to toggle camera-relative rendering, the quads jitter without it");
+}
+
+/// Slowly turns the camera from side to side, the jitter shows while the view changes
+fn look_around(time: Res<Time>, mut cameras: Query<&mut Transform, With<Camera>>) {
+    let yaw = 0.3 * (time.elapsed_seconds() / 8.0 * TAU).sin();
+    for mut transform in &mut cameras {
+        transform.rotation = Quat::from_euler(EulerRot::YXZ, yaw, -0.15, 0.0);
+    }
+}
+
+fn toggle_camera_relative(keys: Res<Input<KeyCode>>, mut origin: ResMut<QuadsOrigin>) {
+    if keys.just_pressed(KeyCode::C) {
+        origin.camera_relative = !origin.camera_relative;
+        info!("Camera-relative rendering: {}", origin.camera_relative);
+    }
+}
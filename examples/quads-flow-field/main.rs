@@ -0,0 +1,165 @@
+use std::f32::consts::TAU;
+
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_vertex_pulling::prelude::*;
+use bevy_vertex_pulling::quads::QuadsFlowField;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+const GLOW_TEXTURE_SIZE: u32 = 64;
+/// Texels per side of the cubic flow field
+const FIELD_SIZE: u32 = 64;
+/// Glows per side of the cubic grid
+const GRID_SIZE: i32 = 30;
+/// Distance between neighbouring glows in world units
+const GRID_SPACING: f32 = 1.0;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::BLACK))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-flow-field",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((
+            CameraControllerPlugin,
+            FrameTimeDiagnosticsPlugin,
+            LogDiagnosticsPlugin::default(),
+            QuadsPlugin::default(),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_flow_field)
+        .run();
+}
+
+/// A soft round glow fading to black at the edge of the inscribed circle
+fn glow_image() -> Image {
+    let mut data = Vec::with_capacity((GLOW_TEXTURE_SIZE * GLOW_TEXTURE_SIZE * 4) as usize);
+    for y in 0..GLOW_TEXTURE_SIZE {
+        for x in 0..GLOW_TEXTURE_SIZE {
+            let p = (Vec2::new(x as f32, y as f32) + 0.5) / GLOW_TEXTURE_SIZE as f32 * 2.0 - 1.0;
+            let intensity = (1.0 - p.length()).max(0.0).powi(2);
+            let value = (255.0 * intensity) as u8;
+            data.extend_from_slice(&[value, value, value, 255]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: GLOW_TEXTURE_SIZE,
+            height: GLOW_TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+/// Divergence-free curl noise, the curl of a vector potential made of sine waves with whole
+/// periods across the texture so the field tiles seamlessly
+fn curl_noise_image() -> Image {
+    let mut data = Vec::with_capacity((FIELD_SIZE * FIELD_SIZE * FIELD_SIZE * 4) as usize);
+    for z in 0..FIELD_SIZE {
+        for y in 0..FIELD_SIZE {
+            for x in 0..FIELD_SIZE {
+                let p = (Vec3::new(x as f32, y as f32, z as f32) + 0.5) / FIELD_SIZE as f32 * TAU;
+                // The potential is (sin(2y + z), sin(2z + x), sin(2x + y)), its curl is scaled
+                // by a third to keep the vectors within -1 to 1
+                let (a, b, c) = (
+                    (2.0 * p.x + p.y).cos(),
+                    (2.0 * p.z + p.x).cos(),
+                    (2.0 * p.y + p.z).cos(),
+                );
+                let curl = Vec3::new(a - 2.0 * b, c - 2.0 * a, b - 2.0 * c) / 3.0;
+                let snorm = |v: f32| (v * 127.0).round() as i8 as u8;
+                data.extend_from_slice(&[snorm(curl.x), snorm(curl.y), snorm(curl.z), 0]);
+            }
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: FIELD_SIZE,
+            height: FIELD_SIZE,
+            depth_or_array_layers: FIELD_SIZE,
+        },
+        TextureDimension::D3,
+        data,
+        TextureFormat::Rgba8Snorm,
+    )
+}
+
+#[derive(Resource)]
+struct FlowField(QuadsFlowField);
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 10.0, 50.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    let mut quads = Quads {
+        image: Some(images.add(glow_image())),
+        ..default()
+    };
+    let half = GRID_SIZE / 2;
+    for z in -half..half {
+        for y in -half..half {
+            for x in -half..half {
+                let position = Vec3::new(x as f32, y as f32, z as f32);
+                quads.insert(Quad {
+                    color: Color::hsl(200.0 + 4.0 * (x + half) as f32, 0.8, 0.6),
+                    center: position * GRID_SPACING,
+                    half_extents: Vec3::splat(0.3),
+                    billboard: Billboard::ViewY,
+                    ..default()
+                });
+            }
+        }
+    }
+
+    // The field repeats every 40 world units and drifts slowly, each glow swirls around its grid
+    // position by up to 3 units
+    let flow_field = QuadsFlowField {
+        image: images.add(curl_noise_image()),
+        strength: 3.0,
+        scale: 1.0 / 40.0,
+        scroll: Vec3::new(0.02, 0.03, 0.01),
+    };
+    quads
+        .set_flow_field(flow_field.clone(), &images)
+        .expect("the curl noise is a valid flow field");
+    commands.insert_resource(quads);
+    commands.insert_resource(FlowField(flow_field));
+
+    info!("Press F to toggle the flow field");
+}
+
+fn toggle_flow_field(
+    keys: Res<Input<KeyCode>>,
+    images: Res<Assets<Image>>,
+    flow_field: Res<FlowField>,
+    mut quads: ResMut<Quads>,
+) {
+    if !keys.just_pressed(KeyCode::F) {
+        return;
+    }
+    if quads.flow_field.is_some() {
+        quads.flow_field = None;
+    } else if let Err(err) = quads.set_flow_field(flow_field.0.clone(), &images) {
+        error!("{err}");
+    }
+}
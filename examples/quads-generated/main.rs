@@ -0,0 +1,101 @@
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::Rng;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads generated on the GPU",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((
+            CameraControllerPlugin,
+            FrameTimeDiagnosticsPlugin,
+            LogDiagnosticsPlugin::default(),
+            QuadsPlugin::default(),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(50.0 * Vec3::Z).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    // Regular CPU-side quads marking the positive x, y and z axes
+    let mut quads = Quads::default();
+    for (axis, color) in [
+        (Vec3::X, Color::RED),
+        (Vec3::Y, Color::GREEN),
+        (Vec3::Z, Color::BLUE),
+    ] {
+        quads.data.push(Quad {
+            color,
+            center: 12.0 * axis,
+            half_extents: 0.5 * Vec3::ONE,
+            billboard: Billboard::ViewY,
+            ..default()
+        });
+    }
+    commands.insert_resource(quads);
+
+    // Only the positions and kinds of the points are generated on the CPU, the quads are expanded
+    // from the kinds on the GPU
+    let kinds = vec![
+        Quad {
+            color: Color::ORANGE,
+            half_extents: 0.02 * Vec3::ONE,
+            billboard: Billboard::ViewY,
+            ..default()
+        },
+        Quad {
+            color: Color::CYAN,
+            half_extents: 0.01 * Vec3::ONE,
+            billboard: Billboard::ViewY,
+            ..default()
+        },
+        Quad {
+            color: Color::WHITE,
+            half_extents: 1.5 * Vec3::ONE,
+            billboard: Billboard::FixedScreenSize,
+            ..default()
+        },
+    ];
+    let n_points = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse::<usize>().ok())
+        .unwrap_or(1_000_000);
+    info!("Generating {} points", n_points);
+    let mut rng = rand::thread_rng();
+    let points = (0..n_points)
+        .map(|_| QuadPoint {
+            position: Vec3::new(
+                rng.gen_range(-10.0..10.0),
+                rng.gen_range(-10.0..10.0),
+                rng.gen_range(-10.0..10.0),
+            ),
+            kind: rng.gen_range(0..kinds.len() as u32),
+        })
+        .collect();
+    commands.insert_resource(QuadsPoints {
+        points,
+        kinds,
+        ..default()
+    });
+}
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.05, 0.05, 0.08)))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-glass",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        // NOTE: Without `before_transparent` the quads behind the glass would be drawn over it
+        .add_plugins((
+            CameraControllerPlugin,
+            QuadsPlugin {
+                before_transparent: true,
+                ..default()
+            },
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 3.0, 14.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    // Tinted glass panes, blended over whatever was drawn before the transparent pass
+    let pane = meshes.add(shape::Box::new(4.0, 4.0, 0.05).into());
+    for (x, color) in [
+        (-4.5, Color::rgba(1.0, 0.2, 0.2, 0.4)),
+        (0.0, Color::rgba(0.2, 1.0, 0.2, 0.4)),
+        (4.5, Color::rgba(0.2, 0.4, 1.0, 0.4)),
+    ] {
+        commands.spawn(PbrBundle {
+            mesh: pane.clone(),
+            material: materials.add(StandardMaterial {
+                base_color: color,
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                ..default()
+            }),
+            transform: Transform::from_xyz(x, 0.0, 0.0),
+            ..default()
+        });
+    }
+
+    // A row of quads behind the panes, tinted by the glass, and a row in front of them, hiding it
+    let mut quads = Quads::default();
+    for (z, y) in [(-2.0, 0.8), (2.0, -0.8)] {
+        for i in 0..24 {
+            quads.insert(Quad {
+                color: Color::hsl(i as f32 * 15.0, 0.7, 0.7),
+                center: Vec3::new(-7.0 + i as f32 * 0.6, y, z),
+                half_extents: Vec3::splat(0.25),
+                billboard: Billboard::ViewY,
+                ..default()
+            });
+        }
+    }
+    commands.insert_resource(quads);
+}
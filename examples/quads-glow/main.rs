@@ -0,0 +1,118 @@
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const TEXTURE_SIZE: u32 = 128;
+/// Large glows overlapping many times over, so drawing them is bound by fill rate
+const GLOWS: usize = 20_000;
+/// Half the width of the cube the glows are scattered in
+const FIELD_HALF_SIZE: f32 = 20.0;
+/// Corner counts cycled through with C
+const CORNER_COUNTS: [u32; 3] = [4, 8, 16];
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::BLACK))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-glow",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((
+            CameraControllerPlugin,
+            FrameTimeDiagnosticsPlugin,
+            LogDiagnosticsPlugin::default(),
+            QuadsPlugin::default(),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (cycle_corners, toggle_overdraw))
+        .run();
+}
+
+/// A soft round glow fading to black at the edge of the inscribed circle, so the corners of the
+/// quad are fully black
+fn glow_image() -> Image {
+    let mut data = Vec::with_capacity((TEXTURE_SIZE * TEXTURE_SIZE * 4) as usize);
+    for y in 0..TEXTURE_SIZE {
+        for x in 0..TEXTURE_SIZE {
+            let p = (Vec2::new(x as f32, y as f32) + 0.5) / TEXTURE_SIZE as f32 * 2.0 - 1.0;
+            let intensity = (1.0 - p.length()).max(0.0).powi(2);
+            let value = (255.0 * intensity) as u8;
+            data.extend_from_slice(&[value, value, value, 255]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: TEXTURE_SIZE,
+            height: TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 0.0, 45.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut quads = Quads {
+        image: Some(images.add(glow_image())),
+        ..default()
+    };
+    quads.data.extend((0..GLOWS).map(|_| Quad {
+        color: Color::hsl(rng.gen_range(0.0..360.0), 0.8, 0.6),
+        center: Vec3::new(
+            rng.gen_range(-FIELD_HALF_SIZE..FIELD_HALF_SIZE),
+            rng.gen_range(-FIELD_HALF_SIZE..FIELD_HALF_SIZE),
+            rng.gen_range(-FIELD_HALF_SIZE..FIELD_HALF_SIZE),
+        ),
+        half_extents: Vec3::splat(rng.gen_range(2.0..5.0)),
+        billboard: Billboard::ViewY,
+        ..default()
+    }));
+    commands.insert_resource(quads);
+
+    info!("Press C to cycle the corners per glow through {CORNER_COUNTS:?}");
+    info!("Press O to toggle the overdraw view");
+}
+
+fn cycle_corners(keys: Res<Input<KeyCode>>, mut quads: ResMut<Quads>) {
+    if !keys.just_pressed(KeyCode::C) {
+        return;
+    }
+    let current = CORNER_COUNTS
+        .iter()
+        .position(|&corners| corners == quads.corner_count)
+        .unwrap_or(0);
+    quads.corner_count = CORNER_COUNTS[(current + 1) % CORNER_COUNTS.len()];
+    info!("Drawing the glows with {} corners", quads.corner_count);
+}
+
+fn toggle_overdraw(keys: Res<Input<KeyCode>>, mut debug_view: ResMut<QuadsDebugView>) {
+    if keys.just_pressed(KeyCode::O) {
+        *debug_view = match *debug_view {
+            QuadsDebugView::Overdraw => QuadsDebugView::Off,
+            _ => QuadsDebugView::Overdraw,
+        };
+    }
+}
@@ -0,0 +1,135 @@
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const TEXTURE_SIZE: u32 = 128;
+/// Large puffs overlapping many times over, so drawing them is bound by fill rate
+const PUFFS: usize = 30_000;
+/// Half the width of the column of smoke
+const SMOKE_HALF_WIDTH: f32 = 8.0;
+const SMOKE_HEIGHT: f32 = 40.0;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.35, 0.45, 0.6)))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-half-res",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((
+            CameraControllerPlugin,
+            FrameTimeDiagnosticsPlugin,
+            LogDiagnosticsPlugin::default(),
+            QuadsPlugin::default(),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_resolution)
+        .run();
+}
+
+/// A soft round puff, brightest in the middle
+fn puff_image() -> Image {
+    let mut data = Vec::with_capacity((TEXTURE_SIZE * TEXTURE_SIZE * 4) as usize);
+    for y in 0..TEXTURE_SIZE {
+        for x in 0..TEXTURE_SIZE {
+            let p = (Vec2::new(x as f32, y as f32) + 0.5) / TEXTURE_SIZE as f32 * 2.0 - 1.0;
+            let intensity = 0.6 + 0.4 * (1.0 - p.length()).max(0.0);
+            let value = (255.0 * intensity) as u8;
+            data.extend_from_slice(&[value, value, value, 255]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: TEXTURE_SIZE,
+            height: TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+fn setup(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 15.0, 45.0))
+                .looking_at(Vec3::new(0.0, 15.0, 0.0), Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    // Pillars in and around the smoke, cutting into it with full resolution edges
+    let pillar = meshes.add(shape::Box::new(1.5, SMOKE_HEIGHT, 1.5).into());
+    let material = materials.add(Color::rgb(0.2, 0.2, 0.25).into());
+    for x in [-10.0, -3.0, 4.0, 11.0] {
+        commands.spawn(PbrBundle {
+            mesh: pillar.clone(),
+            material: material.clone(),
+            transform: Transform::from_xyz(x, SMOKE_HEIGHT / 2.0, x * 0.3),
+            ..default()
+        });
+    }
+    commands.insert_resource(AmbientLight {
+        color: Color::WHITE,
+        brightness: 1.0,
+    });
+
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut quads = Quads {
+        image: Some(images.add(puff_image())),
+        corner_count: 8,
+        resolution: QuadsResolution::Half,
+        ..default()
+    };
+    quads.data.extend((0..PUFFS).map(|_| {
+        let height = rng.gen_range(0.0..SMOKE_HEIGHT);
+        // The column widens as the smoke rises
+        let spread = SMOKE_HALF_WIDTH * (0.3 + height / SMOKE_HEIGHT);
+        let grey = rng.gen_range(0.5..0.8);
+        Quad {
+            color: Color::rgb(grey, grey, grey),
+            center: Vec3::new(
+                rng.gen_range(-spread..spread),
+                height,
+                rng.gen_range(-spread..spread),
+            ),
+            half_extents: Vec3::splat(rng.gen_range(2.0..4.0)),
+            billboard: Billboard::ViewY,
+            spin: rng.gen_range(-0.3..0.3),
+            ..default()
+        }
+    }));
+    commands.insert_resource(quads);
+
+    info!("Press H to toggle the smoke between half and full resolution");
+}
+
+fn toggle_resolution(keys: Res<Input<KeyCode>>, mut quads: ResMut<Quads>) {
+    if !keys.just_pressed(KeyCode::H) {
+        return;
+    }
+    quads.resolution = match quads.resolution {
+        QuadsResolution::Full => QuadsResolution::Half,
+        _ => QuadsResolution::Full,
+    };
+    info!("Drawing the smoke at {:?} resolution", quads.resolution);
+}
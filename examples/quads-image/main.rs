@@ -0,0 +1,85 @@
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+/// Width and height of the generated sprite in pixels
+const SPRITE_SIZE: u32 = 96;
+/// Distance between the centers of neighbouring pixel quads
+const CELL_SIZE: f32 = 0.1;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-image",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    let quads = Quads::from_image(&planet_sprite(), CELL_SIZE, 0.45 * CELL_SIZE * Vec3::ONE)
+        .expect("the sprite is Rgba8UnormSrgb");
+    info!("Created {} quads from the sprite", quads.data.len());
+    commands.insert_resource(quads);
+
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(3.0, 2.0, 12.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+}
+
+/// A ringed planet on a transparent background, standing in for a sprite loaded from disk
+fn planet_sprite() -> Image {
+    let half_size = 0.5 * SPRITE_SIZE as f32;
+    let mut data = Vec::with_capacity((4 * SPRITE_SIZE * SPRITE_SIZE) as usize);
+    for y in 0..SPRITE_SIZE {
+        for x in 0..SPRITE_SIZE {
+            let p = (Vec2::new(x as f32, y as f32) + 0.5 - half_size) / half_size;
+            // The ring is an ellipse tilted around the planet, hidden behind its upper half
+            let ring = Vec2::new(p.x, 3.0 * (p.y + 0.3 * p.x)).length();
+            let in_ring =
+                (0.75..0.95).contains(&ring) && !(p.length() < 0.5 && p.y + 0.3 * p.x < 0.0);
+            let color = if in_ring {
+                Color::rgb(0.9, 0.8, 0.6)
+            } else if p.length() < 0.5 {
+                // Bands of color across the planet, darker towards its edge
+                let band = (p.y * 12.0).sin() * 0.5 + 0.5;
+                let shade = 1.0 - p.length();
+                Color::rgb(
+                    (0.8 + 0.2 * band) * shade,
+                    (0.4 + 0.2 * band) * shade,
+                    0.3 * shade,
+                )
+            } else {
+                Color::NONE
+            };
+            data.extend(color.as_rgba_u8());
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: SPRITE_SIZE,
+            height: SPRITE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
@@ -0,0 +1,163 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use bevy::prelude::*;
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Seconds for the sun to circle the tree once
+const DAY_LENGTH: f32 = 30.0;
+/// Distance of the sun marker from the center of the canopy
+const SUN_DISTANCE: f32 = 40.0;
+/// Elevation of the sun in radians, low so it shines through the canopy from the side
+const SUN_ELEVATION: f32 = 0.3;
+/// Number of leaves in the canopy
+const LEAVES: usize = 6_000;
+/// Center and radius of the roughly spherical canopy
+const CANOPY_CENTER: Vec3 = Vec3::new(0.0, 6.0, 0.0);
+const CANOPY_RADIUS: f32 = 4.0;
+/// Translucency of the leaves, toggled with T
+const TRANSLUCENCY: f32 = 0.6;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.45, 0.6, 0.8)))
+        .insert_resource(AmbientLight {
+            color: Color::rgb(0.6, 0.7, 0.9),
+            brightness: 0.1,
+        })
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-leaves",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (move_sun, toggle_translucency))
+        .run();
+}
+
+/// The unlit quad marking where the light comes from
+#[derive(Resource)]
+struct SunMarker(QuadId);
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 4.0, 16.0))
+                .looking_at(CANOPY_CENTER, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            color: Color::rgb(1.0, 0.9, 0.7),
+            illuminance: 3.0,
+            ..default()
+        },
+        ..default()
+    });
+
+    // The leaves are double-sided so the sun shining through the canopy lights the backs the
+    // camera sees. Their translucency lets it through in the color of the leaves, so the canopy
+    // glows a warm green when the sun is behind it instead of turning dark.
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut quads = Quads {
+        double_sided: true,
+        ..default()
+    };
+    for _ in 0..LEAVES {
+        // NOTE: Denser toward the outside of the canopy, where the leaves of a real tree grow
+        let direction = Vec3::new(
+            rng.gen_range(-1.0..1.0),
+            rng.gen_range(-1.0..1.0),
+            rng.gen_range(-1.0..1.0),
+        )
+        .normalize_or_zero();
+        let radius = CANOPY_RADIUS * rng.gen_range(0.5f32..1.0).sqrt();
+        quads.insert(Quad {
+            color: Color::hsl(rng.gen_range(75.0..110.0), 0.6, rng.gen_range(0.25..0.4)),
+            center: CANOPY_CENTER + radius * direction * Vec3::new(1.0, 0.7, 1.0),
+            half_extents: Vec3::new(0.12, 0.2, 0.0),
+            // NOTE: Leaves tilted every which way still turn toward the camera around their own
+            // axis, so the canopy looks full from any side
+            billboard: Billboard::OrientedY {
+                orientation: Quat::from_euler(
+                    EulerRot::YXZ,
+                    rng.gen_range(0.0..TAU),
+                    rng.gen_range(-FRAC_PI_2..FRAC_PI_2),
+                    0.0,
+                ),
+            },
+            lit: true,
+            translucency: TRANSLUCENCY,
+            ..default()
+        });
+    }
+
+    // The trunk, opaque bark for comparison
+    for y in 0..6 {
+        quads.insert(Quad {
+            color: Color::rgb(0.35, 0.25, 0.15),
+            center: Vec3::new(0.0, y as f32 + 0.5, 0.0),
+            half_extents: Vec3::new(0.3, 0.5, 0.0),
+            billboard: Billboard::WorldY,
+            lit: true,
+            ..default()
+        });
+    }
+
+    let sun = quads.insert(Quad {
+        color: Color::rgb(1.0, 0.9, 0.5),
+        half_extents: Vec3::splat(8.0),
+        billboard: Billboard::FixedScreenSize,
+        ..default()
+    });
+
+    commands.insert_resource(quads);
+    commands.insert_resource(SunMarker(sun));
+
+    info!("Press T to toggle the translucency of the leaves");
+}
+
+/// Circles the directional light around the tree low above the horizon and keeps the sun marker
+/// in the direction the light comes from
+fn move_sun(
+    time: Res<Time>,
+    sun: Res<SunMarker>,
+    mut lights: Query<&mut Transform, With<DirectionalLight>>,
+    mut quads: ResMut<Quads>,
+) {
+    let azimuth = time.elapsed_seconds() / DAY_LENGTH * TAU;
+    let rotation = Quat::from_euler(EulerRot::YXZ, azimuth, -SUN_ELEVATION, 0.0);
+    for mut transform in &mut lights {
+        transform.rotation = rotation;
+    }
+    // The light shines along its forward direction, so it comes from behind it
+    if let Some(marker) = quads.get_mut(sun.0) {
+        marker.center = CANOPY_CENTER + SUN_DISTANCE * (rotation * Vec3::Z);
+    }
+}
+
+fn toggle_translucency(keys: Res<Input<KeyCode>>, mut quads: ResMut<Quads>) {
+    if !keys.just_pressed(KeyCode::T) {
+        return;
+    }
+    let translucent = quads.data.iter().any(|quad| quad.translucency > 0.0);
+    let translucency = if translucent { 0.0 } else { TRANSLUCENCY };
+    for quad in quads.data.iter_mut().filter(|quad| quad.lit) {
+        quad.translucency = translucency;
+    }
+    info!(
+        "Leaves {}",
+        if translucent { "opaque" } else { "translucent" }
+    );
+}
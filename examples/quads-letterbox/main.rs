@@ -0,0 +1,121 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+/// Length of one cutscene loop in seconds
+const CUTSCENE_DURATION: f32 = 8.0;
+/// Height of each letterbox bar in normalized device coordinates while the cutscene plays
+const BAR_HEIGHT: f32 = 0.25;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-letterbox",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, cutscene)
+        .run();
+}
+
+/// Ids of the screen-space quads animated by the cutscene
+#[derive(Resource)]
+struct Cutscene {
+    top_bar: QuadId,
+    bottom_bar: QuadId,
+    backdrop: QuadId,
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 4.0, 12.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    // A ring of world-space pillars for the bars and the backdrop to frame
+    let mut quads = Quads::default();
+    for i in 0..24 {
+        let angle = i as f32 / 24.0 * TAU;
+        quads.insert(Quad {
+            color: Color::hsl(i as f32 * 15.0, 0.7, 0.6),
+            center: 6.0 * Vec3::new(angle.cos(), 0.0, angle.sin()),
+            half_extents: Vec3::new(0.3, 1.5, 0.0),
+            billboard: Billboard::WorldY,
+            ..default()
+        });
+    }
+
+    // Clip-space quads are authored in normalized device coordinates and ignore the camera, the
+    // z of the center is the depth. The bars sit at the near plane, in front of everything, and
+    // start just outside of the view.
+    let bar = |y: f32| Quad {
+        color: Color::BLACK,
+        center: Vec3::new(0.0, y, 1.0),
+        half_extents: Vec3::new(1.0, BAR_HEIGHT, 0.0),
+        billboard: Billboard::ClipSpace,
+        ..default()
+    };
+    let top_bar = quads.insert(bar(1.0 + BAR_HEIGHT));
+    let bottom_bar = quads.insert(bar(-1.0 - BAR_HEIGHT));
+    // The backdrop covers the whole view behind all world content. The depth test is strict, so
+    // it sits just in front of the far plane the depth buffer is cleared to.
+    let backdrop = quads.insert(Quad {
+        color: Color::MIDNIGHT_BLUE,
+        center: Vec3::new(0.0, 0.0, 1e-6),
+        half_extents: Vec3::new(1.0, 1.0, 0.0),
+        billboard: Billboard::ClipSpace,
+        ..default()
+    });
+
+    commands.insert_resource(quads);
+    commands.insert_resource(Cutscene {
+        top_bar,
+        bottom_bar,
+        backdrop,
+    });
+}
+
+/// Slides the letterbox bars in, closes them to black, opens them again and slides them out,
+/// while the backdrop shifts from night to dusk and back
+fn cutscene(time: Res<Time>, cutscene: Res<Cutscene>, mut quads: ResMut<Quads>) {
+    let t = (time.elapsed_seconds() / CUTSCENE_DURATION).fract();
+    // How far each bar reaches into the view, from 0 (hidden) to 1 (the bars meet in the middle)
+    let closed = match t {
+        t if t < 0.2 => t / 0.2 * BAR_HEIGHT,
+        t if t < 0.4 => BAR_HEIGHT,
+        t if t < 0.5 => BAR_HEIGHT + (t - 0.4) / 0.1 * (1.0 - BAR_HEIGHT),
+        t if t < 0.6 => 1.0 - (t - 0.5) / 0.1 * (1.0 - BAR_HEIGHT),
+        t if t < 0.8 => BAR_HEIGHT,
+        t => (1.0 - t) / 0.2 * BAR_HEIGHT,
+    };
+    // A bar with half-height h whose inner edge reaches `closed` into the view
+    let half_height = closed.max(BAR_HEIGHT);
+    let offset = 1.0 + half_height - closed;
+    if let Some(bar) = quads.get_mut(cutscene.top_bar) {
+        bar.center.y = offset;
+        bar.half_extents.y = half_height;
+    }
+    if let Some(bar) = quads.get_mut(cutscene.bottom_bar) {
+        bar.center.y = -offset;
+        bar.half_extents.y = half_height;
+    }
+
+    let dusk = 0.5 - 0.5 * (t * TAU).cos();
+    if let Some(backdrop) = quads.get_mut(cutscene.backdrop) {
+        backdrop.color = Color::MIDNIGHT_BLUE * (1.0 - dusk) + Color::ORANGE_RED * dusk;
+    }
+}
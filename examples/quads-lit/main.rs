@@ -0,0 +1,136 @@
+use std::f32::consts::TAU;
+
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+const GRID_SIZE: i32 = 400;
+const GRID_SPACING: f32 = 0.1;
+const N_LIGHTS: usize = 12;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::BLACK))
+        .insert_resource(AmbientLight {
+            color: Color::WHITE,
+            brightness: 0.02,
+        })
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-lit",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((
+            CameraControllerPlugin,
+            FrameTimeDiagnosticsPlugin,
+            LogDiagnosticsPlugin::default(),
+            QuadsPlugin::default(),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (move_lights, toggle_double_sided))
+        .run();
+}
+
+#[derive(Component)]
+struct Orbit {
+    radius: f32,
+    speed: f32,
+    phase: f32,
+    /// Side of the carpet the light hovers over
+    side: f32,
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, -12.0, 18.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    // A carpet of lit quads facing +z. Every other column is shiny and shows the highlights of
+    // the lights orbiting above it. The tiles get a slightly different shade and size each, derived
+    // from their seed when they are uploaded. The carpet is double-sided, so its back shows the
+    // lights orbiting below it.
+    let half_grid = GRID_SIZE / 2;
+    let mut quads = Quads {
+        variation: Some(QuadsVariation {
+            hue: 20.0,
+            saturation: 0.2,
+            lightness: 0.1,
+            extent: 0.1,
+            ..default()
+        }),
+        double_sided: true,
+        ..default()
+    };
+    for y in -half_grid..half_grid {
+        for x in -half_grid..half_grid {
+            quads.data.push(Quad {
+                color: Color::rgb(0.8, 0.7, 0.6),
+                center: Vec3::new(x as f32 * GRID_SPACING, y as f32 * GRID_SPACING, 0.0),
+                half_extents: 0.45 * GRID_SPACING * Vec3::ONE,
+                billboard: Billboard::None,
+                lit: true,
+                specular_color: Color::WHITE,
+                specular_power: if x % 2 == 0 { 64.0 } else { 0.0 },
+                seed: quads.data.len() as u32,
+                ..default()
+            });
+        }
+    }
+    commands.insert_resource(quads);
+
+    // Colored point lights hovering just above and below the carpet
+    for i in 0..N_LIGHTS {
+        let t = i as f32 / N_LIGHTS as f32;
+        commands.spawn((
+            PointLightBundle {
+                point_light: PointLight {
+                    color: Color::hsl(360.0 * t, 1.0, 0.5),
+                    intensity: 60.0,
+                    range: 6.0,
+                    ..default()
+                },
+                ..default()
+            },
+            Orbit {
+                radius: 4.0 + 10.0 * t,
+                speed: if i % 2 == 0 { 0.3 } else { -0.2 },
+                phase: TAU * t,
+                side: if i % 2 == 0 { 1.0 } else { -1.0 },
+            },
+        ));
+    }
+
+    info!("Press D to toggle drawing the back of the carpet");
+}
+
+fn move_lights(time: Res<Time>, mut lights: Query<(&Orbit, &mut Transform)>) {
+    for (orbit, mut transform) in &mut lights {
+        let angle = orbit.phase + orbit.speed * time.elapsed_seconds();
+        transform.translation = Vec3::new(
+            orbit.radius * angle.cos(),
+            orbit.radius * angle.sin(),
+            orbit.side,
+        );
+    }
+}
+
+fn toggle_double_sided(keys: Res<Input<KeyCode>>, mut quads: ResMut<Quads>) {
+    if keys.just_pressed(KeyCode::D) {
+        quads.double_sided = !quads.double_sided;
+        info!("Double-sided: {}", quads.double_sided);
+    }
+}
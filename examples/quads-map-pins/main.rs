@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+/// Pins per side of the square grid
+const PINS_PER_SIDE: i32 = 40;
+/// Distance between neighbouring pins in world units
+const PIN_SPACING: f32 = 10.0;
+/// Minimum on-screen y half-extent of the pins in pixels
+const MIN_HALF_EXTENT: f32 = 6.0;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-map-pins",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_min_screen_size)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 30.0, 60.0))
+                .looking_at(Vec3::new(0.0, 0.0, -100.0), Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    // Pins spread far into the distance, the nearby ones are sized in world units and the distant
+    // ones stay readable at the minimum screen size
+    let mut quads = Quads::default();
+    let half = PINS_PER_SIDE / 2;
+    for z in -half..half {
+        for x in -half..half {
+            quads.insert(Quad {
+                color: Color::hsl((x + half) as f32 * 360.0 / PINS_PER_SIDE as f32, 0.8, 0.5),
+                center: Vec3::new(x as f32 * PIN_SPACING, 1.0, z as f32 * PIN_SPACING),
+                half_extents: Vec3::new(0.6, 1.0, 0.0),
+                billboard: Billboard::ViewYMinScreenSize {
+                    min_half_extent: MIN_HALF_EXTENT,
+                },
+                ..default()
+            });
+        }
+    }
+    commands.insert_resource(quads);
+
+    info!("Press M to toggle the minimum screen size of the pins");
+}
+
+fn toggle_min_screen_size(keys: Res<Input<KeyCode>>, mut quads: ResMut<Quads>) {
+    if !keys.just_pressed(KeyCode::M) {
+        return;
+    }
+    for quad in &mut quads.data {
+        quad.billboard = match quad.billboard {
+            Billboard::ViewY => Billboard::ViewYMinScreenSize {
+                min_half_extent: MIN_HALF_EXTENT,
+            },
+            _ => Billboard::ViewY,
+        };
+    }
+}
@@ -0,0 +1,78 @@
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+    render::camera::RenderTarget,
+    window::WindowRef,
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::Rng;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads multi-window",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1280.0, 720.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((
+            CameraControllerPlugin,
+            FrameTimeDiagnosticsPlugin,
+            LogDiagnosticsPlugin::default(),
+            QuadsPlugin::default(),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(50.0 * Vec3::Z).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    // The observer window looks down on the scene from above. Its camera renders to an HDR main
+    // texture so the two windows use different view target formats.
+    let observer_window = commands
+        .spawn(Window {
+            title: "observer".to_owned(),
+            resolution: (640.0, 640.0).into(),
+            ..default()
+        })
+        .id();
+    commands.spawn(Camera3dBundle {
+        camera: Camera {
+            target: RenderTarget::Window(WindowRef::Entity(observer_window)),
+            hdr: true,
+            ..default()
+        },
+        transform: Transform::from_translation(60.0 * Vec3::Y).looking_at(Vec3::ZERO, Vec3::NEG_Z),
+        ..default()
+    });
+
+    let mut quads = Quads::default();
+    let mut rng = rand::thread_rng();
+    for _ in 0..100_000 {
+        quads.data.push(Quad {
+            color: Color::hsl(rng.gen_range(0.0..360.0), 0.8, 0.6),
+            center: Vec3::new(
+                rng.gen_range(-20.0..20.0),
+                rng.gen_range(-5.0..5.0),
+                rng.gen_range(-20.0..20.0),
+            ),
+            half_extents: 0.05 * Vec3::ONE,
+            billboard: Billboard::ViewY,
+            ..default()
+        });
+    }
+    commands.insert_resource(quads);
+}
@@ -0,0 +1,383 @@
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::{core_3d, fullscreen_vertex_shader::fullscreen_shader_vertex_state},
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::ExtractedCamera,
+        extract_component::{ExtractComponent, ExtractComponentPlugin},
+        render_graph::{
+            NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner,
+        },
+        render_resource::{
+            BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+            BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType,
+            BufferBindingType, BufferInitDescriptor, BufferSize, BufferUsages,
+            CachedRenderPipelineId, ColorTargetState, ColorWrites, Extent3d, FragmentState,
+            MultisampleState, Operations, PipelineCache, PrimitiveState, RenderPassColorAttachment,
+            RenderPassDescriptor, RenderPipelineDescriptor, ShaderStages, TextureDescriptor,
+            TextureDimension, TextureFormat, TextureSampleType, TextureUsages, TextureView,
+            TextureViewDimension,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::{BevyDefault, CachedTexture, TextureCache},
+        view::ViewTarget,
+        Render, RenderApp, RenderSet,
+    },
+};
+use bevy_vertex_pulling::prelude::*;
+use bevy_vertex_pulling::quads::{node, QuadsMask, QuadsMaskTexture};
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+/// Side length of the grid of quads
+const GRID: usize = 8;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.1, 0.1, 0.12)))
+        // NOTE: The outline shader loads the mask as a single sampled texture
+        .insert_resource(Msaa::Off)
+        .insert_resource(QuadsMask)
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-outline",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((
+            CameraControllerPlugin,
+            QuadsPlugin::default(),
+            OutlinePlugin,
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (select, change_width, toggle_mask))
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 6.0, 14.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert((CameraController::default(), Outline { width: 6 }));
+
+    let mut quads = Quads::default();
+    for z in 0..GRID {
+        for x in 0..GRID {
+            let p = Vec2::new(x as f32, z as f32) - (GRID - 1) as f32 / 2.0;
+            quads.data.push(Quad {
+                color: Color::hsl(360.0 * (x + z) as f32 / (2 * GRID) as f32, 0.6, 0.5),
+                center: Vec3::new(1.5 * p.x, 0.5, 1.5 * p.y),
+                half_extents: Vec3::new(0.5, 0.5, 0.0),
+                billboard: Billboard::ViewY,
+                ..default()
+            });
+        }
+    }
+    select_quads(&mut quads, 0);
+    commands.insert_resource(quads);
+
+    info!("Press Space to select other quads, Up and Down to change the outline width and M to toggle the mask");
+}
+
+/// Selects every seventh quad starting at `offset`, alternating between the two outline colors
+fn select_quads(quads: &mut Quads, offset: usize) {
+    for (i, quad) in quads.data.iter_mut().enumerate() {
+        quad.mask = match (i + offset) % 7 {
+            0 => 1 + (i / 7 % 2) as u8,
+            _ => 0,
+        };
+    }
+}
+
+fn select(keys: Res<Input<KeyCode>>, mut quads: ResMut<Quads>, mut offset: Local<usize>) {
+    if keys.just_pressed(KeyCode::Space) {
+        *offset += 1;
+        select_quads(&mut quads, *offset);
+    }
+}
+
+fn change_width(keys: Res<Input<KeyCode>>, mut outlines: Query<&mut Outline>) {
+    for mut outline in &mut outlines {
+        if keys.just_pressed(KeyCode::Up) {
+            outline.width = (outline.width + 1).min(64);
+        }
+        if keys.just_pressed(KeyCode::Down) {
+            outline.width = outline.width.saturating_sub(1).max(1);
+        }
+    }
+}
+
+fn toggle_mask(mut commands: Commands, keys: Res<Input<KeyCode>>, mask: Option<Res<QuadsMask>>) {
+    if keys.just_pressed(KeyCode::M) {
+        match mask {
+            Some(_) => commands.remove_resource::<QuadsMask>(),
+            None => commands.insert_resource(QuadsMask),
+        }
+    }
+}
+
+const OUTLINE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 7356519283946135081);
+
+/// Draws outlines `width` pixels wide around the quads in the [`QuadsMaskTexture`] of a camera
+#[derive(Component, Clone, Copy, ExtractComponent)]
+struct Outline {
+    width: u32,
+}
+
+struct OutlinePlugin;
+
+impl OutlinePlugin {
+    const NAME: &str = "quads_outline";
+}
+
+impl Plugin for OutlinePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            OUTLINE_SHADER_HANDLE,
+            "outline.wgsl",
+            Shader::from_wgsl
+        );
+        app.add_plugins(ExtractComponentPlugin::<Outline>::default());
+
+        // NOTE: The quads pass runs between tonemapping and FXAA by default, the outlines go in
+        // between so they are anti-aliased as well
+        app.sub_app_mut(RenderApp)
+            .add_render_graph_node::<ViewNodeRunner<OutlineNode>>(core_3d::graph::NAME, Self::NAME)
+            .add_render_graph_edges(
+                core_3d::graph::NAME,
+                &[node::QUADS_PASS, Self::NAME, core_3d::graph::node::FXAA],
+            )
+            .add_systems(Render, prepare_outline_textures.in_set(RenderSet::Prepare));
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<OutlinePipelines>();
+    }
+}
+
+/// The nearest seed of every pixel, flooded back and forth between the two textures
+#[derive(Component)]
+struct OutlineTextures([CachedTexture; 2]);
+
+fn prepare_outline_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    views: Query<(Entity, &ExtractedCamera), With<Outline>>,
+) {
+    for (entity, camera) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+        let descriptor = TextureDescriptor {
+            label: Some("quads_outline_seeds"),
+            size: Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rg32Float,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        };
+        commands.entity(entity).insert(OutlineTextures([
+            texture_cache.get(&render_device, descriptor.clone()),
+            texture_cache.get(&render_device, descriptor),
+        ]));
+    }
+}
+
+#[derive(Resource)]
+struct OutlinePipelines {
+    layout: BindGroupLayout,
+    seed: CachedRenderPipelineId,
+    flood: CachedRenderPipelineId,
+    composite: CachedRenderPipelineId,
+}
+
+impl FromWorld for OutlinePipelines {
+    fn from_world(world: &mut World) -> Self {
+        let texture_entry = |binding, sample_type| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Texture {
+                sample_type,
+                view_dimension: TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let layout =
+            world
+                .resource::<RenderDevice>()
+                .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: Some("quads_outline_layout"),
+                    entries: &[
+                        texture_entry(0, TextureSampleType::Float { filterable: false }),
+                        texture_entry(1, TextureSampleType::Float { filterable: false }),
+                        texture_entry(2, TextureSampleType::Uint),
+                        BindGroupLayoutEntry {
+                            binding: 3,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: BufferSize::new(4),
+                            },
+                            count: None,
+                        },
+                    ],
+                });
+
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let queue = |entry_point: &'static str, format| {
+            pipeline_cache.queue_render_pipeline(RenderPipelineDescriptor {
+                label: Some(format!("quads_outline_{entry_point}").into()),
+                layout: vec![layout.clone()],
+                push_constant_ranges: vec![],
+                vertex: fullscreen_shader_vertex_state(),
+                fragment: Some(FragmentState {
+                    shader: OUTLINE_SHADER_HANDLE.typed(),
+                    shader_defs: vec![],
+                    entry_point: entry_point.into(),
+                    targets: vec![Some(ColorTargetState {
+                        format,
+                        blend: None,
+                        write_mask: ColorWrites::ALL,
+                    })],
+                }),
+                primitive: PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: MultisampleState::default(),
+            })
+        };
+        let seed = queue("seed", TextureFormat::Rg32Float);
+        let flood = queue("flood", TextureFormat::Rg32Float);
+        // NOTE: The camera is not HDR, so the view target uses the default format
+        let composite = queue("composite", TextureFormat::bevy_default());
+
+        Self {
+            layout,
+            seed,
+            flood,
+            composite,
+        }
+    }
+}
+
+#[derive(Default)]
+struct OutlineNode;
+
+impl ViewNode for OutlineNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static Outline,
+        &'static OutlineTextures,
+        &'static QuadsMaskTexture,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, outline, textures, mask): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipelines = world.resource::<OutlinePipelines>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let (Some(seed), Some(flood), Some(composite)) = (
+            pipeline_cache.get_render_pipeline(pipelines.seed),
+            pipeline_cache.get_render_pipeline(pipelines.flood),
+            pipeline_cache.get_render_pipeline(pipelines.composite),
+        ) else {
+            return Ok(());
+        };
+
+        let post_process = view_target.post_process_write();
+        let render_device = render_context.render_device().clone();
+        // NOTE: A few bytes of uniforms per pass, simpler than a dynamic offset into one buffer
+        let bind_group = |seeds: &TextureView, step: u32| -> BindGroup {
+            let step = render_device.create_buffer_with_data(&BufferInitDescriptor {
+                label: Some("quads_outline_step"),
+                contents: &step.to_le_bytes(),
+                usage: BufferUsages::UNIFORM,
+            });
+            render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("quads_outline_bind_group"),
+                layout: &pipelines.layout,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(post_process.source),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: BindingResource::TextureView(seeds),
+                    },
+                    BindGroupEntry {
+                        binding: 2,
+                        resource: BindingResource::TextureView(&mask.0.default_view),
+                    },
+                    BindGroupEntry {
+                        binding: 3,
+                        resource: step.as_entire_binding(),
+                    },
+                ],
+            })
+        };
+        let [texture_a, texture_b] = &textures.0;
+        let (mut source, mut destination) = (&texture_a.default_view, &texture_b.default_view);
+
+        let pass = |render_context: &mut RenderContext,
+                    target: &TextureView,
+                    pipeline,
+                    bind_group: BindGroup| {
+            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some("quads_outline_pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+            });
+            render_pass.set_render_pipeline(pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        };
+
+        // NOTE: The seed pass does not read the seeds, any texture but its target will do
+        pass(render_context, source, seed, bind_group(destination, 0));
+        // Steps down from the outline width, as seeds further away are not drawn anyway
+        let mut step = outline.width.next_power_of_two();
+        while step > 0 {
+            pass(render_context, destination, flood, bind_group(source, step));
+            std::mem::swap(&mut source, &mut destination);
+            step /= 2;
+        }
+        pass(
+            render_context,
+            post_process.destination,
+            composite,
+            bind_group(source, outline.width),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+// Jump flood outlines around the quads in the quads mask texture. The seed pass stores the
+// position of every masked pixel, each flood pass then looks at the 3x3 pixels `step` apart and
+// keeps the nearest seed it finds, halving the step every pass. Afterwards every pixel knows the
+// nearest masked pixel within the largest step, which the composite pass turns into an outline.
+
+@group(0) @binding(0)
+var scene: texture_2d<f32>;
+@group(0) @binding(1)
+var seeds: texture_2d<f32>;
+@group(0) @binding(2)
+var mask: texture_2d<u32>;
+// The flood step in pixels, or the outline width in the composite pass
+@group(0) @binding(3)
+var<uniform> step: u32;
+
+// Pixels that have not found a seed yet
+const NO_SEED: vec2<f32> = vec2<f32>(-65536.0, -65536.0);
+
+@fragment
+fn seed(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    if textureLoad(mask, vec2<i32>(in.position.xy), 0).r != 0u {
+        return vec4<f32>(in.position.xy, 0.0, 0.0);
+    }
+    return vec4<f32>(NO_SEED, 0.0, 0.0);
+}
+
+@fragment
+fn flood(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(in.position.xy);
+    let size = vec2<i32>(textureDimensions(seeds));
+    var nearest = NO_SEED;
+    var nearest_distance = distance(NO_SEED, in.position.xy);
+    for (var y = -1; y <= 1; y += 1) {
+        for (var x = -1; x <= 1; x += 1) {
+            let sample = pixel + vec2<i32>(x, y) * i32(step);
+            if any(sample < vec2<i32>(0)) || any(sample >= size) {
+                continue;
+            }
+            let seed = textureLoad(seeds, sample, 0).xy;
+            let seed_distance = distance(seed, in.position.xy);
+            if seed_distance < nearest_distance {
+                nearest = seed;
+                nearest_distance = seed_distance;
+            }
+        }
+    }
+    return vec4<f32>(nearest, 0.0, 0.0);
+}
+
+// Outline color of a mask value
+fn outline_color(value: u32) -> vec4<f32> {
+    switch value {
+        case 1u: {
+            return vec4<f32>(1.0, 0.6, 0.0, 1.0);
+        }
+        case 2u: {
+            return vec4<f32>(0.0, 0.8, 1.0, 1.0);
+        }
+        default: {
+            return vec4<f32>(1.0, 1.0, 1.0, 1.0);
+        }
+    }
+}
+
+@fragment
+fn composite(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(in.position.xy);
+    let color = textureLoad(scene, pixel, 0);
+    // NOTE: Only outside of the masked quads, so the outlines do not cover them
+    if textureLoad(mask, pixel, 0).r != 0u {
+        return color;
+    }
+    let seed = textureLoad(seeds, pixel, 0).xy;
+    if seed.x < 0.0 {
+        return color;
+    }
+    // Anti-aliased over the last pixel of the outline
+    let coverage = clamp(f32(step) + 0.5 - distance(seed, in.position.xy), 0.0, 1.0);
+    let seed_mask = textureLoad(mask, vec2<i32>(seed), 0).r;
+    return mix(color, outline_color(seed_mask), coverage);
+}
@@ -0,0 +1,127 @@
+use std::time::Instant;
+
+use bevy::{prelude::*, tasks::ComputeTaskPool};
+use bevy_vertex_pulling::prelude::*;
+use bevy_vertex_pulling::quads::QuadsShards;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const QUADS: usize = 1_000_000;
+/// Quads produced by each task, every chunk writes to its own shard
+const CHUNK_SIZE: usize = 16 * 1024;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-parallel-fill",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, refill)
+        .run();
+}
+
+/// The quads of one chunk, standing in for an expensive simulation step. Each chunk has its own
+/// seed so the result does not depend on which thread produces it.
+fn produce_chunk(chunk: usize, mut push: impl FnMut(Quad)) {
+    let mut rng = StdRng::seed_from_u64(chunk as u64);
+    let start = chunk * CHUNK_SIZE;
+    for i in start..(start + CHUNK_SIZE).min(QUADS) {
+        // Points on a sphere, pushed outwards by some noise
+        let angle = i as f32 * 2.399_963;
+        let y = 1.0 - 2.0 * (i as f32 + 0.5) / QUADS as f32;
+        let ring = (1.0 - y * y).sqrt();
+        let radius = 20.0 + (0..8).map(|_| rng.gen_range(-0.4..0.4)).sum::<f32>();
+        push(Quad {
+            color: Color::hsl(180.0 + 120.0 * y, 0.8, 0.5),
+            center: radius * Vec3::new(ring * angle.cos(), y, ring * angle.sin()),
+            half_extents: Vec3::splat(0.02),
+            billboard: Billboard::ViewY,
+            ..default()
+        });
+    }
+}
+
+fn fill_serial() -> Quads {
+    let mut quads = Quads::default();
+    for chunk in 0..QUADS.div_ceil(CHUNK_SIZE) {
+        produce_chunk(chunk, |quad| quads.data.push(quad));
+    }
+    quads
+}
+
+fn fill_parallel() -> Quads {
+    let n_chunks = QUADS.div_ceil(CHUNK_SIZE);
+    let shards = QuadsShards::new(n_chunks);
+    ComputeTaskPool::get().scope(|scope| {
+        for chunk in 0..n_chunks {
+            let shards = &shards;
+            scope.spawn(async move {
+                let mut shard = shards.shard(chunk);
+                produce_chunk(chunk, |quad| shard.push(quad));
+            });
+        }
+    });
+    let mut quads = Quads::default();
+    quads.append_shards(shards);
+    quads
+}
+
+/// Fills the quads both ways, logs the timings and keeps the parallel result
+fn benchmark() -> Quads {
+    let start = Instant::now();
+    let serial = fill_serial();
+    let serial_time = start.elapsed();
+    let start = Instant::now();
+    let parallel = fill_parallel();
+    let parallel_time = start.elapsed();
+
+    // NOTE: The shards are merged in order, so both fills give the same quads
+    let same = serial.data.len() == parallel.data.len()
+        && serial
+            .data
+            .iter()
+            .zip(&parallel.data)
+            .all(|(a, b)| a.center == b.center);
+    info!(
+        "Filled {} quads serially in {:?} and from {} threads in {:?}, {}",
+        parallel.data.len(),
+        serial_time,
+        ComputeTaskPool::get().thread_num(),
+        parallel_time,
+        if same {
+            "with the same result"
+        } else {
+            "WITH DIFFERENT RESULTS"
+        }
+    );
+    parallel
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 10.0, 60.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+    commands.insert_resource(benchmark());
+
+    info!("Press Space to fill the quads again");
+}
+
+fn refill(keys: Res<Input<KeyCode>>, mut quads: ResMut<Quads>) {
+    if keys.just_pressed(KeyCode::Space) {
+        *quads = benchmark();
+    }
+}
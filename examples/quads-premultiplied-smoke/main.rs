@@ -0,0 +1,205 @@
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_vertex_pulling::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Number of smoke puffs
+const PUFFS: usize = 120;
+/// Side length of the smoke image, small so its texels are magnified and their filtering shows
+const SMOKE_SIZE: u32 = 24;
+/// Seconds for the fade to go out and back in
+const FADE_PERIOD: f32 = 6.0;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.55, 0.75, 0.95)))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-premultiplied-smoke",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        // NOTE: The puffs overlap, without depth writes the ones behind are not hidden by the
+        // transparent parts of the ones in front
+        .add_plugins(QuadsPlugin {
+            depth_write_enabled: false,
+            ..default()
+        })
+        .insert_resource(QuadsGlobalAlpha(1.0))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (toggle_premultiplied, toggle_fade, fade))
+        .run();
+}
+
+/// The smoke image in both alpha conventions, swapped when the batch's convention is toggled
+#[derive(Resource)]
+struct SmokeImages {
+    straight: Handle<Image>,
+    premultiplied: Handle<Image>,
+}
+
+/// Whether the blend constant fades the smoke in and out
+#[derive(Resource)]
+struct Fading(bool);
+
+/// A lumpy white puff. The color of the transparent texels is black like in many straight alpha
+/// exports, which the filtering of the straight image bleeds into the edges of the puff.
+fn smoke_images() -> (Image, Image) {
+    let mut rng = StdRng::seed_from_u64(9);
+    let lumps: Vec<(Vec2, f32)> = (0..6)
+        .map(|_| {
+            let center = Vec2::new(rng.gen_range(-0.3..0.3), rng.gen_range(-0.3..0.3));
+            (center, rng.gen_range(0.25..0.45))
+        })
+        .collect();
+    let mut straight = Vec::with_capacity((SMOKE_SIZE * SMOKE_SIZE * 4) as usize);
+    let mut premultiplied = Vec::with_capacity(straight.capacity());
+    for y in 0..SMOKE_SIZE {
+        for x in 0..SMOKE_SIZE {
+            let p = (Vec2::new(x as f32, y as f32) + 0.5) / SMOKE_SIZE as f32 * 2.0 - 1.0;
+            let density = lumps
+                .iter()
+                .map(|(center, radius)| 1.0 - p.distance(*center) / radius)
+                .fold(0.0f32, f32::max);
+            let alpha = (density * 2.0).clamp(0.0, 0.85);
+            let shade = if alpha > 0.0 { 0.95 - 0.2 * p.y } else { 0.0 };
+            let alpha_byte = (alpha * 255.0).round() as u8;
+            let shade_byte = (shade * 255.0).round() as u8;
+            straight.extend_from_slice(&[shade_byte, shade_byte, shade_byte, alpha_byte]);
+            let premultiplied_byte = (shade * alpha * 255.0).round() as u8;
+            premultiplied.extend_from_slice(&[
+                premultiplied_byte,
+                premultiplied_byte,
+                premultiplied_byte,
+                alpha_byte,
+            ]);
+        }
+    }
+    let image = |data| {
+        Image::new(
+            Extent3d {
+                width: SMOKE_SIZE,
+                height: SMOKE_SIZE,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            // NOTE: Linear, as premultiplying sRGB encoded colors would not be undone by decoding
+            TextureFormat::Rgba8Unorm,
+        )
+    };
+    (image(straight), image(premultiplied))
+}
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_translation(Vec3::new(0.0, 2.0, 12.0))
+            .looking_at(Vec3::new(0.0, 2.0, 0.0), Vec3::Y),
+        ..default()
+    });
+
+    let (straight, premultiplied) = smoke_images();
+    let images = SmokeImages {
+        straight: images.add(straight),
+        premultiplied: images.add(premultiplied),
+    };
+
+    let mut rng = StdRng::seed_from_u64(4);
+    let mut puffs: Vec<Quad> = (0..PUFFS)
+        .map(|_| {
+            let height = rng.gen_range(0.0..6.0);
+            // The column of smoke widens as it rises
+            let spread = 0.5 + 0.4 * height;
+            Quad {
+                color: Color::rgba(1.0, 1.0, 1.0, rng.gen_range(0.6..1.0)),
+                center: Vec3::new(
+                    rng.gen_range(-spread..spread),
+                    height,
+                    rng.gen_range(-spread..spread),
+                ),
+                half_extents: Vec3::splat(0.6 + 0.25 * height),
+                uv_rotation: rng.gen_range(0.0..std::f32::consts::TAU),
+                billboard: Billboard::ViewY,
+                ..default()
+            }
+        })
+        .collect();
+    // NOTE: Blended in the order of the data, so the puffs are drawn back to front for the fixed
+    // camera
+    puffs.sort_by(|a, b| a.center.z.total_cmp(&b.center.z));
+
+    commands.insert_resource(Quads {
+        data: puffs,
+        image: Some(images.premultiplied.clone()),
+        alpha_mode: QuadsAlphaMode::PremultipliedBlend,
+        blend_constant: Some(Color::WHITE),
+        ..default()
+    });
+    commands.insert_resource(images);
+    commands.insert_resource(Fading(false));
+
+    info!("Press P to switch between the premultiplied and the straight alpha smoke image");
+    info!("Press F to fade the smoke in and out with the blend constant");
+}
+
+fn toggle_premultiplied(
+    keys: Res<Input<KeyCode>>,
+    images: Res<SmokeImages>,
+    mut quads: ResMut<Quads>,
+) {
+    if !keys.just_pressed(KeyCode::P) {
+        return;
+    }
+    // NOTE: Straight alpha quads are only blended with a blend constant, which the smoke always
+    // has, so both images are blended the same way
+    if quads.alpha_mode == QuadsAlphaMode::PremultipliedBlend {
+        quads.alpha_mode = QuadsAlphaMode::Opaque;
+        quads.image = Some(images.straight.clone());
+        info!("Straight alpha, the black transparent texels darken the edges of the puffs");
+    } else {
+        quads.alpha_mode = QuadsAlphaMode::PremultipliedBlend;
+        quads.image = Some(images.premultiplied.clone());
+        info!("Premultiplied alpha, the edges of the puffs stay white");
+    }
+}
+
+fn toggle_fade(
+    keys: Res<Input<KeyCode>>,
+    mut fading: ResMut<Fading>,
+    mut quads: ResMut<Quads>,
+    mut global_alpha: ResMut<QuadsGlobalAlpha>,
+) {
+    if keys.just_pressed(KeyCode::F) {
+        fading.0 = !fading.0;
+        if !fading.0 {
+            quads.blend_constant = Some(Color::WHITE);
+            global_alpha.0 = 1.0;
+        }
+    }
+}
+
+/// Fades the smoke by the blend constant. The constant only weights the color of the puffs, the
+/// global alpha weights how much they hide the sky behind them, so both fade together.
+fn fade(
+    time: Res<Time>,
+    fading: Res<Fading>,
+    mut quads: ResMut<Quads>,
+    mut global_alpha: ResMut<QuadsGlobalAlpha>,
+) {
+    if !fading.0 {
+        return;
+    }
+    let t = time.elapsed_seconds() / FADE_PERIOD * std::f32::consts::TAU;
+    let fade = 0.5 + 0.5 * t.cos();
+    // NOTE: The constant is linear, an sRGB gray would fade the color unlike the alpha
+    quads.blend_constant = Some(Color::rgba_linear(fade, fade, fade, fade));
+    global_alpha.0 = fade;
+}
@@ -0,0 +1,125 @@
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+const TEXTURE_SIZE: u32 = 16;
+/// Length of the wall of decals along x
+const WALL_LENGTH: i32 = 60;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-samplers",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_samplers)
+        .run();
+}
+
+/// A tiny checkerboard with a colored border, small enough that every texel is visible
+fn pixel_art_image() -> Image {
+    let mut data = Vec::with_capacity((TEXTURE_SIZE * TEXTURE_SIZE * 4) as usize);
+    for y in 0..TEXTURE_SIZE {
+        for x in 0..TEXTURE_SIZE {
+            let border = x == 0 || y == 0 || x == TEXTURE_SIZE - 1 || y == TEXTURE_SIZE - 1;
+            let texel = if border {
+                [255, 140, 0, 255]
+            } else if (x / 2 + y / 2) % 2 == 0 {
+                [240, 240, 240, 255]
+            } else {
+                [40, 40, 60, 255]
+            };
+            data.extend_from_slice(&texel);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: TEXTURE_SIZE,
+            height: TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    // Looking along the wall, so the decals further away are seen at a grazing angle
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(-4.0, 1.5, 4.0))
+                .looking_at(Vec3::new(10.0, 1.0, 0.0), Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    // Large pixel art sprites, sharp with nearest filtering
+    let mut quads = Quads {
+        image: Some(images.add(pixel_art_image())),
+        sampler: Some(QuadsSamplerDesc::nearest()),
+        ..default()
+    };
+    for i in 0..4 {
+        quads.insert(Quad {
+            color: Color::WHITE,
+            center: Vec3::new(2.0 + 3.0 * i as f32, 1.0, 3.0),
+            half_extents: Vec3::splat(1.0),
+            billboard: Billboard::ViewY,
+            ..default()
+        });
+    }
+    commands.insert_resource(quads);
+
+    // A wall of decals with the same image, sharp at grazing angles with anisotropic filtering
+    commands.insert_resource(QuadsPoints {
+        points: (0..WALL_LENGTH)
+            .map(|x| QuadPoint {
+                position: Vec3::new(x as f32 + 0.5, 1.0, 0.0),
+                kind: 0,
+            })
+            .collect(),
+        kinds: vec![Quad {
+            color: Color::WHITE,
+            half_extents: Vec3::new(0.5, 1.0, 0.0),
+            billboard: Billboard::None,
+            ..default()
+        }],
+        sampler: Some(QuadsSamplerDesc::anisotropic(16)),
+        ..default()
+    });
+
+    info!("Press F to toggle between the per-batch samplers and the image's own sampler");
+}
+
+fn toggle_samplers(
+    keys: Res<Input<KeyCode>>,
+    mut quads: ResMut<Quads>,
+    mut points: ResMut<QuadsPoints>,
+) {
+    if !keys.just_pressed(KeyCode::F) {
+        return;
+    }
+    if quads.sampler.is_some() {
+        quads.sampler = None;
+        points.sampler = None;
+        info!("Using the image's own sampler for both batches");
+    } else {
+        quads.sampler = Some(QuadsSamplerDesc::nearest());
+        points.sampler = Some(QuadsSamplerDesc::anisotropic(16));
+        info!("Using nearest filtering for the sprites and anisotropic filtering for the wall");
+    }
+}
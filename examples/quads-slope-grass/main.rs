@@ -0,0 +1,111 @@
+use std::f32::consts::FRAC_PI_6;
+
+use bevy::prelude::*;
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const BLADES: usize = 20_000;
+/// Half the size of the square slope
+const SLOPE_HALF_SIZE: f32 = 15.0;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.55, 0.7, 0.9)))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-slope-grass",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_billboard)
+        .run();
+}
+
+/// Orientation of the slope, tilted around x so that it rises away from the camera
+fn slope_orientation() -> Quat {
+    Quat::from_rotation_x(FRAC_PI_6)
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 8.0, 30.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    let orientation = slope_orientation();
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(shape::Plane::from_size(2.0 * SLOPE_HALF_SIZE).into()),
+        material: materials.add(Color::rgb(0.3, 0.25, 0.15).into()),
+        transform: Transform::from_rotation(orientation),
+        ..default()
+    });
+    commands.insert_resource(AmbientLight {
+        color: Color::WHITE,
+        brightness: 1.0,
+    });
+
+    // Blades of grass growing out of the slope along its normal, and signboards standing on it
+    // tilted the same way, all turning toward the camera around the slope's normal
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut quads = Quads::default();
+    let normal = orientation * Vec3::Y;
+    for _ in 0..BLADES {
+        let on_slope = Vec3::new(
+            rng.gen_range(-SLOPE_HALF_SIZE..SLOPE_HALF_SIZE),
+            0.0,
+            rng.gen_range(-SLOPE_HALF_SIZE..SLOPE_HALF_SIZE),
+        );
+        let half_height = rng.gen_range(0.2..0.5);
+        quads.insert(Quad {
+            color: Color::hsl(rng.gen_range(80.0..130.0), 0.6, rng.gen_range(0.25..0.45)),
+            center: orientation * on_slope + normal * half_height,
+            half_extents: Vec3::new(0.05, half_height, 0.0),
+            billboard: Billboard::OrientedY { orientation },
+            ..default()
+        });
+    }
+    for x in [-8.0, 0.0, 8.0] {
+        quads.insert(Quad {
+            color: Color::rgb(0.9, 0.85, 0.6),
+            center: orientation * Vec3::new(x, 0.0, 5.0) + normal * 2.0,
+            half_extents: Vec3::new(1.5, 1.0, 0.0),
+            billboard: Billboard::OrientedY { orientation },
+            ..default()
+        });
+    }
+    commands.insert_resource(quads);
+
+    info!("Press B to switch between billboarding around the slope normal and world up");
+}
+
+fn toggle_billboard(keys: Res<Input<KeyCode>>, mut quads: ResMut<Quads>) {
+    if !keys.just_pressed(KeyCode::B) {
+        return;
+    }
+    let billboard = match quads.data.first().map(|quad| &quad.billboard) {
+        Some(Billboard::OrientedY { .. }) => Billboard::WorldY,
+        _ => Billboard::OrientedY {
+            orientation: slope_orientation(),
+        },
+    };
+    info!("Billboarding with {:?}", billboard);
+    for quad in &mut quads.data {
+        quad.billboard = billboard.clone();
+    }
+}
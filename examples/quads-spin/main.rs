@@ -0,0 +1,85 @@
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const PARTICLES: u32 = 100_000;
+/// Half the width of the cube the particles are scattered in
+const FIELD_HALF_SIZE: f32 = 30.0;
+/// Largest spin speed of the particles in radians per second, in either direction
+const MAX_SPIN: f32 = 4.0;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-spin",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((
+            CameraControllerPlugin,
+            FrameTimeDiagnosticsPlugin,
+            LogDiagnosticsPlugin::default(),
+            QuadsPlugin::default(),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 0.0, 70.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    // The spins are randomized per seed by the variation when the quads are uploaded, after that
+    // the quads spin on the GPU without any CPU updates
+    let mut rng = StdRng::seed_from_u64(11);
+    let mut quads = Quads {
+        variation: Some(QuadsVariation {
+            hue: 30.0,
+            spin: MAX_SPIN,
+            ..default()
+        }),
+        ..default()
+    };
+    quads.data.extend((0..PARTICLES).map(|seed| Quad {
+        color: Color::hsl(200.0, 0.8, 0.6),
+        center: Vec3::new(
+            rng.gen_range(-FIELD_HALF_SIZE..FIELD_HALF_SIZE),
+            rng.gen_range(-FIELD_HALF_SIZE..FIELD_HALF_SIZE),
+            rng.gen_range(-FIELD_HALF_SIZE..FIELD_HALF_SIZE),
+        ),
+        half_extents: Vec3::new(0.3, 0.15, 0.0),
+        billboard: Billboard::ViewY,
+        seed,
+        ..default()
+    }));
+
+    // Spinning works in screen space too, a ring of markers of a fixed size in pixels
+    quads.data.extend((0..12).map(|i| {
+        let angle = i as f32 / 12.0 * std::f32::consts::TAU;
+        Quad {
+            color: Color::ORANGE,
+            center: 40.0 * Vec3::new(angle.cos(), angle.sin(), 0.0),
+            half_extents: Vec3::new(8.0, 4.0, 0.0),
+            billboard: Billboard::FixedScreenSize,
+            spin: if i % 2 == 0 { 1.0 } else { -1.0 },
+            ..default()
+        }
+    }));
+    commands.insert_resource(quads);
+}
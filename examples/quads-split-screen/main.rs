@@ -0,0 +1,161 @@
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig,
+    ecs::query::Has,
+    prelude::*,
+    render::camera::Viewport,
+    window::{PrimaryWindow, WindowResized},
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const QUADS: usize = 50_000;
+const MARKERS: usize = 8;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-split-screen",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((
+            CameraControllerPlugin,
+            QuadsPlugin {
+                clear: Some(QuadsClear {
+                    color: Color::rgb(0.05, 0.05, 0.08),
+                    ..default()
+                }),
+                ..default()
+            },
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (set_viewports, switch_controlled_camera))
+        .run();
+}
+
+/// Which half of the window a camera renders to, 0 for the left and 1 for the right one
+#[derive(Component)]
+struct SplitScreenHalf(u32);
+
+fn setup(mut commands: Commands) {
+    // The right camera looks down on the scene with a narrower field of view. Only the left camera
+    // clears the window, the right one would clear the left viewport too.
+    commands
+        .spawn((
+            Camera3dBundle {
+                transform: Transform::from_translation(Vec3::new(0.0, 15.0, 40.0))
+                    .looking_at(Vec3::ZERO, Vec3::Y),
+                ..default()
+            },
+            SplitScreenHalf(0),
+        ))
+        .insert(CameraController::default());
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                order: 1,
+                ..default()
+            },
+            camera_3d: Camera3d {
+                clear_color: ClearColorConfig::None,
+                ..default()
+            },
+            projection: PerspectiveProjection {
+                fov: 25f32.to_radians(),
+                ..default()
+            }
+            .into(),
+            transform: Transform::from_translation(Vec3::new(30.0, 40.0, 0.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        },
+        SplitScreenHalf(1),
+    ));
+
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut quads = Quads::default();
+    for _ in 0..QUADS {
+        quads.insert(Quad {
+            color: Color::hsl(rng.gen_range(180.0..260.0), 0.7, 0.5),
+            center: Vec3::new(
+                rng.gen_range(-20.0..20.0),
+                rng.gen_range(0.0..2.0),
+                rng.gen_range(-20.0..20.0),
+            ),
+            half_extents: Vec3::splat(0.1),
+            billboard: Billboard::ViewY,
+            ..default()
+        });
+    }
+    // The markers are the same number of pixels large in both viewports, and the square is placed
+    // in the top left corner of each viewport
+    for i in 0..MARKERS {
+        let angle = i as f32 / MARKERS as f32 * std::f32::consts::TAU;
+        quads.insert(Quad {
+            color: Color::ORANGE,
+            center: Vec3::new(15.0 * angle.cos(), 3.0, 15.0 * angle.sin()),
+            half_extents: Vec3::splat(8.0),
+            billboard: Billboard::FixedScreenSize,
+            ..default()
+        });
+    }
+    quads.insert(Quad {
+        color: Color::WHITE,
+        center: Vec3::new(-0.9, 0.9, 1.0),
+        half_extents: Vec3::new(0.05, 0.05, 0.0),
+        billboard: Billboard::ClipSpace,
+        ..default()
+    });
+    commands.insert_resource(quads);
+
+    info!("Press Tab to switch the camera controlled by the mouse and keyboard");
+}
+
+/// Splits the window between the cameras whenever it is resized
+fn set_viewports(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut resized: EventReader<WindowResized>,
+    mut cameras: Query<(&mut Camera, &SplitScreenHalf)>,
+) {
+    // NOTE: The window is resized once it is created, which also sets the initial viewports
+    if resized.iter().last().is_none() {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let size = UVec2::new(window.physical_width() / 2, window.physical_height());
+    for (mut camera, half) in &mut cameras {
+        camera.viewport = Some(Viewport {
+            physical_position: UVec2::new(half.0 * size.x, 0),
+            physical_size: size,
+            ..default()
+        });
+    }
+}
+
+fn switch_controlled_camera(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    cameras: Query<(Entity, Has<CameraController>), With<SplitScreenHalf>>,
+) {
+    if !keys.just_pressed(KeyCode::Tab) {
+        return;
+    }
+    // NOTE: The controller only moves a camera while there is exactly one of them
+    for (entity, controlled) in &cameras {
+        if controlled {
+            commands.entity(entity).remove::<CameraController>();
+        } else {
+            commands.entity(entity).insert(CameraController::default());
+        }
+    }
+}
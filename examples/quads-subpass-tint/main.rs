@@ -0,0 +1,301 @@
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    ecs::query::Has,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        render_phase::TrackedRenderPass,
+        render_resource::{
+            BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+            BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, BlendState,
+            BufferBindingType, CachedRenderPipelineId, ColorTargetState, ColorWrites,
+            CompareFunction, DepthStencilState, FragmentState, MultisampleState, PipelineCache,
+            PrimitiveState, RenderPipelineDescriptor, ShaderStages, ShaderType,
+            SpecializedRenderPipeline, SpecializedRenderPipelines, TextureFormat, UniformBuffer,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        view::ExtractedView,
+        Render, RenderApp, RenderSet,
+    },
+};
+use bevy_vertex_pulling::prelude::*;
+use bevy_vertex_pulling::quads::{
+    QuadsMaskTexture, QuadsPipelineKey, QuadsSubpassAnchor, QuadsSubpassDraw, QuadsSubpassDraws,
+    QUADS_MASK_TEXTURE_FORMAT,
+};
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+/// Side length of the grid of quads
+const GRID: usize = 16;
+/// Seconds for the tint to cycle through the hues once
+const TINT_PERIOD: f32 = 10.0;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.1, 0.1, 0.12)))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-subpass-tint",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default(), TintPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (cycle_tint, toggle_tint, toggle_resolution))
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 10.0, 22.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    let mut quads = Quads::default();
+    for z in 0..GRID {
+        for x in 0..GRID {
+            let p = Vec2::new(x as f32, z as f32) - (GRID - 1) as f32 / 2.0;
+            quads.data.push(Quad {
+                color: Color::hsl(360.0 * (x + z) as f32 / (2 * GRID) as f32, 0.6, 0.6),
+                center: Vec3::new(1.2 * p.x, 0.5, 1.2 * p.y),
+                half_extents: Vec3::new(0.5, 0.5, 0.0),
+                billboard: Billboard::ViewY,
+                ..default()
+            });
+        }
+    }
+    commands.insert_resource(quads);
+
+    info!("Press T to toggle the tint and H to draw the quads at half resolution");
+    info!("The tint runs after the opaque quads, so half resolution quads are drawn over it");
+}
+
+const TINT_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4720915836627103482);
+
+/// Color blended over the view inside the quads pass, its alpha is the strength of the tint
+#[derive(Clone, Copy, ExtractResource, Resource)]
+struct Tint {
+    color: Color,
+    enabled: bool,
+}
+
+fn cycle_tint(time: Res<Time>, mut tint: ResMut<Tint>) {
+    let hue = 360.0 * (time.elapsed_seconds() / TINT_PERIOD).fract();
+    tint.color = Color::hsla(hue, 0.8, 0.5, 0.35);
+}
+
+fn toggle_tint(keys: Res<Input<KeyCode>>, mut tint: ResMut<Tint>) {
+    if keys.just_pressed(KeyCode::T) {
+        tint.enabled = !tint.enabled;
+    }
+}
+
+fn toggle_resolution(keys: Res<Input<KeyCode>>, mut quads: ResMut<Quads>) {
+    if keys.just_pressed(KeyCode::H) {
+        quads.resolution = match quads.resolution {
+            QuadsResolution::Full => QuadsResolution::Half,
+            _ => QuadsResolution::Full,
+        };
+        info!("{:?} resolution", quads.resolution);
+    }
+}
+
+struct TintPlugin;
+
+impl Plugin for TintPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, TINT_SHADER_HANDLE, "tint.wgsl", Shader::from_wgsl);
+        app.insert_resource(Tint {
+            color: Color::NONE,
+            enabled: true,
+        })
+        .add_plugins(ExtractResourcePlugin::<Tint>::default());
+
+        app.sub_app_mut(RenderApp)
+            .init_resource::<SpecializedRenderPipelines<TintPipeline>>()
+            .add_systems(
+                Render,
+                (
+                    prepare_tint_uniform.in_set(RenderSet::Prepare),
+                    queue_tint_pipelines.in_set(RenderSet::Queue),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app.init_resource::<TintPipeline>();
+        render_app.world.resource_mut::<QuadsSubpassDraws>().add(
+            QuadsSubpassAnchor::AfterOpaque,
+            0,
+            "quads_tint",
+            DrawTint,
+        );
+    }
+}
+
+#[derive(Resource)]
+struct TintPipeline {
+    layout: BindGroupLayout,
+    uniform: UniformBuffer<Vec4>,
+    bind_group: Option<BindGroup>,
+}
+
+impl FromWorld for TintPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout =
+            world
+                .resource::<RenderDevice>()
+                .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: Some("quads_tint_layout"),
+                    entries: &[BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Buffer {
+                            ty: BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: Some(Vec4::min_size()),
+                        },
+                        count: None,
+                    }],
+                });
+
+        Self {
+            layout,
+            uniform: UniformBuffer::default(),
+            bind_group: None,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for TintPipeline {
+    type Key = QuadsPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut targets = vec![Some(ColorTargetState {
+            format: key.view_target_format(),
+            blend: Some(BlendState::ALPHA_BLENDING),
+            write_mask: ColorWrites::ALL,
+        })];
+        if key.contains(QuadsPipelineKey::MASK) {
+            // NOTE: The tint is drawn in the quads pass, which has the mask attached in views with
+            // one, but it isn't a quad and leaves the mask alone
+            targets.push(Some(ColorTargetState {
+                format: QUADS_MASK_TEXTURE_FORMAT,
+                blend: None,
+                write_mask: ColorWrites::empty(),
+            }));
+        }
+        RenderPipelineDescriptor {
+            label: Some("quads_tint_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: TINT_SHADER_HANDLE.typed(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets,
+            }),
+            primitive: PrimitiveState::default(),
+            // NOTE: The pass has the view's depth attached, the tint covers everything regardless
+            depth_stencil: Some(DepthStencilState {
+                format: TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: CompareFunction::Always,
+                stencil: default(),
+                bias: default(),
+            }),
+            multisample: MultisampleState {
+                count: key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            push_constant_ranges: vec![],
+        }
+    }
+}
+
+fn prepare_tint_uniform(
+    tint: Res<Tint>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut pipeline: ResMut<TintPipeline>,
+) {
+    let pipeline = pipeline.as_mut();
+    pipeline.uniform.set(tint.color.as_linear_rgba_f32().into());
+    pipeline.uniform.write_buffer(&render_device, &render_queue);
+    pipeline.bind_group = pipeline.uniform.binding().map(|resource| {
+        render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("quads_tint_bind_group"),
+            layout: &pipeline.layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource,
+            }],
+        })
+    });
+}
+
+/// The tint pipeline specialized for the attachments of a view's quads pass
+#[derive(Component)]
+struct ViewTintPipeline(CachedRenderPipelineId);
+
+fn queue_tint_pipelines(
+    mut commands: Commands,
+    tint_pipeline: Res<TintPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<TintPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    views: Query<(Entity, &ExtractedView, Has<QuadsMaskTexture>)>,
+) {
+    for (entity, view, mask) in &views {
+        let mut key = QuadsPipelineKey::from_msaa_samples(msaa.samples())
+            | QuadsPipelineKey::from_hdr(view.hdr);
+        key.set(QuadsPipelineKey::MASK, mask);
+        let pipeline = pipelines.specialize(&pipeline_cache, &tint_pipeline, key);
+        commands.entity(entity).insert(ViewTintPipeline(pipeline));
+    }
+}
+
+/// Draws the tint over the whole view in the quads pass
+struct DrawTint;
+
+impl QuadsSubpassDraw for DrawTint {
+    fn draw<'w>(
+        &'w self,
+        render_pass: &mut TrackedRenderPass<'w>,
+        world: &'w World,
+        view_entity: Entity,
+    ) {
+        if !world.resource::<Tint>().enabled {
+            return;
+        }
+        let Some(view_pipeline) = world.get::<ViewTintPipeline>(view_entity) else {
+            return;
+        };
+        let Some(pipeline) = world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(view_pipeline.0)
+        else {
+            return;
+        };
+        let Some(bind_group) = world.resource::<TintPipeline>().bind_group.as_ref() else {
+            return;
+        };
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
@@ -0,0 +1,12 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+// The color blended over the view, its alpha is the strength of the tint
+@group(0) @binding(0)
+var<uniform> tint: vec4<f32>;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // Stronger toward the edges of the view, like a colored vignette
+    let edge = length(in.uv * 2.0 - 1.0) * 0.7071;
+    return vec4<f32>(tint.rgb, tint.a * mix(0.5, 1.0, edge * edge));
+}
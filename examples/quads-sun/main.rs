@@ -0,0 +1,121 @@
+use std::f32::consts::{FRAC_PI_4, TAU};
+
+use bevy::prelude::*;
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+/// Seconds for the sun to circle the scene once
+const DAY_LENGTH: f32 = 20.0;
+/// Distance of the sun marker from the center of the scene
+const SUN_DISTANCE: f32 = 40.0;
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.05, 0.05, 0.1)))
+        .insert_resource(AmbientLight {
+            color: Color::WHITE,
+            brightness: 0.05,
+        })
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-sun",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, move_sun)
+        .run();
+}
+
+/// The unlit quad marking where the light comes from
+#[derive(Resource)]
+struct SunMarker(QuadId);
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 6.0, 24.0))
+                .looking_at(Vec3::new(0.0, 2.0, 0.0), Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            color: Color::rgb(1.0, 0.95, 0.85),
+            illuminance: 3.0,
+            ..default()
+        },
+        ..default()
+    });
+
+    let mut quads = Quads::default();
+
+    // A wall of quads facing +z, lit evenly as a whole. Every other row is shiny and shows the
+    // highlight sweep across the wall as the sun moves.
+    for y in 0..8 {
+        for x in -12..12 {
+            quads.insert(Quad {
+                color: Color::rgb(0.8, 0.75, 0.7),
+                center: Vec3::new(x as f32 + 0.5, y as f32 + 0.5, -8.0),
+                half_extents: Vec3::new(0.48, 0.48, 0.0),
+                billboard: Billboard::None,
+                lit: true,
+                specular_color: Color::WHITE,
+                specular_power: if y % 2 == 0 { 32.0 } else { 0.0 },
+                ..default()
+            });
+        }
+    }
+
+    // A field of upright quads turning around the y axis to face the camera. Their normals point
+    // at the camera horizontally, so the side facing the sun is bright and the far side dark.
+    for z in -8..8 {
+        for x in -12..12 {
+            quads.insert(Quad {
+                color: Color::hsl(100.0 + 4.0 * x as f32, 0.5, 0.5),
+                center: Vec3::new(x as f32 + 0.5, 0.6, z as f32 + 0.5),
+                half_extents: Vec3::new(0.2, 0.6, 0.0),
+                billboard: Billboard::WorldY,
+                lit: true,
+                ..default()
+            });
+        }
+    }
+
+    let sun = quads.insert(Quad {
+        color: Color::rgb(1.0, 0.9, 0.5),
+        half_extents: Vec3::splat(8.0),
+        billboard: Billboard::FixedScreenSize,
+        ..default()
+    });
+
+    commands.insert_resource(quads);
+    commands.insert_resource(SunMarker(sun));
+}
+
+/// Circles the directional light around the scene at a fixed elevation and keeps the sun marker
+/// in the direction the light comes from
+fn move_sun(
+    time: Res<Time>,
+    sun: Res<SunMarker>,
+    mut lights: Query<&mut Transform, With<DirectionalLight>>,
+    mut quads: ResMut<Quads>,
+) {
+    let azimuth = time.elapsed_seconds() / DAY_LENGTH * TAU;
+    let rotation = Quat::from_euler(EulerRot::YXZ, azimuth, -FRAC_PI_4, 0.0);
+    for mut transform in &mut lights {
+        transform.rotation = rotation;
+    }
+    // The light shines along its forward direction, so it comes from behind it
+    if let Some(marker) = quads.get_mut(sun.0) {
+        marker.center = SUN_DISTANCE * (rotation * Vec3::Z);
+    }
+}
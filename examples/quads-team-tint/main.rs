@@ -0,0 +1,119 @@
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+const TEXTURE_SIZE: u32 = 128;
+/// Hue shifts of the team variants in degrees, the unit texture itself is red
+const TEAM_HUES: [f32; 4] = [0.0, 120.0, 210.0, 280.0];
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::BLACK))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-team-tint",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_disabled)
+        .run();
+}
+
+/// A red shield with a dark grey rim and a white emblem on black. Only the red is tinted by a hue
+/// shift, the other parts have no saturation to rotate.
+fn unit_image() -> Image {
+    let mut data = Vec::with_capacity((TEXTURE_SIZE * TEXTURE_SIZE * 4) as usize);
+    for y in 0..TEXTURE_SIZE {
+        for x in 0..TEXTURE_SIZE {
+            let p = (Vec2::new(x as f32, y as f32) + 0.5) / TEXTURE_SIZE as f32 * 2.0 - 1.0;
+            // Straight on top and pointed at the bottom, with a cross as the emblem
+            let width = if p.y < 0.0 { 0.9 } else { 0.9 * (1.0 - p.y) };
+            let inside = p.x.abs() < width && p.y.abs() < 0.95;
+            let rim = p.x.abs() > width - 0.12 || p.y < -0.83;
+            let emblem =
+                (p.x.abs() < 0.12 && p.y.abs() < 0.45) || (p.y.abs() < 0.12 && p.x.abs() < 0.4);
+            let texel = match (inside, rim, emblem) {
+                (false, ..) => [0, 0, 0, 255],
+                (true, true, _) => [50, 50, 50, 255],
+                (true, false, true) => [240, 240, 240, 255],
+                (true, false, false) => [200, 30, 30, 255],
+            };
+            data.extend_from_slice(&texel);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: TEXTURE_SIZE,
+            height: TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+fn disabled(quad: Quad) -> Quad {
+    quad.with_saturation_shift(-1.0).with_value_shift(-0.3)
+}
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 0.0, 12.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    // Every unit samples the same texture, the team colors come from the hue shifts alone
+    let mut quads = Quads {
+        image: Some(images.add(unit_image())),
+        ..default()
+    };
+    let unit = Quad {
+        color: Color::WHITE,
+        half_extents: Vec3::new(1.0, 1.0, 0.0),
+        billboard: Billboard::ViewY,
+        ..default()
+    };
+    let x = |i: usize| (i as f32 - TEAM_HUES.len() as f32 / 2.0) * 2.5;
+    for (i, hue) in TEAM_HUES.into_iter().enumerate() {
+        quads.data.push(Quad {
+            center: Vec3::new(x(i), 0.0, 0.0),
+            ..unit.clone().with_hue_shift(hue)
+        });
+    }
+    quads.data.push(Quad {
+        center: Vec3::new(x(TEAM_HUES.len()), 0.0, 0.0),
+        ..disabled(unit)
+    });
+    commands.insert_resource(quads);
+
+    info!("Press D to toggle the disabled look of the first team's unit");
+}
+
+fn toggle_disabled(keys: Res<Input<KeyCode>>, mut quads: ResMut<Quads>) {
+    if !keys.just_pressed(KeyCode::D) {
+        return;
+    }
+    let quad = &mut quads.data[0];
+    *quad = if quad.hsv_shift.y < 0.0 {
+        quad.clone()
+            .with_saturation_shift(0.0)
+            .with_value_shift(0.0)
+    } else {
+        disabled(quad.clone())
+    };
+}
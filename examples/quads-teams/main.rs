@@ -0,0 +1,90 @@
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Number of teams, each is one quad group
+const TEAMS: u8 = 5;
+const QUADS_PER_TEAM: usize = 200_000;
+/// Half the width of the square area the units are scattered over
+const FIELD_HALF_SIZE: f32 = 200.0;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-teams",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((
+            CameraControllerPlugin,
+            FrameTimeDiagnosticsPlugin,
+            LogDiagnosticsPlugin::default(),
+            QuadsPlugin::default(),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, recolor_teams)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // The units of all teams are interleaved, so every team is scattered across the whole buffer
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut quads = Quads::default();
+    quads.data.reserve(TEAMS as usize * QUADS_PER_TEAM);
+    for i in 0..TEAMS as usize * QUADS_PER_TEAM {
+        let team = (i % TEAMS as usize) as u8;
+        quads.data.push(Quad {
+            color: Color::hsl(team as f32 * 360.0 / TEAMS as f32, 0.7, 0.5),
+            center: Vec3::new(
+                rng.gen_range(-FIELD_HALF_SIZE..FIELD_HALF_SIZE),
+                0.5,
+                rng.gen_range(-FIELD_HALF_SIZE..FIELD_HALF_SIZE),
+            ),
+            half_extents: Vec3::splat(0.3),
+            billboard: Billboard::ViewY,
+            // Group 0 is never recolored, so the teams are groups 1 to TEAMS
+            group: team + 1,
+            ..default()
+        });
+    }
+    commands.insert_resource(quads);
+
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 60.0, 120.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    info!("Press Space to recolor a random team, Backspace to restore all team colors");
+}
+
+fn recolor_teams(keys: Res<Input<KeyCode>>, mut recolors: EventWriter<QuadGroupRecolor>) {
+    let mut rng = rand::thread_rng();
+    if keys.just_pressed(KeyCode::Space) {
+        let group = rng.gen_range(1..=TEAMS);
+        let color = Color::hsl(rng.gen_range(0.0..360.0), 1.0, 0.6);
+        info!("Recoloring team {group} to {color:?}");
+        recolors.send(QuadGroupRecolor {
+            group,
+            color: Some(color),
+        });
+    }
+    if keys.just_pressed(KeyCode::Back) {
+        for group in 1..=TEAMS {
+            recolors.send(QuadGroupRecolor { group, color: None });
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::f32::consts::TAU;
+
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+const TEXTURE_SIZE: u32 = 256;
+const PANELS: i32 = 5;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-textured",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, sweep)
+        .run();
+}
+
+/// A radar sweep: a bright green wedge that fades out counter-clockwise, clipped to a disc
+fn radar_image() -> Image {
+    let mut data = Vec::with_capacity((TEXTURE_SIZE * TEXTURE_SIZE * 4) as usize);
+    for y in 0..TEXTURE_SIZE {
+        for x in 0..TEXTURE_SIZE {
+            let p = (Vec2::new(x as f32, y as f32) + 0.5) / TEXTURE_SIZE as f32 * 2.0 - 1.0;
+            let angle = (-p.y).atan2(p.x).rem_euclid(TAU);
+            let intensity = if p.length() <= 1.0 {
+                0.1 + 0.9 * (1.0 - angle / TAU).powi(4)
+            } else {
+                0.0
+            };
+            let green = (255.0 * intensity) as u8;
+            data.extend_from_slice(&[0, green, 0, 255]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: TEXTURE_SIZE,
+            height: TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+/// Horizontal scanlines, multiplied over the radar and scrolled down to make the panels look like
+/// old CRT screens
+fn scanlines_image() -> Image {
+    let mut data = Vec::with_capacity((TEXTURE_SIZE * 4) as usize);
+    for y in 0..TEXTURE_SIZE {
+        let value = if y % 8 < 4 { 255 } else { 96 };
+        data.extend_from_slice(&[value, value, value, 255]);
+    }
+    Image::new(
+        Extent3d {
+            width: 1,
+            height: TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(15.0 * Vec3::Z).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    // A row of static panels whose texture spins while the geometry stays put. Every other panel
+    // has a negative x half-extent which mirrors its texture. The scanlines get stronger and
+    // scroll faster from left to right.
+    let mut quads = Quads {
+        image: Some(images.add(radar_image())),
+        detail: Some(QuadsDetail {
+            image: images.add(scanlines_image()),
+            ..default()
+        }),
+        ..default()
+    };
+    for i in -PANELS / 2..=PANELS / 2 {
+        quads.data.push(Quad {
+            color: Color::WHITE,
+            center: Vec3::new(i as f32 * 2.5, 0.0, 0.0),
+            half_extents: Vec3::new(if i % 2 == 0 { 1.0 } else { -1.0 }, 1.0, 1.0),
+            billboard: Billboard::None,
+            detail_weight: (i + PANELS / 2) as f32 / (PANELS - 1) as f32,
+            detail_scroll: Vec2::new(0.0, -0.05 * (i + PANELS / 2 + 1) as f32),
+            ..default()
+        });
+    }
+    commands.insert_resource(quads);
+}
+
+fn sweep(time: Res<Time>, mut quads: ResMut<Quads>) {
+    for (i, quad) in quads.data.iter_mut().enumerate() {
+        quad.uv_rotation = (1.0 + i as f32 * 0.25) * time.elapsed_seconds();
+    }
+}
@@ -0,0 +1,89 @@
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+    tasks::{ComputeTaskPool, ParallelSliceMut},
+};
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+const GRID_SPACING: f32 = 0.1;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads updated every frame",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((
+            CameraControllerPlugin,
+            FrameTimeDiagnosticsPlugin,
+            LogDiagnosticsPlugin::default(),
+            QuadsPlugin::default(),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, wave)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // The grid is `grid_size` x `grid_size` quads, a million by default
+    let grid_size = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse::<usize>().ok())
+        .unwrap_or(1000);
+    let half_size = 0.5 * grid_size as f32 * GRID_SPACING;
+    info!("Animating {} quads", grid_size * grid_size);
+
+    let mut quads = Quads::default();
+    quads.data.reserve(grid_size * grid_size);
+    for y in 0..grid_size {
+        for x in 0..grid_size {
+            quads.data.push(Quad {
+                color: Color::rgb(
+                    x as f32 / grid_size as f32,
+                    y as f32 / grid_size as f32,
+                    1.0,
+                ),
+                center: Vec3::new(
+                    x as f32 * GRID_SPACING - half_size,
+                    0.0,
+                    y as f32 * GRID_SPACING - half_size,
+                ),
+                half_extents: 0.45 * GRID_SPACING * Vec3::ONE,
+                billboard: Billboard::ViewY,
+                ..default()
+            });
+        }
+    }
+    commands.insert_resource(quads);
+
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 0.6, 1.0) * half_size)
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+}
+
+/// Moves every quad up and down with a radial wave. Every frame touches all quads, so every frame
+/// uploads the whole buffer again.
+fn wave(time: Res<Time>, mut quads: ResMut<Quads>) {
+    let t = time.elapsed_seconds();
+    quads
+        .data
+        .par_splat_map_mut(ComputeTaskPool::get(), None, |chunk| {
+            for quad in chunk {
+                let distance = quad.center.x.hypot(quad.center.z);
+                quad.center.y = 0.5 * (2.0 * distance - 3.0 * t).sin();
+            }
+        });
+}
@@ -0,0 +1,147 @@
+use std::f32::consts::FRAC_PI_6;
+
+use bevy::prelude::*;
+use bevy_vertex_pulling::prelude::*;
+use examples_utils::camera::{CameraController, CameraControllerPlugin};
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.55, 0.7, 0.9)))
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: format!(
+                    "{} {} - quads-z-up",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ),
+                resolution: (1920.0, 1080.0).into(),
+                ..Default::default()
+            }),
+            ..default()
+        }))
+        .add_plugins((CameraControllerPlugin, QuadsPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_conversion)
+        .run();
+}
+
+/// Tilt of the signboards around the authored x axis
+const SIGN_TILT: f32 = FRAC_PI_6;
+
+/// The scene as exported from a z up, left-handed engine, with the ground in the xy plane and y
+/// pointing toward the viewer
+fn authored_quads() -> Quads {
+    let mut quads = Quads {
+        convention: QuadsCoordinateConvention::ZUpLeftHanded,
+        ..default()
+    };
+    // Trees along the road, turning toward the camera around the authored z axis
+    for i in -4..=4 {
+        for side in [-1.0, 1.0] {
+            quads.insert(Quad {
+                color: Color::rgb(0.2, 0.5, 0.2),
+                center: Vec3::new(3.0 * i as f32, side * 4.0, 1.5),
+                half_extents: Vec3::new(0.8, 1.5, 0.0),
+                billboard: Billboard::WorldY,
+                ..default()
+            });
+        }
+    }
+    // Signboards leaning back around x, away from the road
+    for x in [-6.0, 6.0] {
+        quads.insert(Quad {
+            color: Color::rgb(0.9, 0.85, 0.6),
+            center: Vec3::new(x, 0.0, 1.0),
+            half_extents: Vec3::new(1.2, 0.8, 0.0),
+            billboard: Billboard::OrientedY {
+                orientation: Quat::from_rotation_x(SIGN_TILT),
+            },
+            ..default()
+        });
+    }
+    // A wall facing the viewer
+    quads.insert(Quad {
+        color: Color::rgb(0.6, 0.3, 0.25),
+        center: Vec3::new(0.0, -8.0, 2.0),
+        half_extents: Vec3::new(10.0, 2.0, 0.0),
+        ..default()
+    });
+    quads
+}
+
+/// The same scene converted by hand to bevy's y up, right-handed coordinates
+fn hand_converted_quads() -> Quads {
+    let mut quads = Quads::default();
+    for i in -4..=4 {
+        for side in [-1.0, 1.0] {
+            quads.insert(Quad {
+                color: Color::rgb(0.2, 0.5, 0.2),
+                // NOTE: The authored y and z swap places
+                center: Vec3::new(3.0 * i as f32, 1.5, side * 4.0),
+                half_extents: Vec3::new(0.8, 1.5, 0.0),
+                billboard: Billboard::WorldY,
+                ..default()
+            });
+        }
+    }
+    for x in [-6.0, 6.0] {
+        quads.insert(Quad {
+            color: Color::rgb(0.9, 0.85, 0.6),
+            center: Vec3::new(x, 1.0, 0.0),
+            half_extents: Vec3::new(1.2, 0.8, 0.0),
+            // NOTE: Mirroring the axes reverses the direction of the rotation
+            billboard: Billboard::OrientedY {
+                orientation: Quat::from_rotation_x(-SIGN_TILT),
+            },
+            ..default()
+        });
+    }
+    quads.insert(Quad {
+        color: Color::rgb(0.6, 0.3, 0.25),
+        center: Vec3::new(0.0, 2.0, -8.0),
+        half_extents: Vec3::new(10.0, 2.0, 0.0),
+        ..default()
+    });
+    quads
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_translation(Vec3::new(0.0, 8.0, 20.0))
+                .looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        })
+        .insert(CameraController::default());
+
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(shape::Plane::from_size(40.0).into()),
+        material: materials.add(Color::rgb(0.3, 0.25, 0.15).into()),
+        ..default()
+    });
+    commands.insert_resource(AmbientLight {
+        color: Color::WHITE,
+        brightness: 1.0,
+    });
+
+    commands.insert_resource(authored_quads());
+
+    info!("Press C to switch between the converted authored quads and the quads converted by hand, which look the same");
+}
+
+fn toggle_conversion(keys: Res<Input<KeyCode>>, mut quads: ResMut<Quads>) {
+    if !keys.just_pressed(KeyCode::C) {
+        return;
+    }
+    *quads = if quads.convention == QuadsCoordinateConvention::YUpRightHanded {
+        info!("Showing the authored quads converted with QuadsCoordinateConvention::ZUpLeftHanded");
+        authored_quads()
+    } else {
+        info!("Showing the quads converted by hand");
+        hand_converted_quads()
+    };
+}
@@ -0,0 +1,559 @@
+//! Renders a suite of small scenes offscreen and compares them with golden images in
+//! `tests/golden`, printing a summary and exiting with a non-zero status if any scene differs.
+//!
+//! ```text
+//! cargo run --example regression_runner --features test_support -- [--update] [filter...]
+//! ```
+//!
+//! Missing golden images are written from the rendered frame, `--update` overwrites all of them.
+//! Only the scenes whose name contains one of the filters are run. The actual and diff images of
+//! a mismatch are written next to the golden image, or to the directory in the
+//! `BEVY_VERTEX_PULLING_TEST_ARTIFACTS` environment variable.
+//!
+//! The suite also runs as a test against the golden images committed in `tests/golden`:
+//!
+//! ```text
+//! cargo test --example regression_runner --features test_support
+//! ```
+//!
+//! Golden images depend on the GPU and driver. On a machine whose output differs from the
+//! committed images, render them with `--update` before making changes and compare against those.
+
+use std::{
+    f32::consts::{FRAC_PI_4, TAU},
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::Path,
+    process::ExitCode,
+    time::Duration,
+};
+
+use bevy::{
+    prelude::*,
+    render::{
+        camera::ScalingMode,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+    time::TimeUpdateStrategy,
+};
+use bevy_vertex_pulling::{
+    prelude::*,
+    test_support::{
+        compare_images, load_png, render_once, save_diff_artifacts, save_png, ImageTolerance,
+        UPDATE_GOLDEN_ENV,
+    },
+};
+
+/// A scene of the suite, rendered into an image of `size` pixels and compared with
+/// `tests/golden/<name>.png`
+struct Scene {
+    name: &'static str,
+    size: UVec2,
+    tolerance: ImageTolerance,
+    build: fn(&mut App),
+}
+
+/// Tolerance of scenes of flat colors, which should render the same on every run
+const EXACT: ImageTolerance = ImageTolerance {
+    channel: 2.0 / 255.0,
+    max_mismatched_pixels: 0,
+    ignore_edges: true,
+};
+
+/// Tolerance of lit and filtered scenes, whose shading may round differently across drivers
+const SHADED: ImageTolerance = ImageTolerance {
+    channel: 4.0 / 255.0,
+    max_mismatched_pixels: 16,
+    ignore_edges: true,
+};
+
+const SCENES: &[Scene] = &[
+    Scene {
+        name: "billboards",
+        size: UVec2::new(160, 120),
+        tolerance: EXACT,
+        build: billboards,
+    },
+    Scene {
+        name: "polygons",
+        size: UVec2::new(128, 128),
+        tolerance: EXACT,
+        build: polygons,
+    },
+    Scene {
+        name: "blending",
+        size: UVec2::new(128, 128),
+        tolerance: EXACT,
+        build: blending,
+    },
+    Scene {
+        name: "textured",
+        size: UVec2::new(128, 128),
+        tolerance: SHADED,
+        build: textured,
+    },
+    Scene {
+        name: "lit",
+        size: UVec2::new(128, 128),
+        tolerance: SHADED,
+        build: lit,
+    },
+    Scene {
+        name: "overlay_2d",
+        size: UVec2::new(160, 90),
+        tolerance: EXACT,
+        build: overlay_2d,
+    },
+    Scene {
+        name: "near_fade",
+        size: UVec2::new(128, 128),
+        tolerance: EXACT,
+        build: near_fade,
+    },
+    Scene {
+        name: "variation",
+        size: UVec2::new(128, 128),
+        tolerance: EXACT,
+        build: variation,
+    },
+];
+
+/// The outcome of a scene
+enum Outcome {
+    Passed { ignored_pixels: usize },
+    Written,
+    Failed { message: String },
+}
+
+fn main() -> ExitCode {
+    let mut update = std::env::var_os(UPDATE_GOLDEN_ENV).is_some();
+    let mut filters = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--update" => update = true,
+            _ => filters.push(arg),
+        }
+    }
+    if run_scenes(&filters, update) > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Runs the scenes matching `filters`, printing the outcome of each and a summary, and returns
+/// the number of scenes that failed
+fn run_scenes(filters: &[String], update: bool) -> usize {
+    let golden_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+
+    let scenes = SCENES.iter().filter(|scene| {
+        filters.is_empty()
+            || filters
+                .iter()
+                .any(|filter| scene.name.contains(filter.as_str()))
+    });
+    let mut failures = 0;
+    let mut count = 0;
+    for scene in scenes {
+        count += 1;
+        let outcome = run_scene(
+            scene,
+            &golden_dir.join(format!("{}.png", scene.name)),
+            update,
+        );
+        let status = match &outcome {
+            Outcome::Passed { ignored_pixels: 0 } => "ok".to_string(),
+            Outcome::Passed { ignored_pixels } => {
+                format!("ok ({ignored_pixels} edge pixels ignored)")
+            }
+            Outcome::Written => "golden image written".to_string(),
+            Outcome::Failed { message } => {
+                failures += 1;
+                format!("FAILED: {message}")
+            }
+        };
+        println!("{:<12} {status}", scene.name);
+    }
+
+    println!("{} of {count} scenes passed", count - failures);
+    failures
+}
+
+fn run_scene(scene: &Scene, golden: &Path, update: bool) -> Outcome {
+    // NOTE: A scene whose pipelines fail to compile panics inside bevy, the other scenes still
+    // run in their own apps
+    let rendered = catch_unwind(AssertUnwindSafe(|| {
+        render_once(
+            |app| {
+                deterministic(app);
+                (scene.build)(app);
+            },
+            scene.size,
+        )
+    }));
+    let image = match rendered {
+        Ok(image) => image,
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| panic.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_default();
+            return Outcome::Failed {
+                message: format!("rendering panicked: {message}"),
+            };
+        }
+    };
+
+    if update || !golden.exists() {
+        save_png(&image, golden);
+        return Outcome::Written;
+    }
+    let expected = load_png(golden);
+    let expected_size = expected.texture_descriptor.size;
+    if (expected_size.width, expected_size.height) != (scene.size.x, scene.size.y) {
+        return Outcome::Failed {
+            message: format!(
+                "the golden image is {}x{}, the scene {}x{}",
+                expected_size.width, expected_size.height, scene.size.x, scene.size.y
+            ),
+        };
+    }
+    let diff = compare_images(&image, &expected, &scene.tolerance);
+    if scene.tolerance.accepts(&diff) {
+        return Outcome::Passed {
+            ignored_pixels: diff.ignored_pixels,
+        };
+    }
+    let (actual_path, diff_path) = save_diff_artifacts(&image, &diff, golden);
+    Outcome::Failed {
+        message: format!(
+            "{} pixels differ by up to {:.3}, see {} and {}",
+            diff.mismatched_pixels,
+            diff.max_difference,
+            actual_path.display(),
+            diff_path.display()
+        ),
+    }
+}
+
+/// Settings shared by every scene so that they render the same frame on every run. The clock is
+/// stopped at zero, so animated quads are drawn in their initial state.
+fn deterministic(app: &mut App) {
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+        .insert_resource(ClearColor(Color::rgb(0.1, 0.1, 0.12)))
+        .insert_resource(AmbientLight {
+            color: Color::WHITE,
+            brightness: 0.1,
+        })
+        .insert_resource(Msaa::Sample4)
+        .add_plugins(QuadsPlugin::default());
+}
+
+fn spawn_camera(app: &mut App, position: Vec3, target: Vec3) {
+    app.world.spawn(Camera3dBundle {
+        transform: Transform::from_translation(position).looking_at(target, Vec3::Y),
+        ..default()
+    });
+}
+
+/// A checkerboard with a red corner, so rotations and mirroring of the texture are visible
+fn checker_image() -> Image {
+    const SIZE: u32 = 16;
+    let mut data = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let texel = match (x < 4 && y < 4, (x / 4 + y / 4) % 2 == 0) {
+                (true, _) => [255, 40, 40, 255],
+                (false, true) => [230, 230, 230, 255],
+                (false, false) => [40, 40, 40, 255],
+            };
+            data.extend_from_slice(&texel);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+/// One quad in every billboard mode, seen from above at an angle so the modes facing the camera
+/// differ from the ones that don't
+fn billboards(app: &mut App) {
+    spawn_camera(app, Vec3::new(0.0, 4.0, 8.0), Vec3::ZERO);
+    let modes = [
+        (Billboard::None, Vec3::splat(0.4)),
+        (Billboard::ViewY, Vec3::splat(0.4)),
+        (Billboard::WorldY, Vec3::splat(0.4)),
+        (Billboard::FixedScreenSize, Vec3::splat(8.0)),
+        (Billboard::ViewportFraction, Vec3::splat(0.05)),
+        (
+            Billboard::ViewYMinScreenSize {
+                min_half_extent: 6.0,
+            },
+            Vec3::splat(0.05),
+        ),
+        (
+            Billboard::OrientedY {
+                orientation: Quat::from_rotation_z(FRAC_PI_4),
+            },
+            Vec3::splat(0.4),
+        ),
+    ];
+    let mut quads = Quads::default();
+    for (i, (billboard, half_extents)) in modes.into_iter().enumerate() {
+        quads.insert(Quad {
+            color: Color::hsl(360.0 * i as f32 / 7.0, 0.7, 0.5),
+            center: Vec3::new(i as f32 - 3.0, 0.0, 0.0),
+            half_extents,
+            billboard,
+            ..default()
+        });
+    }
+    quads.insert(Quad {
+        color: Color::WHITE,
+        center: Vec3::new(-0.9, 0.9, 1.0),
+        half_extents: Vec3::new(0.05, 0.05, 0.0),
+        billboard: Billboard::ClipSpace,
+        ..default()
+    });
+    app.insert_resource(quads);
+}
+
+/// Quads drawn as regular polygons of growing size
+fn polygons(app: &mut App) {
+    spawn_camera(app, Vec3::new(0.0, 0.0, 6.0), Vec3::ZERO);
+    let mut quads = Quads {
+        corner_count: 12,
+        ..default()
+    };
+    for i in 0..4 {
+        quads.insert(Quad {
+            color: Color::hsl(90.0 * i as f32, 0.6, 0.5),
+            center: Vec3::new(i as f32 - 1.5, (i % 2) as f32 - 0.5, 0.0),
+            half_extents: Vec3::splat(0.2 + 0.1 * i as f32),
+            billboard: Billboard::ViewY,
+            ..default()
+        });
+    }
+    app.insert_resource(quads);
+}
+
+/// Overlapping translucent quads at different depths, blended with premultiplied alpha
+fn blending(app: &mut App) {
+    spawn_camera(app, Vec3::new(0.0, 0.0, 6.0), Vec3::ZERO);
+    let mut quads = Quads {
+        alpha_mode: QuadsAlphaMode::PremultiplyOnUpload,
+        ..default()
+    };
+    for (i, color) in [
+        Color::rgba(1.0, 0.2, 0.2, 0.5),
+        Color::rgba(0.2, 1.0, 0.2, 0.5),
+        Color::rgba(0.2, 0.2, 1.0, 0.5),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let angle = i as f32 * TAU / 3.0;
+        quads.insert(Quad {
+            color,
+            center: 0.5 * Vec3::new(angle.sin(), angle.cos(), i as f32),
+            half_extents: Vec3::new(0.8, 0.8, 0.0),
+            billboard: Billboard::ViewY,
+            ..default()
+        });
+    }
+    app.insert_resource(quads);
+}
+
+/// A checker texture rotated and mirrored
+fn textured(app: &mut App) {
+    spawn_camera(app, Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO);
+    let image = app
+        .world
+        .resource_mut::<Assets<Image>>()
+        .add(checker_image());
+    let mut quads = Quads {
+        image: Some(image),
+        ..default()
+    };
+    for (i, (half_extents, uv_rotation)) in [
+        (Vec3::new(0.8, 0.8, 0.0), 0.0),
+        (Vec3::new(-0.8, 0.8, 0.0), 0.0),
+        (Vec3::new(0.8, 0.8, 0.0), FRAC_PI_4),
+        (Vec3::new(0.8, -0.8, 0.0), 0.0),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        quads.insert(Quad {
+            color: Color::WHITE,
+            center: Vec3::new((i % 2) as f32 * 1.8 - 0.9, (i / 2) as f32 * 1.8 - 0.9, 0.0),
+            half_extents,
+            uv_rotation,
+            billboard: Billboard::ViewY,
+            ..default()
+        });
+    }
+    app.insert_resource(quads);
+}
+
+/// Lit quads with highlights, and a double-sided translucent one lit from behind
+fn lit(app: &mut App) {
+    spawn_camera(app, Vec3::new(0.0, 0.0, 6.0), Vec3::ZERO);
+    app.world.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            illuminance: 3.0,
+            ..default()
+        },
+        transform: Transform::from_xyz(1.0, 2.0, 3.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+    app.world.spawn(PointLightBundle {
+        point_light: PointLight {
+            color: Color::rgb(1.0, 0.5, 0.2),
+            intensity: 200.0,
+            range: 10.0,
+            ..default()
+        },
+        transform: Transform::from_xyz(-1.0, -1.0, 1.5),
+        ..default()
+    });
+    let mut quads = Quads {
+        double_sided: true,
+        ..default()
+    };
+    for (i, (rotation, specular_power, translucency)) in [
+        (Quat::IDENTITY, 0.0, 0.0),
+        (Quat::from_rotation_y(0.6), 32.0, 0.0),
+        (Quat::from_rotation_x(-0.6), 8.0, 0.0),
+        (Quat::from_rotation_y(3.0), 0.0, 0.8),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        quads.insert(Quad {
+            color: Color::rgb(0.7, 0.8, 0.6),
+            center: Vec3::new((i % 2) as f32 * 2.0 - 1.0, (i / 2) as f32 * 2.0 - 1.0, 0.0),
+            half_extents: Vec3::new(0.8, 0.8, 0.0),
+            billboard: Billboard::OrientedY {
+                orientation: rotation,
+            },
+            lit: true,
+            specular_color: Color::WHITE,
+            specular_power,
+            translucency,
+            ..default()
+        });
+    }
+    app.insert_resource(quads);
+}
+
+/// Screen space quads over an orthographic view, like a 2D HUD
+fn overlay_2d(app: &mut App) {
+    app.world.spawn(Camera3dBundle {
+        projection: OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical(2.0),
+            ..default()
+        }
+        .into(),
+        transform: Transform::from_xyz(0.0, 0.0, 5.0),
+        ..default()
+    });
+    let mut quads = Quads::default();
+    quads.insert(Quad {
+        color: Color::rgb(0.2, 0.4, 0.8),
+        center: Vec3::new(0.0, 0.0, 0.0),
+        half_extents: Vec3::new(1.2, 0.6, 0.0),
+        billboard: Billboard::None,
+        ..default()
+    });
+    for i in 0..5 {
+        quads.insert(Quad {
+            color: Color::hsl(60.0 * i as f32, 0.8, 0.6),
+            center: Vec3::new(i as f32 * 0.4 - 0.8, 0.0, 1.0),
+            half_extents: Vec3::splat(4.0 + i as f32),
+            billboard: Billboard::FixedScreenSize,
+            ..default()
+        });
+    }
+    // A bar along the bottom of the view in normalized device coordinates
+    quads.insert(Quad {
+        color: Color::rgb(0.9, 0.9, 0.9),
+        center: Vec3::new(0.0, -0.85, 1.0),
+        half_extents: Vec3::new(0.9, 0.05, 0.0),
+        billboard: Billboard::ClipSpace,
+        ..default()
+    });
+    app.insert_resource(quads);
+}
+
+/// A row of quads approaching the camera, shrinking as they pass the near fade
+fn near_fade(app: &mut App) {
+    spawn_camera(app, Vec3::new(0.0, 0.5, 4.0), Vec3::new(0.0, 0.5, 0.0));
+    app.insert_resource(QuadsNearFade {
+        start: 3.0,
+        end: 1.0,
+    });
+    let mut quads = Quads::default();
+    for i in 0..8 {
+        quads.insert(Quad {
+            color: Color::hsl(40.0 * i as f32, 0.6, 0.5),
+            center: Vec3::new(0.6, 0.0, 3.5 - i as f32 * 0.6),
+            half_extents: Vec3::splat(0.25),
+            billboard: Billboard::ViewY,
+            ..default()
+        });
+    }
+    app.insert_resource(quads);
+}
+
+/// A grid of quads with seeded color and size variation and HSV shifts
+fn variation(app: &mut App) {
+    spawn_camera(app, Vec3::new(0.0, 0.0, 7.0), Vec3::ZERO);
+    let mut quads = Quads {
+        variation: Some(QuadsVariation {
+            hue: 30.0,
+            lightness: 0.1,
+            extent: 0.2,
+            ..default()
+        }),
+        ..default()
+    };
+    for y in 0..5 {
+        for x in 0..5 {
+            quads.insert(Quad {
+                color: Color::rgb(0.3, 0.6, 0.4),
+                center: Vec3::new(x as f32 - 2.0, y as f32 - 2.0, 0.0),
+                half_extents: Vec3::splat(0.4),
+                billboard: Billboard::ViewY,
+                hsv_shift: Vec3::new(0.1 * x as f32, 0.0, 0.05 * y as f32),
+                seed: 5 * y + x,
+                ..default()
+            });
+        }
+    }
+    app.insert_resource(quads);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // NOTE: Run by `cargo test --features test_support` like the integration tests, with the
+    // golden images committed in `tests/golden`
+    #[test]
+    fn scenes_match_the_golden_images() {
+        let update = std::env::var_os(UPDATE_GOLDEN_ENV).is_some();
+        let failures = run_scenes(&[], update);
+        assert_eq!(
+            failures, 0,
+            "{failures} scenes failed, see the output above"
+        );
+    }
+}
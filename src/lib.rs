@@ -1,5 +1,10 @@
 use bevy::prelude::Component;
 
+pub mod prelude;
+pub mod quads;
+#[cfg(feature = "test_support")]
+pub mod test_support;
+
 #[derive(Clone, Component, Default)]
 pub struct Instances<T> {
     pub values: Vec<T>,
@@ -0,0 +1,17 @@
+//! The types most apps drawing quads need, for `use bevy_vertex_pulling::prelude::*;`.
+//!
+//! Types named `Quad*` describe single quads and the ones named `Quads*` the [`Quads`] batch or
+//! the whole quads pass. The less common types, like the render graph nodes, are in
+//! [`quads`](crate::quads).
+
+pub use crate::quads::{
+    Billboard, Quad, QuadCluster, QuadCommand, QuadCurve, QuadGroupRecolor, QuadId, QuadPoint,
+    QuadStampId, Quads, QuadsAlphaMode, QuadsAnimation, QuadsAnimationMode, QuadsClear,
+    QuadsCommandQueue, QuadsCoordinateConvention, QuadsDebugView, QuadsDetail, QuadsDetailBlend,
+    QuadsEnabled, QuadsGlobalAlpha, QuadsGroupColors, QuadsNearFade, QuadsPlugin, QuadsPoints,
+    QuadsResolution, QuadsSamplerDesc, QuadsVariation, QuadsWriter,
+};
+#[allow(deprecated)]
+pub use crate::quads::{QuadGroupColors, QuadPoints, QuadVariation, QuadWriter};
+#[cfg(any(feature = "csv", feature = "parquet"))]
+pub use crate::quads::{QuadsColumnMapping, QuadsImportError, QuadsValueScale};
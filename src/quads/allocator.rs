@@ -0,0 +1,382 @@
+//! Sizing the GPU buffers of the quads, see [`QuadsAllocatorConfig`].
+//!
+//! The render world decides the capacity of a buffer with [`QuadsAllocatorConfig::allocate`]
+//! whenever its contents change and shares the [`QuadsAllocatorEvent`]s of the decision with the main
+//! world, which sends them as bevy events.
+
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{encase::internal::WriteInto, ShaderType, StorageBuffer},
+        renderer::{RenderDevice, RenderQueue},
+        RenderApp,
+    },
+};
+
+/// When a buffer releases the capacity its contents no longer need
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QuadsShrinkPolicy {
+    /// Keep the capacity, so batches that change size every frame are not reallocated over and
+    /// over. It is released by [`QuadsMemoryStats::shrink_to_fit`](super::QuadsMemoryStats::shrink_to_fit).
+    #[default]
+    Retain,
+    /// Reallocate a buffer as soon as its contents fit into a smaller one of
+    /// [`QuadsAllocatorConfig::growth_factor`] times their size. Buffers of empty batches are kept,
+    /// as empty storage buffers can't be bound.
+    Release,
+}
+
+/// How the buffers of the quad batches grow and shrink, see
+/// [`QuadsPlugin::allocator`](super::QuadsPlugin::allocator).
+///
+/// Every buffer is sized on its own: the instance data and index buffer of the
+/// [`Quads`](super::Quads), and the points, expanded quads and index buffer of the
+/// [`QuadsPoints`](super::QuadsPoints). The index buffers hold the indices of the first quads of a
+/// larger buffer as well, so they are only reallocated when they grow, shrink or the corners of
+/// the quads change.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuadsAllocatorConfig {
+    /// Factor the size of the contents is multiplied by for the capacity of a reallocated buffer,
+    /// so a growing batch isn't reallocated for every few quads. 1 by default, which allocates
+    /// the size of the contents. Smaller factors are treated as 1.
+    pub growth_factor: f32,
+    /// Most bytes a single buffer may allocate. The contents beyond it are dropped like the
+    /// quads beyond [`QuadsPlugin::max_quads`](super::QuadsPlugin::max_quads), and are counted by
+    /// [`QuadsPlugin::DROPPED_QUADS`](super::QuadsPlugin::DROPPED_QUADS). Unlimited by default.
+    pub max_retained_bytes: Option<u64>,
+    pub shrink_policy: QuadsShrinkPolicy,
+}
+
+impl Default for QuadsAllocatorConfig {
+    fn default() -> Self {
+        Self {
+            growth_factor: 1.0,
+            max_retained_bytes: None,
+            shrink_policy: QuadsShrinkPolicy::default(),
+        }
+    }
+}
+
+impl QuadsAllocatorConfig {
+    /// Most elements of `element_size` bytes a buffer may hold, `usize::MAX` without a budget
+    pub fn max_len(&self, element_size: u64) -> usize {
+        self.max_retained_bytes.map_or(usize::MAX, |bytes| {
+            usize::try_from(bytes / element_size.max(1)).unwrap_or(usize::MAX)
+        })
+    }
+
+    /// Decides the capacity of a buffer of elements of `element_size` bytes that has room for
+    /// `capacity` elements, when its contents change to `required` elements
+    pub fn allocate(&self, element_size: u64, capacity: usize, required: usize) -> QuadsAllocation {
+        let max_len = self.max_len(element_size);
+        let len = required.min(max_len);
+        // NOTE: f32::max ignores a NaN growth factor
+        let grown = ((len as f64 * f64::from(self.growth_factor.max(1.0))).ceil() as usize)
+            .clamp(len, max_len);
+        let released = self.shrink_policy == QuadsShrinkPolicy::Release && len > 0;
+        QuadsAllocation {
+            element_size,
+            previous: capacity,
+            capacity: if capacity < len || (released && grown < capacity) {
+                grown
+            } else {
+                capacity
+            },
+            required,
+            len,
+        }
+    }
+
+    /// Like [`QuadsAllocatorConfig::allocate`], but reallocates the buffer at the size of its
+    /// contents no matter the growth factor and shrink policy
+    pub fn fit(&self, element_size: u64, capacity: usize, required: usize) -> QuadsAllocation {
+        let len = required.min(self.max_len(element_size));
+        QuadsAllocation {
+            element_size,
+            previous: capacity,
+            capacity: len,
+            required,
+            len,
+        }
+    }
+}
+
+/// The capacity [`QuadsAllocatorConfig::allocate`] decided on for a buffer, in elements
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuadsAllocation {
+    pub element_size: u64,
+    /// Elements the buffer had room for before
+    pub previous: usize,
+    /// Elements the buffer has room for. It is reallocated when this differs from `previous`.
+    pub capacity: usize,
+    /// Elements of the contents
+    pub required: usize,
+    /// Elements uploaded, `required` unless it exceeds the budget of
+    /// [`QuadsAllocatorConfig::max_retained_bytes`]
+    pub len: usize,
+}
+
+impl QuadsAllocation {
+    /// Whether the buffer has to be reallocated
+    pub fn reallocates(&self) -> bool {
+        self.capacity != self.previous
+    }
+
+    /// Bytes of `elements` elements
+    fn bytes(&self, elements: usize) -> u64 {
+        elements as u64 * self.element_size
+    }
+
+    /// What the allocation did to `buffer`
+    pub fn events(&self, buffer: QuadsBuffer) -> impl Iterator<Item = QuadsAllocatorEvent> {
+        let overflow = (self.len < self.required).then_some(QuadsAllocatorEventKind::Overflow {
+            required: self.bytes(self.required),
+            uploaded: self.bytes(self.len),
+        });
+        let (from, to) = (self.bytes(self.previous), self.bytes(self.capacity));
+        let resize = match self.capacity.cmp(&self.previous) {
+            std::cmp::Ordering::Greater => Some(QuadsAllocatorEventKind::Grow { from, to }),
+            std::cmp::Ordering::Less => Some(QuadsAllocatorEventKind::Shrink { from, to }),
+            std::cmp::Ordering::Equal => None,
+        };
+        overflow
+            .into_iter()
+            .chain(resize)
+            .map(move |kind| QuadsAllocatorEvent { buffer, kind })
+    }
+}
+
+/// The buffer a [`QuadsAllocatorEvent`] is about
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum QuadsBuffer {
+    /// The instance data of the [`Quads`](super::Quads)
+    Instances,
+    /// The index buffer of the [`Quads`](super::Quads)
+    Indices,
+    /// The [`QuadsPoints::points`](super::QuadsPoints::points)
+    Points,
+    /// The quads expanded from the [`QuadsPoints`](super::QuadsPoints)
+    GeneratedInstances,
+    /// The index buffer of the quads expanded from the [`QuadsPoints`](super::QuadsPoints)
+    GeneratedIndices,
+}
+
+/// What an allocation did, sizes in bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QuadsAllocatorEventKind {
+    /// The buffer was reallocated with more capacity
+    Grow { from: u64, to: u64 },
+    /// The buffer was reallocated with less capacity, by the
+    /// [`QuadsShrinkPolicy::Release`] policy or
+    /// [`QuadsMemoryStats::shrink_to_fit`](super::QuadsMemoryStats::shrink_to_fit)
+    Shrink { from: u64, to: u64 },
+    /// The contents exceeded [`QuadsAllocatorConfig::max_retained_bytes`], only the first
+    /// `uploaded` bytes of them were uploaded
+    Overflow { required: u64, uploaded: u64 },
+}
+
+/// Sent in the main world for every reallocation of a quads buffer and every upload that
+/// exceeded its budget, in the frame after the render world made the allocation
+#[derive(Clone, Copy, Debug, Event, PartialEq, Eq)]
+pub struct QuadsAllocatorEvent {
+    pub buffer: QuadsBuffer,
+    pub kind: QuadsAllocatorEventKind,
+}
+
+/// Events of the render world's allocations until the main world sends them
+#[derive(Clone, Debug, Default, Resource)]
+pub(crate) struct SharedAllocatorEvents(Arc<Mutex<Vec<QuadsAllocatorEvent>>>);
+
+impl SharedAllocatorEvents {
+    /// Records the events of `allocation` for `buffer` and returns it
+    pub(crate) fn record(
+        &self,
+        buffer: QuadsBuffer,
+        allocation: QuadsAllocation,
+    ) -> QuadsAllocation {
+        self.0.lock().unwrap().extend(allocation.events(buffer));
+        allocation
+    }
+}
+
+/// Writes `buffer`, reallocating it with room for `allocation.capacity` elements of the array
+/// `array` returns if the allocation changed its capacity. The array is padded with `padding`
+/// for the write and truncated to its length again afterwards.
+pub(crate) fn write_allocated<A, T: Clone>(
+    buffer: &mut StorageBuffer<A>,
+    array: impl Fn(&mut A) -> &mut Vec<T>,
+    padding: T,
+    allocation: &QuadsAllocation,
+    render_device: &RenderDevice,
+    render_queue: &RenderQueue,
+) where
+    A: ShaderType + WriteInto + Default,
+{
+    if !allocation.reallocates() {
+        buffer.write_buffer(render_device, render_queue);
+        return;
+    }
+    // NOTE: A new storage buffer always creates its buffer on the first write, while the old
+    // one would write a smaller array into its larger buffer
+    let label = buffer.get_label().map(str::to_owned);
+    *buffer = StorageBuffer::from(std::mem::take(buffer.get_mut()));
+    buffer.set_label(label.as_deref());
+    let len = array(buffer.get_mut()).len();
+    array(buffer.get_mut()).resize(allocation.capacity.max(len), padding);
+    buffer.write_buffer(render_device, render_queue);
+    array(buffer.get_mut()).truncate(len);
+}
+
+pub(crate) struct AllocatorPlugin;
+
+impl Plugin for AllocatorPlugin {
+    fn build(&self, app: &mut App) {
+        let shared = SharedAllocatorEvents::default();
+        app.add_event::<QuadsAllocatorEvent>()
+            .insert_resource(shared.clone())
+            .add_systems(PostUpdate, send_allocator_events);
+        app.sub_app_mut(RenderApp).insert_resource(shared);
+    }
+}
+
+fn send_allocator_events(
+    shared: Res<SharedAllocatorEvents>,
+    mut events: EventWriter<QuadsAllocatorEvent>,
+) {
+    let mut shared = shared.0.lock().unwrap();
+    for event in shared.drain(..) {
+        debug!("{:?} {:?}", event.buffer, event.kind);
+        events.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bytes of an instance in the tests
+    const SIZE: u64 = 16;
+
+    fn events(allocation: &QuadsAllocation) -> Vec<QuadsAllocatorEventKind> {
+        allocation
+            .events(QuadsBuffer::Instances)
+            .map(|event| event.kind)
+            .collect()
+    }
+
+    #[test]
+    fn grows_by_the_growth_factor() {
+        let config = QuadsAllocatorConfig {
+            growth_factor: 1.5,
+            ..default()
+        };
+        let allocation = config.allocate(SIZE, 0, 100);
+        assert_eq!(allocation.capacity, 150);
+        assert_eq!(
+            events(&allocation),
+            [QuadsAllocatorEventKind::Grow { from: 0, to: 2400 }]
+        );
+        // Room to grow into keeps the buffer
+        let allocation = config.allocate(SIZE, 150, 120);
+        assert!(!allocation.reallocates());
+        assert!(events(&allocation).is_empty());
+        assert_eq!(config.allocate(SIZE, 150, 151).capacity, 227);
+    }
+
+    #[test]
+    fn retains_capacity_by_default() {
+        let config = QuadsAllocatorConfig::default();
+        assert_eq!(config.allocate(SIZE, 0, 100).capacity, 100);
+        let allocation = config.allocate(SIZE, 100, 10);
+        assert_eq!(allocation.capacity, 100);
+        assert_eq!(allocation.len, 10);
+        assert!(!allocation.reallocates());
+    }
+
+    #[test]
+    fn release_shrinks_to_the_grown_size() {
+        let config = QuadsAllocatorConfig {
+            growth_factor: 1.5,
+            shrink_policy: QuadsShrinkPolicy::Release,
+            ..default()
+        };
+        let allocation = config.allocate(SIZE, 150, 10);
+        assert_eq!(allocation.capacity, 15);
+        assert_eq!(
+            events(&allocation),
+            [QuadsAllocatorEventKind::Shrink {
+                from: 2400,
+                to: 240
+            }]
+        );
+        // Contents that would grow past the capacity again keep the buffer
+        assert!(!config.allocate(SIZE, 150, 120).reallocates());
+        // Empty storage buffers can't be bound
+        assert!(!config.allocate(SIZE, 150, 0).reallocates());
+    }
+
+    #[test]
+    fn overflows_the_budget() {
+        let config = QuadsAllocatorConfig {
+            growth_factor: 2.0,
+            max_retained_bytes: Some(100 * SIZE + SIZE / 2),
+            ..default()
+        };
+        assert_eq!(config.max_len(SIZE), 100);
+        let allocation = config.allocate(SIZE, 0, 250);
+        assert_eq!((allocation.len, allocation.capacity), (100, 100));
+        assert_eq!(
+            events(&allocation),
+            [
+                QuadsAllocatorEventKind::Overflow {
+                    required: 4000,
+                    uploaded: 1600
+                },
+                QuadsAllocatorEventKind::Grow { from: 0, to: 1600 },
+            ]
+        );
+        // The growth is clamped to the budget as well
+        assert_eq!(config.allocate(SIZE, 0, 80).capacity, 100);
+    }
+
+    #[test]
+    fn nan_and_small_growth_factors_grow_by_one() {
+        for growth_factor in [f32::NAN, 0.5, -2.0] {
+            let config = QuadsAllocatorConfig {
+                growth_factor,
+                ..default()
+            };
+            assert_eq!(
+                config.allocate(SIZE, 0, 10).capacity,
+                10,
+                "growth factor {growth_factor}"
+            );
+        }
+    }
+
+    #[test]
+    fn fits_the_contents() {
+        let config = QuadsAllocatorConfig {
+            growth_factor: 2.0,
+            max_retained_bytes: Some(100 * SIZE),
+            ..default()
+        };
+        let allocation = config.fit(SIZE, 150, 10);
+        assert_eq!(allocation.capacity, 10);
+        assert_eq!(
+            events(&allocation),
+            [QuadsAllocatorEventKind::Shrink {
+                from: 2400,
+                to: 160
+            }]
+        );
+        assert!(!config.fit(SIZE, 10, 10).reallocates());
+        let allocation = config.fit(SIZE, 0, 250);
+        assert_eq!((allocation.len, allocation.capacity), (100, 100));
+    }
+}
@@ -0,0 +1,186 @@
+//! Batch-wide keyframe animations evaluated in the vertex shader, e.g. pulsing or fading markers,
+//! so animated batches need no buffer writes while they play.
+
+use std::fmt;
+
+use bevy::{prelude::*, render::render_resource::ShaderType};
+
+/// Maximum number of keys of a [`QuadCurve`]
+pub const QUAD_CURVE_MAX_KEYS: usize = 8;
+
+/// Keyframes of one track of a [`QuadsAnimation`], linearly interpolated between the keys and
+/// holding the first and last value before and after them
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuadCurve {
+    keys: Vec<(f32, f32)>,
+}
+
+/// Why the keys passed to [`QuadCurve::new`] were rejected
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum QuadCurveError {
+    Empty,
+    /// More than [`QUAD_CURVE_MAX_KEYS`] keys
+    TooManyKeys(usize),
+    /// The time of the key at the index is infinite or NaN
+    NotFinite(usize),
+    /// The key at the index is not later than the key before it
+    NotSorted(usize),
+}
+
+impl fmt::Display for QuadCurveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "quad curve has no keys"),
+            Self::TooManyKeys(count) => write!(
+                f,
+                "quad curve has {count} keys, at most {QUAD_CURVE_MAX_KEYS} are supported"
+            ),
+            Self::NotFinite(index) => write!(f, "quad curve key {index} has no finite time"),
+            Self::NotSorted(index) => write!(
+                f,
+                "quad curve key {index} is not later than the key before it"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QuadCurveError {}
+
+impl QuadCurve {
+    /// A curve through `(time, value)` keys, with the times in seconds from the start of the
+    /// animation. There must be 1 to [`QUAD_CURVE_MAX_KEYS`] keys in order of strictly increasing
+    /// time.
+    pub fn new(keys: impl Into<Vec<(f32, f32)>>) -> Result<Self, QuadCurveError> {
+        let keys = keys.into();
+        if keys.is_empty() {
+            return Err(QuadCurveError::Empty);
+        }
+        if keys.len() > QUAD_CURVE_MAX_KEYS {
+            return Err(QuadCurveError::TooManyKeys(keys.len()));
+        }
+        if let Some(index) = keys.iter().position(|(time, _)| !time.is_finite()) {
+            return Err(QuadCurveError::NotFinite(index));
+        }
+        if let Some(index) = keys.windows(2).position(|pair| pair[1].0 <= pair[0].0) {
+            return Err(QuadCurveError::NotSorted(index + 1));
+        }
+        Ok(Self { keys })
+    }
+
+    pub fn keys(&self) -> &[(f32, f32)] {
+        &self.keys
+    }
+
+    /// Time of the last key
+    pub fn end_time(&self) -> f32 {
+        self.keys.last().unwrap().0
+    }
+}
+
+/// How a [`QuadsAnimation`] continues after its last key
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QuadsAnimationMode {
+    /// Starts over from the beginning
+    #[default]
+    Loop,
+    /// Plays backwards to the beginning, then forwards again
+    PingPong,
+    /// Holds the last values
+    Once,
+}
+
+/// Keyframed tracks animating every quad of a batch alike, see
+/// [`Quads::animation`](super::Quads::animation) and
+/// [`QuadsPoints::animation`](super::QuadsPoints::animation). Tracks that are `None` leave the quads
+/// unchanged. The animation lasts until the latest last key of its tracks.
+#[derive(Clone, Debug, Default)]
+pub struct QuadsAnimation {
+    /// Multiplies the half-extents
+    pub scale: Option<QuadCurve>,
+    /// Multiplies the alpha, and the color too when the [`Quads::alpha_mode`](super::Quads::alpha_mode)
+    /// premultiplies it
+    pub alpha: Option<QuadCurve>,
+    /// Rotates the hue of the color by this many degrees
+    pub hue_shift: Option<QuadCurve>,
+    /// Added to the [`Quad::dissolve`](super::Quad::dissolve) threshold, e.g. to burn a batch
+    /// away. Only batches with a [`Quads::dissolve`](super::Quads::dissolve) dissolve.
+    pub dissolve: Option<QuadCurve>,
+    pub mode: QuadsAnimationMode,
+    /// Time the animation starts at, in seconds of `Time::elapsed_seconds_wrapped` which the
+    /// shader's clock follows. The tracks hold their first values until then.
+    pub start_time: f32,
+}
+
+impl QuadsAnimation {
+    /// The tracks in the order the shader reads them
+    fn tracks(&self) -> [&Option<QuadCurve>; 4] {
+        [&self.scale, &self.alpha, &self.hue_shift, &self.dissolve]
+    }
+
+    fn duration(&self) -> f32 {
+        self.tracks()
+            .into_iter()
+            .flatten()
+            .map(QuadCurve::end_time)
+            .fold(0.0, f32::max)
+    }
+}
+
+/// The tracks of a [`QuadsAnimation`] as read by the vertex shader
+#[derive(Clone, ShaderType)]
+pub(crate) struct GpuBatchAnimation {
+    /// The time and value of every key in xy, with the keys of the scale, alpha, hue shift and
+    /// dissolve tracks [`QUAD_CURVE_MAX_KEYS`] apart
+    keys: [Vec4; 4 * QUAD_CURVE_MAX_KEYS],
+    /// Number of keys of every track, 0 for tracks that are not animated
+    key_counts: UVec4,
+    mode: u32,
+    start_time: f32,
+    duration: f32,
+    /// Whether the alpha track scales the color too
+    premultiplied: u32,
+}
+
+impl Default for GpuBatchAnimation {
+    fn default() -> Self {
+        Self {
+            keys: [Vec4::ZERO; 4 * QUAD_CURVE_MAX_KEYS],
+            key_counts: UVec4::ZERO,
+            mode: 0,
+            start_time: 0.0,
+            duration: 0.0,
+            premultiplied: 0,
+        }
+    }
+}
+
+impl GpuBatchAnimation {
+    pub(crate) fn new(animation: &QuadsAnimation, premultiplied: bool) -> Self {
+        let mut gpu_animation = Self {
+            mode: animation.mode as u32,
+            start_time: animation.start_time,
+            duration: animation.duration(),
+            premultiplied: premultiplied.into(),
+            ..default()
+        };
+        for (track, curve) in animation.tracks().into_iter().enumerate() {
+            let Some(curve) = curve else {
+                continue;
+            };
+            gpu_animation.key_counts[track] = curve.keys.len() as u32;
+            let keys = &mut gpu_animation.keys[track * QUAD_CURVE_MAX_KEYS..];
+            for (gpu_key, &(time, value)) in keys.iter_mut().zip(&curve.keys) {
+                // NOTE: The shader rotates hues in radians
+                let value = if track == 2 {
+                    value.to_radians()
+                } else {
+                    value
+                };
+                *gpu_key = Vec4::new(time, value, 0.0, 0.0);
+            }
+        }
+        gpu_animation
+    }
+}
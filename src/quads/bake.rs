@@ -0,0 +1,151 @@
+//! Baking static [`Quads`] into a GPU-only batch.
+//!
+//! [`Quads::bake`] moves [`Quads::data`] into a [`BakedQuads`] that is shared with the render
+//! world. The quads are uploaded once and then dropped, so neither world keeps a CPU copy of a
+//! large static dataset, and later changes to the other batch settings like the image no longer
+//! convert and upload every quad again.
+//!
+//! The render world converts and uploads baked quads a chunk at a time, and
+//! [`Quads::bake_compressed`] decodes a compressed file straight into those chunks, so a baked
+//! dataset never has to fit into memory at once.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex, Weak},
+};
+
+use bevy::prelude::*;
+
+use super::{Quad, Quads};
+
+/// Number of quads converted and uploaded at a time
+pub(crate) const BAKE_CHUNK: usize = 64 * 1024;
+
+/// Why baked quads could not be uploaded
+#[cfg(feature = "compression")]
+pub(crate) type BakeError = super::QuadsFileError;
+#[cfg(not(feature = "compression"))]
+pub(crate) type BakeError = std::convert::Infallible;
+
+/// Where baked quads are read from when they are uploaded
+pub(crate) enum BakedSource {
+    Quads(Vec<Quad>),
+    #[cfg(feature = "compression")]
+    File(super::file::QuadsFileReader<std::fs::File>),
+}
+
+impl BakedSource {
+    /// Calls `upload` with the index of the first quad of every chunk of at most [`BAKE_CHUNK`]
+    /// quads and the chunk. A file is read to its end, as its checksum only covers all quads.
+    pub(crate) fn for_each_chunk(
+        self,
+        mut upload: impl FnMut(usize, &[Quad]),
+    ) -> Result<(), BakeError> {
+        match self {
+            Self::Quads(quads) => {
+                for (i, chunk) in quads.chunks(BAKE_CHUNK).enumerate() {
+                    upload(i * BAKE_CHUNK, chunk);
+                }
+            }
+            #[cfg(feature = "compression")]
+            Self::File(mut reader) => {
+                let mut chunk = Vec::new();
+                let mut first = 0;
+                while reader.read_chunk(&mut chunk)? {
+                    upload(first, &chunk);
+                    first += chunk.len();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Quads moved out of [`Quads::data`] by [`Quads::bake`], until the render world uploads them
+#[derive(Clone)]
+pub struct BakedQuads {
+    source: Arc<Mutex<Option<BakedSource>>>,
+    len: usize,
+}
+
+impl fmt::Debug for BakedQuads {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BakedQuads")
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BakedQuads {
+    /// Number of baked quads, also after they were uploaded
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub(crate) fn new(source: BakedSource, len: usize) -> Self {
+        Self {
+            source: Arc::new(Mutex::new(Some(source))),
+            len,
+        }
+    }
+
+    /// The quads if they have not been uploaded yet, leaving `None` for the next call
+    pub(crate) fn take(&self) -> Option<BakedSource> {
+        self.source.lock().unwrap().take()
+    }
+}
+
+impl Quads {
+    /// Freezes the quads for a static dataset. `data` is moved into [`Quads::baked`] and uploaded
+    /// once, together with the [`Quads::variation`], [`Quads::convention`] and
+    /// [`Quads::alpha_mode`] at that time, after which the quads only exist on the GPU. The other settings keep working as before.
+    ///
+    /// Quads added to `data` afterwards are not drawn and warned about. Baking again replaces the
+    /// baked quads with the ones in `data`, and setting `baked` to `None` draws `data` again.
+    /// [`QuadId`](super::QuadId)s are invalidated, and baked quads can't be saved, converted to a
+    /// mesh or read back.
+    pub fn bake(&mut self) {
+        let quads = std::mem::take(&mut self.data);
+        self.slots = default();
+        let len = quads.len();
+        self.baked = Some(BakedQuads::new(BakedSource::Quads(quads), len));
+    }
+
+    /// Number of quads drawn, the baked ones for a baked batch and the ones in `data` otherwise
+    pub fn len(&self) -> usize {
+        match &self.baked {
+            Some(baked) => baked.len(),
+            None => self.data.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Warns once per bake when quads are added to a baked batch, as they are not drawn
+pub(crate) fn diagnose_baked_quads(
+    quads: Option<Res<Quads>>,
+    mut warned: Local<Weak<Mutex<Option<BakedSource>>>>,
+) {
+    let Some(quads) = quads else {
+        return;
+    };
+    let Some(baked) = &quads.baked else {
+        return;
+    };
+    let baked_quads = Arc::downgrade(&baked.source);
+    if !quads.data.is_empty() && !warned.ptr_eq(&baked_quads) {
+        *warned = baked_quads;
+        warn!(
+            "{} quads were added to a baked Quads and are not rendered, call Quads::bake again to \
+            replace the baked quads",
+            quads.data.len()
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Uncompressed binary blobs of [`GpuQuad`] records, for large precomputed datasets that are
+//! memory-mapped or streamed in at startup without parsing every quad.
+
+use std::fmt;
+
+use bevy::render::render_resource::ShaderSize;
+use bytemuck::{cast_slice, pod_read_unaligned, try_cast_slice};
+
+use super::{GpuQuad, Quad, Quads};
+
+/// Size of one record in bytes
+const RECORD_SIZE: usize = std::mem::size_of::<GpuQuad>();
+
+// NOTE: The records are the instance data as is, so they must not contain any padding
+const _: () = assert!(RECORD_SIZE as u64 == GpuQuad::SHADER_SIZE.get());
+
+/// The blob passed to [`Quads::from_bytes`] is not a whole number of records
+#[derive(Debug)]
+pub struct QuadsBytesError {
+    pub len: usize,
+}
+
+impl fmt::Display for QuadsBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "quads blob of {} bytes is not a whole number of {RECORD_SIZE} byte records",
+            self.len
+        )
+    }
+}
+
+impl std::error::Error for QuadsBytesError {}
+
+impl Quads {
+    /// Decodes quads from a blob in the layout described in [`Quads::to_bytes`]. Blobs aligned to
+    /// 16 bytes, like memory-mapped files, are read in place, others are copied one record at a
+    /// time.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Quads, QuadsBytesError> {
+        if !bytes.len().is_multiple_of(RECORD_SIZE) {
+            return Err(QuadsBytesError { len: bytes.len() });
+        }
+        let mut quads = Quads::default();
+        match try_cast_slice::<u8, GpuQuad>(bytes) {
+            Ok(records) if cfg!(target_endian = "little") => {
+                quads.data.extend(records.iter().map(Quad::from));
+            }
+            _ => {
+                quads.data.extend(
+                    bytes
+                        .chunks_exact(RECORD_SIZE)
+                        .map(|record| Quad::from(&GpuQuad::le_swapped(pod_read_unaligned(record)))),
+                );
+            }
+        }
+        Ok(quads)
+    }
+
+    /// Encodes the quads as a blob of their instance data, 128 bytes per quad back to back in the
+    /// layout documented on [`GpuQuad`], with every `f32` and `u32` in little-endian byte order.
+    /// There is no header, the number of quads is the length of the blob divided by 128.
+    ///
+    /// Unlike the compressed quads format the blob follows the instance data, so it is not
+    /// readable by versions of the crate with a different layout. The variation, coordinate
+    /// convention and the alpha mode are not applied, like in the compressed format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.data.len() * RECORD_SIZE);
+        for quad in &self.data {
+            let record = GpuQuad::from(quad).le_swapped();
+            bytes.extend_from_slice(cast_slice(std::slice::from_ref(&record)));
+        }
+        bytes
+    }
+}
+
+impl GpuQuad {
+    /// Swaps the bytes of every field on big-endian targets, converting between native and
+    /// little-endian records in either direction. Does nothing on little-endian targets.
+    fn le_swapped(self) -> Self {
+        if cfg!(target_endian = "little") {
+            return self;
+        }
+        let mut words: [u32; RECORD_SIZE / 4] = bytemuck::cast(self);
+        for word in &mut words {
+            *word = word.swap_bytes();
+        }
+        bytemuck::cast(words)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::quads::Billboard;
+
+    fn quads() -> Quads {
+        Quads {
+            data: vec![
+                Quad {
+                    center: Vec3::new(1.0, -2.0, 3.5),
+                    half_extents: Vec3::new(0.5, 0.25, 0.0),
+                    color: Color::rgba(0.25, 0.5, 0.75, 0.5),
+                    ..default()
+                },
+                Quad {
+                    center: Vec3::new(-100.0, 0.0, 1e6),
+                    half_extents: Vec3::new(4.0, 8.0, 0.0),
+                    billboard: Billboard::ViewY,
+                    ..default()
+                },
+                Quad {
+                    half_extents: Vec3::new(10.0, 10.0, 0.0),
+                    billboard: Billboard::ViewYMinScreenSize {
+                        min_half_extent: 2.0,
+                    },
+                    hidden: true,
+                    ..default()
+                },
+            ],
+            ..default()
+        }
+    }
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let quads = quads();
+        let bytes = quads.to_bytes();
+        assert_eq!(bytes.len(), quads.data.len() * RECORD_SIZE);
+
+        let decoded = Quads::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.data.len(), quads.data.len());
+        for (decoded, quad) in decoded.data.iter().zip(&quads.data) {
+            assert_eq!(decoded.center, quad.center);
+            assert_eq!(decoded.half_extents, quad.half_extents);
+            assert_eq!(decoded.hidden, quad.hidden);
+            assert!(matches!(
+                (&decoded.billboard, &quad.billboard),
+                (Billboard::None, Billboard::None)
+                    | (Billboard::ViewY, Billboard::ViewY)
+                    | (
+                        Billboard::ViewYMinScreenSize { .. },
+                        Billboard::ViewYMinScreenSize { .. }
+                    )
+            ));
+        }
+        assert_eq!(decoded.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn round_trips_empty_quads() {
+        let bytes = Quads::default().to_bytes();
+        assert!(bytes.is_empty());
+        assert!(Quads::from_bytes(&bytes).unwrap().data.is_empty());
+    }
+
+    #[test]
+    fn encodes_little_endian_records() {
+        let bytes = quads().to_bytes();
+        // NOTE: The center is the first field of a record
+        assert_eq!(bytes[0..4], 1.0f32.to_le_bytes());
+        assert_eq!(bytes[4..8], (-2.0f32).to_le_bytes());
+        assert_eq!(
+            bytes[RECORD_SIZE..RECORD_SIZE + 4],
+            (-100.0f32).to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn decodes_unaligned_blobs() {
+        let bytes = quads().to_bytes();
+        let mut shifted = vec![0];
+        shifted.extend_from_slice(&bytes);
+        // NOTE: One of the two slices is not 16 byte aligned and takes the copying path
+        for blob in [&bytes[..], &shifted[1..]] {
+            assert_eq!(Quads::from_bytes(blob).unwrap().to_bytes(), bytes);
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_blobs() {
+        let bytes = quads().to_bytes();
+        for len in [
+            1,
+            RECORD_SIZE - 1,
+            bytes.len() - 1,
+            bytes.len() - RECORD_SIZE / 2,
+        ] {
+            let err = Quads::from_bytes(&bytes[..len]).unwrap_err();
+            assert_eq!(err.len, len);
+        }
+    }
+
+    #[test]
+    fn rejects_blobs_of_partial_records() {
+        let mut bytes = quads().to_bytes();
+        bytes.extend_from_slice(&[0; 4]);
+        let err = Quads::from_bytes(&bytes).unwrap_err();
+        assert_eq!(err.len, 3 * RECORD_SIZE + 4);
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "quads blob of {} bytes is not a whole number of 128 byte records",
+                3 * RECORD_SIZE + 4
+            )
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Changing [`Quads`] from other threads, e.g. a networking thread receiving entity updates.
+//!
+//! Commands pushed to a [`QuadsCommandQueue`] from any thread are applied to the [`Quads`]
+//! resource in the order they were pushed, all at once at the start of every frame in
+//! `PreUpdate`, so systems in `Update` see their result.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{Quad, QuadId, Quads};
+
+/// A change to the [`Quads`] resource pushed to a [`QuadsCommandQueue`].
+///
+/// Other threads can't know the [`QuadId`] a quad will get, so the commands address quads by keys
+/// of the caller's choice instead, like the network ids of the entities the quads belong to. The
+/// ids the keys map to are kept in [`QuadCommandKeys`]. Commands with keys that have no quad are
+/// ignored.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum QuadCommand {
+    /// Inserts a quad under `key`, replacing the quad already inserted under it
+    Insert {
+        key: u64,
+        quad: Quad,
+    },
+    /// Replaces the quad under `key`
+    Set {
+        key: u64,
+        quad: Quad,
+    },
+    Remove {
+        key: u64,
+    },
+    SetColor {
+        key: u64,
+        color: Color,
+    },
+    SetCenter {
+        key: u64,
+        center: Vec3,
+    },
+    /// Removes all quads inserted by commands. Quads inserted in other ways are left in place.
+    Clear,
+}
+
+#[derive(Debug, Default)]
+struct SharedQueue {
+    commands: Mutex<VecDeque<QuadCommand>>,
+    /// Signalled when the queue is drained, to wake up pushes waiting for space
+    drained: Condvar,
+    /// `None` for unbounded queues
+    capacity: Option<usize>,
+}
+
+/// Commands changing the [`Quads`] resource that can be pushed from any thread, see
+/// [`QuadCommand`]. Clones push to the same queue.
+///
+/// The queue is unbounded by default. Insert a [`QuadsCommandQueue::bounded`] queue before adding
+/// the [`QuadsPlugin`](super::QuadsPlugin) to limit how many commands can wait for the next
+/// frame, so producers that outpace the app are slowed down instead of piling up commands.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct QuadsCommandQueue {
+    shared: Arc<SharedQueue>,
+}
+
+impl QuadsCommandQueue {
+    /// A queue holding at most `capacity` commands, at least one
+    pub fn bounded(capacity: usize) -> Self {
+        Self {
+            shared: Arc::new(SharedQueue {
+                capacity: Some(capacity.max(1)),
+                ..default()
+            }),
+        }
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.shared.capacity
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<QuadCommand>> {
+        // NOTE: A producer that panicked while pushing leaves the commands pushed before intact
+        self.shared
+            .commands
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn is_full(&self, commands: &VecDeque<QuadCommand>) -> bool {
+        self.shared
+            .capacity
+            .is_some_and(|capacity| commands.len() >= capacity)
+    }
+
+    /// Appends a command, waiting for the next frame to drain the queue while a bounded queue is
+    /// full. Must not be called from the thread running the app's schedule, which would wait for
+    /// itself.
+    pub fn push(&self, command: QuadCommand) {
+        let mut commands = self.lock();
+        while self.is_full(&commands) {
+            commands = self
+                .shared
+                .drained
+                .wait(commands)
+                .unwrap_or_else(|err| err.into_inner());
+        }
+        commands.push_back(command);
+    }
+
+    /// Appends a command unless a bounded queue is full, in which case the command is returned
+    // NOTE: Boxing the returned command would allocate for every push into a full queue
+    #[allow(clippy::result_large_err)]
+    pub fn try_push(&self, command: QuadCommand) -> Result<(), QuadCommand> {
+        let mut commands = self.lock();
+        if self.is_full(&commands) {
+            return Err(command);
+        }
+        commands.push_back(command);
+        Ok(())
+    }
+
+    /// Number of commands waiting for the next frame
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Applies all queued commands in order, returning whether any quad changed. Called every
+    /// frame by the [`QuadsPlugin`](super::QuadsPlugin), but can be called directly for queues
+    /// that are not the resource.
+    pub fn apply(&self, quads: &mut Quads, keys: &mut QuadCommandKeys) -> bool {
+        let commands = std::mem::take(&mut *self.lock());
+        self.shared.drained.notify_all();
+        let mut changed = false;
+        for command in commands {
+            changed |= keys.apply(quads, command);
+        }
+        changed
+    }
+}
+
+/// The [`QuadId`]s of the quads inserted by [`QuadCommand`]s, by their keys
+#[derive(Clone, Debug, Default, Resource)]
+pub struct QuadCommandKeys {
+    ids: HashMap<u64, QuadId>,
+}
+
+impl QuadCommandKeys {
+    pub fn get(&self, key: u64) -> Option<QuadId> {
+        self.ids.get(&key).copied()
+    }
+
+    /// Number of quads inserted by commands
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u64, QuadId)> + '_ {
+        self.ids.iter().map(|(&key, &id)| (key, id))
+    }
+
+    fn quad<'a>(&self, quads: &'a mut Quads, key: u64) -> Option<&'a mut Quad> {
+        self.get(key).and_then(|id| quads.get_mut(id))
+    }
+
+    fn apply(&mut self, quads: &mut Quads, command: QuadCommand) -> bool {
+        match command {
+            QuadCommand::Insert { key, quad } => {
+                match self.quad(quads, key) {
+                    Some(slot) => *slot = quad,
+                    None => {
+                        self.ids.insert(key, quads.insert(quad));
+                    }
+                }
+                true
+            }
+            QuadCommand::Set { key, quad } => {
+                self.quad(quads, key).map(|slot| *slot = quad).is_some()
+            }
+            QuadCommand::Remove { key } => self
+                .ids
+                .remove(&key)
+                .and_then(|id| quads.remove(id))
+                .is_some(),
+            QuadCommand::SetColor { key, color } => self
+                .quad(quads, key)
+                .map(|slot| slot.color = color)
+                .is_some(),
+            QuadCommand::SetCenter { key, center } => self
+                .quad(quads, key)
+                .map(|slot| slot.center = center)
+                .is_some(),
+            QuadCommand::Clear => {
+                let mut changed = false;
+                for (_, id) in self.ids.drain() {
+                    changed |= quads.remove(id).is_some();
+                }
+                changed
+            }
+        }
+    }
+}
+
+pub(crate) fn apply_quad_commands(
+    queue: Res<QuadsCommandQueue>,
+    mut keys: ResMut<QuadCommandKeys>,
+    quads: Option<ResMut<Quads>>,
+) {
+    // NOTE: The commands wait in the queue until the quads exist
+    let Some(mut quads) = quads else {
+        return;
+    };
+    // NOTE: Only flag the quads as changed when a command changed them, any change triggers a
+    // full upload
+    if queue.apply(quads.bypass_change_detection(), &mut keys) {
+        quads.set_changed();
+    }
+}
@@ -0,0 +1,188 @@
+//! Conversion of quads authored in another engine's coordinate system.
+//!
+//! The conversion is applied when the quads are uploaded, like the
+//! [`Quads::variation`](super::Quads::variation), so the authored data keeps its convention.
+
+use bevy::prelude::*;
+
+use super::{Billboard, Quad};
+
+/// Coordinate system the quads of a [`Quads`](super::Quads) batch are authored in. Bevy's own is
+/// y up and right-handed, with x to the right and z pointing toward the viewer.
+///
+/// Only positions and rotations are converted. The quads are still built in bevy's coordinates
+/// on the GPU, so their winding, and with it culling, needs no flip for left-handed data, and
+/// [`Billboard::WorldY`] turns around the up axis of every convention, which is z for the z up
+/// ones. Half-extents, spins and texture rotations are relative to the quad and stay as they are,
+/// [`Billboard::None`] quads keep facing bevy's +z, and [`Billboard::ClipSpace`] quads are
+/// placed in normalized device coordinates and are not converted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QuadsCoordinateConvention {
+    #[default]
+    YUpRightHanded,
+    /// Z up with y pointing away from the viewer, like Blender. Bevy's y is the authored z and
+    /// bevy's z the negated authored y.
+    ZUpRightHanded,
+    /// Z up with y pointing toward the viewer, the authored y and z are swapped
+    ZUpLeftHanded,
+}
+
+impl QuadsCoordinateConvention {
+    /// Converts a position or direction in this convention to bevy's
+    pub fn to_bevy(self, v: Vec3) -> Vec3 {
+        match self {
+            Self::YUpRightHanded => v,
+            Self::ZUpRightHanded => Vec3::new(v.x, v.z, -v.y),
+            Self::ZUpLeftHanded => Vec3::new(v.x, v.z, v.y),
+        }
+    }
+
+    /// Converts a position or direction in bevy's convention to this one, the inverse of
+    /// [`QuadsCoordinateConvention::to_bevy`]
+    pub fn from_bevy(self, v: Vec3) -> Vec3 {
+        match self {
+            Self::YUpRightHanded => v,
+            Self::ZUpRightHanded => Vec3::new(v.x, -v.z, v.y),
+            Self::ZUpLeftHanded => Vec3::new(v.x, v.z, v.y),
+        }
+    }
+
+    /// Matrix of [`QuadsCoordinateConvention::to_bevy`], orthogonal so its inverse is its transpose
+    fn basis(self) -> Mat3 {
+        Mat3::from_cols(
+            self.to_bevy(Vec3::X),
+            self.to_bevy(Vec3::Y),
+            self.to_bevy(Vec3::Z),
+        )
+    }
+
+    /// Converts a rotation in this convention to the rotation with the same effect in bevy's. The
+    /// up axis of the convention is rotated onto bevy's y, so the rotated up axis of a
+    /// [`Billboard::OrientedY`] orientation stays its up axis.
+    pub fn rotation_to_bevy(self, rotation: Quat) -> Quat {
+        if self == Self::YUpRightHanded {
+            return rotation;
+        }
+        // NOTE: A change of basis. Mirroring twice for left-handed data keeps it a rotation.
+        let basis = self.basis();
+        Quat::from_mat3(&(basis * Mat3::from_quat(rotation) * basis.transpose())).normalize()
+    }
+
+    /// Converts a rotation in bevy's convention to this one, the inverse of
+    /// [`QuadsCoordinateConvention::rotation_to_bevy`]
+    pub fn rotation_from_bevy(self, rotation: Quat) -> Quat {
+        if self == Self::YUpRightHanded {
+            return rotation;
+        }
+        let basis = self.basis();
+        Quat::from_mat3(&(basis.transpose() * Mat3::from_quat(rotation) * basis)).normalize()
+    }
+
+    /// Returns a copy of `quad` converted to bevy's convention
+    pub fn quad_to_bevy(self, quad: &Quad) -> Quad {
+        self.convert(quad, Self::to_bevy, Self::rotation_to_bevy)
+    }
+
+    /// Returns a copy of `quad` converted from bevy's convention, the inverse of
+    /// [`QuadsCoordinateConvention::quad_to_bevy`]
+    pub fn quad_from_bevy(self, quad: &Quad) -> Quad {
+        self.convert(quad, Self::from_bevy, Self::rotation_from_bevy)
+    }
+
+    fn convert(
+        self,
+        quad: &Quad,
+        position: fn(Self, Vec3) -> Vec3,
+        rotation: fn(Self, Quat) -> Quat,
+    ) -> Quad {
+        let mut quad = quad.clone();
+        if self == Self::YUpRightHanded {
+            return quad;
+        }
+        if !matches!(quad.billboard, Billboard::ClipSpace) {
+            quad.center = position(self, quad.center);
+        }
+        if let Billboard::OrientedY { orientation } = &mut quad.billboard {
+            *orientation = rotation(self, *orientation);
+        }
+        quad
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONVENTIONS: [QuadsCoordinateConvention; 3] = [
+        QuadsCoordinateConvention::YUpRightHanded,
+        QuadsCoordinateConvention::ZUpRightHanded,
+        QuadsCoordinateConvention::ZUpLeftHanded,
+    ];
+
+    fn quads() -> Vec<Quad> {
+        let center = Vec3::new(1.0, -2.0, 3.0);
+        [
+            Billboard::None,
+            Billboard::ViewY,
+            Billboard::WorldY,
+            Billboard::OrientedY {
+                orientation: Quat::from_euler(EulerRot::XYZ, 0.3, -1.2, 2.0),
+            },
+            Billboard::FixedScreenSize,
+            Billboard::ClipSpace,
+        ]
+        .into_iter()
+        .map(|billboard| Quad {
+            center,
+            half_extents: Vec3::new(0.5, 0.25, 0.0),
+            billboard,
+            ..default()
+        })
+        .collect()
+    }
+
+    fn orientation(quad: &Quad) -> Option<Quat> {
+        match quad.billboard {
+            Billboard::OrientedY { orientation } => Some(orientation),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn quads_round_trip() {
+        for convention in CONVENTIONS {
+            for quad in quads() {
+                let round_trip = convention.quad_from_bevy(&convention.quad_to_bevy(&quad));
+                assert!(
+                    round_trip.center.abs_diff_eq(quad.center, 1e-6),
+                    "{convention:?} moved {quad:?} to {round_trip:?}"
+                );
+                if let (Some(expected), Some(actual)) =
+                    (orientation(&quad), orientation(&round_trip))
+                {
+                    // NOTE: q and -q are the same rotation
+                    assert!(
+                        expected.dot(actual).abs() > 1.0 - 1e-6,
+                        "{convention:?} turned {expected} into {actual}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn up_axis_becomes_bevy_y() {
+        for (convention, up) in CONVENTIONS.into_iter().zip([Vec3::Y, Vec3::Z, Vec3::Z]) {
+            assert_eq!(convention.to_bevy(up), Vec3::Y);
+            assert_eq!(convention.from_bevy(Vec3::Y), up);
+            // The rotated up axis of an orientation stays the rotated up axis
+            let rotation = Quat::from_euler(EulerRot::XYZ, 0.3, -1.2, 2.0);
+            let converted = convention.rotation_to_bevy(rotation);
+            assert!(
+                (converted * Vec3::Y).abs_diff_eq(convention.to_bevy(rotation * up), 1e-5),
+                "{convention:?} does not keep the up axis of {rotation}"
+            );
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! Thinning out crowds of markers on screen, like map labels, so only the most important of the
+//! markers overlapping each other are drawn.
+
+use std::cmp::Reverse;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{Quads, QuadsScreenIndex};
+
+/// Hides the quads with a non-zero [`Quad::priority`](super::Quad::priority) that overlap a
+/// higher-priority quad on screen, by setting their [`Quad::hidden`](super::Quad::hidden).
+/// Requires a [`QuadsScreenIndex`].
+///
+/// Whenever the index projected the quads of `camera` again, its quads on screen are visited from
+/// the highest priority down, and every quad that doesn't overlap one kept before it is kept. Quads
+/// of the same priority that were visible before are visited first. Quads off screen keep their
+/// flag. All views draw the same quads, so they are decluttered for one camera.
+///
+/// The flags of all quads that changed are written at once, which uploads the quads again, so
+/// decluttering only costs an upload when the set of kept quads changes.
+#[derive(Clone, Debug, Resource)]
+pub struct QuadsDeclutter {
+    /// Camera the quads are decluttered for
+    pub camera: Entity,
+    /// Logical pixels visible quads may overlap a kept quad by before they are hidden, and hidden
+    /// quads need to be clear of the kept quads by before they are shown again. Keeps markers
+    /// close to touching from flickering as the camera drifts.
+    pub hysteresis: f32,
+    kept: usize,
+    hidden: usize,
+}
+
+impl QuadsDeclutter {
+    pub fn new(camera: Entity) -> Self {
+        Self {
+            camera,
+            hysteresis: 4.0,
+            kept: 0,
+            hidden: 0,
+        }
+    }
+
+    /// Number of quads with a priority on screen kept by the last pass
+    pub fn kept(&self) -> usize {
+        self.kept
+    }
+
+    /// Number of quads with a priority on screen hidden by the last pass
+    pub fn hidden(&self) -> usize {
+        self.hidden
+    }
+}
+
+/// Whether two rects overlap by more than touching edges
+fn overlaps(a: Rect, b: Rect) -> bool {
+    a.min.cmplt(b.max).all() && b.min.cmplt(a.max).all()
+}
+
+fn cells(rect: Rect) -> impl Iterator<Item = IVec2> {
+    let min = (rect.min / QuadsScreenIndex::CELL_SIZE).floor().as_ivec2();
+    let max = (rect.max / QuadsScreenIndex::CELL_SIZE).floor().as_ivec2();
+    (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+}
+
+pub(crate) fn declutter_quads(
+    mut declutter: ResMut<QuadsDeclutter>,
+    index: Res<QuadsScreenIndex>,
+    quads: Option<ResMut<Quads>>,
+) {
+    let Some(mut quads) = quads else {
+        return;
+    };
+    if !index.is_changed() && !declutter.is_changed() {
+        return;
+    }
+
+    let mut candidates: Vec<_> = index
+        .quads(declutter.camera)
+        .iter()
+        .filter_map(|screen_quad| {
+            let quad = quads.data.get(screen_quad.index)?;
+            (quad.priority > 0).then_some((screen_quad, quad.priority, quad.hidden))
+        })
+        .collect();
+    candidates.sort_unstable_by_key(|&(screen_quad, priority, hidden)| {
+        (Reverse(priority), hidden, screen_quad.index)
+    });
+
+    // NOTE: The kept rects are bucketed in the cells of the screen index, so every candidate is
+    // only tested against the kept quads close to it
+    let mut kept_cells: HashMap<IVec2, Vec<Rect>> = HashMap::default();
+    let mut changes = Vec::new();
+    let mut kept = 0;
+    let on_screen = candidates.len();
+    for (screen_quad, _, hidden) in candidates {
+        let tested = if hidden {
+            screen_quad.rect.inset(declutter.hysteresis)
+        } else {
+            screen_quad.rect.inset(-declutter.hysteresis)
+        };
+        let keep = !cells(tested).any(|cell| {
+            kept_cells
+                .get(&cell)
+                .is_some_and(|rects| rects.iter().any(|&rect| overlaps(rect, tested)))
+        });
+        if keep {
+            kept += 1;
+            for cell in cells(screen_quad.rect) {
+                kept_cells.entry(cell).or_default().push(screen_quad.rect);
+            }
+        }
+        if keep == hidden {
+            changes.push((screen_quad.index, !keep));
+        }
+    }
+
+    let declutter = declutter.bypass_change_detection();
+    declutter.hidden = on_screen - kept;
+    declutter.kept = kept;
+    // NOTE: Any change uploads all quads, so the flags are changed all at once
+    if changes.is_empty() {
+        return;
+    }
+    let data = &mut quads.bypass_change_detection().data;
+    for (i, hidden) in changes {
+        data[i].hidden = hidden;
+    }
+    quads.set_changed();
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        asset::AssetPlugin,
+        render::camera::CameraPlugin,
+        window::{ExitCondition, WindowResolution},
+    };
+
+    use super::*;
+    use crate::quads::{screen_index::ScreenIndexPlugin, Billboard, Quad};
+
+    const WINDOW_SIZE: Vec2 = Vec2::new(400.0, 300.0);
+
+    /// An app with a window, the screen index and decluttering, but without rendering
+    fn app(quads: Quads) -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            WindowPlugin {
+                primary_window: Some(Window {
+                    resolution: WindowResolution::new(WINDOW_SIZE.x, WINDOW_SIZE.y)
+                        .with_scale_factor_override(1.0),
+                    ..default()
+                }),
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+            },
+            AssetPlugin::default(),
+        ))
+        .add_asset::<Image>()
+        .add_plugins((CameraPlugin, ScreenIndexPlugin))
+        .insert_resource(QuadsScreenIndex::default())
+        .insert_resource(quads);
+        let camera = app.world.spawn(Camera3dBundle::default()).id();
+        app.insert_resource(QuadsDeclutter::new(camera));
+        app
+    }
+
+    /// A marker whose rect in the screen index reaches 10 pixels from `pixel` in every direction
+    fn marker(pixel: Vec2, priority: u16, hidden: bool) -> Quad {
+        let ndc = Vec2::new(pixel.x, WINDOW_SIZE.y - pixel.y) / WINDOW_SIZE * 2.0 - 1.0;
+        Quad {
+            center: ndc.extend(0.5),
+            // NOTE: The circle around the corners of a 6 by 8 pixel half size has a radius of 10
+            half_extents: (Vec2::new(6.0, 8.0) * 2.0 / WINDOW_SIZE).extend(0.0),
+            billboard: Billboard::ClipSpace,
+            priority,
+            hidden,
+            ..default()
+        }
+    }
+
+    fn hidden_flags(app: &App) -> Vec<bool> {
+        let quads = app.world.resource::<Quads>();
+        quads.data.iter().map(|quad| quad.hidden).collect()
+    }
+
+    #[test]
+    fn keeps_the_highest_priorities() {
+        let center = WINDOW_SIZE / 2.0;
+        let mut app = app(Quads {
+            data: vec![
+                marker(center, 1, false),
+                marker(center + Vec2::new(5.0, 5.0), 3, true),
+                marker(center + Vec2::new(-8.0, 0.0), 2, false),
+                marker(center + Vec2::new(100.0, 0.0), 1, true),
+                // NOTE: Quads without a priority are left alone
+                marker(center, 0, false),
+            ],
+            ..default()
+        });
+        app.update();
+
+        assert_eq!(hidden_flags(&app), [true, false, true, false, false]);
+        let declutter = app.world.resource::<QuadsDeclutter>();
+        assert_eq!((declutter.kept(), declutter.hidden()), (2, 2));
+    }
+
+    #[test]
+    fn prefers_visible_quads_of_the_same_priority() {
+        let center = WINDOW_SIZE / 2.0;
+        let mut app = app(Quads {
+            data: vec![
+                marker(center, 1, true),
+                marker(center + Vec2::new(4.0, 0.0), 1, false),
+            ],
+            ..default()
+        });
+        app.update();
+        assert_eq!(hidden_flags(&app), [true, false]);
+    }
+
+    #[test]
+    fn hysteresis_keeps_the_flags_of_quads_close_to_touching() {
+        let center = WINDOW_SIZE / 2.0;
+        // NOTE: The rects are 20 pixels wide, so an offset of 20 pixels touches the kept quad
+        for (offset, hidden, expected) in [
+            // Overlapping by 2 pixels, within the hysteresis of 4
+            (18.0, false, false),
+            (18.0, true, true),
+            // Overlapping by 6 pixels and 6 pixels apart
+            (14.0, false, true),
+            (26.0, true, false),
+        ] {
+            let mut app = app(Quads {
+                data: vec![
+                    marker(center, 2, false),
+                    marker(center + Vec2::new(offset, 0.0), 1, hidden),
+                ],
+                ..default()
+            });
+            app.update();
+            assert_eq!(
+                hidden_flags(&app),
+                [false, expected],
+                "{offset} pixels apart starting out hidden: {hidden}"
+            );
+        }
+    }
+
+    #[test]
+    fn quads_off_screen_keep_their_flag() {
+        let mut app = app(Quads {
+            data: vec![
+                marker(WINDOW_SIZE / 2.0, 1, false),
+                marker(Vec2::new(-100.0, 150.0), 2, true),
+                marker(Vec2::new(600.0, 150.0), 2, false),
+            ],
+            ..default()
+        });
+        app.update();
+
+        assert_eq!(hidden_flags(&app), [false, true, false]);
+        let declutter = app.world.resource::<QuadsDeclutter>();
+        assert_eq!((declutter.kept(), declutter.hidden()), (1, 0));
+    }
+}
@@ -0,0 +1,211 @@
+//! Sizing bokeh sprites by the depth of field of the view they are drawn in, see
+//! [`Billboard::CocScaled`](super::Billboard::CocScaled).
+//!
+//! Every camera with a [`QuadsDepthOfField`] gets its lens extracted into a uniform, which is
+//! bound with a dynamic offset as the third bind group of the quads pipelines specialized for
+//! views with a depth of field.
+
+use bevy::{
+    ecs::{
+        query::ROQueryItem,
+        system::{lifetimeless::*, SystemParamItem},
+    },
+    prelude::*,
+    render::{
+        camera::Projection,
+        extract_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
+        render_phase::{PhaseItem, RenderCommand, RenderCommandResult, TrackedRenderPass},
+        render_resource::{BindGroup, BindGroupDescriptor, BindGroupEntry, ShaderType},
+        renderer::RenderDevice,
+        Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+    },
+};
+
+use super::QuadsPipeline;
+
+/// How the circle of confusion blurs a point of light
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QuadsDepthOfFieldMode {
+    /// A hard-edged disc with the diameter of the circle of confusion, like the bokeh of a real
+    /// aperture. Quads grow by half the diameter.
+    #[default]
+    Bokeh,
+    /// A Gaussian with a standard deviation of a quarter of the circle of confusion, so most of
+    /// the light stays within the circle. Quads grow by three standard deviations so the tail of a
+    /// Gaussian sprite texture isn't cut off.
+    Gaussian,
+}
+
+/// The lens of a camera, which grows its [`Billboard::CocScaled`](super::Billboard::CocScaled)
+/// quads by their circle of confusion. Add it to a 3D camera with a perspective projection,
+/// orthographic cameras are always in focus.
+///
+/// The circle of confusion follows the thin lens equation. The focal length is the one of a lens
+/// covering the camera's vertical field of view with the sensor height, so zooming in with the
+/// field of view makes the depth of field shallower like on a real camera.
+#[derive(Clone, Component, Debug)]
+pub struct QuadsDepthOfField {
+    pub mode: QuadsDepthOfFieldMode,
+    /// View distance in meters that is in perfect focus
+    pub focal_distance: f32,
+    /// Height of the sensor in meters, 18.66mm by default like a Super 35 film frame
+    pub sensor_height: f32,
+    /// Focal length divided by the aperture diameter. Smaller f-numbers open the aperture and
+    /// blur more.
+    pub aperture_f_stops: f32,
+    /// Smallest circle of confusion in physical pixels, quads in focus still grow by it
+    pub min_circle_of_confusion: f32,
+    /// Largest circle of confusion in physical pixels, limits the growth of quads far out of
+    /// focus and the overdraw they cost
+    pub max_circle_of_confusion: f32,
+}
+
+impl Default for QuadsDepthOfField {
+    fn default() -> Self {
+        Self {
+            mode: QuadsDepthOfFieldMode::default(),
+            focal_distance: 10.0,
+            sensor_height: 0.01866,
+            aperture_f_stops: 1.0,
+            min_circle_of_confusion: 0.0,
+            max_circle_of_confusion: 64.0,
+        }
+    }
+}
+
+impl QuadsDepthOfField {
+    /// Focal length in meters of a lens covering the vertical field of view `fov` in radians
+    pub fn focal_length(&self, fov: f32) -> f32 {
+        0.5 * self.sensor_height / (0.5 * fov).tan()
+    }
+
+    /// Diameter in physical pixels of the circle of confusion of a point `depth` meters in front
+    /// of a camera with the vertical field of view `fov`, clamped to the minimum and maximum
+    pub fn circle_of_confusion(&self, fov: f32, viewport_height: f32, depth: f32) -> f32 {
+        let focal_length = self.focal_length(fov);
+        let aperture = focal_length / self.aperture_f_stops;
+        // NOTE: Focusing closer than the focal length is impossible, the lens would have to be
+        // behind the sensor
+        let sensor_coc = aperture * focal_length * (depth - self.focal_distance).abs()
+            / (depth * (self.focal_distance - focal_length).max(1e-6));
+        (sensor_coc / self.sensor_height * viewport_height)
+            .clamp(self.min_circle_of_confusion, self.max_circle_of_confusion)
+    }
+
+    /// Pixels [`Billboard::CocScaled`](super::Billboard::CocScaled) quads grow by for a circle of
+    /// confusion of `circle_of_confusion` pixels
+    pub fn blur_radius(&self, circle_of_confusion: f32) -> f32 {
+        match self.mode {
+            QuadsDepthOfFieldMode::Bokeh => 0.5 * circle_of_confusion,
+            QuadsDepthOfFieldMode::Gaussian => 0.75 * circle_of_confusion,
+        }
+    }
+}
+
+// NOTE: Must match the DEPTH_OF_FIELD_MODE constants in quads_bindings.wgsl
+const DEPTH_OF_FIELD_MODE_BOKEH: u32 = 0;
+const DEPTH_OF_FIELD_MODE_GAUSSIAN: u32 = 1;
+
+#[derive(Clone, Component, ShaderType)]
+pub(crate) struct GpuDepthOfField {
+    focal_distance: f32,
+    focal_length: f32,
+    aperture: f32,
+    sensor_height: f32,
+    min_circle_of_confusion: f32,
+    max_circle_of_confusion: f32,
+    mode: u32,
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct DepthOfFieldBindGroup(Option<BindGroup>);
+
+pub(crate) struct DepthOfFieldPlugin;
+
+impl Plugin for DepthOfFieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(UniformComponentPlugin::<GpuDepthOfField>::default());
+        app.sub_app_mut(RenderApp)
+            .init_resource::<DepthOfFieldBindGroup>()
+            .add_systems(ExtractSchedule, extract_depth_of_field)
+            .add_systems(
+                Render,
+                queue_depth_of_field_bind_group
+                    .in_set(RenderSet::Queue)
+                    .run_if(resource_exists::<QuadsPipeline>()),
+            );
+    }
+}
+
+fn extract_depth_of_field(
+    mut commands: Commands,
+    cameras: Extract<Query<(Entity, &Camera, &Projection, &QuadsDepthOfField)>>,
+) {
+    for (entity, camera, projection, depth_of_field) in &cameras {
+        let Projection::Perspective(perspective) = projection else {
+            continue;
+        };
+        if !camera.is_active {
+            continue;
+        }
+        let focal_length = depth_of_field.focal_length(perspective.fov);
+        commands.get_or_spawn(entity).insert(GpuDepthOfField {
+            focal_distance: depth_of_field.focal_distance,
+            focal_length,
+            aperture: focal_length / depth_of_field.aperture_f_stops,
+            sensor_height: depth_of_field.sensor_height,
+            min_circle_of_confusion: depth_of_field.min_circle_of_confusion.max(0.0),
+            max_circle_of_confusion: depth_of_field.max_circle_of_confusion,
+            mode: match depth_of_field.mode {
+                QuadsDepthOfFieldMode::Bokeh => DEPTH_OF_FIELD_MODE_BOKEH,
+                QuadsDepthOfFieldMode::Gaussian => DEPTH_OF_FIELD_MODE_GAUSSIAN,
+            },
+        });
+    }
+}
+
+/// The uniform buffer of all views is rewritten every frame and may be reallocated, so the bind
+/// group is too
+fn queue_depth_of_field_bind_group(
+    render_device: Res<RenderDevice>,
+    quads_pipeline: Res<QuadsPipeline>,
+    uniforms: Res<ComponentUniforms<GpuDepthOfField>>,
+    mut bind_group: ResMut<DepthOfFieldBindGroup>,
+) {
+    bind_group.0 = uniforms.binding().map(|resource| {
+        render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("quads_depth_of_field_bind_group"),
+            layout: &quads_pipeline.depth_of_field_layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource,
+            }],
+        })
+    });
+}
+
+/// Binds the view's depth of field, views without one are drawn with pipelines that have no
+/// such bind group
+pub(crate) struct SetDepthOfFieldBindGroup<const I: usize>;
+impl<const I: usize, P: PhaseItem> RenderCommand<P> for SetDepthOfFieldBindGroup<I> {
+    type Param = SRes<DepthOfFieldBindGroup>;
+    type ViewWorldQuery = Option<Read<DynamicUniformIndex<GpuDepthOfField>>>;
+    type ItemWorldQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        index: ROQueryItem<'w, Self::ViewWorldQuery>,
+        _entity: ROQueryItem<'w, Self::ItemWorldQuery>,
+        bind_group: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let (Some(index), Some(bind_group)) = (index, bind_group.into_inner().0.as_ref()) else {
+            return RenderCommandResult::Success;
+        };
+        pass.set_bind_group(I, bind_group, &[index.index()]);
+
+        RenderCommandResult::Success
+    }
+}
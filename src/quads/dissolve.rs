@@ -0,0 +1,197 @@
+//! Quads dissolving away along a noise texture, e.g. burning or disintegrating on death, by
+//! discarding the fragments whose noise is below the [`Quad::dissolve`] threshold.
+
+use bevy::{
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        render_resource::{
+            AddressMode, Buffer, Extent3d, FilterMode, Sampler, SamplerDescriptor, ShaderType,
+            TextureDimension, TextureFormat, UniformBuffer,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        Render, RenderApp, RenderSet,
+    },
+};
+
+use super::{half_float::f32_to_f16, Quad, Quads};
+
+/// Blue noise sampled by [`QuadsDissolve`] without an image of its own
+pub(crate) const DISSOLVE_NOISE_IMAGE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 5802214493357012874);
+
+/// Width and height of the embedded blue noise
+const BLUE_NOISE_SIZE: usize = 64;
+
+/// Noise and edge of the quads dissolving with [`Quad::dissolve`], see [`Quads::dissolve`].
+///
+/// The dissolved fragments are discarded, so the quads stay opaque and write depth where they
+/// remain, like alpha masked materials, and need no blending or sorting while they dissolve.
+#[derive(Clone, Debug)]
+pub struct QuadsDissolve {
+    /// Noise in the red channel of a 2D image in a filterable float format, sampled with linear
+    /// filtering and wrapping coordinates. `None` samples an embedded tileable blue noise, which
+    /// dissolves the quads in evenly spread specks.
+    pub noise: Option<Handle<Image>>,
+    /// Repeats of the noise across every quad. Quads with different [`Quad::seed`]s sample it at
+    /// different offsets, so they do not dissolve in the same pattern.
+    pub noise_scale: f32,
+    /// Width of the band of noise values just above the threshold that is drawn in
+    /// `edge_color`, 0 disables the edge
+    pub edge_width: f32,
+    /// Replaces the color of the edge after lighting, so values above 1 glow with bloom on HDR
+    /// cameras
+    pub edge_color: Color,
+}
+
+impl Default for QuadsDissolve {
+    fn default() -> Self {
+        Self {
+            noise: None,
+            noise_scale: 1.0,
+            edge_width: 0.05,
+            edge_color: Color::rgb(4.0, 1.2, 0.2),
+        }
+    }
+}
+
+impl QuadsDissolve {
+    /// The image the noise is sampled from
+    pub fn noise_image(&self) -> Handle<Image> {
+        self.noise
+            .clone()
+            .unwrap_or_else(|| DISSOLVE_NOISE_IMAGE_HANDLE.typed())
+    }
+}
+
+/// Packs the quad's dissolve threshold as a half float in the low half and the offset of its
+/// noise in the high half
+pub(crate) fn pack_dissolve(quad: &Quad) -> u32 {
+    let threshold = f32_to_f16(quad.dissolve.clamp(0.0, 1.0)) as u32;
+    // NOTE: Hashed so consecutive seeds get unrelated offsets
+    let mut offset = quad.seed.wrapping_mul(0x9e37_79b9);
+    offset ^= offset >> 16;
+    threshold | offset << 16
+}
+
+/// Ranks the texels of a tileable blue noise in the order they are filled in by repeatedly
+/// picking the texel furthest from the ones picked before, which gives the void-and-cluster
+/// dither matrices their even spread
+fn blue_noise() -> Vec<u8> {
+    const SIZE: usize = BLUE_NOISE_SIZE;
+    const SIGMA: f32 = 1.9;
+    // NOTE: The energy of far away texels is negligible, so every pick only updates its
+    // neighborhood, wrapping around the edges
+    const RADIUS: isize = 6;
+    let mut energy = vec![0.0f32; SIZE * SIZE];
+    // NOTE: A tiny deterministic jitter breaks the ties of the empty texture, which would
+    // otherwise be filled in rows
+    for (i, e) in energy.iter_mut().enumerate() {
+        let mut x = (i as u32).wrapping_mul(0x045d_9f3b);
+        x ^= x >> 16;
+        *e = (x & 0xffff) as f32 * 1e-9;
+    }
+    let mut ranks = vec![u8::MAX; SIZE * SIZE];
+    let mut picked = vec![false; SIZE * SIZE];
+    for rank in 0..SIZE * SIZE {
+        let (texel, _) = energy
+            .iter()
+            .enumerate()
+            .filter(|(texel, _)| !picked[*texel])
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .unwrap();
+        picked[texel] = true;
+        ranks[texel] = (rank * 256 / (SIZE * SIZE)) as u8;
+        let (x, y) = ((texel % SIZE) as isize, (texel / SIZE) as isize);
+        for dy in -RADIUS..=RADIUS {
+            for dx in -RADIUS..=RADIUS {
+                let neighbor = (x + dx).rem_euclid(SIZE as isize) as usize
+                    + (y + dy).rem_euclid(SIZE as isize) as usize * SIZE;
+                energy[neighbor] += (-((dx * dx + dy * dy) as f32) / (2.0 * SIGMA * SIGMA)).exp();
+            }
+        }
+    }
+    ranks
+}
+
+#[derive(Clone, Default, ShaderType)]
+struct GpuDissolve {
+    edge_color: Vec4,
+    edge_width: f32,
+    noise_scale: f32,
+}
+
+/// Bound to the quads bind groups even without a dissolve, the shader only reads it in pipelines
+/// specialized for it
+#[derive(Resource, Default)]
+pub(crate) struct DissolveUniform {
+    uniform: UniformBuffer<GpuDissolve>,
+    sampler: Option<Sampler>,
+}
+
+impl DissolveUniform {
+    pub(crate) fn buffer(&self) -> Option<&Buffer> {
+        self.uniform.buffer()
+    }
+
+    /// Wraps like the sampler of the flow field, so the noise tiles across the quads
+    pub(crate) fn sampler(&self) -> Option<&Sampler> {
+        self.sampler.as_ref()
+    }
+}
+
+pub(crate) struct DissolvePlugin;
+
+impl Plugin for DissolvePlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(mut images) = app.world.get_resource_mut::<Assets<Image>>() {
+            images.set_untracked(
+                DISSOLVE_NOISE_IMAGE_HANDLE,
+                Image::new(
+                    Extent3d {
+                        width: BLUE_NOISE_SIZE as u32,
+                        height: BLUE_NOISE_SIZE as u32,
+                        depth_or_array_layers: 1,
+                    },
+                    TextureDimension::D2,
+                    blue_noise(),
+                    TextureFormat::R8Unorm,
+                ),
+            );
+        }
+        app.sub_app_mut(RenderApp)
+            .init_resource::<DissolveUniform>()
+            .add_systems(Render, prepare_dissolve.in_set(RenderSet::Prepare));
+    }
+}
+
+fn prepare_dissolve(
+    quads: Option<Res<Quads>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut uniform: ResMut<DissolveUniform>,
+) {
+    if uniform.sampler.is_none() {
+        uniform.sampler = Some(render_device.create_sampler(&SamplerDescriptor {
+            label: Some("quads_dissolve_sampler"),
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::Repeat,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        }));
+    }
+    let changed = quads.as_ref().is_some_and(|quads| quads.is_changed());
+    // NOTE: The buffer has to exist for the quads bind groups before a dissolve is first assigned
+    if !changed && uniform.uniform.buffer().is_some() {
+        return;
+    }
+    if let Some(dissolve) = quads.and_then(|quads| quads.dissolve.clone()) {
+        uniform.uniform.set(GpuDissolve {
+            edge_color: Vec4::from(dissolve.edge_color.as_rgba_f32()),
+            edge_width: dissolve.edge_width.max(0.0),
+            noise_scale: dissolve.noise_scale,
+        });
+    }
+    uniform.uniform.write_buffer(&render_device, &render_queue);
+}
@@ -0,0 +1,566 @@
+//! Compressed on-disk storage for static [`Quads`] datasets.
+//!
+//! A `.quads.zst` file is a small uncompressed header followed by a zstd stream of fixed-size
+//! little-endian [`GpuQuad`] records:
+//!
+//! | offset | size | field                                          |
+//! |--------|------|------------------------------------------------|
+//! | 0      | 8    | magic `b"QUADSZST"`                            |
+//! | 8      | 4    | format version, `u32`                          |
+//! | 12     | 8    | number of quads, `u64`                         |
+//! | 20     | 4    | CRC32 of the uncompressed record bytes, `u32`  |
+//! | 24     | ..   | zstd stream of `count` 128 byte records        |
+//!
+//! Each record is `center: [f32; 3], flags: u32, half_extents: [f32; 4], color: [f32; 4],
+//! specular: [f32; 4], detail: [f32; 4], uv: [f32; 4], motion: [f32; 4], adjust: [u32; 4]`, the
+//! same layout as the instance data uploaded to the GPU. Older files are still readable: version 1
+//! has 48 byte records without `specular`, `detail`, `uv`, `motion` and `adjust`, version 2 has 64
+//! byte records without `detail`, `uv`, `motion` and `adjust`, version 3 has 80 byte records
+//! without `uv`, `motion` and `adjust`, version 4 has 96 byte records without `motion` and
+//! `adjust`, version 5 has 112 byte records without `adjust`.
+
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    utils::BoxedFuture,
+};
+
+use super::{
+    bake::{BakedQuads, BakedSource, BAKE_CHUNK},
+    GpuQuad, Quad, Quads,
+};
+
+const MAGIC: [u8; 8] = *b"QUADSZST";
+const VERSION: u32 = 6;
+const HEADER_SIZE: usize = 24;
+const RECORD_SIZE: usize = 128;
+/// Version 1 records have no specular and detail
+const RECORD_SIZE_V1: usize = 48;
+/// Version 2 records have no detail
+const RECORD_SIZE_V2: usize = 64;
+/// Version 3 records have no uv tiling and scrolling
+const RECORD_SIZE_V3: usize = 80;
+/// Version 4 records have no motion
+const RECORD_SIZE_V4: usize = 96;
+/// Version 5 records have no HSV shift
+const RECORD_SIZE_V5: usize = 112;
+/// Number of records decoded at a time, so peak scratch memory stays at one chunk
+const CHUNK_RECORDS: usize = BAKE_CHUNK;
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum QuadsFileError {
+    Io(io::Error),
+    InvalidMagic,
+    UnsupportedVersion(u32),
+    /// The stream ended before `count` records were decoded
+    Truncated {
+        expected: u64,
+        decoded: u64,
+    },
+    ChecksumMismatch {
+        expected: u32,
+        actual: u32,
+    },
+}
+
+impl fmt::Display for QuadsFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "quads file i/o error: {err}"),
+            Self::InvalidMagic => write!(f, "not a compressed quads file (bad magic bytes)"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported compressed quads file version {version} (expected {VERSION})"
+            ),
+            Self::Truncated { expected, decoded } => write!(
+                f,
+                "compressed quads file is truncated: expected {expected} quads, decoded {decoded}"
+            ),
+            Self::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "compressed quads file is corrupt: checksum {actual:#010x} does not match \
+                {expected:#010x}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QuadsFileError {}
+
+impl From<io::Error> for QuadsFileError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+fn encode_record(gpu_quad: &GpuQuad, out: &mut Vec<u8>) {
+    let words = [
+        gpu_quad.center.x.to_bits(),
+        gpu_quad.center.y.to_bits(),
+        gpu_quad.center.z.to_bits(),
+        gpu_quad.flags,
+        gpu_quad.half_extents.x.to_bits(),
+        gpu_quad.half_extents.y.to_bits(),
+        gpu_quad.half_extents.z.to_bits(),
+        gpu_quad.half_extents.w.to_bits(),
+        gpu_quad.color[0].to_bits(),
+        gpu_quad.color[1].to_bits(),
+        gpu_quad.color[2].to_bits(),
+        gpu_quad.color[3].to_bits(),
+        gpu_quad.specular.x.to_bits(),
+        gpu_quad.specular.y.to_bits(),
+        gpu_quad.specular.z.to_bits(),
+        gpu_quad.specular.w.to_bits(),
+        gpu_quad.detail.x.to_bits(),
+        gpu_quad.detail.y.to_bits(),
+        gpu_quad.detail.z.to_bits(),
+        gpu_quad.detail.w.to_bits(),
+        gpu_quad.uv.x.to_bits(),
+        gpu_quad.uv.y.to_bits(),
+        gpu_quad.uv.z.to_bits(),
+        gpu_quad.uv.w.to_bits(),
+        gpu_quad.motion.x.to_bits(),
+        gpu_quad.motion.y.to_bits(),
+        gpu_quad.motion.z.to_bits(),
+        gpu_quad.motion.w.to_bits(),
+        gpu_quad.adjust.x,
+        gpu_quad.adjust.y,
+        gpu_quad.adjust.z,
+        gpu_quad.adjust.w,
+    ];
+    for word in words {
+        out.extend_from_slice(&word.to_le_bytes());
+    }
+}
+
+fn decode_record(bytes: &[u8]) -> GpuQuad {
+    let word = |i: usize| u32::from_le_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap());
+    let float = |i: usize| f32::from_bits(word(i));
+    GpuQuad {
+        center: Vec3::new(float(0), float(1), float(2)),
+        flags: word(3),
+        half_extents: Vec4::new(float(4), float(5), float(6), float(7)),
+        color: [float(8), float(9), float(10), float(11)],
+        specular: if bytes.len() >= RECORD_SIZE_V2 {
+            Vec4::new(float(12), float(13), float(14), float(15))
+        } else {
+            Vec4::ZERO
+        },
+        detail: if bytes.len() >= RECORD_SIZE_V3 {
+            Vec4::new(float(16), float(17), float(18), float(19))
+        } else {
+            Vec4::ZERO
+        },
+        uv: if bytes.len() >= RECORD_SIZE_V4 {
+            Vec4::new(float(20), float(21), float(22), float(23))
+        } else {
+            Vec4::ZERO
+        },
+        motion: if bytes.len() >= RECORD_SIZE_V5 {
+            Vec4::new(float(24), float(25), float(26), float(27))
+        } else {
+            Vec4::ZERO
+        },
+        adjust: if bytes.len() >= RECORD_SIZE {
+            UVec4::new(word(28), word(29), word(30), word(31))
+        } else {
+            UVec4::ZERO
+        },
+    }
+}
+
+impl Quads {
+    /// Writes the quads to `path` in the compressed quads format. `level` is the zstd compression
+    /// level, `0` selects zstd's default.
+    pub fn save_compressed(
+        &self,
+        path: impl AsRef<Path>,
+        level: i32,
+    ) -> Result<(), QuadsFileError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_compressed(&mut writer, level)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads quads from a file written by [`Quads::save_compressed`]
+    pub fn load_compressed(path: impl AsRef<Path>) -> Result<Quads, QuadsFileError> {
+        Self::read_compressed(BufReader::new(File::open(path)?))
+    }
+
+    pub fn write_compressed<W: Write>(
+        &self,
+        mut writer: W,
+        level: i32,
+    ) -> Result<(), QuadsFileError> {
+        // The checksum is stored in the header so it has to be computed before streaming
+        let mut hasher = crc32fast::Hasher::new();
+        let mut chunk = Vec::with_capacity(CHUNK_RECORDS * RECORD_SIZE);
+        for quads in self.data.chunks(CHUNK_RECORDS) {
+            chunk.clear();
+            for quad in quads {
+                encode_record(&GpuQuad::from(quad), &mut chunk);
+            }
+            hasher.update(&chunk);
+        }
+
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(self.data.len() as u64).to_le_bytes())?;
+        writer.write_all(&hasher.finalize().to_le_bytes())?;
+
+        let mut encoder = zstd::Encoder::new(writer, level)?;
+        for quads in self.data.chunks(CHUNK_RECORDS) {
+            chunk.clear();
+            for quad in quads {
+                encode_record(&GpuQuad::from(quad), &mut chunk);
+            }
+            encoder.write_all(&chunk)?;
+        }
+        encoder.finish()?;
+        Ok(())
+    }
+
+    /// Decodes a compressed quads stream chunk by chunk, validating the checksum at the end
+    pub fn read_compressed<R: Read>(reader: R) -> Result<Quads, QuadsFileError> {
+        let mut reader = QuadsFileReader::new(reader)?;
+        let mut quads = Quads::default();
+        let mut chunk = Vec::new();
+        // NOTE: The quads grow as they are decoded instead of reserving the count of the header,
+        // which a corrupt file could make arbitrarily large
+        while reader.read_chunk(&mut chunk)? {
+            quads.data.append(&mut chunk);
+        }
+        Ok(quads)
+    }
+
+    /// Like [`Quads::bake`] for the quads of a file written by [`Quads::save_compressed`]. The
+    /// render world decodes the file a chunk at a time straight into the chunks it uploads, so
+    /// the quads are never all in memory. Only the header is read here, a truncated or corrupt
+    /// stream is logged when the quads are uploaded and the batch draws nothing.
+    pub fn bake_compressed(&mut self, path: impl AsRef<Path>) -> Result<(), QuadsFileError> {
+        let reader = QuadsFileReader::new(File::open(path)?)?;
+        let len = usize::try_from(reader.count).unwrap_or(usize::MAX);
+        self.data = Vec::new();
+        self.slots = default();
+        self.baked = Some(BakedQuads::new(BakedSource::File(reader), len));
+        Ok(())
+    }
+}
+
+/// Decodes a compressed quads stream a chunk at a time
+pub(crate) struct QuadsFileReader<R: Read> {
+    decoder: zstd::Decoder<'static, BufReader<R>>,
+    record_size: usize,
+    /// Number of quads in the header
+    count: u64,
+    remaining: u64,
+    expected: u32,
+    hasher: crc32fast::Hasher,
+    /// Undecoded records of the current chunk
+    bytes: Vec<u8>,
+}
+
+impl<R: Read> QuadsFileReader<R> {
+    /// Reads and checks the header
+    pub(crate) fn new(mut reader: R) -> Result<Self, QuadsFileError> {
+        let mut header = [0u8; HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        if header[0..8] != MAGIC {
+            return Err(QuadsFileError::InvalidMagic);
+        }
+        let version = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        let record_size = match version {
+            1 => RECORD_SIZE_V1,
+            2 => RECORD_SIZE_V2,
+            3 => RECORD_SIZE_V3,
+            4 => RECORD_SIZE_V4,
+            5 => RECORD_SIZE_V5,
+            VERSION => RECORD_SIZE,
+            _ => return Err(QuadsFileError::UnsupportedVersion(version)),
+        };
+        let count = u64::from_le_bytes(header[12..20].try_into().unwrap());
+        Ok(Self {
+            decoder: zstd::Decoder::new(reader)?,
+            record_size,
+            count,
+            remaining: count,
+            expected: u32::from_le_bytes(header[20..24].try_into().unwrap()),
+            hasher: crc32fast::Hasher::new(),
+            bytes: Vec::new(),
+        })
+    }
+
+    /// Replaces the quads in `chunk` with the next at most [`CHUNK_RECORDS`] quads. Returns
+    /// `false` once all quads were read, and validates the checksum with the last chunk.
+    pub(crate) fn read_chunk(&mut self, chunk: &mut Vec<Quad>) -> Result<bool, QuadsFileError> {
+        chunk.clear();
+        if self.remaining == 0 {
+            return Ok(false);
+        }
+        let records = self.remaining.min(CHUNK_RECORDS as u64) as usize;
+        self.bytes.resize(records * self.record_size, 0);
+        self.decoder
+            .read_exact(&mut self.bytes)
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::UnexpectedEof => QuadsFileError::Truncated {
+                    expected: self.count,
+                    decoded: self.count - self.remaining,
+                },
+                _ => QuadsFileError::Io(err),
+            })?;
+        self.hasher.update(&self.bytes);
+        chunk.extend(
+            self.bytes
+                .chunks_exact(self.record_size)
+                .map(|record| Quad::from(&decode_record(record))),
+        );
+        self.remaining -= records as u64;
+        if self.remaining == 0 {
+            let actual = self.hasher.clone().finalize();
+            if actual != self.expected {
+                return Err(QuadsFileError::ChecksumMismatch {
+                    expected: self.expected,
+                    actual,
+                });
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Loads `.quads.zst` files as [`Quads`] assets
+#[derive(Default)]
+pub struct CompressedQuadsLoader;
+
+impl AssetLoader for CompressedQuadsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let quads = Quads::read_compressed(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(quads));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["quads.zst"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Quads that only use the fields stored by `version`
+    fn versioned_quads(n: usize, version: u32) -> Vec<Quad> {
+        (0..n)
+            .map(|i| {
+                let i = i as f32;
+                let mut quad = Quad {
+                    center: Vec3::new(i, -i, 0.5 * i),
+                    half_extents: Vec3::new(1.0 + i, 2.0, 0.0),
+                    color: Color::rgba_linear(0.25, 0.5, 0.75, 1.0),
+                    ..default()
+                };
+                if version >= 2 {
+                    quad.specular_power = 8.0;
+                } else {
+                    // The specular color of version 1 files decodes as black
+                    quad.specular_color = Color::BLACK;
+                }
+                if version >= 3 {
+                    quad.detail_weight = 0.5;
+                }
+                if version >= 4 {
+                    quad.uv_tile = Vec2::new(2.0, 3.0);
+                }
+                if version >= 5 {
+                    quad.spin = 1.5;
+                }
+                if version >= 6 {
+                    quad.hsv_shift = Vec3::new(0.25, 0.5, 0.0);
+                }
+                quad
+            })
+            .collect()
+    }
+
+    fn test_quads(n: usize) -> Vec<Quad> {
+        versioned_quads(n, VERSION)
+    }
+
+    /// Writes `quads` in the format of `version`, whose records are a prefix of the current ones
+    fn write_version(quads: &[Quad], version: u32, record_size: usize) -> Vec<u8> {
+        let mut records = Vec::new();
+        let mut record = Vec::new();
+        for quad in quads {
+            record.clear();
+            encode_record(&GpuQuad::from(quad), &mut record);
+            records.extend_from_slice(&record[..record_size]);
+        }
+        let mut file = Vec::new();
+        file.extend_from_slice(&MAGIC);
+        file.extend_from_slice(&version.to_le_bytes());
+        file.extend_from_slice(&(quads.len() as u64).to_le_bytes());
+        file.extend_from_slice(&crc32fast::hash(&records).to_le_bytes());
+        file.extend(zstd::encode_all(records.as_slice(), 0).unwrap());
+        file
+    }
+
+    fn instances(quads: &[Quad]) -> Vec<u8> {
+        let mut instances = Vec::new();
+        for quad in quads {
+            encode_record(&GpuQuad::from(quad), &mut instances);
+        }
+        instances
+    }
+
+    fn compressed(quads: Vec<Quad>) -> Vec<u8> {
+        let mut file = Vec::new();
+        Quads {
+            data: quads,
+            ..default()
+        }
+        .write_compressed(&mut file, 0)
+        .unwrap();
+        file
+    }
+
+    #[test]
+    fn round_trips_every_version() {
+        for (version, record_size) in [
+            (1, RECORD_SIZE_V1),
+            (2, RECORD_SIZE_V2),
+            (3, RECORD_SIZE_V3),
+            (4, RECORD_SIZE_V4),
+            (5, RECORD_SIZE_V5),
+            (VERSION, RECORD_SIZE),
+        ] {
+            let quads = versioned_quads(100, version);
+            let file = write_version(&quads, version, record_size);
+            let loaded = Quads::read_compressed(file.as_slice()).unwrap();
+            assert_eq!(
+                instances(&loaded.data),
+                instances(&quads),
+                "version {version}"
+            );
+        }
+    }
+
+    #[test]
+    fn round_trips_several_chunks() {
+        let quads = test_quads(2 * CHUNK_RECORDS + 3);
+        let loaded = Quads::read_compressed(compressed(quads.clone()).as_slice()).unwrap();
+        assert_eq!(instances(&loaded.data), instances(&quads));
+    }
+
+    #[test]
+    fn rejects_invalid_headers() {
+        let mut file = compressed(test_quads(4));
+        file[8..12].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            Quads::read_compressed(file.as_slice()),
+            Err(QuadsFileError::UnsupportedVersion(version)) if version == VERSION + 1
+        ));
+        file[0] = b'X';
+        assert!(matches!(
+            Quads::read_compressed(file.as_slice()),
+            Err(QuadsFileError::InvalidMagic)
+        ));
+    }
+
+    #[test]
+    fn detects_truncation() {
+        let file = compressed(test_quads(CHUNK_RECORDS + 10));
+        // Cut into the zstd stream
+        let cut = &file[..HEADER_SIZE + (file.len() - HEADER_SIZE) / 2];
+        assert!(matches!(
+            Quads::read_compressed(cut),
+            Err(QuadsFileError::Truncated { .. } | QuadsFileError::Io(_))
+        ));
+
+        // A complete stream with fewer quads than the header claims
+        let mut file = compressed(test_quads(CHUNK_RECORDS + 10));
+        file[12..20].copy_from_slice(&(CHUNK_RECORDS as u64 + 11).to_le_bytes());
+        assert!(matches!(
+            Quads::read_compressed(file.as_slice()),
+            Err(QuadsFileError::Truncated {
+                expected,
+                decoded,
+            }) if expected == CHUNK_RECORDS as u64 + 11 && decoded == CHUNK_RECORDS as u64
+        ));
+    }
+
+    #[test]
+    fn does_not_trust_the_header_count() {
+        let mut file = compressed(test_quads(4));
+        file[12..20].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(
+            Quads::read_compressed(file.as_slice()),
+            Err(QuadsFileError::Truncated {
+                expected: u64::MAX,
+                decoded: 0,
+            })
+        ));
+    }
+
+    #[test]
+    fn detects_checksum_corruption() {
+        let quads = test_quads(10);
+        let mut file = write_version(&quads, VERSION, RECORD_SIZE);
+        file[20] ^= 0x01;
+        assert!(matches!(
+            Quads::read_compressed(file.as_slice()),
+            Err(QuadsFileError::ChecksumMismatch { .. })
+        ));
+
+        // A corrupt record, with the checksum of the intact records
+        let mut corrupt = quads.clone();
+        corrupt[5].center.x += 1.0;
+        let mut file = write_version(&corrupt, VERSION, RECORD_SIZE);
+        file[20..24].copy_from_slice(&write_version(&quads, VERSION, RECORD_SIZE)[20..24]);
+        assert!(matches!(
+            Quads::read_compressed(file.as_slice()),
+            Err(QuadsFileError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn bakes_a_file_in_chunks() {
+        let quads = test_quads(CHUNK_RECORDS + 10);
+        let path = std::env::temp_dir().join(format!(
+            "bevy_vertex_pulling_bake_{}.quads.zst",
+            std::process::id()
+        ));
+        std::fs::write(&path, compressed(quads.clone())).unwrap();
+        let mut baked = Quads::default();
+        baked.bake_compressed(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(baked.len(), quads.len());
+
+        let mut chunks = Vec::new();
+        let mut loaded = Vec::new();
+        let source = baked.baked.as_ref().unwrap().take().unwrap();
+        source
+            .for_each_chunk(|first, chunk| {
+                chunks.push((first, chunk.len()));
+                loaded.extend_from_slice(chunk);
+            })
+            .unwrap();
+        assert_eq!(chunks, [(0, CHUNK_RECORDS), (CHUNK_RECORDS, 10)]);
+        assert_eq!(instances(&loaded), instances(&quads));
+        assert!(baked.baked.as_ref().unwrap().take().is_none());
+    }
+}
@@ -0,0 +1,187 @@
+//! Displacement of quads by a 3D texture of vectors, e.g. baked curl noise for aurora, smoke or
+//! energy flow effects, without touching the quads on the CPU.
+
+use std::fmt;
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{
+            AddressMode, Buffer, FilterMode, Sampler, SamplerDescriptor, ShaderType,
+            TextureDimension, TextureFormat, TextureSampleType, UniformBuffer,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        Render, RenderApp, RenderSet,
+    },
+};
+
+use super::Quads;
+
+/// A vector field for [`Quads::flow_field`]. The vertex shader moves the center of every quad by
+/// the vector sampled at the quad's center, so the quads keep their shape and orientation while
+/// they swirl through the field.
+///
+/// Quads in [`Billboard::ClipSpace`](super::Billboard::ClipSpace) are not displaced.
+#[derive(Clone, Debug)]
+pub struct QuadsFlowField {
+    /// 3D image with the displacement vectors in its rgb channels, see
+    /// [`QuadsFlowField::validate`] for the supported formats. Sampled with linear filtering and
+    /// wrapping coordinates, so tileable fields repeat without seams.
+    pub image: Handle<Image>,
+    /// Displacement in world units per unit of the sampled vectors
+    pub strength: f32,
+    /// Repeats of the field per world unit. The field is sampled at the quad centers relative to
+    /// the [`QuadsOrigin`](super::QuadsOrigin), so it moves along with the origin.
+    pub scale: f32,
+    /// Speed in repeats per second the field moves past the quads, animating the displacement
+    pub scroll: Vec3,
+}
+
+impl Default for QuadsFlowField {
+    fn default() -> Self {
+        Self {
+            image: Handle::default(),
+            strength: 1.0,
+            scale: 1.0,
+            scroll: Vec3::ZERO,
+        }
+    }
+}
+
+/// Why a [`QuadsFlowField`] was rejected by [`Quads::set_flow_field`]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum QuadsFlowFieldError {
+    /// The image is not in the image assets, e.g. because it is still loading
+    NotLoaded,
+    NotThreeDimensional(TextureDimension),
+    /// The format is not a filterable, non-sRGB float format
+    UnsupportedFormat(TextureFormat),
+}
+
+impl fmt::Display for QuadsFlowFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotLoaded => write!(f, "flow field image is not loaded"),
+            Self::NotThreeDimensional(dimension) => {
+                write!(f, "flow field image is {dimension:?}, not D3")
+            }
+            Self::UnsupportedFormat(format) => write!(
+                f,
+                "flow field image format {format:?} is not a filterable linear float format"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QuadsFlowFieldError {}
+
+/// Whether images of `format` can be bound as the flow field. sRGB formats would bend the vectors.
+pub(crate) fn is_flow_field_format(format: TextureFormat) -> bool {
+    !format.is_srgb()
+        && format.sample_type(None) == Some(TextureSampleType::Float { filterable: true })
+}
+
+impl QuadsFlowField {
+    /// Checks that the image is a 3D image in a filterable format that is not sRGB, like
+    /// `Rgba16Float` or `Rgba8Snorm`. Fields failing the check are not drawn with, the quads stay
+    /// in place instead.
+    pub fn validate(&self, images: &Assets<Image>) -> Result<(), QuadsFlowFieldError> {
+        let image = images
+            .get(&self.image)
+            .ok_or(QuadsFlowFieldError::NotLoaded)?;
+        let descriptor = &image.texture_descriptor;
+        if descriptor.dimension != TextureDimension::D3 {
+            return Err(QuadsFlowFieldError::NotThreeDimensional(
+                descriptor.dimension,
+            ));
+        }
+        if !is_flow_field_format(descriptor.format) {
+            return Err(QuadsFlowFieldError::UnsupportedFormat(descriptor.format));
+        }
+        Ok(())
+    }
+}
+
+impl Quads {
+    /// Validates the flow field with [`QuadsFlowField::validate`] and assigns it to
+    /// [`Quads::flow_field`], leaving the current field in place if it is rejected
+    pub fn set_flow_field(
+        &mut self,
+        flow_field: QuadsFlowField,
+        images: &Assets<Image>,
+    ) -> Result<(), QuadsFlowFieldError> {
+        flow_field.validate(images)?;
+        self.flow_field = Some(flow_field);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default, ShaderType)]
+struct GpuFlowField {
+    scroll: Vec3,
+    strength: f32,
+    scale: f32,
+}
+
+/// Bound to the quads bind groups even without a flow field, the shader only reads it in
+/// pipelines specialized for the field
+#[derive(Resource, Default)]
+pub(crate) struct FlowFieldUniform {
+    uniform: UniformBuffer<GpuFlowField>,
+    sampler: Option<Sampler>,
+}
+
+impl FlowFieldUniform {
+    pub(crate) fn buffer(&self) -> Option<&Buffer> {
+        self.uniform.buffer()
+    }
+
+    /// Wraps in all three directions, unlike the samplers of [`QuadsSamplerDesc`](super::QuadsSamplerDesc)
+    pub(crate) fn sampler(&self) -> Option<&Sampler> {
+        self.sampler.as_ref()
+    }
+}
+
+pub(crate) struct FlowFieldPlugin;
+
+impl Plugin for FlowFieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<FlowFieldUniform>()
+            .add_systems(Render, prepare_flow_field.in_set(RenderSet::Prepare));
+    }
+}
+
+fn prepare_flow_field(
+    quads: Option<Res<Quads>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut uniform: ResMut<FlowFieldUniform>,
+) {
+    if uniform.sampler.is_none() {
+        uniform.sampler = Some(render_device.create_sampler(&SamplerDescriptor {
+            label: Some("quads_flow_field_sampler"),
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::Repeat,
+            address_mode_w: AddressMode::Repeat,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Linear,
+            ..default()
+        }));
+    }
+    let changed = quads.as_ref().is_some_and(|quads| quads.is_changed());
+    // NOTE: The buffer has to exist for the quads bind groups before a field is first assigned
+    if !changed && uniform.uniform.buffer().is_some() {
+        return;
+    }
+    if let Some(flow_field) = quads.and_then(|quads| quads.flow_field.clone()) {
+        uniform.uniform.set(GpuFlowField {
+            scroll: flow_field.scroll,
+            strength: flow_field.strength,
+            scale: flow_field.scale,
+        });
+    }
+    uniform.uniform.write_buffer(&render_device, &render_queue);
+}
@@ -0,0 +1,658 @@
+//! Quads generated on the GPU from a buffer of points.
+//!
+//! Only the points are uploaded. A compute shader expands each point into a full quad by copying
+//! the quad template for its kind and offsetting it by the point position, so large procedural
+//! scatters never have to be built as [`Quad`]s on the CPU.
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::{
+        query::ROQueryItem,
+        system::{lifetimeless::SRes, SystemParamItem},
+    },
+    pbr::SetMeshViewBindGroup,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        render_asset::RenderAssets,
+        render_phase::{
+            AddRenderCommand, PhaseItem, RenderCommand, RenderCommandResult, SetItemPipeline,
+            TrackedRenderPass,
+        },
+        render_resource::{
+            BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+            BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, Buffer,
+            BufferBinding, BufferBindingType, BufferDescriptor, BufferId, BufferSize, BufferUsages,
+            CachedComputePipelineId, CommandEncoderDescriptor, ComputePassDescriptor,
+            ComputePipelineDescriptor, IndexFormat, PipelineCache, ShaderStages, ShaderType,
+            StorageBuffer, UniformBuffer,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        texture::FallbackImage,
+        Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+    },
+};
+
+use super::{
+    allocator::{write_allocated, SharedAllocatorEvents},
+    animation::GpuBatchAnimation,
+    create_index_buffer,
+    depth_of_field::SetDepthOfFieldBindGroup,
+    dissolve::DissolveUniform,
+    flow_field::FlowFieldUniform,
+    global_alpha::GlobalAlphaUniform,
+    group_colors::GroupColorsUniform,
+    index_size,
+    memory::buffer_size,
+    near_fade::NearFadeUniform,
+    origin::OriginUniform,
+    sampler::QuadSamplers,
+    GpuQuad, GpuQuads, Quad, QuadsAnimation, QuadsBatchMemory, QuadsBuffer, QuadsPhaseItem,
+    QuadsPipeline, QuadsSamplerDesc, QuadsSettings,
+};
+
+/// Must match the workgroup size in quads_expand.wgsl
+const WORKGROUP_SIZE: u32 = 64;
+/// Maximum number of workgroups per dispatch dimension guaranteed by wgpu's default limits
+const MAX_WORKGROUPS_PER_DIMENSION: u32 = 65535;
+
+/// A point that is expanded into a copy of [`QuadsPoints::kinds`]`[kind]` on the GPU
+#[derive(Clone, Copy, Debug, Default, ShaderType)]
+pub struct QuadPoint {
+    pub position: Vec3,
+    pub kind: u32,
+}
+
+/// Inserting this resource draws one quad per point in addition to the [`Quads`](super::Quads).
+/// The quads are expanded from the points by a compute shader whenever the resource changes.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct QuadsPoints {
+    pub points: Vec<QuadPoint>,
+    /// Quad templates looked up by [`QuadPoint::kind`]. The template center is an offset from the
+    /// point position. Points with an out of range kind use the last template.
+    pub kinds: Vec<Quad>,
+    /// Sampler for the image of the [`Quads`](super::Quads), instead of the image's own sampler
+    pub sampler: Option<QuadsSamplerDesc>,
+    /// Keyframed scale, alpha and hue shift applied to every generated quad in the vertex shader
+    pub animation: Option<QuadsAnimation>,
+}
+
+#[derive(Default, ShaderType)]
+struct GpuQuadPointsArray {
+    #[size(runtime)]
+    array: Vec<QuadPoint>,
+}
+
+#[derive(Default, ShaderType)]
+struct GpuQuadKindsArray {
+    #[size(runtime)]
+    array: Vec<GpuQuad>,
+}
+
+/// Render world buffers of the [`QuadsPoints`] resource
+#[derive(Resource)]
+pub struct GpuQuadPoints {
+    points: StorageBuffer<GpuQuadPointsArray>,
+    kinds: StorageBuffer<GpuQuadKindsArray>,
+    quads: Option<Buffer>,
+    index_buffer: Option<Buffer>,
+    count: u32,
+    /// Set when the points changed and the quads have not been expanded from them yet
+    needs_expand: bool,
+    expand_bind_group: Option<BindGroup>,
+    /// Whether the quads image was loaded when `bind_group` was created
+    image_bound: bool,
+    /// The quads image and `quads` buffer `bind_group` was created with
+    bound_image: Option<Handle<Image>>,
+    bound_quads: Option<BufferId>,
+    sampler: Option<QuadsSamplerDesc>,
+    /// Like `GpuQuads::animation`, written whenever the points change
+    animation: UniformBuffer<GpuBatchAnimation>,
+    pub(crate) animated: bool,
+    /// Whether any kind has a [`Quad::hsv_shift`]
+    pub(crate) hsv_adjusted: bool,
+    bind_group: Option<BindGroup>,
+}
+
+impl Default for GpuQuadPoints {
+    fn default() -> Self {
+        let mut points = StorageBuffer::<GpuQuadPointsArray>::default();
+        points.set_label(Some("gpu_quad_points"));
+        let mut kinds = StorageBuffer::<GpuQuadKindsArray>::default();
+        kinds.set_label(Some("gpu_quad_point_kinds"));
+        Self {
+            points,
+            kinds,
+            quads: None,
+            index_buffer: None,
+            count: 0,
+            needs_expand: false,
+            expand_bind_group: None,
+            image_bound: false,
+            bound_image: None,
+            bound_quads: None,
+            sampler: None,
+            animation: UniformBuffer::default(),
+            animated: false,
+            hsv_adjusted: false,
+            bind_group: None,
+        }
+    }
+}
+
+impl GpuQuadPoints {
+    /// Storage buffer holding the source [`QuadPoint`]s
+    pub fn points_buffer(&self) -> Option<&Buffer> {
+        self.points.buffer()
+    }
+
+    /// Storage buffer the expanded quads are written to, in the same layout as the instance data
+    /// of the [`Quads`](super::Quads)
+    pub fn quads_buffer(&self) -> Option<&Buffer> {
+        self.quads.as_ref()
+    }
+
+    pub fn len(&self) -> u32 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub(crate) fn memory(&self) -> QuadsBatchMemory {
+        QuadsBatchMemory {
+            instances_allocated: buffer_size(self.quads.as_ref()),
+            instances_used: self.count as u64 * GpuQuad::min_size().get(),
+            indices: buffer_size(self.index_buffer.as_ref()),
+            auxiliary: buffer_size(self.points.buffer())
+                + buffer_size(self.kinds.buffer())
+                + buffer_size(self.animation.buffer()),
+        }
+    }
+}
+
+#[derive(Component)]
+pub(crate) struct GpuGeneratedQuadsMarker;
+
+const EXPAND_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 13204931570290455417);
+
+pub(crate) struct GeneratedQuadsPlugin;
+
+impl Plugin for GeneratedQuadsPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            EXPAND_SHADER_HANDLE,
+            "quads_expand.wgsl",
+            Shader::from_wgsl
+        );
+
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app
+            .add_render_command::<QuadsPhaseItem, DrawGeneratedQuads>()
+            .add_systems(ExtractSchedule, extract_quad_points)
+            .add_systems(
+                Render,
+                (
+                    prepare_quad_points.in_set(RenderSet::Prepare),
+                    expand_quad_points
+                        .in_set(RenderSet::Prepare)
+                        .after(prepare_quad_points),
+                    queue_generated_quads_bind_group.in_set(RenderSet::Queue),
+                )
+                    .run_if(resource_exists::<ExpandQuadsPipeline>()),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let render_app = app.sub_app_mut(RenderApp);
+        // NOTE: QuadsPlugin does not create its pipeline without storage buffer support, which
+        // the expansion needs as well
+        if render_app.world.contains_resource::<QuadsPipeline>() {
+            render_app.init_resource::<ExpandQuadsPipeline>();
+        }
+    }
+}
+
+fn extract_quad_points(mut commands: Commands, points: Extract<Option<Res<QuadsPoints>>>) {
+    match points.as_ref() {
+        Some(points) if points.is_changed() => {
+            commands.insert_resource(QuadsPoints::clone(points));
+        }
+        Some(_) => {}
+        None => commands.remove_resource::<QuadsPoints>(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prepare_quad_points(
+    mut commands: Commands,
+    settings: Res<QuadsSettings>,
+    points: Option<Res<QuadsPoints>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    pipeline: Res<ExpandQuadsPipeline>,
+    gpu_points: Option<ResMut<GpuQuadPoints>>,
+    allocator_events: Res<SharedAllocatorEvents>,
+) {
+    let Some(points) = points else {
+        if gpu_points.is_some() {
+            commands.remove_resource::<GpuQuadPoints>();
+        }
+        return;
+    };
+    // NOTE: The points beyond the budget of the allocator are dropped, see diagnose_dropped_quads
+    let count = points
+        .points
+        .len()
+        .min(settings.max_points().unwrap_or(usize::MAX));
+    if count == 0 {
+        return;
+    }
+    commands.spawn(GpuGeneratedQuadsMarker);
+    if !points.is_changed() && gpu_points.is_some() {
+        return;
+    }
+
+    let mut new_gpu_points = None;
+    let gpu_points = if let Some(gpu_points) = gpu_points {
+        gpu_points.into_inner()
+    } else {
+        new_gpu_points = Some(GpuQuadPoints::default());
+        new_gpu_points.as_mut().unwrap()
+    };
+
+    if gpu_points.sampler != points.sampler {
+        gpu_points.bind_group = None;
+    }
+    gpu_points.sampler = points.sampler;
+    let allocator = &settings.allocator;
+    let point_size = QuadPoint::min_size().get();
+    let points_allocation = allocator_events.record(
+        QuadsBuffer::Points,
+        allocator.allocate(
+            point_size,
+            (buffer_size(gpu_points.points.buffer()) / point_size) as usize,
+            points.points.len(),
+        ),
+    );
+    let array = &mut gpu_points.points.get_mut().array;
+    array.clear();
+    array.extend_from_slice(&points.points[..count]);
+    let kinds = &mut gpu_points.kinds.get_mut().array;
+    kinds.clear();
+    kinds.extend(points.kinds.iter().map(GpuQuad::from));
+    if kinds.is_empty() {
+        kinds.push(GpuQuad::from(&Quad::default()));
+    }
+    gpu_points.hsv_adjusted = kinds.iter().any(GpuQuad::hsv_adjusted);
+    write_allocated(
+        &mut gpu_points.points,
+        |points| &mut points.array,
+        QuadPoint::default(),
+        &points_allocation,
+        &render_device,
+        &render_queue,
+    );
+    gpu_points.kinds.write_buffer(&render_device, &render_queue);
+    gpu_points.animation.set(match &points.animation {
+        Some(animation) => GpuBatchAnimation::new(animation, false),
+        None => GpuBatchAnimation::default(),
+    });
+    gpu_points
+        .animation
+        .write_buffer(&render_device, &render_queue);
+    gpu_points.animated = points.animation.is_some();
+
+    let instance_size = GpuQuad::min_size().get();
+    let quads = allocator_events.record(
+        QuadsBuffer::GeneratedInstances,
+        allocator.allocate(
+            instance_size,
+            (buffer_size(gpu_points.quads.as_ref()) / instance_size) as usize,
+            points.points.len(),
+        ),
+    );
+    if gpu_points.quads.is_none() || quads.reallocates() {
+        gpu_points.quads = Some(render_device.create_buffer(&BufferDescriptor {
+            label: Some("gpu_generated_quads"),
+            size: quads.capacity as u64 * instance_size,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        }));
+    }
+    let index_size = index_size(4);
+    let indices = allocator_events.record(
+        QuadsBuffer::GeneratedIndices,
+        allocator.allocate(
+            index_size,
+            (buffer_size(gpu_points.index_buffer.as_ref()) / index_size) as usize,
+            points.points.len(),
+        ),
+    );
+    if gpu_points.index_buffer.is_none() || indices.reallocates() {
+        gpu_points.index_buffer = Some(create_index_buffer(
+            &render_device,
+            "gpu_generated_quads_index_buffer",
+            indices.capacity,
+            4,
+        ));
+    }
+    gpu_points.count = count as u32;
+    gpu_points.expand_bind_group = Some(render_device.create_bind_group(&BindGroupDescriptor {
+        label: Some("quads_expand_bind_group"),
+        layout: &pipeline.layout,
+        entries: &[
+            BindGroupEntry {
+                binding: 0,
+                // NOTE: Bound at the size of the points, so the expansion stops at the end of the
+                // points rather than at the end of a buffer with room to grow
+                resource: BindingResource::Buffer(BufferBinding {
+                    buffer: gpu_points.points.buffer().unwrap(),
+                    offset: 0,
+                    size: BufferSize::new(count as u64 * point_size),
+                }),
+            },
+            BindGroupEntry {
+                binding: 1,
+                resource: gpu_points.kinds.binding().unwrap(),
+            },
+            BindGroupEntry {
+                binding: 2,
+                resource: gpu_points.quads.as_ref().unwrap().as_entire_binding(),
+            },
+        ],
+    }));
+    gpu_points.needs_expand = true;
+
+    if let Some(new_gpu_points) = new_gpu_points {
+        commands.insert_resource(new_gpu_points);
+    }
+}
+
+/// Runs the expansion compute shader once after the points changed. The work is submitted
+/// before the render graph runs, so the quads are ready when they are drawn.
+fn expand_quad_points(
+    pipeline: Res<ExpandQuadsPipeline>,
+    pipeline_cache: Res<PipelineCache>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    gpu_points: Option<ResMut<GpuQuadPoints>>,
+) {
+    let Some(mut gpu_points) = gpu_points else {
+        return;
+    };
+    if !gpu_points.needs_expand {
+        return;
+    }
+    // NOTE: The pipeline is compiled asynchronously, retry next frame until it is ready
+    let Some(compute_pipeline) = pipeline_cache.get_compute_pipeline(pipeline.pipeline_id) else {
+        return;
+    };
+
+    let workgroups = gpu_points.count.div_ceil(WORKGROUP_SIZE);
+    let workgroups_x = workgroups.min(MAX_WORKGROUPS_PER_DIMENSION);
+    let workgroups_y = workgroups.div_ceil(workgroups_x);
+
+    let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("quads_expand_encoder"),
+    });
+    {
+        let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("quads_expand_pass"),
+        });
+        pass.set_pipeline(compute_pipeline);
+        pass.set_bind_group(0, gpu_points.expand_bind_group.as_ref().unwrap(), &[]);
+        pass.dispatch_workgroups(workgroups_x, workgroups_y, 1);
+    }
+    render_queue.submit([encoder.finish()]);
+    gpu_points.needs_expand = false;
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_generated_quads_bind_group(
+    quads_pipeline: Res<QuadsPipeline>,
+    render_device: Res<RenderDevice>,
+    images: Res<RenderAssets<Image>>,
+    fallback_image: Res<FallbackImage>,
+    mut samplers: ResMut<QuadSamplers>,
+    near_fade: Res<NearFadeUniform>,
+    origin: Res<OriginUniform>,
+    group_colors: Res<GroupColorsUniform>,
+    flow_field: Res<FlowFieldUniform>,
+    global_alpha: Res<GlobalAlphaUniform>,
+    dissolve: Res<DissolveUniform>,
+    gpu_quads: Option<Res<GpuQuads>>,
+    gpu_points: Option<ResMut<GpuQuadPoints>>,
+) {
+    let (
+        Some(mut gpu_points),
+        Some(near_fade),
+        Some(origin),
+        Some(group_colors),
+        Some(flow_field_uniform),
+        Some(flow_field_sampler),
+        Some(global_alpha),
+        Some(dissolve_uniform),
+        Some(dissolve_sampler),
+    ) = (
+        gpu_points,
+        near_fade.buffer(),
+        origin.buffer(),
+        group_colors.buffer(),
+        flow_field.buffer(),
+        flow_field.sampler(),
+        global_alpha.buffer(),
+        dissolve.buffer(),
+        dissolve.sampler(),
+    )
+    else {
+        return;
+    };
+    // NOTE: The generated quads are textured with the image of the Quads resource, if any
+    let image_handle = gpu_quads.and_then(|gpu_quads| gpu_quads.image.clone());
+    let image = image_handle.as_ref().and_then(|handle| images.get(handle));
+    let quads = gpu_points.quads.as_ref().unwrap();
+    let animation = gpu_points.animation.buffer().unwrap();
+    if gpu_points.bind_group.is_some()
+        && gpu_points.bound_quads == Some(quads.id())
+        && gpu_points.bound_image == image_handle
+        && image.is_some() == gpu_points.image_bound
+    {
+        return;
+    }
+    trace!("Creating the generated quads bind group");
+    let bound_quads = quads.id();
+    let image_bound = image.is_some();
+    let image = image.unwrap_or(&fallback_image.d2);
+    let sampler = match &gpu_points.sampler {
+        Some(desc) => samplers.get_or_create(&render_device, desc),
+        None => image.sampler.clone(),
+    };
+    let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+        label: Some("gpu_generated_quads_bind_group"),
+        layout: &quads_pipeline.quads_layout,
+        entries: &[
+            BindGroupEntry {
+                binding: 0,
+                resource: quads.as_entire_binding(),
+            },
+            BindGroupEntry {
+                binding: 1,
+                resource: BindingResource::TextureView(&image.texture_view),
+            },
+            BindGroupEntry {
+                binding: 2,
+                resource: BindingResource::Sampler(&sampler),
+            },
+            // NOTE: Generated quads are never specialized with the detail texture
+            BindGroupEntry {
+                binding: 3,
+                resource: BindingResource::TextureView(&fallback_image.d2.texture_view),
+            },
+            BindGroupEntry {
+                binding: 4,
+                resource: BindingResource::Sampler(&fallback_image.d2.sampler),
+            },
+            BindGroupEntry {
+                binding: 5,
+                resource: near_fade.as_entire_binding(),
+            },
+            BindGroupEntry {
+                binding: 6,
+                resource: origin.as_entire_binding(),
+            },
+            BindGroupEntry {
+                binding: 7,
+                resource: group_colors.as_entire_binding(),
+            },
+            // NOTE: Generated quads are never specialized with the flow field either
+            BindGroupEntry {
+                binding: 8,
+                resource: BindingResource::TextureView(&fallback_image.d3.texture_view),
+            },
+            BindGroupEntry {
+                binding: 9,
+                resource: BindingResource::Sampler(flow_field_sampler),
+            },
+            BindGroupEntry {
+                binding: 10,
+                resource: flow_field_uniform.as_entire_binding(),
+            },
+            BindGroupEntry {
+                binding: 11,
+                resource: animation.as_entire_binding(),
+            },
+            BindGroupEntry {
+                binding: 12,
+                resource: global_alpha.as_entire_binding(),
+            },
+            // NOTE: Nor do they dissolve
+            BindGroupEntry {
+                binding: 13,
+                resource: BindingResource::TextureView(&fallback_image.d2.texture_view),
+            },
+            BindGroupEntry {
+                binding: 14,
+                resource: BindingResource::Sampler(dissolve_sampler),
+            },
+            BindGroupEntry {
+                binding: 15,
+                resource: dissolve_uniform.as_entire_binding(),
+            },
+        ],
+    });
+    gpu_points.bind_group = Some(bind_group);
+    gpu_points.image_bound = image_bound;
+    gpu_points.bound_image = image_handle;
+    gpu_points.bound_quads = Some(bound_quads);
+}
+
+#[derive(Resource)]
+struct ExpandQuadsPipeline {
+    layout: BindGroupLayout,
+    pipeline_id: CachedComputePipelineId,
+}
+
+impl FromWorld for ExpandQuadsPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let storage = |binding, read_only| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: BufferSize::new(0),
+            },
+            count: None,
+        };
+        let layout =
+            world
+                .resource::<RenderDevice>()
+                .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: Some("quads_expand_layout"),
+                    entries: &[
+                        // Points
+                        storage(0, true),
+                        // Kinds
+                        storage(1, true),
+                        // Expanded quads
+                        storage(2, false),
+                    ],
+                });
+
+        let pipeline_id = world
+            .resource_mut::<PipelineCache>()
+            .queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some("quads_expand_pipeline".into()),
+                layout: vec![layout.clone()],
+                push_constant_ranges: vec![],
+                shader: EXPAND_SHADER_HANDLE.typed(),
+                shader_defs: vec![],
+                entry_point: "expand".into(),
+            });
+
+        Self {
+            layout,
+            pipeline_id,
+        }
+    }
+}
+
+pub(crate) type DrawGeneratedQuads = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetGeneratedQuadsBindGroup<1>,
+    SetDepthOfFieldBindGroup<2>,
+    DrawVertexPulledGeneratedQuads,
+);
+
+pub(crate) struct SetGeneratedQuadsBindGroup<const I: usize>;
+impl<const I: usize, P: PhaseItem> RenderCommand<P> for SetGeneratedQuadsBindGroup<I> {
+    type Param = SRes<GpuQuadPoints>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: ROQueryItem<'w, Self::ViewWorldQuery>,
+        _entity: ROQueryItem<'w, Self::ItemWorldQuery>,
+        gpu_points: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(bind_group) = gpu_points.into_inner().bind_group.as_ref() else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_bind_group(I, bind_group, &[]);
+
+        RenderCommandResult::Success
+    }
+}
+
+pub(crate) struct DrawVertexPulledGeneratedQuads;
+impl<P: PhaseItem> RenderCommand<P> for DrawVertexPulledGeneratedQuads {
+    type Param = SRes<GpuQuadPoints>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: ROQueryItem<'w, Self::ViewWorldQuery>,
+        _entity: ROQueryItem<'w, Self::ItemWorldQuery>,
+        gpu_points: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let gpu_points = gpu_points.into_inner();
+        pass.set_index_buffer(
+            gpu_points.index_buffer.as_ref().unwrap().slice(..),
+            0,
+            IndexFormat::Uint32,
+        );
+        pass.draw_indexed(0..gpu_points.count * 6, 0, 0..1);
+        RenderCommandResult::Success
+    }
+}
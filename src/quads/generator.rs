@@ -0,0 +1,169 @@
+//! Background generation of large procedural [`Quads`] datasets.
+//!
+//! [`Quads::generate_async`] splits the work into chunks that run on a task pool. Finished chunks
+//! are appended to the [`Quads`] resource in order, at most [`QuadsGenerator::quads_per_frame`]
+//! quads per frame, so the app keeps running while the quads pour in.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+use bevy::{
+    prelude::*,
+    tasks::{Task, TaskPool},
+};
+
+use super::{Quad, Quads};
+
+/// Number of quads generated by each task
+const CHUNK_SIZE: u64 = 64 * 1024;
+
+/// Handle to a running [`Quads::generate_async`]. While it is attached to an entity, the quads
+/// generated so far are appended to the [`Quads`] resource every frame, and the component is
+/// removed once all quads have been appended.
+///
+/// Dropping the generator, e.g. by despawning its entity, cancels the generation. Chunks that
+/// have not started are never run and running chunks stop at the next quad.
+#[derive(Component)]
+pub struct QuadsGenerator {
+    /// Maximum number of quads appended to [`Quads`] per frame, limiting the size of each upload
+    pub quads_per_frame: usize,
+    count: u64,
+    appended: u64,
+    /// One slot per chunk, filled by the chunk's task when it finishes
+    chunks: Arc<Vec<Mutex<Option<Vec<Quad>>>>>,
+    next_chunk: usize,
+    /// Quads of a finished chunk that did not fit into a previous frame's budget
+    current: std::vec::IntoIter<Quad>,
+    cancelled: Arc<AtomicBool>,
+    _tasks: Vec<Task<()>>,
+}
+
+impl QuadsGenerator {
+    pub fn with_quads_per_frame(mut self, quads_per_frame: usize) -> Self {
+        self.quads_per_frame = quads_per_frame;
+        self
+    }
+
+    /// Total number of quads that will be generated
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Number of quads appended to [`Quads`] so far
+    pub fn appended(&self) -> u64 {
+        self.appended
+    }
+
+    /// Fraction of the quads appended to [`Quads`] so far, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        if self.count == 0 {
+            1.0
+        } else {
+            self.appended as f32 / self.count as f32
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.appended == self.count
+    }
+
+    /// Appends finished chunks, in order, to `quads` until the per-frame budget is used up or the
+    /// next chunk is still being generated. Returns the number of quads appended.
+    pub fn poll(&mut self, quads: &mut Vec<Quad>) -> usize {
+        let mut budget = self.quads_per_frame;
+        let start = quads.len();
+        while budget > 0 {
+            if self.current.len() == 0 {
+                let Some(slot) = self.chunks.get(self.next_chunk) else {
+                    break;
+                };
+                let Some(chunk) = slot.lock().unwrap().take() else {
+                    break;
+                };
+                self.current = chunk.into_iter();
+                self.next_chunk += 1;
+            }
+            let n = budget.min(self.current.len());
+            quads.extend(self.current.by_ref().take(n));
+            budget -= n;
+        }
+        let appended = quads.len() - start;
+        self.appended += appended as u64;
+        appended
+    }
+}
+
+impl Drop for QuadsGenerator {
+    fn drop(&mut self) {
+        // NOTE: Dropping the tasks cancels those that have not started, the flag stops those that
+        // are running
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Quads {
+    /// Generates `count` quads on `task_pool` by calling `f` with each quad's index. The quads
+    /// are appended to the [`Quads`] resource in index order once the returned generator is
+    /// spawned as a component.
+    pub fn generate_async<F>(task_pool: &TaskPool, count: u64, f: F) -> QuadsGenerator
+    where
+        F: Fn(u64) -> Quad + Send + Sync + 'static,
+    {
+        let f = Arc::new(f);
+        let n_chunks = count.div_ceil(CHUNK_SIZE) as usize;
+        let chunks = Arc::new((0..n_chunks).map(|_| Mutex::new(None)).collect::<Vec<_>>());
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let tasks = (0..n_chunks)
+            .map(|chunk_index| {
+                let f = f.clone();
+                let chunks = chunks.clone();
+                let cancelled = cancelled.clone();
+                task_pool.spawn(async move {
+                    let start = chunk_index as u64 * CHUNK_SIZE;
+                    let end = (start + CHUNK_SIZE).min(count);
+                    let mut chunk = Vec::with_capacity((end - start) as usize);
+                    for i in start..end {
+                        if cancelled.load(Ordering::Relaxed) {
+                            return;
+                        }
+                        chunk.push(f(i));
+                    }
+                    *chunks[chunk_index].lock().unwrap() = Some(chunk);
+                })
+            })
+            .collect();
+
+        QuadsGenerator {
+            quads_per_frame: 1_000_000,
+            count,
+            appended: 0,
+            chunks,
+            next_chunk: 0,
+            current: Vec::new().into_iter(),
+            cancelled,
+            _tasks: tasks,
+        }
+    }
+}
+
+pub(crate) fn poll_quads_generators(
+    mut commands: Commands,
+    quads: Option<ResMut<Quads>>,
+    mut generators: Query<(Entity, &mut QuadsGenerator)>,
+) {
+    let Some(mut quads) = quads else {
+        return;
+    };
+    for (entity, mut generator) in &mut generators {
+        // NOTE: Only flag the quads as changed when something was appended, any change triggers a
+        // full upload
+        if generator.poll(&mut quads.bypass_change_detection().data) > 0 {
+            quads.set_changed();
+        }
+        if generator.is_finished() {
+            commands.entity(entity).remove::<QuadsGenerator>();
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{Buffer, ShaderType, UniformBuffer},
+        renderer::{RenderDevice, RenderQueue},
+        Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+    },
+};
+
+/// Multiplies the alpha every quad is written with, to fade the whole quad layer in and out at
+/// once instead of animating every quad, e.g. a debug overlay. Without the resource the alpha is
+/// left unchanged.
+///
+/// Opaque quads are not blended, so the fade is only visible where the alpha of the target is
+/// used afterwards, like a render-to-texture layer composited over the scene. Blended quads cover
+/// the target by the faded alpha, see [`Quads::blend_constant`](super::Quads::blend_constant).
+/// Batches drawn at [`QuadsResolution::Half`](super::QuadsResolution::Half) are not
+/// faded.
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct QuadsGlobalAlpha(pub f32);
+
+impl Default for QuadsGlobalAlpha {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+#[derive(Clone, ShaderType)]
+struct GpuGlobalAlpha {
+    alpha: f32,
+}
+
+impl Default for GpuGlobalAlpha {
+    fn default() -> Self {
+        Self { alpha: 1.0 }
+    }
+}
+
+/// Bound to the quads bind group even without a [`QuadsGlobalAlpha`], which reads as an alpha of 1
+#[derive(Resource, Default)]
+pub(crate) struct GlobalAlphaUniform(UniformBuffer<GpuGlobalAlpha>);
+
+impl GlobalAlphaUniform {
+    pub(crate) fn buffer(&self) -> Option<&Buffer> {
+        self.0.buffer()
+    }
+}
+
+pub(crate) struct GlobalAlphaPlugin;
+
+impl Plugin for GlobalAlphaPlugin {
+    fn build(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<GlobalAlphaUniform>()
+            .add_systems(ExtractSchedule, extract_global_alpha)
+            .add_systems(Render, prepare_global_alpha.in_set(RenderSet::Prepare));
+    }
+}
+
+/// Also removes the render world copy when the resource is removed, which resets the alpha
+fn extract_global_alpha(mut commands: Commands, alpha: Extract<Option<Res<QuadsGlobalAlpha>>>) {
+    match alpha.as_ref() {
+        Some(alpha) if alpha.is_changed() => commands.insert_resource(**alpha),
+        Some(_) => {}
+        None => commands.remove_resource::<QuadsGlobalAlpha>(),
+    }
+}
+
+fn prepare_global_alpha(
+    alpha: Option<Res<QuadsGlobalAlpha>>,
+    mut had_alpha: Local<bool>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut uniform: ResMut<GlobalAlphaUniform>,
+) {
+    // NOTE: Like the origin, the alpha is read by every pipeline, so removing the resource has to
+    // reset it
+    let changed = match &alpha {
+        Some(alpha) => alpha.is_changed(),
+        None => *had_alpha,
+    };
+    *had_alpha = alpha.is_some();
+    if !changed && uniform.0.buffer().is_some() {
+        return;
+    }
+    uniform.0.set(GpuGlobalAlpha {
+        alpha: alpha.map_or(1.0, |alpha| alpha.0),
+    });
+    uniform.0.write_buffer(&render_device, &render_queue);
+}
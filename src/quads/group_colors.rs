@@ -0,0 +1,130 @@
+//! Recoloring whole groups of quads without touching [`Quads`].
+//!
+//! Every quad with a non-zero [`Quad::group`](super::Quad::group) takes the color assigned to
+//! its group in [`QuadsGroupColors`], if any. The colors are a small table looked up in the vertex
+//! shader, so recoloring a group costs the same no matter how many quads are in it, and the
+//! [`Quads`](super::Quads) are not uploaded again.
+
+use bevy::{
+    prelude::*,
+    render::{
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        render_resource::{Buffer, ShaderType, UniformBuffer},
+        renderer::{RenderDevice, RenderQueue},
+        Render, RenderApp, RenderSet,
+    },
+};
+
+/// Number of entries in the group color table, group 0 is reserved for ungrouped quads
+pub const QUAD_GROUPS: usize = 256;
+
+/// Sets the color of every quad in a group, or restores their own colors with `None`
+#[derive(Clone, Debug, Event)]
+pub struct QuadGroupRecolor {
+    /// Group to recolor, from 1 to 255
+    pub group: u8,
+    pub color: Option<Color>,
+}
+
+/// Colors replacing the color of every quad in a group, including any
+/// [`Quads::variation`](super::Quads::variation) and
+/// [`Quads::alpha_mode`](super::Quads::alpha_mode) premultiplication applied to it. Updated from
+/// [`QuadGroupRecolor`] events in [`PostUpdate`], or can be changed directly.
+#[derive(Clone, Debug, Resource, ExtractResource)]
+pub struct QuadsGroupColors {
+    colors: [Option<Color>; QUAD_GROUPS],
+}
+
+impl Default for QuadsGroupColors {
+    fn default() -> Self {
+        Self {
+            colors: [None; QUAD_GROUPS],
+        }
+    }
+}
+
+impl QuadsGroupColors {
+    pub fn get(&self, group: u8) -> Option<Color> {
+        self.colors[group as usize]
+    }
+
+    /// Sets the color of a group, `None` restores the quads' own colors. Group 0 cannot be
+    /// recolored.
+    pub fn set(&mut self, group: u8, color: Option<Color>) {
+        if group == 0 {
+            warn!("Quad group 0 is reserved for ungrouped quads and cannot be recolored");
+            return;
+        }
+        self.colors[group as usize] = color;
+    }
+}
+
+/// The group colors as uploaded, entries with a negative alpha keep the quads' own colors
+#[derive(Clone, ShaderType)]
+struct GpuGroupColors {
+    colors: [Vec4; QUAD_GROUPS],
+}
+
+impl Default for GpuGroupColors {
+    fn default() -> Self {
+        Self {
+            colors: [Vec4::NEG_ONE; QUAD_GROUPS],
+        }
+    }
+}
+
+/// Bound to the quads bind group of both the quads resource and generated quads
+#[derive(Resource, Default)]
+pub(crate) struct GroupColorsUniform(UniformBuffer<GpuGroupColors>);
+
+impl GroupColorsUniform {
+    pub(crate) fn buffer(&self) -> Option<&Buffer> {
+        self.0.buffer()
+    }
+}
+
+pub(crate) struct GroupColorsPlugin;
+
+impl Plugin for GroupColorsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ExtractResourcePlugin::<QuadsGroupColors>::default())
+            .add_event::<QuadGroupRecolor>()
+            .init_resource::<QuadsGroupColors>()
+            .add_systems(PostUpdate, apply_group_recolors);
+
+        app.sub_app_mut(RenderApp)
+            .init_resource::<GroupColorsUniform>()
+            .add_systems(Render, prepare_group_colors.in_set(RenderSet::Prepare));
+    }
+}
+
+fn apply_group_recolors(
+    mut events: EventReader<QuadGroupRecolor>,
+    mut group_colors: ResMut<QuadsGroupColors>,
+) {
+    for event in events.iter() {
+        group_colors.set(event.group, event.color);
+    }
+}
+
+fn prepare_group_colors(
+    group_colors: Option<Res<QuadsGroupColors>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut uniform: ResMut<GroupColorsUniform>,
+) {
+    let changed = group_colors
+        .as_ref()
+        .is_some_and(|group_colors| group_colors.is_changed());
+    // NOTE: The buffer has to exist for the quads bind groups before any group is recolored
+    if !changed && uniform.0.buffer().is_some() {
+        return;
+    }
+    if let Some(group_colors) = group_colors {
+        let gpu_group_colors = uniform.0.get_mut();
+        for (gpu_color, color) in gpu_group_colors.colors.iter_mut().zip(&group_colors.colors) {
+            *gpu_color = color.map_or(Vec4::NEG_ONE, |color| Vec4::from(color.as_rgba_f32()));
+        }
+    }
+    uniform.0.write_buffer(&render_device, &render_queue);
+}
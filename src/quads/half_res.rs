@@ -0,0 +1,394 @@
+//! Rendering fill-rate bound batches at half resolution. The batch is drawn into per-view
+//! offscreen targets with half the width and height of the view, which are upsampled and
+//! composited into the view at the end of the quads pass.
+
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::{core_3d, fullscreen_vertex_shader::fullscreen_shader_vertex_state},
+    ecs::query::{Has, QueryItem},
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::{ExtractedCamera, Viewport},
+        render_graph::{
+            NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner,
+        },
+        render_phase::{RenderPhase, TrackedRenderPass},
+        render_resource::{
+            BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+            BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType,
+            BlendState, CachedRenderPipelineId, ColorTargetState, ColorWrites, CompareFunction,
+            DepthStencilState, Extent3d, FragmentState, LoadOp, MultisampleState, Operations,
+            PipelineCache, PrimitiveState, RenderPassColorAttachment,
+            RenderPassDepthStencilAttachment, RenderPassDescriptor, RenderPipelineDescriptor,
+            ShaderStages, SpecializedRenderPipeline, SpecializedRenderPipelines, TextureDescriptor,
+            TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+            TextureViewDimension,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::{CachedTexture, TextureCache},
+        view::ExtractedView,
+        Render, RenderApp, RenderSet,
+    },
+};
+
+use super::{
+    node, render_quads_phase, Quads, QuadsDebugView, QuadsEnabled, QuadsHeatmap, QuadsMaskTexture,
+    QuadsPhaseItem, QuadsPipelineKey, QuadsSettings, QUADS_MASK_TEXTURE_FORMAT,
+};
+
+/// Resolution a batch of quads is rendered at, see [`Quads::resolution`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QuadsResolution {
+    #[default]
+    Full,
+    /// Rendered into a target with half the width and height of the view, shading a quarter of
+    /// the fragments. The quads are upsampled with a depth-aware filter and depth tested against
+    /// the scene at full resolution, so they are blurrier but still occluded with sharp edges.
+    Half,
+}
+
+const HALF_RES_COMPOSITE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 2931571809646257403);
+
+/// Per-view half resolution targets and the batches drawn into them
+#[derive(Component)]
+pub struct ViewHalfResQuads {
+    color: CachedTexture,
+    depth: CachedTexture,
+    pub(crate) phase: RenderPhase<QuadsPhaseItem>,
+}
+
+/// Upsamples the half resolution targets into the view at the end of the quads pass
+#[derive(Component)]
+pub struct ViewHalfResComposite {
+    pipeline: CachedRenderPipelineId,
+    bind_group: BindGroup,
+}
+
+impl ViewHalfResComposite {
+    /// Draws the composite into the quads pass, which has the view's depth attached. Nothing is
+    /// drawn if the half resolution pass was skipped and left the targets stale.
+    pub(crate) fn draw<'w>(
+        &'w self,
+        half_res: &ViewHalfResQuads,
+        world: &'w World,
+        render_pass: &mut TrackedRenderPass<'w>,
+    ) {
+        if half_res.phase.items.is_empty() {
+            return;
+        }
+        let Some(pipeline) = world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(self.pipeline)
+        else {
+            return;
+        };
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+pub(crate) struct HalfResPlugin {
+    /// Render graph node the half resolution pass has to run after, see `QuadsPlugin::tonemapped`
+    pub previous_node: &'static str,
+}
+
+impl Plugin for HalfResPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            HALF_RES_COMPOSITE_SHADER_HANDLE,
+            "half_res.wgsl",
+            Shader::from_wgsl
+        );
+
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app
+            .init_resource::<SpecializedRenderPipelines<HalfResCompositePipeline>>()
+            .add_render_graph_node::<ViewNodeRunner<QuadsHalfResPassNode>>(
+                core_3d::graph::NAME,
+                node::QUADS_HALF_RES_PASS,
+            )
+            .add_render_graph_edges(
+                core_3d::graph::NAME,
+                &[
+                    self.previous_node,
+                    node::QUADS_HALF_RES_PASS,
+                    node::QUADS_PASS,
+                ],
+            )
+            .add_systems(
+                Render,
+                (
+                    prepare_half_res_textures
+                        .in_set(RenderSet::Prepare)
+                        .run_if(half_res_active),
+                    queue_half_res_composite.in_set(RenderSet::Queue),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<HalfResCompositePipeline>();
+    }
+}
+
+/// The heatmap and debug views draw every batch at full resolution, so they show what is drawn
+/// rather than how it is upsampled
+fn half_res_active(
+    quads: Option<Res<Quads>>,
+    enabled: Res<QuadsEnabled>,
+    heatmap: Option<Res<QuadsHeatmap>>,
+    debug_view: Res<QuadsDebugView>,
+) -> bool {
+    quads.is_some_and(|quads| quads.resolution == QuadsResolution::Half && !quads.is_empty())
+        && enabled.0
+        && heatmap.is_none()
+        && *debug_view == QuadsDebugView::Off
+}
+
+fn prepare_half_res_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    views: Query<(Entity, &ExtractedCamera, &ExtractedView), With<RenderPhase<QuadsPhaseItem>>>,
+) {
+    for (entity, camera, view) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+        let size = Extent3d {
+            width: size.x.div_ceil(2),
+            height: size.y.div_ceil(2),
+            depth_or_array_layers: 1,
+        };
+        // NOTE: The targets are single-sampled, the composite is drawn into every sample of a
+        // multisampled view. The cache frees the textures of despawned or resized cameras.
+        let mut texture = |label, format| {
+            texture_cache.get(
+                &render_device,
+                TextureDescriptor {
+                    label: Some(label),
+                    size,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format,
+                    usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                },
+            )
+        };
+        let color = texture(
+            "quads_half_res_color_texture",
+            QuadsPipelineKey::from_hdr(view.hdr).view_target_format(),
+        );
+        let depth = texture("quads_half_res_depth_texture", TextureFormat::Depth32Float);
+        commands.entity(entity).insert(ViewHalfResQuads {
+            color,
+            depth,
+            phase: RenderPhase::default(),
+        });
+    }
+}
+
+fn queue_half_res_composite(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    composite_pipeline: Res<HalfResCompositePipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<HalfResCompositePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    views: Query<(
+        Entity,
+        &ExtractedView,
+        &ViewHalfResQuads,
+        Has<QuadsMaskTexture>,
+    )>,
+) {
+    for (entity, view, half_res, mask) in &views {
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("quads_half_res_composite_bind_group"),
+            layout: &composite_pipeline.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&half_res.color.default_view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&half_res.depth.default_view),
+                },
+            ],
+        });
+        let mut key = QuadsPipelineKey::from_msaa_samples(msaa.samples())
+            | QuadsPipelineKey::from_hdr(view.hdr);
+        key.set(QuadsPipelineKey::MASK, mask);
+        let pipeline = pipelines.specialize(&pipeline_cache, &composite_pipeline, key);
+        commands.entity(entity).insert(ViewHalfResComposite {
+            pipeline,
+            bind_group,
+        });
+    }
+}
+
+#[derive(Resource)]
+struct HalfResCompositePipeline {
+    layout: BindGroupLayout,
+    depth_write_enabled: bool,
+}
+
+impl FromWorld for HalfResCompositePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout =
+            world
+                .resource::<RenderDevice>()
+                .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: Some("quads_half_res_composite_layout"),
+                    entries: &[
+                        // Half resolution color
+                        BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Texture {
+                                multisampled: false,
+                                sample_type: TextureSampleType::Float { filterable: false },
+                                view_dimension: TextureViewDimension::D2,
+                            },
+                            count: None,
+                        },
+                        // Half resolution depth
+                        BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Texture {
+                                multisampled: false,
+                                // NOTE: Depth textures can be bound as unfilterable float
+                                // textures, which unlike depth textures GL can load texels from
+                                sample_type: TextureSampleType::Float { filterable: false },
+                                view_dimension: TextureViewDimension::D2,
+                            },
+                            count: None,
+                        },
+                    ],
+                });
+
+        Self {
+            layout,
+            depth_write_enabled: world.resource::<QuadsSettings>().depth_write_enabled,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for HalfResCompositePipeline {
+    type Key = QuadsPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut targets = vec![Some(ColorTargetState {
+            format: key.view_target_format(),
+            // NOTE: Only the edges of the upsampled quads are partially covered
+            blend: Some(BlendState::ALPHA_BLENDING),
+            write_mask: ColorWrites::ALL,
+        })];
+        if key.contains(QuadsPipelineKey::MASK) {
+            // NOTE: The composite is drawn in the quads pass, which has the mask attached, but
+            // the half resolution quads have no mask to write
+            targets.push(Some(ColorTargetState {
+                format: QUADS_MASK_TEXTURE_FORMAT,
+                blend: None,
+                write_mask: ColorWrites::empty(),
+            }));
+        }
+        RenderPipelineDescriptor {
+            label: Some("quads_half_res_composite_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: HALF_RES_COMPOSITE_SHADER_HANDLE.typed(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets,
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: Some(DepthStencilState {
+                format: TextureFormat::Depth32Float,
+                depth_write_enabled: self.depth_write_enabled,
+                depth_compare: CompareFunction::Greater,
+                stencil: default(),
+                bias: default(),
+            }),
+            multisample: MultisampleState {
+                count: key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            push_constant_ranges: vec![],
+        }
+    }
+}
+
+/// Draws the half resolution batches into the view's half resolution targets
+#[derive(Default)]
+struct QuadsHalfResPassNode;
+
+impl ViewNode for QuadsHalfResPassNode {
+    type ViewQuery = (&'static ExtractedCamera, &'static ViewHalfResQuads);
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, half_res): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        if half_res.phase.items.is_empty() {
+            return Ok(());
+        }
+
+        #[cfg(feature = "trace")]
+        let _quads_half_res_pass_span = info_span!("quads_half_res_pass").entered();
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("quads_half_res_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &half_res.color.default_view,
+                resolve_target: None,
+                ops: Operations {
+                    // NOTE: The transparent alpha marks the texels without quads
+                    load: LoadOp::Clear(Color::NONE.into()),
+                    store: true,
+                },
+            })],
+            // NOTE: The quads are only depth tested against each other here, the composite tests
+            // them against the scene at full resolution
+            depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                view: &half_res.depth.default_view,
+                depth_ops: Some(Operations {
+                    load: LoadOp::Clear(0.0),
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+        });
+
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(&Viewport {
+                physical_position: viewport.physical_position / 2,
+                physical_size: (viewport.physical_size / 2).max(UVec2::ONE),
+                depth: viewport.depth.clone(),
+            });
+        }
+
+        render_quads_phase(
+            &half_res.phase,
+            world,
+            &mut render_pass,
+            graph.view_entity(),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+// Quads rendered at half resolution, the alpha is 1 where a quad was drawn and 0 elsewhere
+@group(0) @binding(0)
+var color_texture: texture_2d<f32>;
+// Half resolution depth, bound as a float texture for GL
+@group(0) @binding(1)
+var depth_texture: texture_2d<f32>;
+
+// Texels further away than this fraction of the view distance of the closest of the four nearest
+// texels belong to another surface, their colors are left out so they do not bleed into its edges
+const SAME_SURFACE_TOLERANCE: f32 = 0.05;
+
+struct FragmentOutput {
+    @location(0) color: vec4<f32>,
+    // The depth test against the full resolution depth buffer hides the quads behind the scene
+    // with full resolution edges
+    @builtin(frag_depth) depth: f32,
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> FragmentOutput {
+    let max_texel = vec2<i32>(textureDimensions(color_texture)) - 1;
+    // The pixel center relative to the centers of the four nearest half resolution texels
+    let position = in.position.xy * 0.5 - 0.5;
+    let base = vec2<i32>(floor(position));
+    let f = fract(position);
+    var offsets = array<vec2<i32>, 4>(
+        vec2<i32>(0, 0),
+        vec2<i32>(1, 0),
+        vec2<i32>(0, 1),
+        vec2<i32>(1, 1),
+    );
+    let bilinear = vec4<f32>(
+        (1.0 - f.x) * (1.0 - f.y),
+        f.x * (1.0 - f.y),
+        (1.0 - f.x) * f.y,
+        f.x * f.y,
+    );
+
+    var colors: array<vec4<f32>, 4>;
+    var depths: vec4<f32>;
+    // Reverse-Z, the closest covered texel has the largest depth and the far plane is 0
+    var depth = 0.0;
+    for (var i = 0; i < 4; i += 1) {
+        let texel = clamp(base + offsets[i], vec2<i32>(0), max_texel);
+        colors[i] = textureLoad(color_texture, texel, 0);
+        depths[i] = textureLoad(depth_texture, texel, 0).r;
+        if bilinear[i] * colors[i].a > 0.0 {
+            depth = max(depth, depths[i]);
+        }
+    }
+    if depth == 0.0 {
+        discard;
+    }
+
+    var color = vec3<f32>(0.0);
+    var color_weight = 0.0;
+    var coverage = 0.0;
+    for (var i = 0; i < 4; i += 1) {
+        let weight = bilinear[i] * colors[i].a;
+        coverage += weight;
+        // Reverse-Z depth is inversely proportional to the view distance
+        if depths[i] >= depth * (1.0 - SAME_SURFACE_TOLERANCE) {
+            color += colors[i].rgb * weight;
+            color_weight += weight;
+        }
+    }
+
+    var out: FragmentOutput;
+    out.color = vec4<f32>(color / color_weight, coverage);
+    out.depth = depth;
+    return out;
+}
@@ -0,0 +1,402 @@
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::{core_3d, fullscreen_vertex_shader::fullscreen_shader_vertex_state},
+    ecs::query::QueryItem,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        camera::ExtractedCamera,
+        render_graph::{
+            NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner,
+        },
+        render_phase::RenderPhase,
+        render_resource::{
+            BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+            BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType,
+            BlendComponent, BlendFactor, BlendOperation, BlendState, BufferBindingType,
+            CachedRenderPipelineId, ColorTargetState, ColorWrites, Extent3d, FragmentState, LoadOp,
+            MultisampleState, Operations, PipelineCache, PrimitiveState, RenderPassDescriptor,
+            RenderPipelineDescriptor, ShaderStages, ShaderType, SpecializedRenderPipeline,
+            SpecializedRenderPipelines, TextureDescriptor, TextureDimension, TextureFormat,
+            TextureSampleType, TextureUsages, TextureViewDimension, UniformBuffer,
+        },
+        renderer::{RenderContext, RenderDevice, RenderQueue},
+        texture::{CachedTexture, TextureCache},
+        view::{ExtractedView, ViewTarget},
+        Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+    },
+};
+
+use super::{node, QuadsPhaseItem, QuadsPipelineKey};
+
+/// Maximum number of colors in a [`QuadsHeatmap`] ramp
+pub const HEATMAP_MAX_RAMP_STOPS: usize = 8;
+
+/// Inserting this resource switches the quads into heatmap mode: overlapping quads additively
+/// accumulate their alpha into a float target, and the accumulated density is then mapped through
+/// `ramp` and blended over the view.
+#[derive(Clone, Debug, Resource)]
+pub struct QuadsHeatmap {
+    /// Scale applied to the accumulated value before looking it up in the ramp. An accumulated
+    /// value of `1.0 / intensity` maps to the last ramp color.
+    pub intensity: f32,
+    /// Colors evenly spaced from an accumulated value of zero to saturation. At most
+    /// [`HEATMAP_MAX_RAMP_STOPS`] colors are used.
+    pub ramp: Vec<Color>,
+}
+
+impl Default for QuadsHeatmap {
+    fn default() -> Self {
+        Self {
+            intensity: 0.05,
+            ramp: vec![
+                Color::rgba(0.0, 0.0, 0.0, 0.0),
+                Color::rgb(0.0, 0.0, 1.0),
+                Color::rgb(0.0, 1.0, 1.0),
+                Color::rgb(0.0, 1.0, 0.0),
+                Color::rgb(1.0, 1.0, 0.0),
+                Color::rgb(1.0, 0.0, 0.0),
+                Color::WHITE,
+            ],
+        }
+    }
+}
+
+pub(crate) const HEATMAP_TEXTURE_FORMAT: TextureFormat = TextureFormat::R16Float;
+
+pub(crate) const HEATMAP_BLEND: BlendState = BlendState {
+    color: BlendComponent {
+        src_factor: BlendFactor::One,
+        dst_factor: BlendFactor::One,
+        operation: BlendOperation::Add,
+    },
+    alpha: BlendComponent {
+        src_factor: BlendFactor::One,
+        dst_factor: BlendFactor::One,
+        operation: BlendOperation::Add,
+    },
+};
+
+const HEATMAP_RESOLVE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4209521880946713651);
+
+#[derive(Clone, Default, ShaderType)]
+struct GpuHeatmapSettings {
+    ramp: [Vec4; HEATMAP_MAX_RAMP_STOPS],
+    stop_count: u32,
+    intensity: f32,
+}
+
+#[derive(Resource, Default)]
+struct HeatmapSettingsUniform(UniformBuffer<GpuHeatmapSettings>);
+
+/// Per-view accumulation target written by the quads pass in heatmap mode
+#[derive(Component)]
+pub struct ViewQuadsHeatmapTexture(pub CachedTexture);
+
+#[derive(Component)]
+struct ViewQuadsHeatmapBindGroup(BindGroup);
+
+#[derive(Component)]
+struct ViewQuadsHeatmapResolvePipeline(CachedRenderPipelineId);
+
+pub(crate) struct HeatmapPlugin {
+    /// Render graph node the resolve pass has to run before, see `QuadsPlugin::tonemapped`
+    pub next_node: &'static str,
+}
+
+impl Plugin for HeatmapPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            HEATMAP_RESOLVE_SHADER_HANDLE,
+            "heatmap.wgsl",
+            Shader::from_wgsl
+        );
+
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app
+            .init_resource::<HeatmapSettingsUniform>()
+            .init_resource::<SpecializedRenderPipelines<HeatmapResolvePipeline>>()
+            .add_render_graph_node::<ViewNodeRunner<QuadsHeatmapResolveNode>>(
+                core_3d::graph::NAME,
+                node::QUADS_HEATMAP_RESOLVE,
+            )
+            .add_render_graph_edges(
+                core_3d::graph::NAME,
+                &[
+                    node::QUADS_PASS,
+                    node::QUADS_HEATMAP_RESOLVE,
+                    self.next_node,
+                ],
+            )
+            .add_systems(ExtractSchedule, extract_heatmap)
+            .add_systems(
+                Render,
+                (
+                    prepare_heatmap_textures.in_set(RenderSet::Prepare),
+                    prepare_heatmap_settings.in_set(RenderSet::Prepare),
+                    queue_heatmap_bind_groups.in_set(RenderSet::Queue),
+                    queue_heatmap_resolve_pipelines.in_set(RenderSet::Queue),
+                )
+                    .run_if(resource_exists::<QuadsHeatmap>()),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<HeatmapResolvePipeline>();
+    }
+}
+
+/// Unlike `ExtractResourcePlugin` this also removes the render world copy when the resource is
+/// removed, so the heatmap can be toggled at runtime
+fn extract_heatmap(mut commands: Commands, heatmap: Extract<Option<Res<QuadsHeatmap>>>) {
+    match heatmap.as_ref() {
+        Some(heatmap) if heatmap.is_changed() => {
+            commands.insert_resource(QuadsHeatmap::clone(heatmap))
+        }
+        Some(_) => {}
+        None => commands.remove_resource::<QuadsHeatmap>(),
+    }
+}
+
+fn prepare_heatmap_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    views: Query<(Entity, &ExtractedCamera), With<RenderPhase<QuadsPhaseItem>>>,
+) {
+    for (entity, camera) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+        // NOTE: The cache frees textures that have not been requested for a few frames, so the
+        // textures of despawned or resized cameras are released
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("quads_heatmap_texture"),
+                size: Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: HEATMAP_TEXTURE_FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+        commands
+            .entity(entity)
+            .insert(ViewQuadsHeatmapTexture(texture));
+    }
+}
+
+fn prepare_heatmap_settings(
+    heatmap: Res<QuadsHeatmap>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut settings: ResMut<HeatmapSettingsUniform>,
+) {
+    if !heatmap.is_changed() {
+        return;
+    }
+    if heatmap.ramp.len() > HEATMAP_MAX_RAMP_STOPS {
+        warn!(
+            "QuadsHeatmap ramp has {} colors, only the first {} are used",
+            heatmap.ramp.len(),
+            HEATMAP_MAX_RAMP_STOPS
+        );
+    }
+    let mut gpu_settings = GpuHeatmapSettings {
+        intensity: heatmap.intensity,
+        ..default()
+    };
+    let stops = &heatmap.ramp[..heatmap.ramp.len().min(HEATMAP_MAX_RAMP_STOPS)];
+    for (gpu_stop, stop) in gpu_settings.ramp.iter_mut().zip(stops) {
+        *gpu_stop = Vec4::from_array(stop.as_rgba_f32());
+    }
+    // NOTE: The shader interpolates between pairs of stops so it needs at least two
+    gpu_settings.stop_count = match stops.len() {
+        0 => 2,
+        1 => {
+            gpu_settings.ramp[1] = gpu_settings.ramp[0];
+            2
+        }
+        n => n as u32,
+    };
+    settings.0.set(gpu_settings);
+    settings.0.write_buffer(&render_device, &render_queue);
+}
+
+fn queue_heatmap_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    pipeline: Res<HeatmapResolvePipeline>,
+    settings: Res<HeatmapSettingsUniform>,
+    views: Query<(Entity, &ViewQuadsHeatmapTexture)>,
+) {
+    let Some(settings_binding) = settings.0.binding() else {
+        return;
+    };
+    for (entity, texture) in &views {
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("quads_heatmap_resolve_bind_group"),
+            layout: &pipeline.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&texture.0.default_view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: settings_binding.clone(),
+                },
+            ],
+        });
+        commands
+            .entity(entity)
+            .insert(ViewQuadsHeatmapBindGroup(bind_group));
+    }
+}
+
+fn queue_heatmap_resolve_pipelines(
+    mut commands: Commands,
+    resolve_pipeline: Res<HeatmapResolvePipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<HeatmapResolvePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    views: Query<(Entity, &ExtractedView), With<ViewQuadsHeatmapTexture>>,
+) {
+    for (entity, view) in &views {
+        let key = QuadsPipelineKey::from_msaa_samples(msaa.samples())
+            | QuadsPipelineKey::from_hdr(view.hdr);
+        let pipeline_id = pipelines.specialize(&pipeline_cache, &resolve_pipeline, key);
+        commands
+            .entity(entity)
+            .insert(ViewQuadsHeatmapResolvePipeline(pipeline_id));
+    }
+}
+
+#[derive(Resource)]
+struct HeatmapResolvePipeline {
+    layout: BindGroupLayout,
+}
+
+impl FromWorld for HeatmapResolvePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout =
+            world
+                .resource::<RenderDevice>()
+                .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: Some("quads_heatmap_resolve_layout"),
+                    entries: &[
+                        // Accumulated heatmap
+                        BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Texture {
+                                multisampled: false,
+                                sample_type: TextureSampleType::Float { filterable: false },
+                                view_dimension: TextureViewDimension::D2,
+                            },
+                            count: None,
+                        },
+                        // Ramp and intensity
+                        BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: Some(GpuHeatmapSettings::min_size()),
+                            },
+                            count: None,
+                        },
+                    ],
+                });
+
+        Self { layout }
+    }
+}
+
+impl SpecializedRenderPipeline for HeatmapResolvePipeline {
+    type Key = QuadsPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("quads_heatmap_resolve_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: HEATMAP_RESOLVE_SHADER_HANDLE.typed(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.view_target_format(),
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            push_constant_ranges: vec![],
+        }
+    }
+}
+
+/// Maps the accumulated heatmap through the color ramp and blends it over the view target
+#[derive(Default)]
+struct QuadsHeatmapResolveNode;
+
+impl ViewNode for QuadsHeatmapResolveNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static ViewTarget,
+        &'static ViewQuadsHeatmapBindGroup,
+        &'static ViewQuadsHeatmapResolvePipeline,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, target, bind_group, resolve_pipeline): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Some(pipeline) = world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(resolve_pipeline.0)
+        else {
+            return Ok(());
+        };
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("quads_heatmap_resolve_pass"),
+            color_attachments: &[Some(target.get_color_attachment(Operations {
+                load: LoadOp::Load,
+                store: true,
+            }))],
+            depth_stencil_attachment: None,
+        });
+
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(viewport);
+        }
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group.0, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
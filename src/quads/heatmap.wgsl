@@ -0,0 +1,23 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+// NOTE: The array length must match HEATMAP_MAX_RAMP_STOPS in heatmap.rs
+struct HeatmapSettings {
+    ramp: array<vec4<f32>, 8>,
+    stop_count: u32,
+    intensity: f32,
+}
+
+@group(0) @binding(0)
+var heatmap_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var<uniform> settings: HeatmapSettings;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let accumulated = textureLoad(heatmap_texture, vec2<i32>(in.position.xy), 0).r;
+    // Map the scaled density onto the evenly spaced ramp stops and interpolate between the two
+    // nearest ones
+    let t = saturate(accumulated * settings.intensity) * f32(settings.stop_count - 1u);
+    let i = min(u32(t), settings.stop_count - 2u);
+    return mix(settings.ramp[i], settings.ramp[i + 1u], t - f32(i));
+}
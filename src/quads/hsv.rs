@@ -0,0 +1,49 @@
+//! Per-quad hue, saturation and value adjustments, see [`Quad::hsv_shift`].
+
+use bevy::prelude::*;
+
+use super::{
+    half_float::{f16_to_f32, f32_to_f16},
+    Quad,
+};
+
+impl Quad {
+    /// Rotates the hue of the quad by `degrees`, e.g. to tint one texture in team colors
+    pub fn with_hue_shift(mut self, degrees: f32) -> Self {
+        self.hsv_shift.x = degrees;
+        self
+    }
+
+    /// Adds `amount` to the saturation of the quad, -1 turns it grey
+    pub fn with_saturation_shift(mut self, amount: f32) -> Self {
+        self.hsv_shift.y = amount;
+        self
+    }
+
+    /// Adds `amount` to the value of the quad, negative amounts darken it
+    pub fn with_value_shift(mut self, amount: f32) -> Self {
+        self.hsv_shift.z = amount;
+        self
+    }
+}
+
+/// Packs a [`Quad::hsv_shift`] into two words of half floats as read by `unpack2x16float`, the
+/// hue wrapped into turns from 0 to 1 in the low half of x, the saturation in the high half of x
+/// and the value in the low half of y
+pub(crate) fn pack_hsv_shift(shift: Vec3) -> UVec2 {
+    // NOTE: Turns keep the hue's precision at a fraction of a degree in a half float
+    let hue = (shift.x / 360.0).rem_euclid(1.0);
+    UVec2::new(
+        f32_to_f16(hue) as u32 | (f32_to_f16(shift.y) as u32) << 16,
+        f32_to_f16(shift.z) as u32,
+    )
+}
+
+/// The inverse of [`pack_hsv_shift`], with the hue in degrees from 0 to 360
+pub(crate) fn unpack_hsv_shift(packed: UVec2) -> Vec3 {
+    Vec3::new(
+        f16_to_f32(packed.x as u16) * 360.0,
+        f16_to_f32((packed.x >> 16) as u16),
+        f16_to_f32(packed.y as u16),
+    )
+}
@@ -0,0 +1,249 @@
+//! Stable, generational handles to quads in [`Quads::data`].
+//!
+//! [`Quads::remove`] swap-removes from `data` to keep the instance buffer densely packed, which
+//! moves the last quad into the removed slot. A [`QuadId`] stays valid across such moves and
+//! becomes invalid once its quad is removed, even if the slot is later reused.
+
+use super::{Quad, Quads};
+
+/// Marks a `data` index that has no id, i.e. the quad was pushed to [`Quads::data`] directly
+const NO_SLOT: u32 = u32::MAX;
+
+/// A handle to a quad inserted with [`Quads::insert`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct QuadId {
+    slot: u32,
+    generation: u32,
+}
+
+#[derive(Clone, Debug)]
+struct Slot {
+    generation: u32,
+    /// Index into `Quads::data`, `None` while the slot is free
+    index: Option<u32>,
+}
+
+/// Mapping between [`QuadId`]s and indices into [`Quads::data`]
+#[derive(Clone, Debug, Default)]
+pub struct QuadsSlots {
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    /// Slot of each quad in `data`, or `NO_SLOT`. May be shorter than `data` if quads were pushed
+    /// directly.
+    data_slots: Vec<u32>,
+}
+
+impl QuadsSlots {
+    fn index(&self, id: QuadId) -> Option<usize> {
+        let slot = self.slots.get(id.slot as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        slot.index.map(|index| index as usize)
+    }
+}
+
+impl Quads {
+    /// Appends a quad and returns a handle that stays valid until the quad is removed
+    pub fn insert(&mut self, quad: Quad) -> QuadId {
+        let index = self.data.len() as u32;
+        self.data.push(quad);
+
+        let slots = &mut self.slots;
+        let id = if let Some(slot) = slots.free.pop() {
+            let entry = &mut slots.slots[slot as usize];
+            entry.index = Some(index);
+            QuadId {
+                slot,
+                generation: entry.generation,
+            }
+        } else {
+            slots.slots.push(Slot {
+                generation: 0,
+                index: Some(index),
+            });
+            QuadId {
+                slot: slots.slots.len() as u32 - 1,
+                generation: 0,
+            }
+        };
+        slots.data_slots.resize(index as usize, NO_SLOT);
+        slots.data_slots.push(id.slot);
+        id
+    }
+
+    /// Swap-removes the quad, invalidating `id`. The last quad in `data` takes its place, and its
+    /// id, if any, is updated to point at the new index. `None` if `id` is no longer valid or
+    /// `data` was shortened directly so its index is out of bounds.
+    pub fn remove(&mut self, id: QuadId) -> Option<Quad> {
+        let index = self.slots.index(id)?;
+        if index >= self.data.len() {
+            return None;
+        }
+        let last = self.data.len() - 1;
+        let quad = self.data.swap_remove(index);
+
+        let slots = &mut self.slots;
+        slots.data_slots.resize(last + 1, NO_SLOT);
+        slots.data_slots.swap_remove(index);
+        if let Some(&moved_slot) = slots.data_slots.get(index) {
+            if moved_slot != NO_SLOT {
+                slots.slots[moved_slot as usize].index = Some(index as u32);
+            }
+        }
+
+        let slot = &mut slots.slots[id.slot as usize];
+        slot.index = None;
+        slot.generation = slot.generation.wrapping_add(1);
+        slots.free.push(id.slot);
+        Some(quad)
+    }
+
+    /// Current index of the quad in `data`, which is also its index in the GPU instance buffer
+    pub fn index(&self, id: QuadId) -> Option<usize> {
+        self.slots.index(id)
+    }
+
+    /// Id of the quad at `index` in `data`, `None` for quads pushed to `data` directly
+    pub fn id(&self, index: usize) -> Option<QuadId> {
+        let slot = *self.slots.data_slots.get(index)?;
+        (slot != NO_SLOT).then(|| QuadId {
+            slot,
+            generation: self.slots.slots[slot as usize].generation,
+        })
+    }
+
+    pub fn contains(&self, id: QuadId) -> bool {
+        self.index(id).is_some()
+    }
+
+    pub fn get(&self, id: QuadId) -> Option<&Quad> {
+        self.index(id).and_then(|index| self.data.get(index))
+    }
+
+    pub fn get_mut(&mut self, id: QuadId) -> Option<&mut Quad> {
+        self.index(id).and_then(|index| self.data.get_mut(index))
+    }
+
+    /// Replaces the quad, returning `false` if `id` is no longer valid
+    pub fn set(&mut self, id: QuadId, quad: Quad) -> bool {
+        if let Some(slot) = self.get_mut(id) {
+            *slot = quad;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes all quads and invalidates all ids
+    pub fn clear(&mut self) {
+        self.data.clear();
+        let slots = &mut self.slots;
+        slots.data_slots.clear();
+        slots.free.clear();
+        for (i, slot) in slots.slots.iter_mut().enumerate() {
+            if slot.index.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+            }
+            slots.free.push(i as u32);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+
+    fn quad(x: f32) -> Quad {
+        Quad {
+            center: Vec3::new(x, 0.0, 0.0),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn reused_slots_bump_the_generation() {
+        let mut quads = Quads::default();
+        let first = quads.insert(quad(0.0));
+        assert!(quads.remove(first).is_some());
+        let second = quads.insert(quad(1.0));
+        assert_eq!(second.slot, first.slot);
+        assert_eq!(second.generation, first.generation + 1);
+        assert_eq!(quads.get(second).unwrap().center.x, 1.0);
+    }
+
+    #[test]
+    fn stale_ids_are_rejected() {
+        let mut quads = Quads::default();
+        let stale = quads.insert(quad(0.0));
+        quads.remove(stale);
+        quads.insert(quad(1.0));
+        assert!(!quads.contains(stale));
+        assert!(quads.get(stale).is_none());
+        assert!(quads.get_mut(stale).is_none());
+        assert!(!quads.set(stale, quad(2.0)));
+        assert!(quads.remove(stale).is_none());
+        assert_eq!(quads.data.len(), 1);
+        assert_eq!(quads.data[0].center.x, 1.0);
+    }
+
+    #[test]
+    fn removing_remaps_the_moved_quad() {
+        let mut quads = Quads::default();
+        let ids: Vec<_> = (0..4).map(|i| quads.insert(quad(i as f32))).collect();
+        assert_eq!(quads.remove(ids[1]).unwrap().center.x, 1.0);
+        // The last quad took the removed one's index
+        assert_eq!(quads.index(ids[3]), Some(1));
+        assert_eq!(quads.id(1), Some(ids[3]));
+        assert_eq!(quads.get(ids[3]).unwrap().center.x, 3.0);
+        // The others kept their index
+        assert_eq!(quads.index(ids[0]), Some(0));
+        assert_eq!(quads.index(ids[2]), Some(2));
+        // Removing the last quad moves nothing
+        assert_eq!(quads.remove(ids[2]).unwrap().center.x, 2.0);
+        assert_eq!(quads.index(ids[0]), Some(0));
+        assert_eq!(quads.index(ids[3]), Some(1));
+    }
+
+    #[test]
+    fn removing_next_to_directly_pushed_quads() {
+        let mut quads = Quads::default();
+        let id = quads.insert(quad(0.0));
+        quads.data.push(quad(1.0));
+        assert_eq!(quads.id(1), None);
+        assert_eq!(quads.remove(id).unwrap().center.x, 0.0);
+        assert_eq!(quads.data.len(), 1);
+        assert_eq!(quads.data[0].center.x, 1.0);
+        assert_eq!(quads.id(0), None);
+    }
+
+    #[test]
+    fn removing_after_data_shrank_returns_none() {
+        let mut quads = Quads::default();
+        let first = quads.insert(quad(0.0));
+        let second = quads.insert(quad(1.0));
+        quads.data.truncate(1);
+        assert!(quads.remove(second).is_none());
+        assert_eq!(quads.remove(first).unwrap().center.x, 0.0);
+        quads.data.clear();
+        assert!(quads.remove(second).is_none());
+    }
+
+    #[test]
+    fn clear_invalidates_every_id() {
+        let mut quads = Quads::default();
+        let ids: Vec<_> = (0..3).map(|i| quads.insert(quad(i as f32))).collect();
+        quads.clear();
+        assert!(quads.data.is_empty());
+        assert!(ids.iter().all(|&id| !quads.contains(id)));
+        // The slots are reused with new generations
+        let reused: Vec<_> = (0..3).map(|i| quads.insert(quad(i as f32))).collect();
+        for id in &reused {
+            assert!(ids.iter().any(|old| old.slot == id.slot));
+            assert!(!ids.contains(id));
+        }
+        assert_eq!(quads.slots.slots.len(), 3);
+    }
+}
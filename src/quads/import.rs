@@ -0,0 +1,525 @@
+//! Importing point data tables as [`Quads`], see [`Quads::from_csv`] and [`Quads::from_parquet`].
+//!
+//! Every row becomes one quad, a copy of [`QuadsColumnMapping::quad`] with the fields the mapping
+//! reads from the row's columns. Rows are decoded one at a time, so only the quads are held in
+//! memory, not the table.
+
+use std::{collections::HashMap, fmt, ops::Range};
+
+use bevy::prelude::*;
+
+use super::{Billboard, Quad, Quads};
+
+/// How [`QuadsColumnMapping::value_range`] maps values onto the ramp
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QuadsValueScale {
+    #[default]
+    Linear,
+    /// By the logarithm of the value, for values spanning orders of magnitude like populations.
+    /// Values of 0 and below map to the first ramp color.
+    Logarithmic,
+}
+
+/// Which columns of a table become which fields of the imported quads
+#[derive(Clone, Debug)]
+pub struct QuadsColumnMapping {
+    /// Column of the x coordinate of the quad centers, "x" by default
+    pub x: String,
+    /// Column of the y coordinate of the quad centers, "y" by default
+    pub y: String,
+    /// Column of the z coordinate of the quad centers, "z" by default. Without one the quads keep
+    /// the z of [`QuadsColumnMapping::quad`].
+    pub z: Option<String>,
+    /// Column of the value the quads are colored by through the ramp, "value" by default.
+    /// Without one the quads keep the color of [`QuadsColumnMapping::quad`].
+    pub value: Option<String>,
+    /// Colors evenly spaced over `value_range`, interpolated in linear RGB. Values outside the
+    /// range get the first or last color.
+    pub ramp: Vec<Color>,
+    /// Values mapped to the first and last ramp color, 0 to 1 by default
+    pub value_range: Range<f32>,
+    pub value_scale: QuadsValueScale,
+    /// Column of a factor the half-extents of [`QuadsColumnMapping::quad`] are multiplied by,
+    /// none by default
+    pub size: Option<String>,
+    /// Column of the category of the quads, none by default. Every distinct category gets its own
+    /// [`Quad::group`], numbered from 1 in the order the categories first appear, so each can be
+    /// recolored with a [`QuadGroupRecolor`](super::QuadGroupRecolor). There can be at most 255.
+    pub category: Option<String>,
+    /// The quad every row is a copy of, with the fields above replaced. A camera facing quad of
+    /// one unit by default.
+    pub quad: Quad,
+}
+
+impl Default for QuadsColumnMapping {
+    fn default() -> Self {
+        Self {
+            x: "x".to_string(),
+            y: "y".to_string(),
+            z: Some("z".to_string()),
+            value: Some("value".to_string()),
+            ramp: vec![
+                Color::rgb(0.0, 0.0, 1.0),
+                Color::rgb(0.0, 1.0, 1.0),
+                Color::rgb(0.0, 1.0, 0.0),
+                Color::rgb(1.0, 1.0, 0.0),
+                Color::rgb(1.0, 0.0, 0.0),
+            ],
+            value_range: 0.0..1.0,
+            value_scale: QuadsValueScale::default(),
+            size: None,
+            category: None,
+            quad: Quad {
+                half_extents: Vec3::new(0.5, 0.5, 0.0),
+                billboard: Billboard::ViewY,
+                ..default()
+            },
+        }
+    }
+}
+
+impl QuadsColumnMapping {
+    /// Color of `value` on the ramp, e.g. for a legend. White without ramp colors.
+    pub fn ramp_color(&self, value: f32) -> Color {
+        let (Some(first), Some(last)) = (self.ramp.first(), self.ramp.last()) else {
+            return Color::WHITE;
+        };
+        let scale = |value: f32| match self.value_scale {
+            QuadsValueScale::Linear => value,
+            QuadsValueScale::Logarithmic => value.max(f32::MIN_POSITIVE).ln(),
+        };
+        let (start, end) = (scale(self.value_range.start), scale(self.value_range.end));
+        let t = (scale(value) - start) / (end - start);
+        // NOTE: The NaN of an empty range or value maps to the first color too
+        if t.is_nan() || t <= 0.0 {
+            return *first;
+        }
+        if t >= 1.0 {
+            return *last;
+        }
+        let position = t * (self.ramp.len() - 1) as f32;
+        let index = position as usize;
+        let (from, to) = (
+            Vec4::from(self.ramp[index].as_linear_rgba_f32()),
+            Vec4::from(self.ramp[index + 1].as_linear_rgba_f32()),
+        );
+        let [r, g, b, a] = from.lerp(to, position.fract()).to_array();
+        Color::rgba_linear(r, g, b, a)
+    }
+
+    /// Looks up the mapped columns with `position`, which returns the index of a column by name
+    fn columns(
+        &self,
+        position: impl Fn(&str) -> Option<usize>,
+    ) -> Result<ImportColumns, QuadsImportError> {
+        let column = |name: &str| {
+            position(name)
+                .map(|index| (index, name.to_string()))
+                .ok_or_else(|| QuadsImportError::MissingColumn(name.to_string()))
+        };
+        let optional = |name: &Option<String>| name.as_deref().map(column).transpose();
+        Ok(ImportColumns {
+            x: column(&self.x)?,
+            y: column(&self.y)?,
+            z: optional(&self.z)?,
+            value: optional(&self.value)?,
+            size: optional(&self.size)?,
+            category: optional(&self.category)?,
+        })
+    }
+}
+
+/// Why a table could not be imported
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum QuadsImportError {
+    #[cfg(feature = "csv")]
+    Csv(csv::Error),
+    #[cfg(feature = "parquet")]
+    Parquet(parquet::errors::ParquetError),
+    /// A column of the mapping is not in the table
+    MissingColumn(String),
+    /// A field of a numeric column is not a number. Lines are counted from 1 including the
+    /// header of a CSV file, Parquet rows from 1.
+    InvalidNumber {
+        line: u64,
+        column: String,
+        value: String,
+    },
+    /// The category column has more than 255 distinct categories
+    TooManyCategories { line: u64, category: String },
+}
+
+impl fmt::Display for QuadsImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "csv")]
+            Self::Csv(err) => write!(f, "quads csv error: {err}"),
+            #[cfg(feature = "parquet")]
+            Self::Parquet(err) => write!(f, "quads parquet error: {err}"),
+            Self::MissingColumn(column) => write!(f, "quads table has no column {column:?}"),
+            Self::InvalidNumber {
+                line,
+                column,
+                value,
+            } => write!(
+                f,
+                "line {line}: {value:?} in column {column:?} is not a number"
+            ),
+            Self::TooManyCategories { line, category } => write!(
+                f,
+                "line {line}: category {category:?} exceeds the limit of 255 categories"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QuadsImportError {}
+
+#[cfg(feature = "csv")]
+impl From<csv::Error> for QuadsImportError {
+    fn from(err: csv::Error) -> Self {
+        Self::Csv(err)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<parquet::errors::ParquetError> for QuadsImportError {
+    fn from(err: parquet::errors::ParquetError) -> Self {
+        Self::Parquet(err)
+    }
+}
+
+/// Indices and names of the mapped columns
+struct ImportColumns {
+    x: (usize, String),
+    y: (usize, String),
+    z: Option<(usize, String)>,
+    value: Option<(usize, String)>,
+    size: Option<(usize, String)>,
+    category: Option<(usize, String)>,
+}
+
+/// Turns rows into quads, numbering the categories as they appear
+struct RowImporter<'a> {
+    mapping: &'a QuadsColumnMapping,
+    columns: ImportColumns,
+    categories: HashMap<String, u8>,
+}
+
+/// A row of a table, fields are looked up by column index
+trait ImportRow {
+    /// The field as a number, or its text if it isn't one
+    fn number(&self, index: usize) -> Result<f32, String>;
+    fn text(&self, index: usize) -> String;
+}
+
+impl<'a> RowImporter<'a> {
+    fn new(mapping: &'a QuadsColumnMapping, columns: ImportColumns) -> Self {
+        Self {
+            mapping,
+            columns,
+            categories: HashMap::new(),
+        }
+    }
+
+    fn quad(&mut self, line: u64, row: &impl ImportRow) -> Result<Quad, QuadsImportError> {
+        let number = |(index, column): &(usize, String)| {
+            row.number(*index)
+                .map_err(|value| QuadsImportError::InvalidNumber {
+                    line,
+                    column: column.clone(),
+                    value,
+                })
+        };
+        let mut quad = self.mapping.quad.clone();
+        quad.center.x = number(&self.columns.x)?;
+        quad.center.y = number(&self.columns.y)?;
+        if let Some(z) = &self.columns.z {
+            quad.center.z = number(z)?;
+        }
+        if let Some(value) = &self.columns.value {
+            quad.color = self.mapping.ramp_color(number(value)?);
+        }
+        if let Some(size) = &self.columns.size {
+            quad.half_extents *= number(size)?;
+        }
+        if let Some((index, _)) = &self.columns.category {
+            let category = row.text(*index);
+            let next = self.categories.len() + 1;
+            quad.group = match self.categories.get(&category) {
+                Some(&group) => group,
+                None => {
+                    let Ok(group) = u8::try_from(next) else {
+                        return Err(QuadsImportError::TooManyCategories { line, category });
+                    };
+                    self.categories.insert(category, group);
+                    group
+                }
+            };
+        }
+        Ok(quad)
+    }
+}
+
+#[cfg(feature = "csv")]
+impl ImportRow for csv::StringRecord {
+    fn number(&self, index: usize) -> Result<f32, String> {
+        let field = self.get(index).unwrap_or_default();
+        field.parse().map_err(|_| field.to_string())
+    }
+
+    fn text(&self, index: usize) -> String {
+        self.get(index).unwrap_or_default().to_string()
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl ImportRow for parquet::record::Row {
+    fn number(&self, index: usize) -> Result<f32, String> {
+        use parquet::record::Field;
+
+        match self.get_column_iter().nth(index).map(|(_, field)| field) {
+            Some(Field::Float(value)) => Ok(*value),
+            Some(Field::Double(value)) => Ok(*value as f32),
+            Some(Field::Byte(value)) => Ok(f32::from(*value)),
+            Some(Field::Short(value)) => Ok(f32::from(*value)),
+            Some(Field::Int(value)) => Ok(*value as f32),
+            Some(Field::Long(value)) => Ok(*value as f32),
+            Some(Field::UByte(value)) => Ok(f32::from(*value)),
+            Some(Field::UShort(value)) => Ok(f32::from(*value)),
+            Some(Field::UInt(value)) => Ok(*value as f32),
+            Some(Field::ULong(value)) => Ok(*value as f32),
+            Some(Field::Str(value)) => value.trim().parse().map_err(|_| value.clone()),
+            Some(field) => Err(field.to_string()),
+            None => Err(String::new()),
+        }
+    }
+
+    fn text(&self, index: usize) -> String {
+        use parquet::record::Field;
+
+        match self.get_column_iter().nth(index).map(|(_, field)| field) {
+            Some(Field::Str(value)) => value.clone(),
+            Some(field) => field.to_string(),
+            None => String::new(),
+        }
+    }
+}
+
+impl Quads {
+    /// Imports a CSV table with a header row, one quad per row, see [`QuadsColumnMapping`]. The
+    /// rows are parsed as they are read, fields are trimmed of surrounding whitespace.
+    #[cfg(feature = "csv")]
+    pub fn from_csv(
+        reader: impl std::io::Read,
+        mapping: &QuadsColumnMapping,
+    ) -> Result<Quads, QuadsImportError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+        let headers = reader.headers()?.clone();
+        let columns = mapping.columns(|name| headers.iter().position(|header| header == name))?;
+        let mut importer = RowImporter::new(mapping, columns);
+
+        let mut quads = Quads::default();
+        let mut record = csv::StringRecord::new();
+        while reader.read_record(&mut record)? {
+            let line = record.position().map_or(0, |position| position.line());
+            quads.data.push(importer.quad(line, &record)?);
+        }
+        Ok(quads)
+    }
+
+    /// Imports a Parquet table, one quad per row, see [`QuadsColumnMapping`]. Numeric columns
+    /// may be of any integer or floating point type, or strings holding numbers. The rows are
+    /// decoded a row group at a time.
+    #[cfg(feature = "parquet")]
+    pub fn from_parquet<R: parquet::file::reader::ChunkReader + 'static>(
+        reader: R,
+        mapping: &QuadsColumnMapping,
+    ) -> Result<Quads, QuadsImportError> {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let reader = SerializedFileReader::new(reader)?;
+        let metadata = reader.metadata().file_metadata();
+        // NOTE: The fields of a row are the leaf columns of a flat schema in order
+        let columns = mapping.columns(|name| {
+            metadata
+                .schema_descr()
+                .columns()
+                .iter()
+                .position(|column| column.name() == name)
+        })?;
+        let mut importer = RowImporter::new(mapping, columns);
+
+        let mut quads = Quads::default();
+        quads
+            .data
+            .reserve(usize::try_from(metadata.num_rows()).unwrap_or_default());
+        for (row_index, row) in reader.get_row_iter(None)?.enumerate() {
+            quads.data.push(importer.quad(row_index as u64 + 1, &row?)?);
+        }
+        Ok(quads)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_linear_eq(color: Color, expected: [f32; 4]) {
+        let linear = Vec4::from(color.as_linear_rgba_f32());
+        assert!(
+            linear.abs_diff_eq(Vec4::from(expected), 1e-5),
+            "{linear} is not {expected:?}"
+        );
+    }
+
+    fn gray_ramp(value_range: Range<f32>, value_scale: QuadsValueScale) -> QuadsColumnMapping {
+        QuadsColumnMapping {
+            ramp: vec![Color::BLACK, Color::WHITE],
+            value_range,
+            value_scale,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn maps_values_linearly_onto_the_ramp() {
+        let mapping = gray_ramp(0.0..4.0, QuadsValueScale::Linear);
+        assert_linear_eq(mapping.ramp_color(1.0), [0.25, 0.25, 0.25, 1.0]);
+        assert_linear_eq(mapping.ramp_color(3.0), [0.75, 0.75, 0.75, 1.0]);
+        for (value, expected) in [(-1.0, 0.0), (5.0, 1.0), (f32::NAN, 0.0)] {
+            assert_linear_eq(
+                mapping.ramp_color(value),
+                [expected, expected, expected, 1.0],
+            );
+        }
+    }
+
+    #[test]
+    fn maps_values_logarithmically_onto_the_ramp() {
+        let mapping = gray_ramp(1.0..100.0, QuadsValueScale::Logarithmic);
+        assert_linear_eq(mapping.ramp_color(10.0), [0.5, 0.5, 0.5, 1.0]);
+        assert_linear_eq(mapping.ramp_color(1000.0), [1.0, 1.0, 1.0, 1.0]);
+        for value in [1.0, 0.0, -5.0] {
+            assert_linear_eq(mapping.ramp_color(value), [0.0, 0.0, 0.0, 1.0]);
+        }
+    }
+
+    #[test]
+    fn interpolates_between_ramp_colors() {
+        let mapping = QuadsColumnMapping {
+            ramp: vec![Color::RED, Color::GREEN, Color::BLUE],
+            ..default()
+        };
+        assert_linear_eq(mapping.ramp_color(0.25), [0.5, 0.5, 0.0, 1.0]);
+        assert_linear_eq(mapping.ramp_color(0.5), [0.0, 1.0, 0.0, 1.0]);
+        assert_linear_eq(mapping.ramp_color(0.75), [0.0, 0.5, 0.5, 1.0]);
+        let empty = QuadsColumnMapping {
+            ramp: Vec::new(),
+            ..default()
+        };
+        assert_eq!(empty.ramp_color(0.5), Color::WHITE);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn imports_csv_rows() {
+        let csv = "x, y, z, value\n1, 2, 3, 0.5\n-4.5, 0, 1e3, 2\n";
+        let mapping = QuadsColumnMapping::default();
+        let quads = Quads::from_csv(csv.as_bytes(), &mapping).unwrap();
+
+        assert_eq!(quads.data.len(), 2);
+        assert_eq!(quads.data[0].center, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(quads.data[1].center, Vec3::new(-4.5, 0.0, 1000.0));
+        assert_eq!(quads.data[0].color, mapping.ramp_color(0.5));
+        assert_eq!(quads.data[1].color, *mapping.ramp.last().unwrap());
+        for quad in &quads.data {
+            assert_eq!(quad.half_extents, mapping.quad.half_extents);
+            assert!(matches!(quad.billboard, Billboard::ViewY));
+        }
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn maps_sizes_and_categories() {
+        let csv = "lon,lat,kind,size\n1,2,city,2\n3,4,town,0.5\n5,6,city,1\n";
+        let mapping = QuadsColumnMapping {
+            x: "lon".to_string(),
+            y: "lat".to_string(),
+            z: None,
+            value: None,
+            size: Some("size".to_string()),
+            category: Some("kind".to_string()),
+            quad: Quad {
+                center: Vec3::new(0.0, 0.0, 7.0),
+                color: Color::ORANGE,
+                half_extents: Vec3::new(1.0, 2.0, 0.0),
+                ..default()
+            },
+            ..default()
+        };
+        let quads = Quads::from_csv(csv.as_bytes(), &mapping).unwrap();
+
+        let groups: Vec<_> = quads.data.iter().map(|quad| quad.group).collect();
+        assert_eq!(groups, [1, 2, 1]);
+        assert_eq!(quads.data[0].half_extents, Vec3::new(2.0, 4.0, 0.0));
+        assert_eq!(quads.data[1].half_extents, Vec3::new(0.5, 1.0, 0.0));
+        for quad in &quads.data {
+            assert_eq!(quad.center.z, 7.0);
+            assert_eq!(quad.color, Color::ORANGE);
+        }
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn reports_missing_columns() {
+        let csv = "x,z,value\n1,2,3\n";
+        let err = Quads::from_csv(csv.as_bytes(), &QuadsColumnMapping::default()).unwrap_err();
+        assert!(matches!(&err, QuadsImportError::MissingColumn(column) if column == "y"));
+        assert_eq!(err.to_string(), r#"quads table has no column "y""#);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn reports_invalid_numbers_with_their_line() {
+        let csv = "x,y,z,value\n1,2,3,0\n4,five,6,0\n7,8,9,0\n";
+        let err = Quads::from_csv(csv.as_bytes(), &QuadsColumnMapping::default()).unwrap_err();
+        let QuadsImportError::InvalidNumber {
+            line,
+            column,
+            value,
+        } = &err
+        else {
+            panic!("{err} is not an invalid number");
+        };
+        assert_eq!((*line, column.as_str(), value.as_str()), (3, "y", "five"));
+        assert_eq!(
+            err.to_string(),
+            r#"line 3: "five" in column "y" is not a number"#
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn reports_too_many_categories() {
+        let mut csv = "x,y,category\n".to_string();
+        for category in 0..256 {
+            csv.push_str(&format!("0,0,{category}\n"));
+        }
+        let mapping = QuadsColumnMapping {
+            z: None,
+            value: None,
+            category: Some("category".to_string()),
+            ..default()
+        };
+        let err = Quads::from_csv(csv.as_bytes(), &mapping).unwrap_err();
+        assert!(matches!(
+            &err,
+            QuadsImportError::TooManyCategories { line: 257, category } if category == "255"
+        ));
+    }
+}
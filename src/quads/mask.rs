@@ -0,0 +1,97 @@
+use bevy::{
+    prelude::*,
+    render::{
+        camera::ExtractedCamera,
+        render_phase::RenderPhase,
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        renderer::RenderDevice,
+        texture::{CachedTexture, TextureCache},
+        Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+    },
+};
+
+use super::{QuadsHeatmap, QuadsPhaseItem};
+
+/// Format of the [`QuadsMaskTexture`], one [`Quad::mask`](super::Quad::mask) per sample
+pub const QUADS_MASK_TEXTURE_FORMAT: TextureFormat = TextureFormat::R8Uint;
+
+/// Inserting this resource makes the quads pass write the [`Quad::mask`](super::Quad::mask) of
+/// every visible quad into a [`QuadsMaskTexture`] of each view, for post-processes like outlines
+/// around selected quads. Quads without a mask write 0, so masked quads hidden behind other quads
+/// are not in the mask.
+///
+/// Half resolution batches leave the mask unchanged, and no mask is written while the
+/// [`QuadsHeatmap`] is shown.
+#[derive(Clone, Copy, Debug, Default, Resource)]
+pub struct QuadsMask;
+
+/// Per-view mask written by the quads pass while the [`QuadsMask`] resource exists, cleared to 0
+/// every frame. It has the view's size and [`Msaa`] sample count, so multisampled views have to
+/// load it as a `texture_multisampled_2d<u32>`. User render graph nodes can query it on the view
+/// entity once they run after the quads pass.
+#[derive(Component)]
+pub struct QuadsMaskTexture(pub CachedTexture);
+
+pub(crate) struct MaskPlugin;
+
+impl Plugin for MaskPlugin {
+    fn build(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .add_systems(ExtractSchedule, extract_mask)
+            .add_systems(
+                Render,
+                prepare_mask_textures
+                    .in_set(RenderSet::Prepare)
+                    .run_if(resource_exists::<QuadsMask>()),
+            );
+    }
+}
+
+/// Unlike `ExtractResourcePlugin` this also removes the render world copy when the resource is
+/// removed, so the mask can be toggled at runtime
+fn extract_mask(mut commands: Commands, mask: Extract<Option<Res<QuadsMask>>>) {
+    match mask.as_ref() {
+        Some(mask) if mask.is_changed() => commands.insert_resource(**mask),
+        Some(_) => {}
+        None => commands.remove_resource::<QuadsMask>(),
+    }
+}
+
+fn prepare_mask_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    msaa: Res<Msaa>,
+    heatmap: Option<Res<QuadsHeatmap>>,
+    views: Query<(Entity, &ExtractedCamera), With<RenderPhase<QuadsPhaseItem>>>,
+) {
+    // NOTE: The heatmap replaces the quads pass, which leaves nothing to write the mask
+    if heatmap.is_some() {
+        return;
+    }
+    for (entity, camera) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("quads_mask_texture"),
+                size: Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: msaa.samples(),
+                dimension: TextureDimension::D2,
+                format: QUADS_MASK_TEXTURE_FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+        commands.entity(entity).insert(QuadsMaskTexture(texture));
+    }
+}
@@ -0,0 +1,271 @@
+//! Accounting of the GPU memory allocated for the quads.
+//!
+//! The render world sums up the buffers of every batch at the end of each frame into counters
+//! shared with the main world, which copies them into [`QuadsMemoryStats`] and the
+//! [`QuadsPlugin::GPU_MEMORY`] diagnostic once per second.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use bevy::{
+    diagnostic::{Diagnostic, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+    render::{
+        render_resource::Buffer,
+        renderer::{RenderDevice, RenderQueue},
+        Render, RenderApp, RenderSet,
+    },
+    time::common_conditions::on_timer,
+};
+
+use super::{
+    allocator::SharedAllocatorEvents, GpuQuadPoints, GpuQuads, QuadsPlugin, QuadsSettings,
+};
+
+/// GPU memory of one batch of quads in bytes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QuadsBatchMemory {
+    /// Size of the buffer the quads are drawn from
+    pub instances_allocated: u64,
+    /// Part of `instances_allocated` holding quads. The rest is room to grow into from the
+    /// [`QuadsAllocatorConfig::growth_factor`](super::QuadsAllocatorConfig::growth_factor), or
+    /// left over from a batch that had more quads and is released by
+    /// [`QuadsMemoryStats::shrink_to_fit`].
+    pub instances_used: u64,
+    pub indices: u64,
+    /// The batch's uniforms, and the points and kinds the generated quads are expanded from
+    pub auxiliary: u64,
+}
+
+impl QuadsBatchMemory {
+    /// Bytes allocated for the batch
+    pub fn total(&self) -> u64 {
+        self.instances_allocated + self.indices + self.auxiliary
+    }
+}
+
+/// GPU memory allocated for the quads, copied from the render world once per second and also
+/// recorded as the [`QuadsPlugin::GPU_MEMORY`] diagnostic.
+///
+/// Only the buffers of the batches are counted. Images are assets shared with the rest of the
+/// app, and the per-view textures of half resolution batches, the heatmap and the mask come from
+/// bevy's `TextureCache`.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct QuadsMemoryStats {
+    /// The [`Quads`](super::Quads) resource
+    pub quads: QuadsBatchMemory,
+    /// The quads generated from [`QuadsPoints`](super::QuadsPoints)
+    pub generated: QuadsBatchMemory,
+    /// Highest [`QuadsMemoryStats::total`] of any frame so far, including the frames in between
+    /// the updates
+    pub high_water_mark: u64,
+    shared: SharedQuadsMemory,
+}
+
+impl QuadsMemoryStats {
+    /// Bytes allocated for all batches
+    pub fn total(&self) -> u64 {
+        self.quads.total() + self.generated.total()
+    }
+
+    /// Reallocates the buffers of every batch at the size of their current contents at the end
+    /// of the next rendered frame, releasing the capacity left over from larger batches and the
+    /// room to grow. Growing a batch reallocates its buffers anyway, while shrinking it keeps them
+    /// with the default [`QuadsShrinkPolicy::Retain`](super::QuadsShrinkPolicy::Retain) to avoid
+    /// reallocating batches that change size every frame.
+    ///
+    /// Baked quads are already uploaded into a buffer of their size, and the generated quads are
+    /// expanded again.
+    pub fn shrink_to_fit(&self) {
+        self.shared.shrink.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Written by the render world and read by the main world
+#[derive(Clone, Copy, Debug, Default)]
+struct MemoryCounters {
+    quads: QuadsBatchMemory,
+    generated: QuadsBatchMemory,
+    high_water_mark: u64,
+}
+
+#[derive(Clone, Debug, Default, Resource)]
+struct SharedQuadsMemory {
+    counters: Arc<Mutex<MemoryCounters>>,
+    shrink: Arc<AtomicBool>,
+}
+
+/// Size of an optional buffer, 0 while it has not been created
+pub(crate) fn buffer_size(buffer: Option<&Buffer>) -> u64 {
+    buffer.map_or(0, |buffer| buffer.size())
+}
+
+pub(crate) struct MemoryPlugin;
+
+impl Plugin for MemoryPlugin {
+    fn build(&self, app: &mut App) {
+        let shared = SharedQuadsMemory::default();
+        app.insert_resource(QuadsMemoryStats {
+            shared: shared.clone(),
+            ..default()
+        })
+        .register_diagnostic(
+            Diagnostic::new(QuadsPlugin::GPU_MEMORY, "quads_gpu_memory", 20).with_suffix(" MiB"),
+        )
+        .add_systems(
+            PostUpdate,
+            update_memory_stats.run_if(on_timer(Duration::from_secs(1))),
+        );
+        app.sub_app_mut(RenderApp)
+            .insert_resource(shared)
+            // NOTE: At the end of the frame, so the buffers were reallocated for this frame's
+            // quads and the batches rebuilt by a shrink are prepared again before the next draw
+            .add_systems(
+                Render,
+                (shrink_quads_memory, count_quads_memory)
+                    .chain()
+                    .in_set(RenderSet::Cleanup),
+            );
+    }
+}
+
+fn update_memory_stats(mut stats: ResMut<QuadsMemoryStats>, mut diagnostics: Diagnostics) {
+    let counters = *stats.shared.counters.lock().unwrap();
+    stats.quads = counters.quads;
+    stats.generated = counters.generated;
+    stats.high_water_mark = counters.high_water_mark;
+    let total = stats.total();
+    diagnostics.add_measurement(QuadsPlugin::GPU_MEMORY, || total as f64 / (1024.0 * 1024.0));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn shrink_quads_memory(
+    mut commands: Commands,
+    memory: Res<SharedQuadsMemory>,
+    settings: Res<QuadsSettings>,
+    allocator_events: Res<SharedAllocatorEvents>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    gpu_quads: Option<ResMut<GpuQuads>>,
+    gpu_points: Option<Res<GpuQuadPoints>>,
+) {
+    if !memory.shrink.swap(false, Ordering::Relaxed) {
+        return;
+    }
+    if let Some(mut gpu_quads) = gpu_quads {
+        gpu_quads.shrink_to_fit(
+            &settings.allocator,
+            &allocator_events,
+            &render_device,
+            &render_queue,
+        );
+    }
+    // NOTE: The generated quads are prepared again from the points in the render world, with
+    // buffers of their size
+    if gpu_points.is_some() {
+        commands.remove_resource::<GpuQuadPoints>();
+    }
+}
+
+fn count_quads_memory(
+    memory: Res<SharedQuadsMemory>,
+    gpu_quads: Option<Res<GpuQuads>>,
+    gpu_points: Option<Res<GpuQuadPoints>>,
+) {
+    let mut counters = memory.counters.lock().unwrap();
+    counters.quads = gpu_quads.map_or_else(default, |gpu_quads| gpu_quads.memory());
+    counters.generated = gpu_points.map_or_else(default, |gpu_points| gpu_points.memory());
+    counters.high_water_mark = counters
+        .high_water_mark
+        .max(counters.quads.total() + counters.generated.total());
+}
+
+#[cfg(all(test, feature = "test_support"))]
+mod tests {
+    use bevy::render::render_resource::ShaderType;
+
+    use super::*;
+    use crate::{
+        quads::{GpuQuad, Quad, Quads},
+        test_support::render_frames,
+    };
+
+    /// Frame the batch shrinks from `LARGE` to `SMALL` quads in
+    const SHRINK_FRAME: usize = 4;
+    /// Frame [`QuadsMemoryStats::shrink_to_fit`] is called in
+    const SHRINK_TO_FIT_FRAME: usize = 8;
+    const LARGE: usize = 1000;
+    const SMALL: usize = 10;
+
+    #[test]
+    fn counts_the_quads_buffers() {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let recorded = records.clone();
+        render_frames(
+            |app| {
+                app.add_plugins(QuadsPlugin::default());
+                app.world.spawn(Camera3dBundle::default());
+                app.insert_resource(Quads::default()).add_systems(
+                    Update,
+                    |mut quads: ResMut<Quads>,
+                     stats: Res<QuadsMemoryStats>,
+                     mut frame: Local<usize>| {
+                        let len = if *frame < SHRINK_FRAME { LARGE } else { SMALL };
+                        if quads.data.len() != len {
+                            quads.data = vec![Quad::default(); len];
+                        }
+                        if *frame == SHRINK_TO_FIT_FRAME {
+                            stats.shrink_to_fit();
+                        }
+                        *frame += 1;
+                    },
+                );
+                // NOTE: The counters are recorded with the extracted quads they were counted for
+                app.sub_app_mut(RenderApp).add_systems(
+                    Render,
+                    (move |quads: Res<Quads>, memory: Res<SharedQuadsMemory>| {
+                        let counters = *memory.counters.lock().unwrap();
+                        recorded.lock().unwrap().push((quads.data.len(), counters));
+                    })
+                    .after(count_quads_memory)
+                    .in_set(RenderSet::Cleanup),
+                );
+            },
+            UVec2::new(64, 64),
+            12,
+        );
+
+        let records = records.lock().unwrap();
+        let instance_size = GpuQuad::min_size().get();
+        let mut high_water_mark = 0;
+        for (len, counters) in records.iter().filter(|(len, _)| *len > 0) {
+            let memory = counters.quads;
+            assert_eq!(memory.instances_used, *len as u64 * instance_size);
+            assert!(memory.instances_allocated >= memory.instances_used);
+            assert!(memory.indices >= *len as u64 * 6 * 4);
+            assert!(memory.auxiliary > 0);
+            assert_eq!(counters.generated, QuadsBatchMemory::default());
+            high_water_mark = high_water_mark.max(memory.total());
+            assert_eq!(counters.high_water_mark, high_water_mark);
+        }
+
+        let large = records.iter().rfind(|(len, _)| *len == LARGE).unwrap().1;
+        let first_small = records.iter().find(|(len, _)| *len == SMALL).unwrap().1;
+        // NOTE: Shrinking the batch retains its buffers by default
+        assert_eq!(
+            first_small.quads.instances_allocated,
+            large.quads.instances_allocated
+        );
+        let last = records.last().unwrap().1;
+        assert_eq!(
+            last.quads.instances_allocated, last.quads.instances_used,
+            "shrink_to_fit did not release the retained instances"
+        );
+        assert!(last.quads.indices < large.quads.indices);
+    }
+}
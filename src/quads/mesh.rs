@@ -0,0 +1,307 @@
+//! Baking [`Quads`] into a regular bevy [`Mesh`], for exporting them, static baking or processing
+//! them on the CPU with anything that works on meshes.
+
+use bevy::{
+    prelude::*,
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+
+use super::{Billboard, Quad, Quads, QuadsCoordinateConvention};
+
+/// Triangles of one quad, in the same order as the quads index buffer so the winding matches
+const QUAD_INDICES: [u32; 6] = [2, 0, 1, 1, 3, 2];
+
+impl Quads {
+    /// Bakes the quads into a triangle list [`Mesh`] with positions, normals, UVs and colors, four
+    /// vertices and two counter-clockwise triangles per quad like on the GPU.
+    ///
+    /// Billboarded quads face the camera at `camera`, or are left facing +z like
+    /// [`Billboard::None`] quads for `None`. Quads sized in screen space,
+    /// [`Billboard::FixedScreenSize`], [`Billboard::ViewportFraction`], [`Billboard::CocScaled`],
+    /// [`Billboard::ClipSpace`] and [`Billboard::ViewYMinScreenSize`], depend on the projection
+    /// and viewport and are left out, and so are [`Quad::hidden`] quads.
+    ///
+    /// Positions are relative to [`QuadsOrigin`](super::QuadsOrigin) like [`Quad::center`], and so
+    /// is `camera`. Both are in bevy's coordinate system, [`Quads::convention`] is converted.
+    /// [`Quads::variation`] and [`Quad::scale`] are applied and [`Quad::uv_rotation`] and
+    /// [`Quad::uv_tile`] are baked into the UVs, tiled quads need a repeating sampler on the
+    /// mesh's material. Colors are linear like bevy's vertex colors. Group colors, depth offsets, [`Quad::uv_scroll`],
+    /// [`Quad::spin`], [`Quad::hsv_shift`], [`Quad::mask`] and the remaining per-quad shading
+    /// options have no mesh equivalent and are ignored.
+    pub fn to_mesh(&self, camera: Option<&GlobalTransform>) -> Mesh {
+        let mut positions = Vec::with_capacity(4 * self.data.len());
+        let mut normals = Vec::with_capacity(4 * self.data.len());
+        let mut uvs = Vec::with_capacity(4 * self.data.len());
+        let mut colors = Vec::with_capacity(4 * self.data.len());
+        let mut indices = Vec::with_capacity(6 * self.data.len());
+
+        for quad in self.data.iter().filter(|quad| !quad.hidden) {
+            let varied;
+            let quad = if let Some(variation) = &self.variation {
+                varied = self.convention.quad_to_bevy(&variation.apply(quad));
+                &varied
+            } else if self.convention != QuadsCoordinateConvention::YUpRightHanded {
+                varied = self.convention.quad_to_bevy(quad);
+                &varied
+            } else {
+                quad
+            };
+            let Some((right, up, normal)) = quad_axes(quad, camera) else {
+                continue;
+            };
+
+            let base = positions.len() as u32;
+            indices.extend(QUAD_INDICES.iter().map(|index| base + index));
+            let half_extents = quad.half_extents.abs() * quad.scale.max(0.0);
+            let color = quad.color.as_linear_rgba_f32();
+            let rotation = Vec2::from_angle(quad.uv_rotation);
+            let tile = Vec2::select(quad.uv_tile.cmpeq(Vec2::ZERO), Vec2::ONE, quad.uv_tile);
+            for vertex in 0..4 {
+                let unit = Vec2::new((vertex & 1) as f32, (vertex >> 1) as f32);
+                let offset = (unit * 2.0 - 1.0) * half_extents.truncate();
+                positions.push((quad.center + right * offset.x + up * offset.y).to_array());
+                normals.push(normal.to_array());
+
+                // NOTE: Texture v coordinates point down while the quad's y points up, and
+                // negative half-extents mirror the texture like on the GPU
+                let mut uv = Vec2::new(unit.x, 1.0 - unit.y);
+                if quad.half_extents.x.is_sign_negative() {
+                    uv.x = 1.0 - uv.x;
+                }
+                if quad.half_extents.y.is_sign_negative() {
+                    uv.y = 1.0 - uv.y;
+                }
+                uvs.push(((rotation.rotate(uv - 0.5) + 0.5) * tile).to_array());
+                colors.push(color);
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh
+    }
+}
+
+/// World-space right and up directions and normal of a quad as the vertex shader computes them,
+/// `None` for quads sized in screen space
+fn quad_axes(quad: &Quad, camera: Option<&GlobalTransform>) -> Option<(Vec3, Vec3, Vec3)> {
+    let facing_z = (Vec3::X, Vec3::Y, Vec3::Z);
+    match (&quad.billboard, camera) {
+        (Billboard::None, _) | (Billboard::ViewY | Billboard::WorldY, None) => Some(facing_z),
+        (Billboard::ViewY, Some(camera)) => Some((
+            camera.right(),
+            camera.up(),
+            (camera.translation() - quad.center)
+                .try_normalize()
+                .unwrap_or(camera.back()),
+        )),
+        (Billboard::WorldY, Some(camera)) => {
+            let to_camera = camera.translation() - quad.center;
+            let right = (camera.right() * Vec3::new(1.0, 0.0, 1.0))
+                .try_normalize()
+                .unwrap_or_else(|| Vec3::Y.cross(to_camera).normalize());
+            // NOTE: Mirrored like in the shader when it would face away from the camera
+            let right = if right.cross(Vec3::Y).dot(to_camera) < 0.0 {
+                -right
+            } else {
+                right
+            };
+            Some((right, Vec3::Y, right.cross(Vec3::Y)))
+        }
+        (Billboard::OrientedY { orientation }, None) => {
+            let orientation = orientation.normalize();
+            Some((
+                orientation * Vec3::X,
+                orientation * Vec3::Y,
+                orientation * Vec3::Z,
+            ))
+        }
+        (Billboard::OrientedY { orientation }, Some(camera)) => {
+            let up = orientation.normalize() * Vec3::Y;
+            let right = up
+                .cross(camera.translation() - quad.center)
+                .try_normalize()
+                .unwrap_or_else(|| (camera.right() - up * camera.right().dot(up)).normalize());
+            Some((right, up, right.cross(up)))
+        }
+        (
+            Billboard::FixedScreenSize
+            | Billboard::ViewportFraction
+            | Billboard::CocScaled
+            | Billboard::ClipSpace
+            | Billboard::ViewYMinScreenSize { .. },
+            _,
+        ) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::mesh::VertexAttributeValues;
+
+    use super::*;
+
+    fn quad(center: Vec3, billboard: Billboard) -> Quad {
+        Quad {
+            center,
+            half_extents: Vec3::new(1.0, 2.0, 0.0),
+            billboard,
+            ..default()
+        }
+    }
+
+    fn positions(mesh: &Mesh) -> Vec<Vec3> {
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("the mesh has no positions");
+        };
+        positions.iter().copied().map(Vec3::from).collect()
+    }
+
+    fn normals(mesh: &Mesh) -> Vec<Vec3> {
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("the mesh has no normals");
+        };
+        normals.iter().copied().map(Vec3::from).collect()
+    }
+
+    /// Normals of the triangles of `mesh` by their counter-clockwise winding
+    fn winding_normals(mesh: &Mesh) -> Vec<Vec3> {
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("the mesh has no u32 indices");
+        };
+        let positions = positions(mesh);
+        indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| positions[triangle[i] as usize]);
+                (b - a).cross(c - a).normalize()
+            })
+            .collect()
+    }
+
+    /// Asserts that every triangle winds counter-clockwise around the normals of its vertices.
+    /// [`Billboard::ViewY`] normals point at the camera, so they only face the same side.
+    fn assert_winding_matches_normals(mesh: &Mesh) {
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("the mesh has no u32 indices");
+        };
+        let normals = normals(mesh);
+        for (triangle, winding) in indices.chunks_exact(3).zip(winding_normals(mesh)) {
+            for &index in triangle {
+                let normal = normals[index as usize];
+                assert!(
+                    winding.dot(normal) > 0.0,
+                    "triangle {triangle:?} winds around {winding} but has the normal {normal}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn winds_counter_clockwise_towards_the_normal() {
+        let camera = GlobalTransform::from(
+            Transform::from_xyz(3.0, 4.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+        );
+        let orientation = Quat::from_rotation_x(0.5) * Quat::from_rotation_y(1.0);
+        for billboard in [
+            Billboard::None,
+            Billboard::ViewY,
+            Billboard::WorldY,
+            Billboard::OrientedY { orientation },
+        ] {
+            for camera in [None, Some(&camera)] {
+                let quads = Quads {
+                    data: vec![quad(Vec3::new(1.0, -1.0, 2.0), billboard.clone())],
+                    ..default()
+                };
+                let mesh = quads.to_mesh(camera);
+                assert_eq!(winding_normals(&mesh).len(), 2, "{billboard:?}");
+                assert_winding_matches_normals(&mesh);
+            }
+        }
+    }
+
+    #[test]
+    fn billboards_face_the_camera() {
+        let camera = GlobalTransform::from(
+            Transform::from_xyz(-5.0, 2.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+        );
+        let center = Vec3::new(1.0, 0.0, -1.0);
+        for billboard in [
+            Billboard::ViewY,
+            Billboard::WorldY,
+            Billboard::OrientedY {
+                orientation: Quat::from_rotation_z(0.3),
+            },
+        ] {
+            let quads = Quads {
+                data: vec![quad(center, billboard.clone())],
+                ..default()
+            };
+            let to_camera = camera.translation() - center;
+            for winding in winding_normals(&quads.to_mesh(Some(&camera))) {
+                assert!(winding.dot(to_camera) > 0.0, "{billboard:?} faces away");
+            }
+        }
+    }
+
+    #[test]
+    fn mirrored_quads_keep_their_winding() {
+        let mut mirrored = quad(Vec3::ZERO, Billboard::None);
+        mirrored.half_extents = Vec3::new(-1.0, -2.0, 0.0);
+        let quads = Quads {
+            data: vec![mirrored],
+            ..default()
+        };
+        let mesh = quads.to_mesh(None);
+        assert_winding_matches_normals(&mesh);
+        for winding in winding_normals(&mesh) {
+            assert!(winding.abs_diff_eq(Vec3::Z, 1e-5));
+        }
+    }
+
+    #[test]
+    fn converted_conventions_keep_their_winding() {
+        for convention in [
+            QuadsCoordinateConvention::ZUpRightHanded,
+            QuadsCoordinateConvention::ZUpLeftHanded,
+        ] {
+            let quads = Quads {
+                data: vec![quad(Vec3::new(1.0, 2.0, 3.0), Billboard::ViewY)],
+                convention,
+                ..default()
+            };
+            let camera = GlobalTransform::from(Transform::from_xyz(0.0, 0.0, 10.0));
+            assert_winding_matches_normals(&quads.to_mesh(Some(&camera)));
+        }
+    }
+
+    #[test]
+    fn leaves_out_hidden_and_screen_sized_quads() {
+        let mut hidden = quad(Vec3::ZERO, Billboard::None);
+        hidden.hidden = true;
+        let quads = Quads {
+            data: vec![
+                hidden,
+                quad(Vec3::ZERO, Billboard::FixedScreenSize),
+                quad(Vec3::ZERO, Billboard::ClipSpace),
+                quad(Vec3::X, Billboard::None),
+            ],
+            ..default()
+        };
+        let mesh = quads.to_mesh(None);
+        assert_eq!(mesh.count_vertices(), 4);
+        assert_eq!(mesh.indices().map(Indices::len), Some(6));
+        assert!(positions(&mesh)
+            .iter()
+            .all(|position| position.abs_diff_eq(Vec3::X, 2.0 + 1e-5)));
+    }
+}
@@ -0,0 +1,3084 @@
+use bevy::{
+    asset::load_internal_asset,
+    core_pipeline::{
+        clear_color::ClearColorConfig,
+        core_3d::{self, Camera3d},
+        tonemapping::{DebandDither, Tonemapping},
+    },
+    diagnostic::{Diagnostic, DiagnosticId, Diagnostics, RegisterDiagnostic},
+    ecs::{
+        query::{Has, QueryItem, ROQueryItem},
+        system::{lifetimeless::SRes, SystemParamItem},
+    },
+    pbr::{MeshPipeline, SetMeshViewBindGroup},
+    prelude::*,
+    reflect::{TypePath, TypeUuid},
+    render::{
+        camera::ExtractedCamera,
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        mesh::PrimitiveTopology,
+        render_asset::RenderAssets,
+        render_graph::{
+            NodeRunError, RenderGraph, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner,
+        },
+        render_phase::{
+            AddRenderCommand, CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions,
+            PhaseItem, RenderCommand, RenderCommandResult, RenderPhase, SetItemPipeline,
+            TrackedRenderPass,
+        },
+        render_resource::{
+            encase, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+            BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType,
+            BlendComponent, BlendFactor, BlendOperation, BlendState, Buffer, BufferBindingType,
+            BufferDescriptor, BufferId, BufferInitDescriptor, BufferSize, BufferUsages,
+            CachedRenderPipelineId, ColorTargetState, ColorWrites, CompareFunction, DepthBiasState,
+            DepthStencilState, Face, FragmentState, FrontFace, IndexFormat, LoadOp,
+            MultisampleState, Operations, PipelineCache, PolygonMode, PrimitiveState,
+            RenderPassColorAttachment, RenderPassDepthStencilAttachment, RenderPassDescriptor,
+            RenderPipelineDescriptor, SamplerBindingType, ShaderDefVal, ShaderSize, ShaderStages,
+            ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines, StencilFaceState,
+            StencilState, StorageBuffer, TextureDimension, TextureFormat, TextureSampleType,
+            TextureViewDimension, UniformBuffer, VertexState,
+        },
+        renderer::{RenderContext, RenderDevice, RenderQueue},
+        texture::{BevyDefault, FallbackImage},
+        view::{ExtractedView, ViewDepthTexture, ViewTarget},
+        Extract, Render, RenderApp, RenderSet,
+    },
+};
+use bytemuck::{cast_slice, Pod, Zeroable};
+
+mod allocator;
+mod animation;
+mod bake;
+mod bytes;
+mod commands;
+mod convention;
+mod declutter;
+mod depth_of_field;
+mod dissolve;
+#[cfg(feature = "compression")]
+mod file;
+mod flow_field;
+mod generate;
+mod generator;
+mod global_alpha;
+mod group_colors;
+mod half_float;
+mod half_res;
+mod heatmap;
+mod hsv;
+mod id;
+#[cfg(any(feature = "csv", feature = "parquet"))]
+mod import;
+mod mask;
+mod memory;
+mod mesh;
+mod near_fade;
+mod origin;
+mod pixels;
+mod sampler;
+mod sanitize;
+mod screen_index;
+mod shards;
+mod stamp;
+mod subpass;
+mod variation;
+mod writer;
+
+pub use allocator::{
+    QuadsAllocation, QuadsAllocatorConfig, QuadsAllocatorEvent, QuadsAllocatorEventKind,
+    QuadsBuffer, QuadsShrinkPolicy,
+};
+pub use animation::{
+    QuadCurve, QuadCurveError, QuadsAnimation, QuadsAnimationMode, QUAD_CURVE_MAX_KEYS,
+};
+pub use bake::BakedQuads;
+pub use bytes::QuadsBytesError;
+pub use commands::{QuadCommand, QuadCommandKeys, QuadsCommandQueue};
+pub use convention::QuadsCoordinateConvention;
+pub use declutter::QuadsDeclutter;
+pub use depth_of_field::{QuadsDepthOfField, QuadsDepthOfFieldMode};
+pub use dissolve::QuadsDissolve;
+#[cfg(feature = "compression")]
+pub use file::{CompressedQuadsLoader, QuadsFileError};
+pub use flow_field::{QuadsFlowField, QuadsFlowFieldError};
+pub use generate::{GpuQuadPoints, QuadPoint, QuadsPoints};
+pub use generator::QuadsGenerator;
+pub use global_alpha::QuadsGlobalAlpha;
+pub use group_colors::{QuadGroupRecolor, QuadsGroupColors, QUAD_GROUPS};
+pub use half_res::QuadsResolution;
+pub use heatmap::{QuadsHeatmap, ViewQuadsHeatmapTexture, HEATMAP_MAX_RAMP_STOPS};
+pub use id::{QuadId, QuadsSlots};
+#[cfg(any(feature = "csv", feature = "parquet"))]
+pub use import::{QuadsColumnMapping, QuadsImportError, QuadsValueScale};
+pub use mask::{QuadsMask, QuadsMaskTexture, QUADS_MASK_TEXTURE_FORMAT};
+pub use memory::{QuadsBatchMemory, QuadsMemoryStats};
+pub use near_fade::QuadsNearFade;
+pub use origin::QuadsOrigin;
+pub use sampler::QuadsSamplerDesc;
+pub use screen_index::{QuadsScreenIndex, ScreenQuad};
+pub use shards::QuadsShards;
+pub use stamp::{QuadCluster, QuadStampId};
+pub use subpass::{QuadsSubpassAnchor, QuadsSubpassDraw, QuadsSubpassDraws};
+pub use variation::QuadsVariation;
+pub use writer::QuadsWriter;
+
+// NOTE: The old names of the types that describe the batch, before they were named `Quads*`
+#[deprecated(note = "renamed to `QuadsGroupColors`")]
+pub type QuadGroupColors = QuadsGroupColors;
+#[deprecated(note = "renamed to `QuadsPoints`")]
+pub type QuadPoints = QuadsPoints;
+#[deprecated(note = "renamed to `QuadsShards`")]
+pub type QuadShards = QuadsShards;
+#[deprecated(note = "renamed to `QuadsSlots`")]
+pub type QuadSlots = QuadsSlots;
+#[deprecated(note = "renamed to `QuadsVariation`")]
+pub type QuadVariation = QuadsVariation;
+#[deprecated(note = "renamed to `QuadsWriter`")]
+pub type QuadWriter<'w> = QuadsWriter<'w>;
+
+use allocator::{write_allocated, AllocatorPlugin, SharedAllocatorEvents};
+use animation::GpuBatchAnimation;
+use bake::{diagnose_baked_quads, BakeError, BakedSource};
+use commands::apply_quad_commands;
+use depth_of_field::{DepthOfFieldPlugin, GpuDepthOfField, SetDepthOfFieldBindGroup};
+use dissolve::{pack_dissolve, DissolvePlugin, DissolveUniform};
+use flow_field::{is_flow_field_format, FlowFieldPlugin, FlowFieldUniform};
+use generate::{DrawGeneratedQuads, GeneratedQuadsPlugin, GpuGeneratedQuadsMarker};
+use generator::poll_quads_generators;
+use global_alpha::{GlobalAlphaPlugin, GlobalAlphaUniform};
+use group_colors::{GroupColorsPlugin, GroupColorsUniform};
+use half_float::{f16_to_f32, f32_to_f16};
+use half_res::{HalfResPlugin, ViewHalfResComposite, ViewHalfResQuads};
+use heatmap::{HeatmapPlugin, HEATMAP_BLEND, HEATMAP_TEXTURE_FORMAT};
+use hsv::{pack_hsv_shift, unpack_hsv_shift};
+use mask::MaskPlugin;
+use memory::{buffer_size, MemoryPlugin};
+use near_fade::{NearFadePlugin, NearFadeUniform};
+use origin::{OriginPlugin, OriginUniform};
+use sampler::QuadSamplers;
+use sanitize::{SanitizePlugin, SanitizeReport, SharedSanitizeReport};
+use screen_index::ScreenIndexPlugin;
+use subpass::SubpassPlugin;
+
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub enum Billboard {
+    #[default]
+    None,
+    ViewY,
+    WorldY,
+    FixedScreenSize,
+    /// Screen-aligned like `FixedScreenSize` but sized relative to the viewport height so the quad
+    /// covers the same proportion of the view at any resolution
+    ViewportFraction,
+    /// The quad is placed directly in normalized device coordinates, ignoring the camera, for
+    /// screen-aligned overlays and full-screen washes
+    ClipSpace,
+    /// Faces the camera and is sized in world units like `ViewY`, but never gets smaller on screen
+    /// than a `FixedScreenSize` quad of the same aspect ratio with a y half-extent of
+    /// `min_half_extent` pixels. The shader computes both sizes and scales the quad up uniformly to
+    /// the larger one, so it shrinks with distance until it reaches the floor and then keeps its
+    /// screen size, like a map pin.
+    ViewYMinScreenSize {
+        min_half_extent: f32,
+    },
+    /// Turns toward the camera around the y axis of `orientation`, like `WorldY` around an
+    /// arbitrary per-quad axis, for tilted signboards or grass on slopes. The quad's up is the
+    /// rotated y axis, its normal the direction to the camera with the part along that axis
+    /// removed, and its right the cross product of the two. Looking straight along the axis, the
+    /// quad keeps the camera's right direction instead. Rotations around the y axis itself make no
+    /// difference.
+    OrientedY {
+        orientation: Quat,
+    },
+    /// Screen-aligned and sized in pixels like `FixedScreenSize`, but grown by the circle of
+    /// confusion at the quad's depth in views with a [`QuadsDepthOfField`], for bokeh sprites of
+    /// out of focus lights. The half-extents are the size in focus, and the quad is scaled up
+    /// uniformly until its y half-extent grew by the blur radius of the view's
+    /// [`QuadsDepthOfFieldMode`]. Views without a depth of field draw the quad in focus.
+    CocScaled,
+}
+
+#[derive(Clone, Debug)]
+pub struct Quad {
+    pub color: Color,
+    pub center: Vec3,
+    /// Half-extents are in world units except for in Billboard::FixedScreenSize mode, then they are
+    /// in screen pixels, and in Billboard::ViewportFraction mode, then they are fractions of the
+    /// viewport height (a y half-extent of 0.025 makes the quad 5% of the viewport height).
+    /// In Billboard::ClipSpace mode the center and half-extents are in normalized device
+    /// coordinates, x and y from -1 to 1 across the viewport and z the reverse-Z depth from 1 at the
+    /// near plane to 0 at the far plane. A half-extent of 1 with a z of 1 covers the whole view.
+    /// In Billboard::ViewYMinScreenSize mode they are in world units and scaled up on screen to the
+    /// mode's minimum size. In Billboard::CocScaled mode they are the size in screen pixels in
+    /// focus.
+    ///
+    /// A negative x or y half-extent mirrors the texture along that axis. The quad itself keeps
+    /// the same facing and size as with the absolute half-extents. Zero half-extents are valid and
+    /// draw nothing, while non-finite ones are hidden by [`QuadsPlugin::sanitize`].
+    ///
+    /// Migrating from earlier versions: Billboard::FixedScreenSize half-extents and the
+    /// `min_half_extent` of Billboard::ViewYMinScreenSize used to act as the full size in pixels.
+    /// They now are half of it like everywhere else, so halve old values to keep the same size on
+    /// screen.
+    pub half_extents: Vec3,
+    /// Multiplies the half-extents in the vertex shader, so quads sharing a base size can be
+    /// grown or pulsed individually, e.g. on selection, without touching `half_extents`. It
+    /// applies in every billboard mode to whatever unit the half-extents are in, and
+    /// Billboard::ViewYMinScreenSize quads scale their minimum screen size too. Combines with the
+    /// scale track of [`Quads::animation`]. Uploaded as a half float, negative scales are clamped
+    /// to 0. 1 by default.
+    pub scale: f32,
+    pub billboard: Billboard,
+    /// Lit quads are shaded by the scene's ambient, directional, point and spot lights. Unlit quads
+    /// output their color as-is.
+    pub lit: bool,
+    /// Rotation of the texture coordinates around the quad center in radians, independent of the
+    /// quad geometry
+    pub uv_rotation: f32,
+    /// Speed in radians per second at which the quad spins counter-clockwise around its center in
+    /// its own plane, animated on the GPU. Works in every billboard mode.
+    pub spin: f32,
+    /// Color of the Blinn-Phong highlight of lit quads
+    pub specular_color: Color,
+    /// Blinn-Phong exponent, higher values give smaller, sharper highlights. Zero, the default,
+    /// disables the highlight.
+    pub specular_power: f32,
+    /// Fraction of the light arriving at the back of a lit quad that shines through it, from 0
+    /// to 1, so thin surfaces like leaves glow when they are backlit. At 1 the back lets through
+    /// as much light as the front reflects, tinted by the quad's color. Only applies in
+    /// [`Quads::double_sided`] batches and is uploaded as a byte. 0 by default.
+    pub translucency: f32,
+    /// Selects the random variation applied by [`Quads::variation`]. Quads with the same seed vary
+    /// in the same way, so scatter code would typically use the quad's index at creation time.
+    /// Also offsets the noise of [`Quads::dissolve`].
+    pub seed: u32,
+    /// How strongly the [`Quads::detail`] texture is blended over the base color, from 0 to 1
+    pub detail_weight: f32,
+    /// Scroll speed of the detail texture coordinates in texture sizes per second
+    pub detail_scroll: Vec2,
+    /// Scroll speed of the texture coordinates in texture sizes per second, for conveyor belts and
+    /// flowing energy effects. The texture wraps around, so the image's sampler does not need to
+    /// repeat.
+    pub uv_scroll: Vec2,
+    /// Number of times the texture repeats across the quad along u and v. Zero components, like
+    /// in the default, draw the texture once.
+    pub uv_tile: Vec2,
+    /// Draws the quad in front of all other geometry, like a first-person weapon. Its depth is
+    /// squeezed into a thin slice in front of the rest of the scene, so foreground quads still
+    /// occlude each other correctly but never clip into the world.
+    pub foreground: bool,
+    /// Moves the depth of the quad this many world units toward the camera without moving it on
+    /// screen, to order coplanar quads like decals on the same surface. Ignored by
+    /// [`Billboard::ClipSpace`] quads.
+    pub depth_offset: f32,
+    /// Quads in the same non-zero group can be recolored all at once with a
+    /// [`QuadGroupRecolor`] event, without uploading them again
+    pub group: u8,
+    /// Adjusts the hue, saturation and value of the textured color in the fragment shader, to
+    /// tint one texture in several colors or grey out a quad without recoloring the asset. x
+    /// rotates the hue in degrees, wrapping around, and y and z are added to the saturation and
+    /// value, which are clamped to 0 to 1 and at least 0 afterwards. Uploaded as half floats, so
+    /// the hue is only precise to about a fifth of a degree.
+    ///
+    /// Batches where all quads are left at zero skip the conversion.
+    pub hsv_shift: Vec3,
+    /// Value written into the [`QuadsMaskTexture`] of every view while the [`QuadsMask`] resource
+    /// exists, e.g. to outline selected quads in a post-process. 0 marks quads that are not
+    /// masked.
+    pub mask: u8,
+    /// Discards the fragments of the quad where the noise of [`Quads::dissolve`] is below this
+    /// threshold, from 0 for the whole quad to 1 for none of it. Combines with the dissolve track
+    /// of [`Quads::animation`]. Uploaded as a half float and clamped to 0 to 1, ignored by batches
+    /// without a dissolve.
+    pub dissolve: f32,
+    /// Skips drawing the quad without removing it, so it keeps its index and [`QuadId`]. Hidden
+    /// quads are still uploaded and collapse to a point in the vertex shader. Set by
+    /// [`QuadsDeclutter`] for the quads it hides.
+    pub hidden: bool,
+    /// Rank of the quad for [`QuadsDeclutter`], which keeps quads over the lower-priority quads they
+    /// overlap on screen. 0, the default, leaves the quad out of decluttering. Only used on the
+    /// CPU, so it is not part of the instance data.
+    pub priority: u16,
+}
+
+impl Default for Quad {
+    fn default() -> Self {
+        Self {
+            color: Color::default(),
+            center: Vec3::ZERO,
+            half_extents: Vec3::ZERO,
+            scale: 1.0,
+            billboard: Billboard::default(),
+            lit: false,
+            uv_rotation: 0.0,
+            spin: 0.0,
+            specular_color: Color::default(),
+            specular_power: 0.0,
+            translucency: 0.0,
+            seed: 0,
+            detail_weight: 0.0,
+            detail_scroll: Vec2::ZERO,
+            uv_scroll: Vec2::ZERO,
+            uv_tile: Vec2::ZERO,
+            foreground: false,
+            depth_offset: 0.0,
+            group: 0,
+            hsv_shift: Vec3::ZERO,
+            mask: 0,
+            dissolve: 0.0,
+            hidden: false,
+            priority: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Resource, ExtractResource, TypeUuid, TypePath)]
+#[uuid = "1f2d4e0b-5c59-4a3e-9a57-7c1b8f6f0c21"]
+pub struct Quads {
+    pub data: Vec<Quad>,
+    /// Texture sampled by all quads and multiplied with their color. Quads are drawn with their
+    /// flat color when `None` or while the image is loading.
+    pub image: Option<Handle<Image>>,
+    /// Maps the [`QuadId`]s returned by [`Quads::insert`] to indices in `data`. Quads pushed to
+    /// `data` directly have no id, and reordering or removing from `data` directly invalidates
+    /// the mapping.
+    pub slots: QuadsSlots,
+    /// Jitter applied to the color, size and texture rotation of every quad when it is uploaded,
+    /// based on [`Quad::seed`]. `data` itself is left unchanged.
+    pub variation: Option<QuadsVariation>,
+    /// Coordinate system of `data`, converted to bevy's when the quads are uploaded after the
+    /// variation is applied. `data` itself is left unchanged.
+    pub convention: QuadsCoordinateConvention,
+    /// Secondary texture blended over the base color of quads with a non-zero
+    /// [`Quad::detail_weight`]
+    pub detail: Option<QuadsDetail>,
+    /// How the alpha of the quads is treated, drawn opaque with straight alpha by default
+    pub alpha_mode: QuadsAlphaMode,
+    /// Constant the colors of the quads are weighted by when they are blended, set on the pass
+    /// with `TrackedRenderPass::set_blend_constant`, e.g. to fade the whole batch without
+    /// touching its quads. The color is converted to linear RGB.
+    ///
+    /// Batches with a constant are blended like [`QuadsAlphaMode::PremultipliedBlend`] batches, as
+    /// `color * constant + target * (1 - alpha)` with the color premultiplied. Straight alpha
+    /// batches are premultiplied in the fragment shader after the image is sampled, which blends
+    /// them like straight alpha. The alpha the quads cover the target with is not weighted, a
+    /// [`QuadsGlobalAlpha`] of the constant's alpha scales it too, fading the quads out towards
+    /// what is behind them. `None` by default.
+    pub blend_constant: Option<Color>,
+    /// Number of corners every quad is drawn with. Above 4 the quads are drawn as regular
+    /// polygons with their edges touching the quad's edges, which skips most of the transparent
+    /// corners of round textures like glows at the cost of more vertices per quad. Rounded up to
+    /// a multiple of 4 and limited to [`MAX_QUAD_CORNERS`], 0 and 4 draw plain quads.
+    pub corner_count: u32,
+    /// Draw the backs of the quads instead of culling them, e.g. for cards or leaves seen from
+    /// both sides. [`Quad::lit`] quads are lit on the side that is seen, so their backs are not
+    /// dark, and let the light arriving at their back through by their [`Quad::translucency`].
+    /// Billboarded quads always face the camera and are unaffected.
+    pub double_sided: bool,
+    /// Sampler for `image`, instead of the image's own sampler
+    pub sampler: Option<QuadsSamplerDesc>,
+    /// 3D vector field displacing the quads in the vertex shader, assign it with
+    /// [`Quads::set_flow_field`] to validate the image. Invalid fields leave the quads in place.
+    pub flow_field: Option<QuadsFlowField>,
+    /// Resolution the quads are rendered at, half resolution shades a quarter of the fragments
+    /// for fill-rate bound batches like large overlapping glows. Drawn at full resolution while a
+    /// [`QuadsDebugView`] or [`QuadsHeatmap`] is active.
+    pub resolution: QuadsResolution,
+    /// Noise the quads dissolve along by their [`Quad::dissolve`], quads are not dissolved
+    /// without it
+    pub dissolve: Option<QuadsDissolve>,
+    /// Keyframed scale, alpha, hue shift and dissolve applied to every quad in the vertex shader
+    pub animation: Option<QuadsAnimation>,
+    /// Quads frozen by [`Quads::bake`], drawn instead of `data` while set
+    pub baked: Option<BakedQuads>,
+}
+
+/// Maximum [`Quads::corner_count`]
+pub const MAX_QUAD_CORNERS: u32 = 32;
+
+impl Quads {
+    /// [`Quads::corner_count`] as drawn
+    fn corners(&self) -> u32 {
+        self.corner_count
+            .clamp(4, MAX_QUAD_CORNERS)
+            .next_multiple_of(4)
+    }
+
+    /// Whether the colors of the quads are premultiplied when they are uploaded
+    fn premultiplies_colors(&self) -> bool {
+        self.alpha_mode != QuadsAlphaMode::Opaque
+    }
+
+    /// Whether the colors of the quads are premultiplied when they are uploaded
+    #[deprecated(note = "use `Quads::alpha_mode` instead")]
+    pub fn premultiply_alpha(&self) -> bool {
+        self.premultiplies_colors()
+    }
+
+    /// Premultiplies the colors of the quads when they are uploaded, like
+    /// [`QuadsAlphaMode::PremultiplyOnUpload`]. Batches blended with
+    /// [`QuadsAlphaMode::PremultipliedBlend`] premultiply them either way and are left unchanged.
+    #[deprecated(note = "use `Quads::alpha_mode` instead")]
+    pub fn set_premultiply_alpha(&mut self, premultiply: bool) {
+        match (self.alpha_mode, premultiply) {
+            (QuadsAlphaMode::Opaque, true) => self.alpha_mode = QuadsAlphaMode::PremultiplyOnUpload,
+            (QuadsAlphaMode::PremultiplyOnUpload, false) => {
+                self.alpha_mode = QuadsAlphaMode::Opaque
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether the quads are blended with premultiplied alpha
+    #[deprecated(note = "use `Quads::alpha_mode` instead")]
+    pub fn premultiplied_alpha(&self) -> bool {
+        self.alpha_mode == QuadsAlphaMode::PremultipliedBlend
+    }
+
+    /// Blends the quads with [`QuadsAlphaMode::PremultipliedBlend`], or draws them
+    /// [`QuadsAlphaMode::Opaque`] again
+    #[deprecated(note = "use `Quads::alpha_mode` instead")]
+    pub fn set_premultiplied_alpha(&mut self, premultiplied: bool) {
+        self.alpha_mode = if premultiplied {
+            QuadsAlphaMode::PremultipliedBlend
+        } else {
+            QuadsAlphaMode::Opaque
+        };
+    }
+}
+
+/// How the alpha of the quads is treated, see [`Quads::alpha_mode`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QuadsAlphaMode {
+    /// The colors are uploaded as they are and the quads are drawn opaque, unless the batch has a
+    /// [`Quads::blend_constant`]
+    #[default]
+    Opaque,
+    /// Multiply the color of every quad by its alpha when it is uploaded, for rendering into
+    /// targets that are composited with premultiplied alpha. The quads are still drawn opaque and
+    /// `data` itself is left unchanged.
+    PremultiplyOnUpload,
+    /// Blend the quads over the target with premultiplied alpha, for images authored
+    /// premultiplied like particle sprites, whose filtered edges don't fringe dark like the ones
+    /// of straight alpha images. The image is sampled as it is and the colors of the quads are
+    /// premultiplied when they are uploaded, like with [`QuadsAlphaMode::PremultiplyOnUpload`].
+    ///
+    /// The quads are blended in the order of `data`, and while [`QuadsPlugin::depth_write_enabled`]
+    /// quads behind earlier ones are hidden by them, so overlapping translucent quads usually
+    /// disable it. Batches drawn at [`QuadsResolution::Half`] stay opaque.
+    PremultipliedBlend,
+}
+
+/// A detail texture for [`Quads::detail`], e.g. for dirt overlays or animated shimmer
+#[derive(Clone, Debug, Default)]
+pub struct QuadsDetail {
+    /// Sampled with wrapping texture coordinates so it can scroll, independent of the image's
+    /// sampler address mode
+    pub image: Handle<Image>,
+    pub blend: QuadsDetailBlend,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QuadsDetailBlend {
+    /// Darkens the base color, white leaves it unchanged
+    #[default]
+    Multiply,
+    /// Darkens dark and brightens bright parts of the base color, mid-grey leaves it unchanged
+    Overlay,
+}
+
+/// Whether the quads are drawn at all, can be changed at runtime. Disabling it hides the quads
+/// resource and generated quads without removing the plugin or touching their data, e.g. for a
+/// debug overlay toggle. Enabled by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Resource, ExtractResource)]
+pub struct QuadsEnabled(pub bool);
+
+impl Default for QuadsEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Debug visualization of the quads, can be changed at runtime. Ignored while a
+/// [`QuadsHeatmap`] is active.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Resource, ExtractResource)]
+#[non_exhaustive]
+pub enum QuadsDebugView {
+    #[default]
+    Off,
+    /// Only draw the borders of the quads, in their unlit color
+    Wireframe,
+    /// Additively draw every quad in grey without depth testing, so brighter pixels are covered
+    /// by more quads
+    Overdraw,
+    /// Color each quad by a hash of its instance index
+    InstanceIndex,
+    /// Color each quad by its world-space normal mapped from -1..1 to 0..1, to check which way
+    /// billboards face
+    Normal,
+    /// Color each quad by the texture coordinates it samples at, u in red and v in green, to spot
+    /// flipped or rotated textures
+    Uv,
+}
+
+/// Whether there are any quads to draw. Without them the views get no quads phase, which leaves
+/// the quads systems that work per view with nothing to do and the quads pass node idle.
+fn quads_active(
+    quads: Extract<Option<Res<Quads>>>,
+    points: Extract<Option<Res<QuadsPoints>>>,
+) -> bool {
+    quads.as_ref().is_some_and(|quads| !quads.is_empty())
+        || points
+            .as_ref()
+            .is_some_and(|points| !points.points.is_empty())
+}
+
+fn extract_quads_phase(
+    mut commands: Commands,
+    settings: Res<QuadsSettings>,
+    msaa: Extract<Res<Msaa>>,
+    cameras: Extract<Query<(Entity, &Camera), With<Camera3d>>>,
+    mut msaa_logged: Local<bool>,
+) {
+    // NOTE: Without a phase the quads pass is skipped, see QuadsPlugin::over_ui
+    if settings.over_ui && **msaa != Msaa::Off {
+        if !*msaa_logged {
+            error!("QuadsPlugin::over_ui requires Msaa::Off, the quads are not drawn with MSAA");
+            *msaa_logged = true;
+        }
+        return;
+    }
+    // NOTE: The render world entities are cleared after every frame, so the phase and every other
+    // per-view component is rebuilt from scratch and nothing outlives a despawned camera or an
+    // idle frame. Per-view GPU resources must come from caches like the `TextureCache` instead of
+    // being stored here.
+    for (entity, camera) in cameras.iter() {
+        if !camera.is_active {
+            continue;
+        }
+        commands
+            .get_or_spawn(entity)
+            .insert(RenderPhase::<QuadsPhaseItem>::default());
+    }
+}
+
+// NOTE: These must match the bit flags in quads_types.wgsl!
+bitflags::bitflags! {
+    #[repr(transparent)]
+    pub(crate) struct GpuQuadFlags: u32 {
+        const BILLBOARD                   = (1 << 0);
+        const BILLBOARD_WORLD_Y           = (1 << 1);
+        const BILLBOARD_FIXED_SCREEN_SIZE = (1 << 2);
+        const LIT                         = (1 << 3);
+        const BILLBOARD_VIEWPORT_FRACTION = (1 << 4);
+        /// Set for negative x half-extents, the texture u coordinate is mirrored
+        const FLIP_X                      = (1 << 5);
+        /// Set for negative y half-extents, the texture v coordinate is mirrored
+        const FLIP_Y                      = (1 << 6);
+        const CLIP_SPACE                  = (1 << 7);
+        const FOREGROUND                  = (1 << 8);
+        /// Set for tiled or scrolling texture coordinates, which are wrapped into the texture
+        const UV_WRAP                     = (1 << 9);
+        /// Set with `BILLBOARD`, the half-extents z is the minimum y half-extent in pixels
+        const BILLBOARD_MIN_SCREEN_SIZE   = (1 << 10);
+        /// Set with `BILLBOARD`, the motion yzw is the world-space axis the quad turns around
+        const BILLBOARD_AXIS              = (1 << 11);
+        const HIDDEN                      = (1 << 12);
+        /// Set with `BILLBOARD_FIXED_SCREEN_SIZE`, the quad grows by the view's circle of confusion
+        const BILLBOARD_COC_SCALED        = (1 << 13);
+    }
+}
+
+impl GpuQuadFlags {
+    /// The quad's group is stored in the top byte of the flags
+    const GROUP_SHIFT_BITS: u32 = 24;
+}
+
+/// Per-quad instance data as uploaded to the storage buffer read by the quads shaders. The layout
+/// is the same for every pipeline specialization and feature set, so a capture of the buffer can
+/// always be decoded with [`GpuQuad::FIELD_OFFSETS`]:
+///
+/// | offset | size | field          | contents                                              |
+/// |--------|------|----------------|-------------------------------------------------------|
+/// | 0      | 12   | `center`       | position relative to `QuadsOrigin`, or NDC position and depth for clip space quads |
+/// | 12     | 4    | `flags`        | billboard mode and per-quad options, see `GpuQuadFlags` in `quads_types.wgsl`, group in the top byte |
+/// | 16     | 16   | `half_extents` | xyz half-extents, w uv rotation in radians             |
+/// | 32     | 16   | `color`        | rgba color                                             |
+/// | 48     | 16   | `specular`     | rgb specular color, w specular power                   |
+/// | 64     | 16   | `detail`       | xy detail texture scroll speed, z detail weight, w depth offset |
+/// | 80     | 16   | `uv`           | xy texture tiling, zw texture scroll speed             |
+/// | 96     | 16   | `motion`       | x spin in radians per second, yzw up axis of `OrientedY` quads |
+/// | 112    | 16   | `adjust`       | x hue shift in turns and saturation shift, y value shift and scale minus 1, as pairs of half floats, z mask and translucency bytes, w dissolve threshold as a half float and noise offset |
+///
+/// The instance index of a drawn quad is its index into the buffer, which matches its index into
+/// [`Quads::data`] for the quads resource.
+#[derive(Clone, Copy, Debug, Default, ShaderType, Pod, Zeroable)]
+#[repr(C)]
+pub struct GpuQuad {
+    center: Vec3,
+    flags: u32,
+    /// xyz are the half-extents, w is the uv rotation in radians
+    half_extents: Vec4,
+    color: [f32; 4],
+    /// rgb is the specular color, w the specular power
+    specular: Vec4,
+    /// xy is the detail texture scroll speed, z the detail weight, w the depth offset
+    detail: Vec4,
+    /// xy is the texture tiling, zw the texture scroll speed
+    uv: Vec4,
+    /// x is the spin in radians per second, yzw the up axis of `Billboard::OrientedY` quads
+    motion: Vec4,
+    /// xy is the HSV shift and the scale minus 1 packed as half floats, z is the mask in the low
+    /// byte and the translucency in the next, and w the dissolve threshold as a half float and the
+    /// offset of the dissolve noise
+    adjust: UVec4,
+}
+
+impl GpuQuad {
+    /// Name, byte offset and byte size of every field of the instance data, in buffer order
+    pub const FIELD_OFFSETS: &'static [(&'static str, u64, u64)] = &[
+        ("center", 0, 12),
+        ("flags", 12, 4),
+        ("half_extents", 16, 16),
+        ("color", 32, 16),
+        ("specular", 48, 16),
+        ("detail", 64, 16),
+        ("uv", 80, 16),
+        ("motion", 96, 16),
+        ("adjust", 112, 16),
+    ];
+
+    /// A quad the vertex shader skips before reading anything else
+    pub(crate) fn hidden() -> Self {
+        Self {
+            flags: GpuQuadFlags::HIDDEN.bits(),
+            ..default()
+        }
+    }
+
+    /// Whether the quad is lit and has a [`Quad::translucency`], which needs the `TRANSLUCENCY`
+    /// pipelines
+    pub(crate) fn translucent(&self) -> bool {
+        self.flags & GpuQuadFlags::LIT.bits() != 0 && self.adjust.z >> 8 & 0xff != 0
+    }
+
+    /// Whether the quad has a [`Quad::hsv_shift`], which needs the `HSV_ADJUST` pipelines
+    pub(crate) fn hsv_adjusted(&self) -> bool {
+        self.adjust.x != 0 || self.adjust.y & 0xffff != 0
+    }
+
+    /// Logs the instance data layout, to decode captured quad buffers in graphics debuggers
+    fn log_layout() {
+        let fields = Self::FIELD_OFFSETS
+            .iter()
+            .map(|(name, offset, size)| format!("{name}: offset {offset}, size {size}"))
+            .collect::<Vec<_>>();
+        info!(
+            "Quad instance data is {} bytes per quad: {}",
+            Self::SHADER_SIZE,
+            fields.join(", ")
+        );
+    }
+}
+
+// NOTE: Keeps the documented layout in sync with the struct
+const _: () = {
+    let (_, offset, size) = GpuQuad::FIELD_OFFSETS[GpuQuad::FIELD_OFFSETS.len() - 1];
+    assert!(offset + size == GpuQuad::SHADER_SIZE.get());
+};
+
+/// Packs a value from 0 to 1 into a byte, rounded to the nearest step
+fn pack_unorm8(value: f32) -> u32 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u32
+}
+
+impl From<&Quad> for GpuQuad {
+    fn from(quad: &Quad) -> Self {
+        let mut flags = match quad.billboard {
+            Billboard::None => GpuQuadFlags::empty(),
+            Billboard::ViewY => GpuQuadFlags::BILLBOARD,
+            Billboard::WorldY => GpuQuadFlags::BILLBOARD | GpuQuadFlags::BILLBOARD_WORLD_Y,
+            Billboard::FixedScreenSize => GpuQuadFlags::BILLBOARD_FIXED_SCREEN_SIZE,
+            Billboard::CocScaled => {
+                GpuQuadFlags::BILLBOARD_FIXED_SCREEN_SIZE | GpuQuadFlags::BILLBOARD_COC_SCALED
+            }
+            Billboard::ViewportFraction => GpuQuadFlags::BILLBOARD_VIEWPORT_FRACTION,
+            Billboard::ClipSpace => GpuQuadFlags::CLIP_SPACE,
+            Billboard::ViewYMinScreenSize { .. } => {
+                GpuQuadFlags::BILLBOARD | GpuQuadFlags::BILLBOARD_MIN_SCREEN_SIZE
+            }
+            Billboard::OrientedY { .. } => GpuQuadFlags::BILLBOARD | GpuQuadFlags::BILLBOARD_AXIS,
+        };
+        let axis = match quad.billboard {
+            Billboard::OrientedY { orientation } => orientation.normalize() * Vec3::Y,
+            _ => Vec3::ZERO,
+        };
+        flags.set(GpuQuadFlags::LIT, quad.lit);
+        flags.set(GpuQuadFlags::FOREGROUND, quad.foreground);
+        flags.set(GpuQuadFlags::HIDDEN, quad.hidden);
+        // NOTE: Negative half-extents would flip the winding of the quad's triangles and get them
+        // back-face culled, so only their absolute value is uploaded and the sign is kept in the
+        // flags to mirror the texture instead
+        flags.set(GpuQuadFlags::FLIP_X, quad.half_extents.x.is_sign_negative());
+        flags.set(GpuQuadFlags::FLIP_Y, quad.half_extents.y.is_sign_negative());
+        let uv_tile = Vec2::select(quad.uv_tile.cmpeq(Vec2::ZERO), Vec2::ONE, quad.uv_tile);
+        flags.set(
+            GpuQuadFlags::UV_WRAP,
+            uv_tile != Vec2::ONE || quad.uv_scroll != Vec2::ZERO,
+        );
+        let hsv_shift = pack_hsv_shift(quad.hsv_shift);
+        // NOTE: The scale is stored minus 1, so that zeroed instance data like the records of older
+        // quads files is unscaled
+        let scale = f32_to_f16(quad.scale.max(0.0) - 1.0) as u32;
+        Self {
+            center: quad.center,
+            flags: flags.bits() | (quad.group as u32) << GpuQuadFlags::GROUP_SHIFT_BITS,
+            half_extents: match quad.billboard {
+                // NOTE: Billboards only use the x and y half-extents, so z holds the floor
+                Billboard::ViewYMinScreenSize { min_half_extent } => quad
+                    .half_extents
+                    .truncate()
+                    .abs()
+                    .extend(min_half_extent.max(0.0)),
+                _ => quad.half_extents.abs(),
+            }
+            .extend(quad.uv_rotation),
+            color: quad.color.as_rgba_f32(),
+            specular: Vec4::from(quad.specular_color.as_rgba_f32())
+                .truncate()
+                .extend(quad.specular_power.max(0.0)),
+            detail: quad
+                .detail_scroll
+                .extend(quad.detail_weight.clamp(0.0, 1.0))
+                .extend(quad.depth_offset),
+            uv: uv_tile.extend(quad.uv_scroll.x).extend(quad.uv_scroll.y),
+            motion: Vec4::new(quad.spin, axis.x, axis.y, axis.z),
+            adjust: UVec4::new(
+                hsv_shift.x,
+                hsv_shift.y | scale << 16,
+                quad.mask as u32 | pack_unorm8(quad.translucency) << 8,
+                pack_dissolve(quad),
+            ),
+        }
+    }
+}
+
+impl From<&GpuQuad> for Quad {
+    fn from(gpu_quad: &GpuQuad) -> Self {
+        let flags = GpuQuadFlags::from_bits_truncate(gpu_quad.flags);
+        let billboard = if flags.contains(GpuQuadFlags::BILLBOARD_MIN_SCREEN_SIZE) {
+            Billboard::ViewYMinScreenSize {
+                min_half_extent: gpu_quad.half_extents.z,
+            }
+        } else if flags.contains(GpuQuadFlags::BILLBOARD_COC_SCALED) {
+            Billboard::CocScaled
+        } else if flags.contains(GpuQuadFlags::BILLBOARD_FIXED_SCREEN_SIZE) {
+            Billboard::FixedScreenSize
+        } else if flags.contains(GpuQuadFlags::BILLBOARD_VIEWPORT_FRACTION) {
+            Billboard::ViewportFraction
+        } else if flags.contains(GpuQuadFlags::CLIP_SPACE) {
+            Billboard::ClipSpace
+        } else if flags.contains(GpuQuadFlags::BILLBOARD_AXIS) {
+            // NOTE: Only the axis is uploaded, which is all the shader needs
+            Billboard::OrientedY {
+                orientation: Quat::from_rotation_arc(
+                    Vec3::Y,
+                    Vec3::new(gpu_quad.motion.y, gpu_quad.motion.z, gpu_quad.motion.w),
+                ),
+            }
+        } else if flags.contains(GpuQuadFlags::BILLBOARD | GpuQuadFlags::BILLBOARD_WORLD_Y) {
+            Billboard::WorldY
+        } else if flags.contains(GpuQuadFlags::BILLBOARD) {
+            Billboard::ViewY
+        } else {
+            Billboard::None
+        };
+        let [r, g, b, a] = gpu_quad.color;
+        let mut half_extents = gpu_quad.half_extents.truncate();
+        if flags.contains(GpuQuadFlags::BILLBOARD_MIN_SCREEN_SIZE) {
+            half_extents.z = 0.0;
+        }
+        if flags.contains(GpuQuadFlags::FLIP_X) {
+            half_extents.x = -half_extents.x;
+        }
+        if flags.contains(GpuQuadFlags::FLIP_Y) {
+            half_extents.y = -half_extents.y;
+        }
+        Self {
+            color: Color::rgba(r, g, b, a),
+            center: gpu_quad.center,
+            half_extents,
+            scale: f16_to_f32((gpu_quad.adjust.y >> 16) as u16) + 1.0,
+            billboard,
+            lit: flags.contains(GpuQuadFlags::LIT),
+            foreground: flags.contains(GpuQuadFlags::FOREGROUND),
+            uv_rotation: gpu_quad.half_extents.w,
+            spin: gpu_quad.motion.x,
+            specular_color: Color::rgb(
+                gpu_quad.specular.x,
+                gpu_quad.specular.y,
+                gpu_quad.specular.z,
+            ),
+            specular_power: gpu_quad.specular.w,
+            detail_weight: gpu_quad.detail.z,
+            detail_scroll: gpu_quad.detail.truncate().truncate(),
+            uv_tile: Vec2::new(gpu_quad.uv.x, gpu_quad.uv.y),
+            uv_scroll: Vec2::new(gpu_quad.uv.z, gpu_quad.uv.w),
+            depth_offset: gpu_quad.detail.w,
+            group: (gpu_quad.flags >> GpuQuadFlags::GROUP_SHIFT_BITS) as u8,
+            hsv_shift: unpack_hsv_shift(gpu_quad.adjust.truncate().truncate()),
+            mask: gpu_quad.adjust.z as u8,
+            translucency: (gpu_quad.adjust.z >> 8 & 0xff) as f32 / 255.0,
+            dissolve: f16_to_f32(gpu_quad.adjust.w as u16),
+            hidden: flags.contains(GpuQuadFlags::HIDDEN),
+            priority: 0,
+            // NOTE: The seed only selects the upload-time variation and the offset of the dissolve
+            // noise, which is a hash of it, so it is not part of the instance data
+            seed: 0,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct GpuQuads {
+    index_buffer: Option<Buffer>,
+    index_count: u32,
+    /// Corners of every quad in `index_buffer`, see [`Quads::corner_count`]
+    corners: u32,
+    double_sided: bool,
+    instances: StorageBuffer<GpuQuadsArray>,
+    /// Bytes of `instances` written by the last upload
+    instances_used: u64,
+    /// The instances of a baked batch, uploaded a chunk at a time by [`upload_baked`]. They are
+    /// bound instead of `instances`, which stays empty.
+    baked_instances: Option<Buffer>,
+    /// Instances in `baked_instances`, none when the baked quads could not be read
+    baked_len: usize,
+    image: Option<Handle<Image>>,
+    /// Whether `image` was loaded when `bind_group` was created, or the fallback image was bound
+    image_bound: bool,
+    sampler: Option<QuadsSamplerDesc>,
+    detail: Option<QuadsDetail>,
+    /// Like `image_bound` for the detail image
+    detail_bound: bool,
+    flow_field: Option<Handle<Image>>,
+    /// Like `image_bound` for the flow field image, which is also left unbound when it is not a
+    /// valid flow field
+    flow_field_bound: bool,
+    /// The noise image of [`Quads::dissolve`], `None` without a dissolve
+    dissolve_noise: Option<Handle<Image>>,
+    /// Like `flow_field_bound` for the dissolve noise
+    dissolve_bound: bool,
+    /// The `instances` buffer `bind_group` was created with
+    bound_instances: Option<BufferId>,
+    /// Written whenever the quads change, also while `animated` is false as it is always bound
+    animation: UniformBuffer<GpuBatchAnimation>,
+    animated: bool,
+    /// Whether any quad has a [`Quad::hsv_shift`]
+    hsv_adjusted: bool,
+    /// Whether any lit quad has a [`Quad::translucency`]
+    translucent: bool,
+    premultiplied_blend: bool,
+    blend_constant: Option<Color>,
+    bind_group: Option<BindGroup>,
+}
+
+#[derive(Default, ShaderType)]
+struct GpuQuadsArray {
+    #[size(runtime)]
+    array: Vec<GpuQuad>,
+}
+
+fn instances_buffer() -> StorageBuffer<GpuQuadsArray> {
+    let mut instances = StorageBuffer::<GpuQuadsArray>::default();
+    instances.set_label(Some("gpu_quads_array"));
+    instances
+}
+
+impl Default for GpuQuads {
+    fn default() -> Self {
+        Self {
+            index_buffer: None,
+            index_count: 0,
+            corners: 4,
+            double_sided: false,
+            instances: instances_buffer(),
+            instances_used: 0,
+            baked_instances: None,
+            baked_len: 0,
+            image: None,
+            image_bound: false,
+            sampler: None,
+            detail: None,
+            detail_bound: false,
+            flow_field: None,
+            flow_field_bound: false,
+            dissolve_noise: None,
+            dissolve_bound: false,
+            bound_instances: None,
+            animation: UniformBuffer::default(),
+            animated: false,
+            hsv_adjusted: false,
+            translucent: false,
+            premultiplied_blend: false,
+            blend_constant: None,
+            bind_group: None,
+        }
+    }
+}
+
+impl GpuQuads {
+    /// The buffer the instances are bound from
+    fn instance_buffer(&self) -> Option<&Buffer> {
+        self.baked_instances.as_ref().or(self.instances.buffer())
+    }
+
+    fn memory(&self) -> QuadsBatchMemory {
+        QuadsBatchMemory {
+            instances_allocated: buffer_size(self.instance_buffer()),
+            instances_used: self.instances_used,
+            indices: buffer_size(self.index_buffer.as_ref()),
+            auxiliary: buffer_size(self.animation.buffer()),
+        }
+    }
+
+    /// Reallocates the indices and uploads the instances again into buffers of their size. Baked
+    /// quads are not kept on the CPU and already have a buffer of their size, and empty batches
+    /// keep theirs as empty storage buffers can't be bound.
+    fn shrink_to_fit(
+        &mut self,
+        allocator: &QuadsAllocatorConfig,
+        events: &SharedAllocatorEvents,
+        render_device: &RenderDevice,
+        render_queue: &RenderQueue,
+    ) {
+        let n_instances = (self.index_count / indices_per_quad(self.corners)) as usize;
+        if n_instances == 0 {
+            return;
+        }
+        let index_size = index_size(self.corners);
+        let indices = events.record(
+            QuadsBuffer::Indices,
+            allocator.fit(
+                index_size,
+                (buffer_size(self.index_buffer.as_ref()) / index_size) as usize,
+                n_instances,
+            ),
+        );
+        if indices.reallocates() {
+            self.index_buffer = Some(create_index_buffer(
+                render_device,
+                "gpu_quads_index_buffer",
+                indices.capacity,
+                self.corners,
+            ));
+        }
+        if self.instances.get().array.is_empty() {
+            return;
+        }
+        let instance_size = GpuQuad::min_size().get();
+        let instances = events.record(
+            QuadsBuffer::Instances,
+            allocator.fit(
+                instance_size,
+                (buffer_size(self.instances.buffer()) / instance_size) as usize,
+                self.instances.get().array.len(),
+            ),
+        );
+        if instances.reallocates() {
+            write_allocated(
+                &mut self.instances,
+                |instances| &mut instances.array,
+                GpuQuad::hidden(),
+                &instances,
+                render_device,
+                render_queue,
+            );
+        }
+    }
+}
+
+#[derive(Component)]
+struct GpuQuadsMarker;
+
+/// Warns once and records [`QuadsPlugin::DROPPED_QUADS`] when [`QuadsPlugin::max_quads`] or the
+/// budget of [`QuadsPlugin::allocator`] is exceeded. The quads beyond the limit are skipped in
+/// [`prepare_quads`], and the points beyond the budget of the generated quads when they are
+/// prepared.
+fn diagnose_dropped_quads(
+    settings: Res<QuadsSettings>,
+    quads: Option<Res<Quads>>,
+    points: Option<Res<QuadsPoints>>,
+    mut diagnostics: Diagnostics,
+    mut warned: Local<bool>,
+) {
+    let quads_limit =
+        quads.and_then(|quads| Some((quads.len(), settings.max_quads(quads.corners())?)));
+    let points_limit =
+        points.and_then(|points| Some((points.points.len(), settings.max_points()?)));
+    if quads_limit.is_none() && points_limit.is_none() {
+        return;
+    }
+    let dropped_quads = quads_limit.map_or(0, |(len, max_quads)| len.saturating_sub(max_quads));
+    let dropped_points = points_limit.map_or(0, |(len, max_points)| len.saturating_sub(max_points));
+    if let Some((len, max_quads)) = quads_limit.filter(|_| dropped_quads > 0 && !*warned) {
+        *warned = true;
+        warn!(
+            "Quads has {} quads which exceeds the limit of QuadsPlugin::max_quads and \
+            QuadsPlugin::allocator ({}), only the first {} are rendered",
+            len, max_quads, max_quads
+        );
+    }
+    if let Some((len, max_points)) = points_limit.filter(|_| dropped_points > 0 && !*warned) {
+        *warned = true;
+        warn!(
+            "QuadsPoints has {} points which exceeds the budget of QuadsPlugin::allocator ({}), \
+            only the first {} are rendered",
+            len, max_points, max_points
+        );
+    }
+    diagnostics.add_measurement(QuadsPlugin::DROPPED_QUADS, || {
+        (dropped_quads + dropped_points) as f64
+    });
+}
+
+/// Index buffer for drawing `n_instances` vertex pulled quads with `corners` vertices each.
+/// Plain quads are 2 triangles, polygons a fan of `corners - 2` triangles around their first
+/// corner.
+fn create_index_buffer(
+    render_device: &RenderDevice,
+    label: &str,
+    n_instances: usize,
+    corners: u32,
+) -> Buffer {
+    let mut indices = Vec::with_capacity(n_instances * indices_per_quad(corners) as usize);
+    for i in 0..n_instances {
+        let base = i as u32 * corners;
+        if corners == 4 {
+            indices.push(base + 2);
+            indices.push(base);
+            indices.push(base + 1);
+            indices.push(base + 1);
+            indices.push(base + 3);
+            indices.push(base + 2);
+        } else {
+            // NOTE: The polygon corners are counter-clockwise, like the triangles of plain quads
+            for corner in 1..corners - 1 {
+                indices.push(base);
+                indices.push(base + corner);
+                indices.push(base + corner + 1);
+            }
+        }
+    }
+    render_device.create_buffer_with_data(&BufferInitDescriptor {
+        label: Some(label),
+        contents: cast_slice(&indices),
+        usage: BufferUsages::INDEX,
+    })
+}
+
+/// Number of indices of a quad drawn with `corners` vertices
+fn indices_per_quad(corners: u32) -> u32 {
+    3 * (corners - 2)
+}
+
+/// Bytes of the indices of a quad drawn with `corners` vertices
+fn index_size(corners: u32) -> u64 {
+    indices_per_quad(corners) as u64 * std::mem::size_of::<u32>() as u64
+}
+
+/// Converts quads into instance data with the convention, variation and alpha mode of the batch,
+/// replacing the instances. They are sanitized into `report`, or asserted to be finite without
+/// [`QuadsPlugin::sanitize`]. `first` is the index of the first of the quads in the batch.
+fn convert_instances(
+    quads: &Quads,
+    data: &[Quad],
+    first: usize,
+    instances: &mut Vec<GpuQuad>,
+    report: Option<&mut SanitizeReport>,
+) {
+    instances.clear();
+    let convention = quads.convention;
+    match &quads.variation {
+        None if convention == QuadsCoordinateConvention::YUpRightHanded => {
+            instances.extend(data.iter().map(GpuQuad::from));
+        }
+        None => {
+            instances.extend(
+                data.iter()
+                    .map(|quad| GpuQuad::from(&convention.quad_to_bevy(quad))),
+            );
+        }
+        Some(variation) => {
+            instances.extend(
+                data.iter()
+                    .map(|quad| GpuQuad::from(&convention.quad_to_bevy(&variation.apply(quad)))),
+            );
+        }
+    }
+    // NOTE: Before the premultiplication, which would spread a non-finite alpha
+    if let Some(report) = report {
+        report.sanitize(instances, first);
+    } else {
+        debug_assert!(
+            instances.iter().all(GpuQuad::is_finite),
+            "Quad {} has non-finite values, enable QuadsPlugin::sanitize to hide or fix invalid \
+            quads instead",
+            first
+                + instances
+                    .iter()
+                    .position(|instance| !instance.is_finite())
+                    .unwrap_or_default()
+        );
+    }
+    if quads.premultiplies_colors() {
+        for instance in instances.iter_mut() {
+            let [r, g, b, a] = instance.color;
+            instance.color = [r * a, g * a, b * a, a];
+        }
+    }
+}
+
+/// Uploads the first `len` baked quads into `baked_instances` a chunk of [`BAKE_CHUNK`] quads at a
+/// time, so neither the quads read from a compressed file nor their instance data are ever all
+/// in memory. A file is still read to its end to validate it.
+fn upload_baked(
+    quads: &Quads,
+    source: BakedSource,
+    len: usize,
+    mut report: Option<&mut SanitizeReport>,
+    gpu_quads: &mut GpuQuads,
+    render_device: &RenderDevice,
+    render_queue: &RenderQueue,
+) -> Result<(), BakeError> {
+    let instance_size = GpuQuad::min_size().get();
+    // NOTE: Empty storage buffers can't be bound
+    let buffer = render_device.create_buffer(&BufferDescriptor {
+        label: Some("gpu_quads_baked_array"),
+        size: len.max(1) as u64 * instance_size,
+        usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    gpu_quads.baked_len = 0;
+    gpu_quads.hsv_adjusted = false;
+    gpu_quads.translucent = false;
+    let mut instances = Vec::new();
+    let mut bytes = Vec::new();
+    let result = source.for_each_chunk(|first, chunk| {
+        let chunk = &chunk[..chunk.len().min(len.saturating_sub(first))];
+        if chunk.is_empty() {
+            return;
+        }
+        convert_instances(quads, chunk, first, &mut instances, report.as_deref_mut());
+        gpu_quads.hsv_adjusted |= instances.iter().any(GpuQuad::hsv_adjusted);
+        gpu_quads.translucent |= instances.iter().any(GpuQuad::translucent);
+        bytes.clear();
+        let mut writer = encase::StorageBuffer::new(std::mem::take(&mut bytes));
+        writer.write(&instances).unwrap();
+        bytes = writer.into_inner();
+        render_queue.write_buffer(&buffer, first as u64 * instance_size, &bytes);
+        // NOTE: Submitting hands the staging copy of the chunk to the GPU right away, instead of
+        // keeping the copies of all chunks until the frame is submitted
+        render_queue.submit([]);
+        gpu_quads.baked_len = first + chunk.len();
+    });
+    gpu_quads.baked_instances = Some(buffer);
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prepare_quads(
+    mut commands: Commands,
+    settings: Res<QuadsSettings>,
+    quads: Option<Res<Quads>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    gpu_quads: Option<ResMut<GpuQuads>>,
+    sanitize: Option<Res<SharedSanitizeReport>>,
+    allocator_events: Res<SharedAllocatorEvents>,
+) {
+    if let Some(quads) = quads {
+        if quads.is_changed() {
+            let mut new_gpu_quads = None;
+            let gpu_quads = if let Some(gpu_quads) = gpu_quads {
+                gpu_quads.into_inner()
+            } else {
+                new_gpu_quads = Some(GpuQuads::default());
+                new_gpu_quads.as_mut().unwrap()
+            };
+            let corners = quads.corners();
+            let required = quads.len().min(settings.max_quads.unwrap_or(usize::MAX));
+            let max_quads = settings.max_quads(corners).unwrap_or(usize::MAX);
+            let instance_size = GpuQuad::min_size().get();
+            let mut report = sanitize.is_some().then(SanitizeReport::default);
+            let uploaded = match quads.baked.as_ref().map(BakedQuads::take) {
+                None => {
+                    gpu_quads.baked_instances = None;
+                    let capacity =
+                        (buffer_size(gpu_quads.instances.buffer()) / instance_size) as usize;
+                    let allocation = allocator_events.record(
+                        QuadsBuffer::Instances,
+                        settings
+                            .allocator
+                            .allocate(instance_size, capacity, required),
+                    );
+                    let data = &quads.data[..quads.data.len().min(max_quads)];
+                    let instances = &mut gpu_quads.instances.get_mut().array;
+                    convert_instances(&quads, data, 0, instances, report.as_mut());
+                    gpu_quads.hsv_adjusted = instances.iter().any(GpuQuad::hsv_adjusted);
+                    gpu_quads.translucent = instances.iter().any(GpuQuad::translucent);
+                    gpu_quads.instances_used = instances.len() as u64 * instance_size;
+                    write_allocated(
+                        &mut gpu_quads.instances,
+                        |instances| &mut instances.array,
+                        GpuQuad::hidden(),
+                        &allocation,
+                        &render_device,
+                        &render_queue,
+                    );
+                    true
+                }
+                Some(Some(source)) => {
+                    // NOTE: Baked quads can't be uploaded again by shrink_to_fit, so they get a
+                    // buffer of their size right away. The count of a compressed file is only
+                    // checked against the quads in it once they are read, so it is also limited
+                    // to what the device can bind.
+                    let capacity =
+                        (buffer_size(gpu_quads.instance_buffer()) / instance_size) as usize;
+                    let max_binding =
+                        (u64::from(render_device.limits().max_storage_buffer_binding_size)
+                            / instance_size) as usize;
+                    let allocation = allocator_events.record(
+                        QuadsBuffer::Instances,
+                        settings
+                            .allocator
+                            .fit(instance_size, capacity, required.min(max_binding)),
+                    );
+                    // The instances are uploaded into their own buffer
+                    gpu_quads.instances = instances_buffer();
+                    if let Err(err) = upload_baked(
+                        &quads,
+                        source,
+                        allocation.len.min(max_quads),
+                        report.as_mut(),
+                        gpu_quads,
+                        &render_device,
+                        &render_queue,
+                    ) {
+                        error!("Failed to upload the baked quads, the batch is not drawn: {err}");
+                        gpu_quads.baked_len = 0;
+                    }
+                    gpu_quads.instances_used = gpu_quads.baked_len as u64 * instance_size;
+                    true
+                }
+                // NOTE: Baked quads are only here in the first frame after Quads::bake, afterwards
+                // changes to the batch keep the uploaded instances
+                Some(None) => false,
+            };
+            if let (true, Some(sanitize), Some(report)) = (uploaded, &sanitize, report) {
+                sanitize.publish(report);
+            }
+
+            let n_instances = match quads.baked {
+                Some(_) => gpu_quads.baked_len,
+                None => quads.data.len().min(max_quads),
+            };
+            let index_count = n_instances as u32 * indices_per_quad(corners);
+            // NOTE: The indices only depend on the number of quads and their corners, and the
+            // indices of fewer quads are the start of those of more quads. So moving, recoloring
+            // or removing quads keeps the index buffer.
+            let index_size = index_size(corners);
+            let index_capacity = if corners == gpu_quads.corners {
+                (buffer_size(gpu_quads.index_buffer.as_ref()) / index_size) as usize
+            } else {
+                0
+            };
+            let indices = allocator_events.record(
+                QuadsBuffer::Indices,
+                settings.allocator.allocate(
+                    index_size,
+                    index_capacity,
+                    if quads.baked.is_some() {
+                        n_instances
+                    } else {
+                        required
+                    },
+                ),
+            );
+            if gpu_quads.index_buffer.is_none() || indices.reallocates() {
+                gpu_quads.index_buffer = Some(create_index_buffer(
+                    &render_device,
+                    "gpu_quads_index_buffer",
+                    indices.capacity,
+                    corners,
+                ));
+            }
+            gpu_quads.corners = corners;
+            gpu_quads.double_sided = quads.double_sided;
+            gpu_quads.premultiplied_blend = quads.alpha_mode == QuadsAlphaMode::PremultipliedBlend;
+            gpu_quads.blend_constant = quads.blend_constant;
+            gpu_quads.index_count = index_count;
+            let detail_image = quads.detail.as_ref().map(|detail| &detail.image);
+            let flow_field_image = quads
+                .flow_field
+                .as_ref()
+                .map(|flow_field| flow_field.image.clone());
+            let dissolve_noise = quads.dissolve.as_ref().map(QuadsDissolve::noise_image);
+            if gpu_quads.image != quads.image
+                || gpu_quads.sampler != quads.sampler
+                || gpu_quads.detail.as_ref().map(|detail| &detail.image) != detail_image
+                || gpu_quads.flow_field != flow_field_image
+                || gpu_quads.dissolve_noise != dissolve_noise
+            {
+                // The bound textures changed, see queue_quads_bind_group
+                gpu_quads.bind_group = None;
+            }
+            gpu_quads.image = quads.image.clone();
+            gpu_quads.sampler = quads.sampler;
+            gpu_quads.detail = quads.detail.clone();
+            gpu_quads.flow_field = flow_field_image;
+            gpu_quads.dissolve_noise = dissolve_noise;
+
+            // NOTE: The buffer is created by the first write and written in place afterwards, so
+            // the bind group does not have to be recreated for it
+            gpu_quads.animation.set(match &quads.animation {
+                Some(animation) => GpuBatchAnimation::new(animation, quads.premultiplies_colors()),
+                None => GpuBatchAnimation::default(),
+            });
+            gpu_quads
+                .animation
+                .write_buffer(&render_device, &render_queue);
+            gpu_quads.animated = quads.animation.is_some();
+
+            if let Some(new_gpu_quads) = new_gpu_quads {
+                commands.insert_resource(new_gpu_quads);
+            }
+        }
+        if !quads.is_empty() {
+            commands.spawn(GpuQuadsMarker);
+        }
+    }
+}
+
+pub struct QuadsPhaseItem {
+    /// Name of the batch, shown as a debug group around its draw in graphics debuggers
+    pub label: &'static str,
+    pub draw_function: DrawFunctionId,
+    pub entity: Entity,
+    pub pipeline: CachedRenderPipelineId,
+}
+
+impl PhaseItem for QuadsPhaseItem {
+    type SortKey = u32;
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        0
+    }
+
+    #[inline]
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+impl CachedRenderPipelinePhaseItem for QuadsPhaseItem {
+    fn cached_pipeline(&self) -> CachedRenderPipelineId {
+        self.pipeline
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_quads_bind_group(
+    quads_pipeline: Res<QuadsPipeline>,
+    render_device: Res<RenderDevice>,
+    images: Res<RenderAssets<Image>>,
+    fallback_image: Res<FallbackImage>,
+    mut samplers: ResMut<QuadSamplers>,
+    near_fade: Res<NearFadeUniform>,
+    origin: Res<OriginUniform>,
+    group_colors: Res<GroupColorsUniform>,
+    flow_field: Res<FlowFieldUniform>,
+    global_alpha: Res<GlobalAlphaUniform>,
+    dissolve: Res<DissolveUniform>,
+    gpu_quads: Option<ResMut<GpuQuads>>,
+) {
+    let (
+        Some(mut gpu_quads),
+        Some(near_fade),
+        Some(origin),
+        Some(group_colors),
+        Some(flow_field_uniform),
+        Some(flow_field_sampler),
+        Some(global_alpha),
+        Some(dissolve_uniform),
+        Some(dissolve_sampler),
+    ) = (
+        gpu_quads,
+        near_fade.buffer(),
+        origin.buffer(),
+        group_colors.buffer(),
+        flow_field.buffer(),
+        flow_field.sampler(),
+        global_alpha.buffer(),
+        dissolve.buffer(),
+        dissolve.sampler(),
+    )
+    else {
+        return;
+    };
+    let image = gpu_quads
+        .image
+        .as_ref()
+        .and_then(|handle| images.get(handle));
+    let detail = gpu_quads
+        .detail
+        .as_ref()
+        .and_then(|detail| images.get(&detail.image));
+    // NOTE: Fields assigned without `Quads::set_flow_field` are checked here too, binding a 2D
+    // image to the 3D texture binding would fail validation
+    let flow_field = gpu_quads
+        .flow_field
+        .as_ref()
+        .and_then(|handle| images.get(handle))
+        .filter(|image| {
+            image.texture.dimension() == TextureDimension::D3
+                && is_flow_field_format(image.texture_format)
+        });
+    let dissolve_noise = gpu_quads
+        .dissolve_noise
+        .as_ref()
+        .and_then(|handle| images.get(handle))
+        .filter(|image| {
+            image.texture.dimension() == TextureDimension::D2
+                && image.texture_format.sample_type(None)
+                    == Some(TextureSampleType::Float { filterable: true })
+        });
+    let instances = gpu_quads.instance_buffer().unwrap();
+    let animation = gpu_quads.animation.buffer().unwrap();
+    // NOTE: Uploads that fit into the instances buffer write into it in place, so the bind group
+    // only has to be recreated when the buffer was reallocated or the textures changed. The images
+    // may also finish loading after the quads were prepared, so it is recreated when the loaded
+    // state of an image changes too.
+    if gpu_quads.bind_group.is_some()
+        && gpu_quads.bound_instances == Some(instances.id())
+        && image.is_some() == gpu_quads.image_bound
+        && detail.is_some() == gpu_quads.detail_bound
+        && flow_field.is_some() == gpu_quads.flow_field_bound
+        && dissolve_noise.is_some() == gpu_quads.dissolve_bound
+    {
+        return;
+    }
+    trace!("Creating the quads bind group");
+    let bound_instances = instances.id();
+    let image_bound = image.is_some();
+    let image = image.unwrap_or(&fallback_image.d2);
+    let sampler = match &gpu_quads.sampler {
+        Some(desc) => samplers.get_or_create(&render_device, desc),
+        None => image.sampler.clone(),
+    };
+    let detail_bound = detail.is_some();
+    let detail = detail.unwrap_or(&fallback_image.d2);
+    let flow_field_bound = flow_field.is_some();
+    let flow_field = flow_field.unwrap_or(&fallback_image.d3);
+    let dissolve_bound = dissolve_noise.is_some();
+    let dissolve_noise = dissolve_noise.unwrap_or(&fallback_image.d2);
+    let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+        label: Some("gpu_quads_bind_group"),
+        layout: &quads_pipeline.quads_layout,
+        entries: &[
+            BindGroupEntry {
+                binding: 0,
+                resource: instances.as_entire_binding(),
+            },
+            BindGroupEntry {
+                binding: 1,
+                resource: BindingResource::TextureView(&image.texture_view),
+            },
+            BindGroupEntry {
+                binding: 2,
+                resource: BindingResource::Sampler(&sampler),
+            },
+            BindGroupEntry {
+                binding: 3,
+                resource: BindingResource::TextureView(&detail.texture_view),
+            },
+            BindGroupEntry {
+                binding: 4,
+                resource: BindingResource::Sampler(&detail.sampler),
+            },
+            BindGroupEntry {
+                binding: 5,
+                resource: near_fade.as_entire_binding(),
+            },
+            BindGroupEntry {
+                binding: 6,
+                resource: origin.as_entire_binding(),
+            },
+            BindGroupEntry {
+                binding: 7,
+                resource: group_colors.as_entire_binding(),
+            },
+            BindGroupEntry {
+                binding: 8,
+                resource: BindingResource::TextureView(&flow_field.texture_view),
+            },
+            BindGroupEntry {
+                binding: 9,
+                resource: BindingResource::Sampler(flow_field_sampler),
+            },
+            BindGroupEntry {
+                binding: 10,
+                resource: flow_field_uniform.as_entire_binding(),
+            },
+            BindGroupEntry {
+                binding: 11,
+                resource: animation.as_entire_binding(),
+            },
+            BindGroupEntry {
+                binding: 12,
+                resource: global_alpha.as_entire_binding(),
+            },
+            BindGroupEntry {
+                binding: 13,
+                resource: BindingResource::TextureView(&dissolve_noise.texture_view),
+            },
+            BindGroupEntry {
+                binding: 14,
+                resource: BindingResource::Sampler(dissolve_sampler),
+            },
+            BindGroupEntry {
+                binding: 15,
+                resource: dissolve_uniform.as_entire_binding(),
+            },
+        ],
+    });
+    gpu_quads.bind_group = Some(bind_group);
+    gpu_quads.image_bound = image_bound;
+    gpu_quads.detail_bound = detail_bound;
+    gpu_quads.flow_field_bound = flow_field_bound;
+    gpu_quads.dissolve_bound = dissolve_bound;
+    gpu_quads.bound_instances = Some(bound_instances);
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn queue_quads(
+    opaque_3d_draw_functions: Res<DrawFunctions<QuadsPhaseItem>>,
+    quads_pipeline: Res<QuadsPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<QuadsPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    settings: Res<QuadsSettings>,
+    enabled: Res<QuadsEnabled>,
+    heatmap: Option<Res<QuadsHeatmap>>,
+    debug_view: Res<QuadsDebugView>,
+    near_fade: Option<Res<QuadsNearFade>>,
+    origin: Option<Res<QuadsOrigin>>,
+    gpu_quads: Option<Res<GpuQuads>>,
+    gpu_points: Option<Res<GpuQuadPoints>>,
+    entities: Query<
+        (Entity, Has<GpuGeneratedQuadsMarker>),
+        Or<(With<GpuQuadsMarker>, With<GpuGeneratedQuadsMarker>)>,
+    >,
+    mut views: Query<(
+        &ExtractedView,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+        &mut RenderPhase<QuadsPhaseItem>,
+        Option<&mut ViewHalfResQuads>,
+        Has<QuadsMaskTexture>,
+        Has<GpuDepthOfField>,
+    )>,
+) {
+    // NOTE: The phases are rebuilt every frame, so leaving them empty is enough to draw nothing
+    if !enabled.0 {
+        return;
+    }
+    let draw_functions = opaque_3d_draw_functions.read();
+    let draw_quads = draw_functions.get_id::<DrawQuads>().unwrap();
+    let draw_generated_quads = draw_functions.get_id::<DrawGeneratedQuads>().unwrap();
+
+    // NOTE: The detail texture only applies to the Quads resource, not to generated quads. It is
+    // left out until its image is loaded as the fallback image would skew an overlay blend.
+    let detail_key = match gpu_quads
+        .as_ref()
+        .and_then(|gpu_quads| gpu_quads.detail.as_ref().filter(|_| gpu_quads.detail_bound))
+    {
+        Some(detail) => QuadsPipelineKey::from_detail_blend(detail.blend),
+        None => QuadsPipelineKey::empty(),
+    };
+    // NOTE: Generated quads are always drawn with 4 corners
+    let corners_key = gpu_quads
+        .as_ref()
+        .map_or(QuadsPipelineKey::empty(), |gpu_quads| {
+            QuadsPipelineKey::from_corners(gpu_quads.corners)
+        });
+    // NOTE: Like the detail texture the flow field only applies to the Quads resource
+    let flow_field_key = if gpu_quads
+        .as_ref()
+        .is_some_and(|gpu_quads| gpu_quads.flow_field_bound)
+    {
+        QuadsPipelineKey::FLOW_FIELD
+    } else {
+        QuadsPipelineKey::empty()
+    };
+    // NOTE: Like the detail texture only the Quads resource dissolves, once its noise is loaded
+    let dissolve_key = if gpu_quads
+        .as_ref()
+        .is_some_and(|gpu_quads| gpu_quads.dissolve_bound)
+    {
+        QuadsPipelineKey::DISSOLVE
+    } else {
+        QuadsPipelineKey::empty()
+    };
+    // NOTE: Like the detail texture only the Quads resource can be double-sided, and only
+    // double-sided batches let light through their translucent quads
+    let double_sided_key = match &gpu_quads {
+        Some(gpu_quads) if gpu_quads.double_sided && gpu_quads.translucent => {
+            QuadsPipelineKey::DOUBLE_SIDED | QuadsPipelineKey::TRANSLUCENCY
+        }
+        Some(gpu_quads) if gpu_quads.double_sided => QuadsPipelineKey::DOUBLE_SIDED,
+        _ => QuadsPipelineKey::empty(),
+    };
+    let batch_key = |animated, hsv_adjusted| {
+        let mut key = QuadsPipelineKey::empty();
+        key.set(QuadsPipelineKey::BATCH_ANIMATION, animated);
+        key.set(QuadsPipelineKey::HSV_ADJUST, hsv_adjusted);
+        key
+    };
+    let quads_key = detail_key
+        | corners_key
+        | flow_field_key
+        | double_sided_key
+        | dissolve_key
+        | gpu_quads
+            .as_ref()
+            .map_or(QuadsPipelineKey::empty(), |gpu_quads| {
+                batch_key(gpu_quads.animated, gpu_quads.hsv_adjusted)
+            });
+    // NOTE: Like the detail texture only the Quads resource is blended, and only into the view
+    // target. The heatmap and the debug views keep their own blending.
+    let blend_key = match &gpu_quads {
+        Some(gpu_quads) if heatmap.is_none() && *debug_view == QuadsDebugView::Off => {
+            let mut key = QuadsPipelineKey::empty();
+            key.set(
+                QuadsPipelineKey::PREMULTIPLIED_ALPHA,
+                gpu_quads.premultiplied_blend,
+            );
+            key.set(
+                QuadsPipelineKey::BLEND_CONSTANT,
+                gpu_quads.blend_constant.is_some(),
+            );
+            key
+        }
+        _ => QuadsPipelineKey::empty(),
+    };
+    let generated_key = gpu_points
+        .as_ref()
+        .map_or(QuadsPipelineKey::empty(), |gpu_points| {
+            batch_key(gpu_points.animated, gpu_points.hsv_adjusted)
+        });
+
+    // NOTE: Each view is specialized separately as views rendering to different windows or
+    // images may differ in main texture format
+    for (view, tonemapping, dither, mut opaque_phase, mut half_res, mask, depth_of_field) in
+        views.iter_mut()
+    {
+        let mut key = QuadsPipelineKey::from_msaa_samples(msaa.samples())
+            | QuadsPipelineKey::from_hdr(view.hdr);
+        if heatmap.is_some() {
+            key |= QuadsPipelineKey::HEATMAP;
+        } else {
+            key |= QuadsPipelineKey::from_debug_view(*debug_view);
+        }
+        key.set(QuadsPipelineKey::NEAR_FADE, near_fade.is_some());
+        key.set(QuadsPipelineKey::MASK, mask);
+        key.set(QuadsPipelineKey::DEPTH_OF_FIELD, depth_of_field);
+        key.set(
+            QuadsPipelineKey::CAMERA_RELATIVE,
+            origin.as_ref().is_some_and(|origin| origin.camera_relative),
+        );
+        // NOTE: Like meshes, tonemapped quads are tonemapped by the tonemapping pass in HDR views
+        // and in the fragment shader otherwise
+        if settings.tonemapped && !view.hdr && *debug_view == QuadsDebugView::Off {
+            if let Some(tonemapping) = tonemapping {
+                key |= QuadsPipelineKey::TONEMAP_IN_SHADER
+                    | QuadsPipelineKey::from_tonemapping(*tonemapping);
+            }
+            if let Some(DebandDither::Enabled) = dither {
+                key |= QuadsPipelineKey::DEBAND_DITHER;
+            }
+        }
+        let generated_pipeline =
+            pipelines.specialize(&pipeline_cache, &quads_pipeline, key | generated_key);
+        // NOTE: The view only has half resolution targets while the Quads resource is drawn at
+        // half resolution, see `QuadsResolution`. Those are composited opaque, so the batch
+        // is not blended into them.
+        let quads_key = if half_res.is_some() {
+            quads_key | QuadsPipelineKey::HALF_RESOLUTION
+        } else {
+            quads_key | blend_key
+        };
+        let pipeline = if quads_key == generated_key {
+            generated_pipeline
+        } else if half_res.is_some() {
+            // NOTE: The half resolution pass has no mask attachment
+            let key = key - QuadsPipelineKey::MASK;
+            pipelines.specialize(&pipeline_cache, &quads_pipeline, key | quads_key)
+        } else {
+            pipelines.specialize(&pipeline_cache, &quads_pipeline, key | quads_key)
+        };
+
+        for (entity, generated) in &entities {
+            if generated {
+                opaque_phase.add(QuadsPhaseItem {
+                    label: "generated_quads",
+                    entity,
+                    draw_function: draw_generated_quads,
+                    pipeline: generated_pipeline,
+                });
+                continue;
+            }
+            let item = QuadsPhaseItem {
+                label: "quads",
+                entity,
+                draw_function: draw_quads,
+                pipeline,
+            };
+            match half_res.as_mut() {
+                Some(half_res) => half_res.phase.add(item),
+                None => opaque_phase.add(item),
+            }
+        }
+    }
+}
+
+/// Names of the quads render graph nodes in the 3d graph, to order user nodes like
+/// post-processes reading the [`QuadsMaskTexture`] after the quads pass
+pub mod node {
+    pub const QUADS_PASS: &str = "quads_pass";
+    pub const QUADS_HEATMAP_RESOLVE: &str = "quads_heatmap_resolve";
+    pub const QUADS_HALF_RES_PASS: &str = "quads_half_res_pass";
+    /// `bevy::ui::draw_ui_graph::node::UI_PASS`, spelled out so the quads do not depend on the
+    /// `bevy_ui` feature
+    pub(crate) const UI_PASS: &str = "ui_pass";
+}
+
+#[derive(Default)]
+pub struct QuadsPassNode;
+
+impl ViewNode for QuadsPassNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static Camera3d,
+        &'static RenderPhase<QuadsPhaseItem>,
+        &'static ViewTarget,
+        &'static ViewDepthTexture,
+        Option<&'static ViewQuadsHeatmapTexture>,
+        Option<(&'static ViewHalfResQuads, &'static ViewHalfResComposite)>,
+        Option<&'static QuadsMaskTexture>,
+    );
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, camera_3d, quads_phase, target, depth, heatmap, half_res, mask): QueryItem<
+            Self::ViewQuery,
+        >,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.view_entity();
+
+        #[cfg(feature = "trace")]
+        let _main_quads_pass_span = info_span!("main_quads_pass").entered();
+        let color_attachments;
+        let pass_descriptor = if let Some(heatmap) = heatmap {
+            RenderPassDescriptor {
+                label: Some("main_quads_heatmap_pass"),
+                // NOTE: The heatmap is accumulated from scratch every frame and resolved onto the
+                // view target afterwards.
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &heatmap.0.default_view,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(Color::NONE.into()),
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            }
+        } else {
+            let clear = world.resource::<QuadsSettings>().clear;
+            // NOTE: A clear ignores the viewport, so cameras drawing over the target of an earlier
+            // camera keep its color
+            let clear_color = clear
+                .filter(|_| !matches!(camera_3d.clear_color, ClearColorConfig::None))
+                .map(|clear| clear.color);
+            // NOTE: The quads pass loads the color buffer as well as writing to it, unless the
+            // quads own the whole frame. The mask only holds the quads and starts out empty.
+            color_attachments = [
+                Some(target.get_color_attachment(Operations {
+                    load: match clear_color {
+                        Some(color) => LoadOp::Clear(color.into()),
+                        None => LoadOp::Load,
+                    },
+                    store: true,
+                })),
+                mask.map(|mask| RenderPassColorAttachment {
+                    view: &mask.0.default_view,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(Color::NONE.into()),
+                        store: true,
+                    },
+                }),
+            ];
+            RenderPassDescriptor {
+                label: Some("main_quads_pass"),
+                color_attachments: if mask.is_some() {
+                    &color_attachments
+                } else {
+                    &color_attachments[..1]
+                },
+                depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                    view: &depth.view,
+                    // NOTE: The quads main pass loads the depth buffer and possibly overwrites it.
+                    // Reverse-Z clears it to 0, the far plane.
+                    depth_ops: Some(Operations {
+                        load: match clear {
+                            Some(QuadsClear { depth: true, .. }) => LoadOp::Clear(0.0),
+                            _ => LoadOp::Load,
+                        },
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }),
+            }
+        };
+
+        let mut render_pass = render_context.begin_tracked_render_pass(pass_descriptor);
+
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(viewport);
+        }
+
+        render_quads_phase(quads_phase, world, &mut render_pass, view_entity);
+        // NOTE: The hooks' pipelines are made for the view target, not the heatmap
+        let subpass_draws = heatmap
+            .is_none()
+            .then(|| world.resource::<QuadsSubpassDraws>());
+        if let Some(subpass_draws) = subpass_draws {
+            subpass_draws.draw(
+                QuadsSubpassAnchor::AfterOpaque,
+                &mut render_pass,
+                world,
+                view_entity,
+            );
+        }
+        if let Some((half_res, composite)) = half_res {
+            composite.draw(half_res, world, &mut render_pass);
+        }
+        if let Some(subpass_draws) = subpass_draws {
+            subpass_draws.draw(
+                QuadsSubpassAnchor::AfterTransparent,
+                &mut render_pass,
+                world,
+                view_entity,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Like `RenderPhase::render`, but every batch is wrapped in a debug group named after it so that
+/// its draw can be told apart in graphics debuggers
+fn render_quads_phase<'w>(
+    phase: &'w RenderPhase<QuadsPhaseItem>,
+    world: &'w World,
+    render_pass: &mut TrackedRenderPass<'w>,
+    view_entity: Entity,
+) {
+    let draw_functions = world.resource::<DrawFunctions<QuadsPhaseItem>>();
+    let mut draw_functions = draw_functions.write();
+    draw_functions.prepare(world);
+    for item in &phase.items {
+        render_pass.push_debug_group(item.label);
+        let draw_function = draw_functions.get_mut(item.draw_function).unwrap();
+        draw_function.draw(world, render_pass, view_entity, item);
+        render_pass.pop_debug_group();
+    }
+}
+
+/// Renders the [`Quads`] resource in every 3d view
+///
+/// The quads are opaque and drawn in a pass of their own into the view's color and depth
+/// textures. They are depth tested against everything drawn before them and occlude everything
+/// drawn after them, but Bevy's transparent meshes do not write depth and are blended with
+/// whatever they cover when they are drawn. Where the pass is placed decides what the quads are
+/// composited with:
+///
+/// - By default after tonemapping, overwriting transparent meshes in front of the quads
+/// - With [`QuadsPlugin::tonemapped`] after the main pass but before tonemapping, still drawing
+///   over transparent meshes
+/// - With [`QuadsPlugin::before_transparent`] between the opaque and the transparent pass, so
+///   transparent meshes in front of the quads are blended over them and the ones behind are
+///   hidden by them, like opaque meshes
+/// - With [`QuadsPlugin::over_ui`] after all post-processing and the UI, drawing over both
+pub struct QuadsPlugin {
+    /// Draw the quads before Bevy's tonemapping so that their colors are tonemapped and color
+    /// graded like meshes. By default the quads are drawn after tonemapping and output their
+    /// colors as-is, which only matches unlit meshes in views using `Tonemapping::None`.
+    pub tonemapped: bool,
+    /// Draw the quads between Bevy's opaque and transparent 3d passes instead of after the main
+    /// pass, so they are composited correctly with transparent meshes. The main pass is
+    /// tonemapped afterwards, so this implies [`QuadsPlugin::tonemapped`]. Disabled by default.
+    pub before_transparent: bool,
+    /// Draw the quads after bevy_ui instead of before it, e.g. for markers that must stay visible
+    /// over panels. The quads are drawn after all post-processing, untonemapped like by default,
+    /// and not anti-aliased by FXAA. Takes precedence over [`QuadsPlugin::tonemapped`] and
+    /// [`QuadsPlugin::before_transparent`]. Disabled by default.
+    ///
+    /// The UI is drawn into the view's resolved texture, which a multisampled quads pass would
+    /// overwrite with its own samples, so the quads are only drawn while [`Msaa`] is
+    /// [`Msaa::Off`]. With MSAA an error is logged once and the quads are skipped.
+    pub over_ui: bool,
+    /// Upper limit on the number of quads uploaded to the GPU, protecting against runaway data
+    /// causing huge buffer allocations. Quads beyond the limit are not rendered, a warning is
+    /// logged once, and the number of dropped quads is reported through
+    /// [`QuadsPlugin::DROPPED_QUADS`]. Unlimited by default.
+    pub max_quads: Option<usize>,
+    /// Log the quad instance data layout at startup and the shader defs of every quads pipeline
+    /// when it is specialized, to help correlate GPU captures with the CPU side data
+    pub log_layout: bool,
+    /// Write the depth of the quads, so they occlude whatever is drawn after them. Disabling it
+    /// keeps testing the quads against the depth of the scene without adding to it, for overlay
+    /// quads that must not hide later transparent content. Enabled by default.
+    pub depth_write_enabled: bool,
+    /// Clear the view before drawing the quads instead of drawing over what was rendered before
+    /// them, for apps where the quads are the whole frame. While there are no quads to draw the
+    /// pass is skipped and the view keeps its camera's clear color. `None` by default.
+    ///
+    /// The color is cleared across the whole target, so cameras with a
+    /// [`ClearColorConfig::None`] keep it, like the later cameras of a split screen which would
+    /// otherwise wipe the viewports rendered before them.
+    pub clear: Option<QuadsClear>,
+    /// Shader whose `fragment` entry point replaces the built-in one, e.g. for custom effects. It
+    /// is specialized with the same shader defs and can import the modules the built-in shader is
+    /// composed from, `bevy_vertex_pulling::quads::{types, bindings, unpack, billboard, shading}`,
+    /// for the quads' `FragmentInput` and their texturing and lighting. Returning the
+    /// `FragmentOutput` of `shading::fragment_output` also writes the [`QuadsMask`]. The debug
+    /// views and the heatmap keep the built-in shader. `None` by default.
+    pub fragment_shader: Option<Handle<Shader>>,
+    /// Check the quads for non-finite values while they are uploaded, like NaN centers from a
+    /// physics blowup, which can make the GPU rasterize garbage or stall. Quads with a non-finite
+    /// center or half-extents are hidden, the other non-finite values are replaced, and the
+    /// number of invalid quads is recorded as [`QuadsPlugin::INVALID_QUADS`] with the first few
+    /// logged. `Quads` itself is left unchanged.
+    ///
+    /// Without it, debug builds panic on quads with non-finite values and release builds upload
+    /// them as they are. Disabled by default.
+    pub sanitize: bool,
+    /// How the GPU buffers of the quads grow, shrink and how large they may get. By default they
+    /// are allocated at the size of their contents, keep their capacity when the contents shrink,
+    /// and are unlimited.
+    ///
+    /// Every reallocation is sent as a [`QuadsAllocatorEvent`].
+    pub allocator: QuadsAllocatorConfig,
+}
+
+impl Default for QuadsPlugin {
+    fn default() -> Self {
+        Self {
+            tonemapped: false,
+            before_transparent: false,
+            over_ui: false,
+            max_quads: None,
+            log_layout: false,
+            depth_write_enabled: true,
+            clear: None,
+            fragment_shader: None,
+            sanitize: false,
+            allocator: QuadsAllocatorConfig::default(),
+        }
+    }
+}
+
+/// How the quads pass clears the view with [`QuadsPlugin::clear`]. Ignored while a
+/// [`QuadsHeatmap`] is active.
+#[derive(Clone, Copy, Debug)]
+pub struct QuadsClear {
+    pub color: Color,
+    /// Also clear the depth buffer, so the quads are not occluded by anything drawn before them
+    pub depth: bool,
+}
+
+impl Default for QuadsClear {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            depth: true,
+        }
+    }
+}
+
+impl QuadsPlugin {
+    pub const DROPPED_QUADS: DiagnosticId =
+        DiagnosticId::from_u128(272916248245511393586498580232230026474);
+    /// Quads with non-finite values in the last upload, see [`QuadsPlugin::sanitize`]
+    pub const INVALID_QUADS: DiagnosticId =
+        DiagnosticId::from_u128(65239564048919044310418297257949557);
+    /// GPU memory of all quad batches in MiB, see [`QuadsMemoryStats`]
+    pub const GPU_MEMORY: DiagnosticId =
+        DiagnosticId::from_u128(105368129871630946573320185611894357913);
+}
+
+/// [`QuadsPlugin`] configuration, available in both the main and render worlds
+#[derive(Clone, Resource)]
+struct QuadsSettings {
+    tonemapped: bool,
+    over_ui: bool,
+    max_quads: Option<usize>,
+    log_layout: bool,
+    depth_write_enabled: bool,
+    clear: Option<QuadsClear>,
+    fragment_shader: Option<Handle<Shader>>,
+    allocator: QuadsAllocatorConfig,
+}
+
+impl QuadsSettings {
+    /// Most quads with `corners` corners that are rendered, limited by
+    /// [`QuadsPlugin::max_quads`] and the budget of the instance and index buffers
+    fn max_quads(&self, corners: u32) -> Option<usize> {
+        let max_quads = self
+            .max_quads
+            .unwrap_or(usize::MAX)
+            .min(self.allocator.max_len(GpuQuad::min_size().get()))
+            .min(self.allocator.max_len(index_size(corners)));
+        (max_quads < usize::MAX).then_some(max_quads)
+    }
+
+    /// Most [`QuadPoint`]s that are expanded, limited by the budget of the points, the expanded
+    /// quads and their index buffer
+    fn max_points(&self) -> Option<usize> {
+        let max_points = self
+            .allocator
+            .max_len(QuadPoint::min_size().get())
+            .min(self.allocator.max_len(GpuQuad::min_size().get()))
+            .min(self.allocator.max_len(index_size(4)));
+        (max_points < usize::MAX).then_some(max_points)
+    }
+}
+
+impl Plugin for QuadsPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            QUADS_TYPES_SHADER_HANDLE,
+            "quads_types.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            QUADS_BINDINGS_SHADER_HANDLE,
+            "quads_bindings.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            QUADS_UNPACK_SHADER_HANDLE,
+            "quads_unpack.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            QUADS_BILLBOARD_SHADER_HANDLE,
+            "quads_billboard.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            QUADS_SHADING_SHADER_HANDLE,
+            "quads_shading.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(app, QUADS_SHADER_HANDLE, "quads.wgsl", Shader::from_wgsl);
+        // NOTE: The quads pass is placed either between the opaque and transparent main passes,
+        // between the end of the main pass and tonemapping, between tonemapping and FXAA so it
+        // is still anti-aliased, or after all post-processing so it can follow the UI pass
+        let (previous_node, next_node) = if self.over_ui {
+            (
+                core_3d::graph::node::END_MAIN_PASS_POST_PROCESSING,
+                core_3d::graph::node::UPSCALING,
+            )
+        } else if self.before_transparent {
+            (
+                core_3d::graph::node::MAIN_OPAQUE_PASS,
+                core_3d::graph::node::MAIN_TRANSPARENT_PASS,
+            )
+        } else if self.tonemapped {
+            (
+                core_3d::graph::node::END_MAIN_PASS,
+                core_3d::graph::node::TONEMAPPING,
+            )
+        } else {
+            (
+                core_3d::graph::node::TONEMAPPING,
+                core_3d::graph::node::FXAA,
+            )
+        };
+        let settings = QuadsSettings {
+            tonemapped: !self.over_ui && (self.tonemapped || self.before_transparent),
+            over_ui: self.over_ui,
+            max_quads: self.max_quads,
+            log_layout: self.log_layout,
+            depth_write_enabled: self.depth_write_enabled,
+            clear: self.clear,
+            fragment_shader: self.fragment_shader.clone(),
+            allocator: self.allocator,
+        };
+        // NOTE: The plugins below add their render graph nodes relative to the quads pass and
+        // their draw commands to the quads phase while they are built, which requires both to
+        // exist already
+        app.sub_app_mut(RenderApp)
+            .init_resource::<DrawFunctions<QuadsPhaseItem>>()
+            .add_render_graph_node::<ViewNodeRunner<QuadsPassNode>>(
+                core_3d::graph::NAME,
+                node::QUADS_PASS,
+            );
+        app.add_plugins((
+            ExtractResourcePlugin::<Quads>::default(),
+            ExtractResourcePlugin::<QuadsDebugView>::default(),
+            ExtractResourcePlugin::<QuadsEnabled>::default(),
+            HeatmapPlugin { next_node },
+            HalfResPlugin { previous_node },
+            NearFadePlugin,
+            FlowFieldPlugin,
+            DissolvePlugin,
+            OriginPlugin,
+            ScreenIndexPlugin,
+            GroupColorsPlugin,
+            GlobalAlphaPlugin,
+            MaskPlugin,
+            MemoryPlugin,
+            GeneratedQuadsPlugin,
+        ))
+        .insert_resource(settings.clone())
+        .init_resource::<QuadsDebugView>()
+        .init_resource::<QuadsEnabled>()
+        .init_resource::<QuadsCommandQueue>()
+        .init_resource::<QuadCommandKeys>()
+        .register_diagnostic(Diagnostic::new(Self::DROPPED_QUADS, "dropped_quads", 20))
+        .add_systems(PreUpdate, apply_quad_commands)
+        .add_systems(Update, poll_quads_generators)
+        .add_systems(PostUpdate, (diagnose_dropped_quads, diagnose_baked_quads));
+        app.add_plugins((DepthOfFieldPlugin, AllocatorPlugin, SubpassPlugin));
+        if self.sanitize {
+            app.add_plugins(SanitizePlugin);
+        }
+        #[cfg(feature = "compression")]
+        app.add_asset::<Quads>()
+            .init_asset_loader::<CompressedQuadsLoader>();
+
+        let render_app = app.sub_app_mut(RenderApp);
+
+        render_app
+            .insert_resource(settings)
+            .init_resource::<SpecializedRenderPipelines<QuadsPipeline>>()
+            .init_resource::<QuadSamplers>()
+            .add_render_command::<QuadsPhaseItem, DrawQuads>()
+            .add_render_graph_edges(
+                core_3d::graph::NAME,
+                &[previous_node, node::QUADS_PASS, next_node],
+            )
+            .add_systems(ExtractSchedule, extract_quads_phase.run_if(quads_active))
+            .add_systems(
+                Render,
+                (
+                    prepare_quads
+                        .in_set(RenderSet::Prepare)
+                        .run_if(resource_exists::<Quads>()),
+                    queue_quads_bind_group.in_set(RenderSet::Queue),
+                    queue_quads
+                        .in_set(RenderSet::Queue)
+                        .run_if(any_with_component::<RenderPhase<QuadsPhaseItem>>()),
+                )
+                    .run_if(resource_exists::<QuadsPipeline>()),
+            );
+    }
+    fn finish(&self, app: &mut App) {
+        let render_app = app.sub_app_mut(RenderApp);
+
+        // NOTE: The quads pass already runs before the UI pass through the post processing nodes
+        // in between. The explicit edge keeps the UI drawn over the quads, e.g. tooltips over
+        // health bars, no matter how those nodes are rearranged. The UI plugin has added its node
+        // by now if it is used, as `finish` runs after every plugin was built.
+        let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+        if let Some(graph_3d) = render_graph.get_sub_graph_mut(core_3d::graph::NAME) {
+            if graph_3d.get_node_state(node::UI_PASS).is_ok() {
+                if self.over_ui {
+                    graph_3d.add_node_edge(node::UI_PASS, node::QUADS_PASS);
+                } else {
+                    graph_3d.add_node_edge(node::QUADS_PASS, node::UI_PASS);
+                }
+            }
+        }
+
+        // NOTE: The quad instance data is pulled from a storage buffer in the vertex shader. Some
+        // backends (e.g. WebGL2) do not support storage buffers at all, in which case creating
+        // the bind group would fail with a wgpu validation error mid-frame. Detect that up front
+        // and leave the quads systems disabled instead.
+        let limits = render_app.world.resource::<RenderDevice>().limits();
+        if limits.max_storage_buffers_per_shader_stage == 0 {
+            error!(
+                "QuadsPlugin requires storage buffer support in the vertex shader but the current \
+                render backend reports none (max_storage_buffers_per_shader_stage = 0). Quads will \
+                not be rendered. Use a backend with storage buffer support (Vulkan, Metal, DX12 or \
+                WebGPU)."
+            );
+            return;
+        }
+
+        render_app.init_resource::<QuadsPipeline>();
+
+        if render_app.world.resource::<QuadsSettings>().log_layout {
+            GpuQuad::log_layout();
+        }
+    }
+}
+
+bitflags::bitflags! {
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    #[repr(transparent)]
+    pub struct QuadsPipelineKey: u32 {
+        /// Accumulate quad weights into the view's heatmap target instead of shading them
+        const HEATMAP            = (1 << 0);
+        /// The view renders to an HDR main texture
+        const HDR                = (1 << 1);
+        /// Apply the view's tonemapping in the fragment shader, for tonemapped quads in LDR views
+        const TONEMAP_IN_SHADER  = (1 << 2);
+        const DEBAND_DITHER      = (1 << 3);
+        /// Blend the detail texture over the base color
+        const DETAIL_TEXTURE     = (1 << 4);
+        /// Use the overlay instead of the multiply blend for the detail texture
+        const DETAIL_OVERLAY     = (1 << 5);
+        /// Shrink world-sized quads close to the camera, see `QuadsNearFade`
+        const NEAR_FADE          = (1 << 6);
+        /// Position quads relative to the camera, see `QuadsOrigin::camera_relative`
+        const CAMERA_RELATIVE    = (1 << 7);
+        /// Displace quads by the flow field, see `Quads::flow_field`
+        const FLOW_FIELD         = (1 << 8);
+        /// Render into the view's single-sampled half resolution targets, see
+        /// `QuadsResolution::Half`
+        const HALF_RESOLUTION    = (1 << 9);
+        /// Evaluate the batch's animation, see `QuadsAnimation`
+        const BATCH_ANIMATION    = (1 << 10);
+        /// Apply the quads' HSV shifts, see `Quad::hsv_shift`
+        const HSV_ADJUST         = (1 << 11);
+        /// Write the quads' masks into the view's `QuadsMaskTexture`, see `QuadsMask`
+        const MASK               = (1 << 12);
+        /// Draw the backs of the quads and light them from the side they are seen from, see
+        /// `Quads::double_sided`
+        const DOUBLE_SIDED       = (1 << 13);
+        /// Discard the fragments below the quads' dissolve threshold, see `Quads::dissolve`
+        const DISSOLVE           = (1 << 14);
+        /// Grow `Billboard::CocScaled` quads by the circle of confusion of the view's
+        /// `QuadsDepthOfField`, which is bound as a third bind group
+        const DEPTH_OF_FIELD     = (1 << 15);
+        /// Let the light arriving at the back of lit quads shine through them by their
+        /// translucency, see `Quad::translucency`
+        const TRANSLUCENCY       = (1 << 16);
+        /// Blend the quads with premultiplied alpha, see `QuadsAlphaMode::PremultipliedBlend`
+        const PREMULTIPLIED_ALPHA = (1 << 17);
+        /// Weight the blended quads by the pass's blend constant, see `Quads::blend_constant`
+        const BLEND_CONSTANT     = (1 << 18);
+        const MSAA_RESERVED_BITS = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
+        const TONEMAP_METHOD_RESERVED_BITS = Self::TONEMAP_METHOD_MASK_BITS << Self::TONEMAP_METHOD_SHIFT_BITS;
+        const TONEMAP_METHOD_NONE               = 0 << Self::TONEMAP_METHOD_SHIFT_BITS;
+        const TONEMAP_METHOD_REINHARD           = 1 << Self::TONEMAP_METHOD_SHIFT_BITS;
+        const TONEMAP_METHOD_REINHARD_LUMINANCE = 2 << Self::TONEMAP_METHOD_SHIFT_BITS;
+        const TONEMAP_METHOD_ACES_FITTED        = 3 << Self::TONEMAP_METHOD_SHIFT_BITS;
+        const TONEMAP_METHOD_AGX                = 4 << Self::TONEMAP_METHOD_SHIFT_BITS;
+        const TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM = 5 << Self::TONEMAP_METHOD_SHIFT_BITS;
+        const TONEMAP_METHOD_TONY_MC_MAPFACE    = 6 << Self::TONEMAP_METHOD_SHIFT_BITS;
+        const TONEMAP_METHOD_BLENDER_FILMIC     = 7 << Self::TONEMAP_METHOD_SHIFT_BITS;
+        const DEBUG_VIEW_RESERVED_BITS = Self::DEBUG_VIEW_MASK_BITS << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_OFF            = 0 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_WIREFRAME      = 1 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_OVERDRAW       = 2 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_INSTANCE_INDEX = 3 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_NORMAL         = 4 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_UV             = 5 << Self::DEBUG_VIEW_SHIFT_BITS;
+        /// Corners of every quad divided by 4, minus 1, see `Quads::corner_count`
+        const CORNERS_RESERVED_BITS = Self::CORNERS_MASK_BITS << Self::CORNERS_SHIFT_BITS;
+    }
+}
+
+impl QuadsPipelineKey {
+    const MSAA_MASK_BITS: u32 = 0b111;
+    const MSAA_SHIFT_BITS: u32 = 32 - Self::MSAA_MASK_BITS.count_ones();
+    const TONEMAP_METHOD_MASK_BITS: u32 = 0b111;
+    const TONEMAP_METHOD_SHIFT_BITS: u32 =
+        Self::MSAA_SHIFT_BITS - Self::TONEMAP_METHOD_MASK_BITS.count_ones();
+    const DEBUG_VIEW_MASK_BITS: u32 = 0b111;
+    const DEBUG_VIEW_SHIFT_BITS: u32 =
+        Self::TONEMAP_METHOD_SHIFT_BITS - Self::DEBUG_VIEW_MASK_BITS.count_ones();
+    const CORNERS_MASK_BITS: u32 = 0b111;
+    const CORNERS_SHIFT_BITS: u32 =
+        Self::DEBUG_VIEW_SHIFT_BITS - Self::CORNERS_MASK_BITS.count_ones();
+
+    pub fn from_msaa_samples(msaa_samples: u32) -> Self {
+        let msaa_bits =
+            (msaa_samples.trailing_zeros() & Self::MSAA_MASK_BITS) << Self::MSAA_SHIFT_BITS;
+        Self::from_bits_retain(msaa_bits)
+    }
+
+    pub fn msaa_samples(&self) -> u32 {
+        1 << ((self.bits() >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS)
+    }
+
+    pub fn from_tonemapping(tonemapping: Tonemapping) -> Self {
+        match tonemapping {
+            Tonemapping::None => Self::TONEMAP_METHOD_NONE,
+            Tonemapping::Reinhard => Self::TONEMAP_METHOD_REINHARD,
+            Tonemapping::ReinhardLuminance => Self::TONEMAP_METHOD_REINHARD_LUMINANCE,
+            Tonemapping::AcesFitted => Self::TONEMAP_METHOD_ACES_FITTED,
+            Tonemapping::AgX => Self::TONEMAP_METHOD_AGX,
+            Tonemapping::SomewhatBoringDisplayTransform => {
+                Self::TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM
+            }
+            Tonemapping::TonyMcMapface => Self::TONEMAP_METHOD_TONY_MC_MAPFACE,
+            Tonemapping::BlenderFilmic => Self::TONEMAP_METHOD_BLENDER_FILMIC,
+        }
+    }
+
+    /// Shader def selecting the tonemapping operator in `bevy_core_pipeline::tonemapping`
+    fn tonemap_method_shader_def(&self) -> &'static str {
+        let method = self.intersection(Self::TONEMAP_METHOD_RESERVED_BITS);
+        if method == Self::TONEMAP_METHOD_REINHARD {
+            "TONEMAP_METHOD_REINHARD"
+        } else if method == Self::TONEMAP_METHOD_REINHARD_LUMINANCE {
+            "TONEMAP_METHOD_REINHARD_LUMINANCE"
+        } else if method == Self::TONEMAP_METHOD_ACES_FITTED {
+            "TONEMAP_METHOD_ACES_FITTED"
+        } else if method == Self::TONEMAP_METHOD_AGX {
+            "TONEMAP_METHOD_AGX"
+        } else if method == Self::TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM {
+            "TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM"
+        } else if method == Self::TONEMAP_METHOD_TONY_MC_MAPFACE {
+            "TONEMAP_METHOD_TONY_MC_MAPFACE"
+        } else if method == Self::TONEMAP_METHOD_BLENDER_FILMIC {
+            "TONEMAP_METHOD_BLENDER_FILMIC"
+        } else {
+            "TONEMAP_METHOD_NONE"
+        }
+    }
+
+    pub fn from_debug_view(debug_view: QuadsDebugView) -> Self {
+        match debug_view {
+            QuadsDebugView::Off => Self::DEBUG_VIEW_OFF,
+            QuadsDebugView::Wireframe => Self::DEBUG_VIEW_WIREFRAME,
+            QuadsDebugView::Overdraw => Self::DEBUG_VIEW_OVERDRAW,
+            QuadsDebugView::InstanceIndex => Self::DEBUG_VIEW_INSTANCE_INDEX,
+            QuadsDebugView::Normal => Self::DEBUG_VIEW_NORMAL,
+            QuadsDebugView::Uv => Self::DEBUG_VIEW_UV,
+        }
+    }
+
+    pub fn debug_view(&self) -> QuadsDebugView {
+        let debug_view = self.intersection(Self::DEBUG_VIEW_RESERVED_BITS);
+        if debug_view == Self::DEBUG_VIEW_WIREFRAME {
+            QuadsDebugView::Wireframe
+        } else if debug_view == Self::DEBUG_VIEW_OVERDRAW {
+            QuadsDebugView::Overdraw
+        } else if debug_view == Self::DEBUG_VIEW_INSTANCE_INDEX {
+            QuadsDebugView::InstanceIndex
+        } else if debug_view == Self::DEBUG_VIEW_NORMAL {
+            QuadsDebugView::Normal
+        } else if debug_view == Self::DEBUG_VIEW_UV {
+            QuadsDebugView::Uv
+        } else {
+            QuadsDebugView::Off
+        }
+    }
+
+    pub fn from_detail_blend(blend: QuadsDetailBlend) -> Self {
+        match blend {
+            QuadsDetailBlend::Multiply => Self::DETAIL_TEXTURE,
+            QuadsDetailBlend::Overlay => Self::DETAIL_TEXTURE | Self::DETAIL_OVERLAY,
+        }
+    }
+
+    /// `corners` must be a multiple of 4 from 4 to [`MAX_QUAD_CORNERS`]
+    pub fn from_corners(corners: u32) -> Self {
+        let corners_bits =
+            ((corners / 4).saturating_sub(1) & Self::CORNERS_MASK_BITS) << Self::CORNERS_SHIFT_BITS;
+        Self::from_bits_retain(corners_bits)
+    }
+
+    /// Number of corners of every quad, 4 for plain quads
+    pub fn corners(&self) -> u32 {
+        4 * (((self.bits() >> Self::CORNERS_SHIFT_BITS) & Self::CORNERS_MASK_BITS) + 1)
+    }
+
+    /// Blend state of the view target for quads that are not drawn into the heatmap or a debug
+    /// view, replacing what is behind them unless they are blended
+    pub fn blend_state(&self) -> BlendState {
+        if !self.intersects(Self::PREMULTIPLIED_ALPHA | Self::BLEND_CONSTANT) {
+            return BlendState::REPLACE;
+        }
+        let component = BlendComponent {
+            src_factor: if self.contains(Self::BLEND_CONSTANT) {
+                BlendFactor::Constant
+            } else {
+                BlendFactor::One
+            },
+            dst_factor: BlendFactor::OneMinusSrcAlpha,
+            operation: BlendOperation::Add,
+        };
+        BlendState {
+            color: component,
+            alpha: component,
+        }
+    }
+
+    pub fn from_hdr(hdr: bool) -> Self {
+        if hdr {
+            Self::HDR
+        } else {
+            Self::empty()
+        }
+    }
+
+    /// Shader defs `quads.wgsl` is specialized with for this key. The pipeline cache adds
+    /// `AVAILABLE_STORAGE_BUFFER_BINDINGS` on top of these.
+    pub fn shader_defs(&self) -> Vec<ShaderDefVal> {
+        let mut shader_defs = Vec::new();
+        if self.msaa_samples() > 1 {
+            shader_defs.push("MULTISAMPLED".into());
+        }
+
+        match self.debug_view() {
+            QuadsDebugView::Off => {}
+            QuadsDebugView::Wireframe => shader_defs.push("DEBUG_WIREFRAME".into()),
+            QuadsDebugView::Overdraw => shader_defs.push("DEBUG_OVERDRAW".into()),
+            QuadsDebugView::InstanceIndex => shader_defs.push("DEBUG_INSTANCE_INDEX".into()),
+            QuadsDebugView::Normal => shader_defs.push("DEBUG_NORMAL".into()),
+            QuadsDebugView::Uv => shader_defs.push("DEBUG_UV".into()),
+        }
+
+        if self.contains(Self::DETAIL_TEXTURE) {
+            shader_defs.push("DETAIL_TEXTURE".into());
+            if self.contains(Self::DETAIL_OVERLAY) {
+                shader_defs.push("DETAIL_OVERLAY".into());
+            }
+        }
+
+        if self.contains(Self::NEAR_FADE) {
+            shader_defs.push("NEAR_FADE".into());
+        }
+
+        if self.contains(Self::CAMERA_RELATIVE) {
+            shader_defs.push("CAMERA_RELATIVE".into());
+        }
+
+        if self.contains(Self::FLOW_FIELD) {
+            shader_defs.push("FLOW_FIELD".into());
+        }
+
+        if self.contains(Self::HALF_RESOLUTION) {
+            shader_defs.push("HALF_RESOLUTION".into());
+        }
+
+        if self.contains(Self::BATCH_ANIMATION) {
+            shader_defs.push("BATCH_ANIMATION".into());
+        }
+
+        if self.contains(Self::HSV_ADJUST) {
+            shader_defs.push("HSV_ADJUST".into());
+        }
+
+        if self.contains(Self::MASK) {
+            shader_defs.push("QUADS_MASK".into());
+        }
+
+        if self.contains(Self::DOUBLE_SIDED) {
+            shader_defs.push("DOUBLE_SIDED".into());
+        }
+
+        if self.contains(Self::TRANSLUCENCY) {
+            shader_defs.push("TRANSLUCENCY".into());
+        }
+
+        // NOTE: Blended quads are blended as premultiplied, straight alpha quads are
+        // premultiplied in the shader
+        if self.contains(Self::BLEND_CONSTANT) && !self.contains(Self::PREMULTIPLIED_ALPHA) {
+            shader_defs.push("PREMULTIPLY_OUTPUT".into());
+        }
+
+        if self.contains(Self::DISSOLVE) {
+            shader_defs.push("DISSOLVE".into());
+        }
+        if self.contains(Self::DEPTH_OF_FIELD) {
+            shader_defs.push("DEPTH_OF_FIELD".into());
+        }
+
+        if self.contains(Self::TONEMAP_IN_SHADER) {
+            shader_defs.push("TONEMAP_IN_SHADER".into());
+            shader_defs.push(self.tonemap_method_shader_def().into());
+            if self.contains(Self::DEBAND_DITHER) {
+                shader_defs.push("DEBAND_DITHER".into());
+            }
+        }
+
+        if self.contains(Self::HEATMAP) {
+            shader_defs.push("HEATMAP".into());
+        }
+
+        if self.corners() > 4 {
+            shader_defs.push(ShaderDefVal::UInt("QUAD_CORNERS".into(), self.corners()));
+        }
+        shader_defs
+    }
+
+    /// Format of the main texture of the views this key was created for
+    pub fn view_target_format(&self) -> TextureFormat {
+        if self.contains(Self::HDR) {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        }
+    }
+}
+
+#[derive(Resource)]
+struct QuadsPipeline {
+    view_layout: BindGroupLayout,
+    view_layout_multisampled: BindGroupLayout,
+    quads_layout: BindGroupLayout,
+    depth_of_field_layout: BindGroupLayout,
+    log_layout: bool,
+    depth_write_enabled: bool,
+    fragment_shader: Handle<Shader>,
+}
+
+const QUADS_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 7659167879172469997);
+// NOTE: The modules quads.wgsl is composed from, importable by custom shaders
+const QUADS_TYPES_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 13388242647627389047);
+const QUADS_BINDINGS_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3357874028421896574);
+const QUADS_UNPACK_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9517959398963519177);
+const QUADS_BILLBOARD_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 14562107473016197996);
+const QUADS_SHADING_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 16221609227983874137);
+
+impl FromWorld for QuadsPipeline {
+    fn from_world(world: &mut World) -> Self {
+        // NOTE: The quads use the same view bind group as Bevy's mesh pipeline so that lit quads
+        // can read the lights, clusters and cluster offsets bindings.
+        let mesh_pipeline = world.resource::<MeshPipeline>();
+        let view_layout = mesh_pipeline.view_layout.clone();
+        let view_layout_multisampled = mesh_pipeline.view_layout_multisampled.clone();
+
+        let quads_layout =
+            world
+                .resource::<RenderDevice>()
+                .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: None,
+                    entries: &[
+                        BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: ShaderStages::VERTEX,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Storage { read_only: true },
+                                has_dynamic_offset: false,
+                                min_binding_size: BufferSize::new(0),
+                            },
+                            count: None,
+                        },
+                        // Texture
+                        BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Texture {
+                                multisampled: false,
+                                sample_type: TextureSampleType::Float { filterable: true },
+                                view_dimension: TextureViewDimension::D2,
+                            },
+                            count: None,
+                        },
+                        // Texture sampler
+                        BindGroupLayoutEntry {
+                            binding: 2,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                            count: None,
+                        },
+                        // Detail texture
+                        BindGroupLayoutEntry {
+                            binding: 3,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Texture {
+                                multisampled: false,
+                                sample_type: TextureSampleType::Float { filterable: true },
+                                view_dimension: TextureViewDimension::D2,
+                            },
+                            count: None,
+                        },
+                        // Detail texture sampler
+                        BindGroupLayoutEntry {
+                            binding: 4,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                            count: None,
+                        },
+                        // Near fade distances
+                        BindGroupLayoutEntry {
+                            binding: 5,
+                            visibility: ShaderStages::VERTEX,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                        // Origin
+                        BindGroupLayoutEntry {
+                            binding: 6,
+                            visibility: ShaderStages::VERTEX,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                        // Group colors
+                        BindGroupLayoutEntry {
+                            binding: 7,
+                            visibility: ShaderStages::VERTEX,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                        // Flow field texture
+                        BindGroupLayoutEntry {
+                            binding: 8,
+                            visibility: ShaderStages::VERTEX,
+                            ty: BindingType::Texture {
+                                multisampled: false,
+                                sample_type: TextureSampleType::Float { filterable: true },
+                                view_dimension: TextureViewDimension::D3,
+                            },
+                            count: None,
+                        },
+                        // Flow field sampler
+                        BindGroupLayoutEntry {
+                            binding: 9,
+                            visibility: ShaderStages::VERTEX,
+                            ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                            count: None,
+                        },
+                        // Flow field strength, scale and scroll
+                        BindGroupLayoutEntry {
+                            binding: 10,
+                            visibility: ShaderStages::VERTEX,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                        // Batch animation
+                        BindGroupLayoutEntry {
+                            binding: 11,
+                            visibility: ShaderStages::VERTEX,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                        // Global alpha
+                        BindGroupLayoutEntry {
+                            binding: 12,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                        // Dissolve noise texture
+                        BindGroupLayoutEntry {
+                            binding: 13,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Texture {
+                                multisampled: false,
+                                sample_type: TextureSampleType::Float { filterable: true },
+                                view_dimension: TextureViewDimension::D2,
+                            },
+                            count: None,
+                        },
+                        // Dissolve noise sampler
+                        BindGroupLayoutEntry {
+                            binding: 14,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                            count: None,
+                        },
+                        // Dissolve edge and noise scale
+                        BindGroupLayoutEntry {
+                            binding: 15,
+                            visibility: ShaderStages::FRAGMENT,
+                            ty: BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                    ],
+                });
+
+        // NOTE: One buffer holds the depth of field of every view, each view binds its own
+        let depth_of_field_layout =
+            world
+                .resource::<RenderDevice>()
+                .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: Some("quads_depth_of_field_layout"),
+                    entries: &[BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: ShaderStages::VERTEX,
+                        ty: BindingType::Buffer {
+                            ty: BufferBindingType::Uniform,
+                            has_dynamic_offset: true,
+                            min_binding_size: Some(GpuDepthOfField::min_size()),
+                        },
+                        count: None,
+                    }],
+                });
+
+        let settings = world.resource::<QuadsSettings>();
+        Self {
+            view_layout,
+            view_layout_multisampled,
+            quads_layout,
+            depth_of_field_layout,
+            log_layout: settings.log_layout,
+            depth_write_enabled: settings.depth_write_enabled,
+            fragment_shader: settings
+                .fragment_shader
+                .clone()
+                .unwrap_or_else(|| QUADS_SHADER_HANDLE.typed()),
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for QuadsPipeline {
+    type Key = QuadsPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let shader_defs = key.shader_defs();
+        let msaa_samples = key.msaa_samples();
+        // NOTE: The view bind group layout depends on whether the view's depth and normal
+        // prepass textures are multisampled
+        let view_layout = if msaa_samples > 1 {
+            self.view_layout_multisampled.clone()
+        } else {
+            self.view_layout.clone()
+        };
+
+        let debug_view = key.debug_view();
+        let heatmap = key.contains(QuadsPipelineKey::HEATMAP);
+        let (target, depth_stencil, samples) = if heatmap {
+            // NOTE: The heatmap target is single-sampled and has no depth attachment, every
+            // overlapping quad adds to the accumulated value.
+            (
+                ColorTargetState {
+                    format: HEATMAP_TEXTURE_FORMAT,
+                    blend: Some(HEATMAP_BLEND),
+                    write_mask: ColorWrites::RED,
+                },
+                None,
+                1,
+            )
+        } else {
+            let overdraw = debug_view == QuadsDebugView::Overdraw;
+            // NOTE: Half resolution quads always write depth, it is what the composite tests
+            // against the view's depth. The view bind group still follows the view's MSAA.
+            let half_resolution = key.contains(QuadsPipelineKey::HALF_RESOLUTION);
+            (
+                ColorTargetState {
+                    format: key.view_target_format(),
+                    // NOTE: Overdraw uses the same additive blending as the heatmap
+                    blend: Some(if overdraw {
+                        HEATMAP_BLEND
+                    } else {
+                        key.blend_state()
+                    }),
+                    write_mask: ColorWrites::ALL,
+                },
+                Some(DepthStencilState {
+                    format: TextureFormat::Depth32Float,
+                    depth_write_enabled: (self.depth_write_enabled || half_resolution) && !overdraw,
+                    depth_compare: if overdraw {
+                        CompareFunction::Always
+                    } else {
+                        CompareFunction::Greater
+                    },
+                    stencil: StencilState {
+                        front: StencilFaceState::IGNORE,
+                        back: StencilFaceState::IGNORE,
+                        read_mask: 0,
+                        write_mask: 0,
+                    },
+                    bias: DepthBiasState {
+                        constant: 0,
+                        slope_scale: 0.0,
+                        clamp: 0.0,
+                    },
+                }),
+                if half_resolution { 1 } else { msaa_samples },
+            )
+        };
+
+        let mut targets = vec![Some(target)];
+        if key.contains(QuadsPipelineKey::MASK) {
+            // NOTE: Integer targets can't be blended, quads without a mask overwrite it with 0
+            targets.push(Some(ColorTargetState {
+                format: QUADS_MASK_TEXTURE_FORMAT,
+                blend: None,
+                write_mask: ColorWrites::ALL,
+            }));
+        }
+
+        if self.log_layout {
+            info!("Specializing quads pipeline for {key:?} with shader defs {shader_defs:?}");
+        }
+
+        let mut layout = vec![view_layout, self.quads_layout.clone()];
+        if key.contains(QuadsPipelineKey::DEPTH_OF_FIELD) {
+            layout.push(self.depth_of_field_layout.clone());
+        }
+
+        RenderPipelineDescriptor {
+            label: Some("quads_pipeline".into()),
+            layout,
+            vertex: VertexState {
+                shader: QUADS_SHADER_HANDLE.typed(),
+                shader_defs: shader_defs.clone(),
+                entry_point: "vertex".into(),
+                buffers: vec![],
+            },
+            fragment: Some(FragmentState {
+                shader: if heatmap || debug_view != QuadsDebugView::Off {
+                    QUADS_SHADER_HANDLE.typed()
+                } else {
+                    self.fragment_shader.clone()
+                },
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets,
+            }),
+            primitive: PrimitiveState {
+                front_face: FrontFace::Ccw,
+                cull_mode: if key.contains(QuadsPipelineKey::DOUBLE_SIDED) {
+                    None
+                } else {
+                    Some(Face::Back)
+                },
+                unclipped_depth: false,
+                polygon_mode: PolygonMode::Fill,
+                conservative: false,
+                topology: PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+            },
+            depth_stencil,
+            multisample: MultisampleState {
+                count: samples,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            push_constant_ranges: vec![],
+        }
+    }
+}
+
+type DrawQuads = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetGpuQuadsBindGroup<1>,
+    SetDepthOfFieldBindGroup<2>,
+    SetQuadsBlendConstant,
+    DrawVertexPulledQuads,
+);
+
+struct SetGpuQuadsBindGroup<const I: usize>;
+impl<const I: usize, P: PhaseItem> RenderCommand<P> for SetGpuQuadsBindGroup<I> {
+    type Param = SRes<GpuQuads>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: ROQueryItem<'w, Self::ViewWorldQuery>,
+        _entity: ROQueryItem<'w, Self::ItemWorldQuery>,
+        gpu_quads: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        pass.set_bind_group(I, gpu_quads.into_inner().bind_group.as_ref().unwrap(), &[]);
+
+        RenderCommandResult::Success
+    }
+}
+
+struct SetQuadsBlendConstant;
+impl<P: PhaseItem> RenderCommand<P> for SetQuadsBlendConstant {
+    type Param = SRes<GpuQuads>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: ROQueryItem<'w, Self::ViewWorldQuery>,
+        _entity: ROQueryItem<'w, Self::ItemWorldQuery>,
+        gpu_quads: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        if let Some(blend_constant) = gpu_quads.into_inner().blend_constant {
+            pass.set_blend_constant(blend_constant);
+        }
+        RenderCommandResult::Success
+    }
+}
+
+struct DrawVertexPulledQuads;
+impl<P: PhaseItem> RenderCommand<P> for DrawVertexPulledQuads {
+    type Param = SRes<GpuQuads>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: ROQueryItem<'w, Self::ViewWorldQuery>,
+        _entity: ROQueryItem<'w, Self::ItemWorldQuery>,
+        gpu_quads: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let gpu_quads = gpu_quads.into_inner();
+        pass.set_index_buffer(
+            gpu_quads.index_buffer.as_ref().unwrap().slice(..),
+            0,
+            IndexFormat::Uint32,
+        );
+        pass.draw_indexed(0..gpu_quads.index_count, 0, 0..1);
+        RenderCommandResult::Success
+    }
+}
+
+#[cfg(all(test, feature = "test_support"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use bevy::core_pipeline::tonemapping::Tonemapping;
+
+    use super::*;
+    use crate::test_support::render_frames;
+
+    const STEADY_FRAMES: usize = 100;
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_alpha_flags_map_onto_the_alpha_mode() {
+        let mut quads = Quads::default();
+        quads.set_premultiply_alpha(true);
+        assert_eq!(quads.alpha_mode, QuadsAlphaMode::PremultiplyOnUpload);
+        assert!(quads.premultiply_alpha() && !quads.premultiplied_alpha());
+
+        quads.set_premultiplied_alpha(true);
+        assert_eq!(quads.alpha_mode, QuadsAlphaMode::PremultipliedBlend);
+        // NOTE: Blended batches premultiply their colors either way, like the flag implied before
+        quads.set_premultiply_alpha(false);
+        assert_eq!(quads.alpha_mode, QuadsAlphaMode::PremultipliedBlend);
+        assert!(quads.premultiply_alpha() && quads.premultiplied_alpha());
+
+        quads.set_premultiplied_alpha(false);
+        assert_eq!(quads.alpha_mode, QuadsAlphaMode::Opaque);
+        assert!(!quads.premultiply_alpha() && !quads.premultiplied_alpha());
+    }
+
+    #[test]
+    fn moving_quads_keeps_the_bind_group() {
+        let bind_groups = Arc::new(Mutex::new(Vec::new()));
+        let recorded = bind_groups.clone();
+        render_frames(
+            |app| {
+                app.add_plugins(QuadsPlugin::default());
+                app.world.spawn(Camera3dBundle {
+                    transform: Transform::from_xyz(0.0, 0.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+                    tonemapping: Tonemapping::None,
+                    ..default()
+                });
+                let mut quads = Quads::default();
+                for i in 0..16 {
+                    quads.insert(Quad {
+                        center: Vec3::new(i as f32 * 0.1, 0.0, 0.0),
+                        half_extents: Vec3::splat(0.1),
+                        ..default()
+                    });
+                }
+                // NOTE: Moving the quads every frame uploads them in place
+                app.insert_resource(quads)
+                    .add_systems(Update, |mut quads: ResMut<Quads>| {
+                        for quad in &mut quads.data {
+                            quad.center.y = (quad.center.y + 0.01) % 1.0;
+                        }
+                    });
+                app.sub_app_mut(RenderApp).add_systems(
+                    Render,
+                    (move |gpu_quads: Option<Res<GpuQuads>>| {
+                        let bind_group = gpu_quads
+                            .and_then(|gpu_quads| gpu_quads.bind_group.as_ref().map(BindGroup::id));
+                        recorded.lock().unwrap().push(bind_group);
+                    })
+                    .in_set(RenderSet::Cleanup),
+                );
+            },
+            UVec2::new(64, 64),
+            STEADY_FRAMES,
+        );
+
+        let bind_groups = bind_groups.lock().unwrap();
+        let steady = &bind_groups[bind_groups.len() - STEADY_FRAMES - 1..];
+        assert!(steady[0].is_some(), "the quads have no bind group");
+        let created = steady.windows(2).filter(|pair| pair[0] != pair[1]).count();
+        assert_eq!(
+            created, 0,
+            "the bind group was created {created} times in {STEADY_FRAMES} steady frames"
+        );
+    }
+}
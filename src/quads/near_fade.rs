@@ -0,0 +1,90 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{Buffer, ShaderType, UniformBuffer},
+        renderer::{RenderDevice, RenderQueue},
+        Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+    },
+};
+
+/// Inserting this resource shrinks world-sized quads as they approach the camera, so they vanish
+/// smoothly instead of being cut by the near plane. Quads sized in screen space are not affected.
+#[derive(Clone, Debug, Resource)]
+pub struct QuadsNearFade {
+    /// View distance of the quad center at which quads start to shrink
+    pub start: f32,
+    /// View distance of the quad center at which quads have shrunk to nothing, must be less than
+    /// `start`
+    pub end: f32,
+}
+
+impl Default for QuadsNearFade {
+    fn default() -> Self {
+        Self {
+            start: 1.0,
+            end: 0.2,
+        }
+    }
+}
+
+#[derive(Clone, Default, ShaderType)]
+struct GpuNearFade {
+    start: f32,
+    end: f32,
+}
+
+/// Bound to the quads bind group even while the fade is disabled, the shader only reads it in
+/// pipelines specialized for the fade
+#[derive(Resource, Default)]
+pub(crate) struct NearFadeUniform(UniformBuffer<GpuNearFade>);
+
+impl NearFadeUniform {
+    pub(crate) fn buffer(&self) -> Option<&Buffer> {
+        self.0.buffer()
+    }
+}
+
+pub(crate) struct NearFadePlugin;
+
+impl Plugin for NearFadePlugin {
+    fn build(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<NearFadeUniform>()
+            .add_systems(ExtractSchedule, extract_near_fade)
+            .add_systems(Render, prepare_near_fade.in_set(RenderSet::Prepare));
+    }
+}
+
+/// Unlike `ExtractResourcePlugin` this also removes the render world copy when the resource is
+/// removed, so the fade can be toggled at runtime
+fn extract_near_fade(mut commands: Commands, near_fade: Extract<Option<Res<QuadsNearFade>>>) {
+    match near_fade.as_ref() {
+        Some(near_fade) if near_fade.is_changed() => {
+            commands.insert_resource(QuadsNearFade::clone(near_fade))
+        }
+        Some(_) => {}
+        None => commands.remove_resource::<QuadsNearFade>(),
+    }
+}
+
+fn prepare_near_fade(
+    near_fade: Option<Res<QuadsNearFade>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut uniform: ResMut<NearFadeUniform>,
+) {
+    let changed = near_fade
+        .as_ref()
+        .is_some_and(|near_fade| near_fade.is_changed());
+    // NOTE: The buffer has to exist for the quads bind group before the fade is first enabled
+    if !changed && uniform.0.buffer().is_some() {
+        return;
+    }
+    if let Some(near_fade) = near_fade {
+        uniform.0.set(GpuNearFade {
+            start: near_fade.start,
+            end: near_fade.end,
+        });
+    }
+    uniform.0.write_buffer(&render_device, &render_queue);
+}
@@ -0,0 +1,93 @@
+use bevy::{
+    math::DVec3,
+    prelude::*,
+    render::{
+        render_resource::{Buffer, ShaderType, UniformBuffer},
+        renderer::{RenderDevice, RenderQueue},
+        Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+    },
+};
+
+/// Double precision offset added to the center of every quad, for quads far away from the world
+/// origin. Quad centers are stored relative to it so they keep their precision, and moving the
+/// origin only uploads the origin itself, not the quads.
+///
+/// Quads in [`Billboard::ClipSpace`](super::Billboard::ClipSpace) ignore the origin.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct QuadsOrigin {
+    pub translation: DVec3,
+    /// Compute quad positions relative to the camera instead of the world origin. At large world
+    /// coordinates single precision view transforms make quads jitter as the camera moves, this
+    /// keeps the magnitudes in the shader small close to the camera.
+    pub camera_relative: bool,
+}
+
+/// The origin split into a single precision value and the remainder, so that the shader can
+/// subtract the camera position from the high part without losing the low bits
+#[derive(Clone, Default, ShaderType)]
+struct GpuOrigin {
+    high: Vec3,
+    low: Vec3,
+}
+
+impl From<DVec3> for GpuOrigin {
+    fn from(translation: DVec3) -> Self {
+        let high = translation.as_vec3();
+        Self {
+            high,
+            low: (translation - high.as_dvec3()).as_vec3(),
+        }
+    }
+}
+
+/// Bound to the quads bind group even without a [`QuadsOrigin`], which reads as a zero origin
+#[derive(Resource, Default)]
+pub(crate) struct OriginUniform(UniformBuffer<GpuOrigin>);
+
+impl OriginUniform {
+    pub(crate) fn buffer(&self) -> Option<&Buffer> {
+        self.0.buffer()
+    }
+}
+
+pub(crate) struct OriginPlugin;
+
+impl Plugin for OriginPlugin {
+    fn build(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<OriginUniform>()
+            .add_systems(ExtractSchedule, extract_origin)
+            .add_systems(Render, prepare_origin.in_set(RenderSet::Prepare));
+    }
+}
+
+/// Also removes the render world copy when the resource is removed, which resets the origin
+fn extract_origin(mut commands: Commands, origin: Extract<Option<Res<QuadsOrigin>>>) {
+    match origin.as_ref() {
+        Some(origin) if origin.is_changed() => commands.insert_resource(QuadsOrigin::clone(origin)),
+        Some(_) => {}
+        None => commands.remove_resource::<QuadsOrigin>(),
+    }
+}
+
+fn prepare_origin(
+    origin: Option<Res<QuadsOrigin>>,
+    mut had_origin: Local<bool>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut uniform: ResMut<OriginUniform>,
+) {
+    // NOTE: Unlike the near fade, the origin is read by every pipeline, so removing the resource
+    // has to reset it to zero
+    let changed = match &origin {
+        Some(origin) => origin.is_changed(),
+        None => *had_origin,
+    };
+    *had_origin = origin.is_some();
+    if !changed && uniform.0.buffer().is_some() {
+        return;
+    }
+    let translation = origin.map_or(DVec3::ZERO, |origin| origin.translation);
+    uniform.0.set(translation.into());
+    uniform.0.write_buffer(&render_device, &render_queue);
+}
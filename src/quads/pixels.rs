@@ -0,0 +1,71 @@
+use bevy::{prelude::*, render::render_resource::TextureFormat};
+
+use super::{Billboard, Quad, Quads};
+
+impl Quads {
+    /// Creates one quad per pixel of `image`, colored like the pixel, turning e.g. a sprite into
+    /// voxel-style art. The quads are laid out on a grid with `cell_size` between neighbouring
+    /// centers, centered on the origin in the XY plane with the first image row at the top. Every
+    /// quad has the given `half_extents`, fully transparent pixels are skipped.
+    ///
+    /// Returns `None` if the texture format of `image` is not one of the 8-bit RGBA or BGRA
+    /// formats or [`TextureFormat::Rgba32Float`].
+    pub fn from_image(image: &Image, cell_size: f32, half_extents: Vec3) -> Option<Quads> {
+        let size = image.texture_descriptor.size;
+        let (width, height) = (size.width as usize, size.height as usize);
+        let format = image.texture_descriptor.format;
+        let pixel_size = match format {
+            TextureFormat::Rgba8UnormSrgb
+            | TextureFormat::Rgba8Unorm
+            | TextureFormat::Bgra8UnormSrgb
+            | TextureFormat::Bgra8Unorm => 4,
+            TextureFormat::Rgba32Float => 16,
+            _ => return None,
+        };
+        let offset = 0.5 * Vec2::new(width as f32 - 1.0, height as f32 - 1.0);
+
+        let mut quads = Quads::default();
+        for (index, bytes) in image
+            .data
+            .chunks_exact(pixel_size)
+            .take(width * height)
+            .enumerate()
+        {
+            let color = decode_pixel(format, bytes);
+            if color.a() == 0.0 {
+                continue;
+            }
+            let x = (index % width) as f32 - offset.x;
+            let y = offset.y - (index / width) as f32;
+            quads.data.push(Quad {
+                color,
+                center: cell_size * Vec3::new(x, y, 0.0),
+                half_extents,
+                billboard: Billboard::None,
+                ..default()
+            });
+        }
+        Some(quads)
+    }
+}
+
+fn decode_pixel(format: TextureFormat, bytes: &[u8]) -> Color {
+    match format {
+        TextureFormat::Rgba8UnormSrgb => Color::rgba_u8(bytes[0], bytes[1], bytes[2], bytes[3]),
+        TextureFormat::Bgra8UnormSrgb => Color::rgba_u8(bytes[2], bytes[1], bytes[0], bytes[3]),
+        TextureFormat::Rgba8Unorm | TextureFormat::Bgra8Unorm => {
+            let [r, g, b, a] =
+                [bytes[0], bytes[1], bytes[2], bytes[3]].map(|byte| byte as f32 / 255.0);
+            let [r, b] = if format == TextureFormat::Bgra8Unorm {
+                [b, r]
+            } else {
+                [r, b]
+            };
+            Color::rgba_linear(r, g, b, a)
+        }
+        _ => {
+            let [r, g, b, a]: [f32; 4] = bytemuck::pod_read_unaligned(bytes);
+            Color::rgba_linear(r, g, b, a)
+        }
+    }
+}
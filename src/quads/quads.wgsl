@@ -0,0 +1,160 @@
+#import bevy_pbr::mesh_view_bindings globals
+#import bevy_pbr::utils PI
+#import bevy_vertex_pulling::quads::types VertexOutput, FragmentInput, FragmentOutput, QUAD_FLAG_BILLBOARD_FIXED_SCREEN_SIZE_BIT, QUAD_FLAG_BILLBOARD_VIEWPORT_FRACTION_BIT, QUAD_FLAG_CLIP_SPACE_BIT, QUAD_FLAG_FLIP_X_BIT, QUAD_FLAG_FLIP_Y_BIT, QUAD_FLAG_HIDDEN_BIT
+#import bevy_vertex_pulling::quads::bindings quads_texture, quads_sampler, near_fade, origin, flow_field_texture, flow_field_sampler, flow_field
+#import bevy_vertex_pulling::quads::unpack load_quad, unpack_hsv_shift, unpack_dissolve, unpack_mask, unpack_translucency
+#import bevy_vertex_pulling::quads::billboard billboard, camera_origin, position_to_view
+#import bevy_vertex_pulling::quads::shading finish_color, fragment_output, texture_uv, wrap_texture_uv, blend_detail, adjust_hsv
+
+// The default quads shader, composed from the bevy_vertex_pulling::quads modules. Custom fragment
+// shaders can import the same modules, see QuadsPlugin::fragment_shader.
+
+@vertex
+fn vertex(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+
+#ifdef QUAD_CORNERS
+    let instance_index = vertex_index / #{QUAD_CORNERS}u;
+#else
+    let instance_index = vertex_index >> 2u;
+#endif
+    var quad = load_quad(instance_index);
+    if ((quad.flags & QUAD_FLAG_HIDDEN_BIT) != 0u) {
+        // All corners at the same point outside the clip volume, so the quad covers no pixels
+        out.clip_position = vec4<f32>(0.0);
+        return out;
+    }
+    // The camera position is subtracted from the high part of the origin first, which is exact
+    // when both are close together no matter how far away from the world origin they are
+    let camera = camera_origin();
+    if ((quad.flags & QUAD_FLAG_CLIP_SPACE_BIT) == 0u) {
+#ifdef FLOW_FIELD
+        // The field is sampled at the center relative to the origin, the scroll offset is wrapped
+        // to keep its precision as the time grows
+        let field_position = quad.center * flow_field.scale + fract(flow_field.scroll * globals.time);
+        let displacement = textureSampleLevel(flow_field_texture, flow_field_sampler, field_position, 0.0).xyz;
+        quad.center += displacement * flow_field.strength;
+#endif
+        quad.center = (origin.high - camera) + origin.low + quad.center;
+    }
+#ifdef NEAR_FADE
+    if ((quad.flags & (QUAD_FLAG_BILLBOARD_FIXED_SCREEN_SIZE_BIT | QUAD_FLAG_BILLBOARD_VIEWPORT_FRACTION_BIT | QUAD_FLAG_CLIP_SPACE_BIT)) == 0u) {
+        // Shrink the quad around its center as the center approaches the camera
+        let view_distance = -position_to_view(quad.center).z;
+        let scale = smoothstep(near_fade.end, near_fade.start, view_distance);
+        quad.half_extents = vec4<f32>(quad.half_extents.xyz * scale, quad.half_extents.w);
+    }
+#endif
+
+#ifdef QUAD_CORNERS
+    // The corners of a regular polygon counter-clockwise around the quad center. Its edges touch
+    // the quad's edges, so it stays inside the quad and only cuts off its corners.
+    let corner_angle = 2.0 * PI / f32(#{QUAD_CORNERS}u);
+    let angle = (f32(vertex_index % #{QUAD_CORNERS}u) + 0.5) * corner_angle;
+    let corner = vec2<f32>(cos(angle), sin(angle)) / cos(0.5 * corner_angle);
+    let xyz = vec3<f32>(corner * 0.5 + 0.5, 0.5);
+#else
+    let xyz = vec3<f32>(f32(vertex_index & 0x1u), f32((vertex_index & 0x2u) >> 1u), 0.5);
+#endif
+    // NOTE: Texture v coordinates point down while the quad's y points up
+    out.uv = vec2<f32>(xyz.x, 1.0 - xyz.y);
+    // Mirrored quads have their half-extents made positive on upload, only the texture is flipped
+    if ((quad.flags & QUAD_FLAG_FLIP_X_BIT) != 0u) {
+        out.uv.x = 1.0 - out.uv.x;
+    }
+    if ((quad.flags & QUAD_FLAG_FLIP_Y_BIT) != 0u) {
+        out.uv.y = 1.0 - out.uv.y;
+    }
+    let relative_pos_unit = xyz * 2.0 - vec3<f32>(1.0);
+    // The corner's offset from the center in the units of the half-extents, spun around the center
+    // in the quad's plane. The angle is wrapped before the rotation to keep its precision as the
+    // time grows.
+    let spin = fract(quad.motion.x * globals.time / (2.0 * PI)) * 2.0 * PI;
+    let corner_offset = mat2x2<f32>(cos(spin), sin(spin), -sin(spin), cos(spin))
+        * (relative_pos_unit.xy * quad.half_extents.xy);
+    let placed = billboard(quad, corner_offset, camera);
+    out.clip_position = placed.clip_position;
+    out.world_position = placed.world_position;
+    out.world_normal = placed.world_normal;
+
+    out.color = quad.color;
+    out.flags = quad.flags;
+    out.uv_rotation = quad.half_extents.w;
+    // NOTE: The offset is wrapped here, as the texture repeats anyway, so it keeps its precision
+    // as the time grows
+    out.uv_transform = vec4<f32>(quad.uv.xy, fract(quad.uv.zw * globals.time));
+    out.specular = quad.specular;
+#ifdef DETAIL_TEXTURE
+    out.detail = quad.detail;
+#endif
+#ifdef DEBUG_INSTANCE_INDEX
+    out.instance_index = instance_index;
+#endif
+#ifdef HSV_ADJUST
+    out.hsv_shift = unpack_hsv_shift(quad);
+#endif
+#ifdef QUADS_MASK
+    out.mask = unpack_mask(quad);
+#endif
+#ifdef DISSOLVE
+    out.dissolve = unpack_dissolve(quad);
+#endif
+#ifdef TRANSLUCENCY
+    out.translucency = unpack_translucency(quad);
+#endif
+    return out;
+}
+
+// Width of the debug wireframe edges in pixels
+const DEBUG_WIREFRAME_WIDTH: f32 = 1.5;
+// Value each quad adds to the debug overdraw view
+const DEBUG_OVERDRAW_WEIGHT: f32 = 0.05;
+
+// Maps an integer to a bright, well distributed color
+fn hash_color(value: u32) -> vec3<f32> {
+    var x = value;
+    x = ((x >> 16u) ^ x) * 0x45d9f3bu;
+    x = ((x >> 16u) ^ x) * 0x45d9f3bu;
+    x = (x >> 16u) ^ x;
+    return 0.2 + 0.8 * vec3<f32>(f32(x & 0xffu), f32((x >> 8u) & 0xffu), f32((x >> 16u) & 0xffu)) / 255.0;
+}
+
+fn quad_color(in: FragmentInput) -> vec4<f32> {
+#ifdef DEBUG_WIREFRAME
+    // Only keep the fragments within a few pixels of the quad's border
+    let edge_width = DEBUG_WIREFRAME_WIDTH * fwidth(in.uv);
+    if all(in.uv > edge_width) && all(in.uv < 1.0 - edge_width) {
+        discard;
+    }
+    return vec4<f32>(in.color.rgb, 1.0);
+#else ifdef DEBUG_OVERDRAW
+    // Additively blended, so the brightness shows how many quads cover the pixel
+    return vec4<f32>(vec3<f32>(DEBUG_OVERDRAW_WEIGHT), 1.0);
+#else ifdef DEBUG_INSTANCE_INDEX
+    return vec4<f32>(hash_color(in.instance_index), 1.0);
+#else ifdef DEBUG_NORMAL
+    return vec4<f32>(normalize(in.world_normal) * 0.5 + 0.5, 1.0);
+#else ifdef DEBUG_UV
+    return vec4<f32>(saturate(wrap_texture_uv(in.flags, texture_uv(in))), 0.0, 1.0);
+#else
+    let uv = texture_uv(in);
+    var color = in.color * textureSampleGrad(quads_texture, quads_sampler, wrap_texture_uv(in.flags, uv), dpdx(uv), dpdy(uv));
+#ifdef DETAIL_TEXTURE
+    color = vec4<f32>(blend_detail(color.rgb, in.uv, in.detail), color.a);
+#endif
+#ifdef HSV_ADJUST
+    color = vec4<f32>(adjust_hsv(color.rgb, in.hsv_shift), color.a);
+#endif
+#ifdef HEATMAP
+    // Accumulate the quad's weight, the color ramp is applied when the heatmap is resolved
+    return vec4<f32>(color.a, 0.0, 0.0, 0.0);
+#else
+    return finish_color(in, color);
+#endif
+#endif
+}
+
+@fragment
+fn fragment(in: FragmentInput) -> FragmentOutput {
+    return fragment_output(in, quad_color(in));
+}
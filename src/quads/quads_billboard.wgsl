@@ -0,0 +1,201 @@
+#define_import_path bevy_vertex_pulling::quads::billboard
+
+#import bevy_pbr::mesh_view_bindings view
+#import bevy_vertex_pulling::quads::types Quad, QUAD_FLAG_BILLBOARD_BIT, QUAD_FLAG_BILLBOARD_WORLD_Y_BIT, QUAD_FLAG_BILLBOARD_FIXED_SCREEN_SIZE_BIT, QUAD_FLAG_BILLBOARD_VIEWPORT_FRACTION_BIT, QUAD_FLAG_CLIP_SPACE_BIT, QUAD_FLAG_FOREGROUND_BIT, QUAD_FLAG_BILLBOARD_MIN_SCREEN_SIZE_BIT, QUAD_FLAG_BILLBOARD_AXIS_BIT, QUAD_FLAG_BILLBOARD_COC_SCALED_BIT
+#import bevy_vertex_pulling::quads::bindings depth_of_field, DEPTH_OF_FIELD_MODE_GAUSSIAN
+
+// Foreground quads are drawn with their reverse-Z depth remapped into [1 - slice, 1]. Everything
+// else only reaches into that slice closer than near / (1 - slice) to the camera.
+const FOREGROUND_DEPTH_SLICE: f32 = 0.1;
+
+#ifdef CAMERA_RELATIVE
+// Positions in the vertex shader are relative to the camera, keeping them small close to it
+fn camera_origin() -> vec3<f32> {
+    return view.world_position;
+}
+
+fn position_to_view(position: vec3<f32>) -> vec3<f32> {
+    // The translation of the view transform is already applied, only rotate
+    return mat3x3<f32>(view.inverse_view[0].xyz, view.inverse_view[1].xyz, view.inverse_view[2].xyz) * position;
+}
+
+fn position_to_clip(position: vec3<f32>) -> vec4<f32> {
+    return view.projection * vec4<f32>(position_to_view(position), 1.0);
+}
+#else
+// Positions in the vertex shader are relative to the world origin
+fn camera_origin() -> vec3<f32> {
+    return vec3<f32>(0.0);
+}
+
+fn position_to_view(position: vec3<f32>) -> vec3<f32> {
+    return (view.inverse_view * vec4<f32>(position, 1.0)).xyz;
+}
+
+fn position_to_clip(position: vec3<f32>) -> vec4<f32> {
+    return view.view_proj * vec4<f32>(position, 1.0);
+}
+#endif
+
+// Moves the depth of a clip space position `offset` world units toward the camera without moving
+// it on screen. The third column of the projection is the change in clip space per unit of view
+// space z, which points toward the camera.
+fn offset_depth(clip_position: vec4<f32>, offset: f32) -> vec4<f32> {
+    let nudged = clip_position + view.projection[2] * offset;
+    return vec4<f32>(clip_position.xy, nudged.z / nudged.w * clip_position.w, clip_position.w);
+}
+
+#ifdef DEPTH_OF_FIELD
+// Pixels a CoC scaled quad grows by at `depth` in front of the camera. The circle of confusion on
+// the sensor follows the thin lens equation and is scaled to the viewport's physical pixels.
+fn blur_radius(depth: f32) -> f32 {
+    let focal_length = depth_of_field.focal_length;
+    let focal_distance = depth_of_field.focal_distance;
+    let sensor_coc = depth_of_field.aperture * focal_length * abs(depth - focal_distance)
+        / (depth * max(focal_distance - focal_length, 1e-6));
+    let coc = clamp(sensor_coc / depth_of_field.sensor_height * view.viewport.w, depth_of_field.min_circle_of_confusion, depth_of_field.max_circle_of_confusion);
+    // A bokeh disc covers the circle, a Gaussian with a standard deviation of a quarter of it is
+    // covered out to three standard deviations
+    if (depth_of_field.mode == DEPTH_OF_FIELD_MODE_GAUSSIAN) {
+        return 0.75 * coc;
+    }
+    return 0.5 * coc;
+}
+#endif
+
+// A corner of a quad placed in the view
+struct BillboardVertex {
+    clip_position: vec4<f32>,
+    world_position: vec4<f32>,
+    world_normal: vec3<f32>,
+}
+
+// Places the corner of a quad at `corner_offset` from its center according to its billboard mode.
+// The offset is in the units of the half-extents and the center relative to `camera`, the result
+// of camera_origin().
+fn billboard(quad: Quad, corner_offset: vec2<f32>, camera: vec3<f32>) -> BillboardVertex {
+    var out: BillboardVertex;
+    var relative_pos: vec3<f32>;
+
+    if ((quad.flags & QUAD_FLAG_CLIP_SPACE_BIT) != 0u) {
+        // The center and half-extents are already in normalized device coordinates, the view
+        // transform is bypassed entirely
+        out.clip_position = vec4<f32>(quad.center.xy + corner_offset, quad.center.z, 1.0);
+        // Only needed for lit quads, which are lit as if facing the camera
+        out.world_position = view.inverse_view_proj * out.clip_position;
+        out.world_position = out.world_position / out.world_position.w;
+        out.world_normal = normalize(view.view[2].xyz);
+    } else if ((quad.flags & QUAD_FLAG_BILLBOARD_BIT) != 0u) {
+        // View-right in world space is the 0th column of the view matrix
+        var right = normalize(view.view[0].xyz);
+        var up: vec3<f32>;
+        if ((quad.flags & QUAD_FLAG_BILLBOARD_AXIS_BIT) != 0u) {
+            // Cylindrical billboard around the quad's own up axis. The cross product of the axis
+            // and the direction to the camera is perpendicular to both, so it is the right
+            // direction of a quad that contains the axis and faces the camera as closely as it
+            // can. The normal completes the basis and is the direction to the camera with its part
+            // along the axis removed. Looking along the axis the cross product vanishes, and the
+            // view-right projected onto the plane perpendicular to the axis is used instead.
+            up = quad.motion.yzw;
+            let axis_right = cross(up, view.world_position - camera - quad.center);
+            if (dot(axis_right, axis_right) > 1e-12) {
+                right = normalize(axis_right);
+            } else {
+                right = normalize(right - up * dot(right, up));
+            }
+            out.world_normal = cross(right, up);
+        } else if ((quad.flags & QUAD_FLAG_BILLBOARD_WORLD_Y_BIT) != 0u) {
+            // Use world-space up and the view-right made horizontal, so the quad stays a rectangle
+            // while the camera rolls. A camera rolled on its side has a vertical view-right, then
+            // the quad turns toward the camera position instead.
+            up = vec3<f32>(0.0, 1.0, 0.0);
+            let to_camera = view.world_position - camera - quad.center;
+            let horizontal_right = vec3<f32>(right.x, 0.0, right.z);
+            if (dot(horizontal_right, horizontal_right) > 1e-12) {
+                right = normalize(horizontal_right);
+            } else {
+                right = normalize(cross(up, to_camera));
+            }
+            // The normal of the quad's plane, which has only x and z components and is defined
+            // even with the camera straight above the quad. Seen upside down or from steeply
+            // above, the quad would face away from the camera and be culled, so it is mirrored.
+            out.world_normal = cross(right, up);
+            if (dot(out.world_normal, to_camera) < 0.0) {
+                right = -right;
+                out.world_normal = -out.world_normal;
+            }
+        } else {
+            // The world-space normal points from the quad center to the camera, or along the view
+            // z axis for a quad centered on the camera
+            let to_camera = view.world_position - camera - quad.center;
+            out.world_normal = select(normalize(view.view[2].xyz), normalize(to_camera), dot(to_camera, to_camera) > 1e-12);
+            // View-up in world space is the 1st column of the view matrix
+            up = normalize(view.view[1].xyz);
+        }
+        var scale = 1.0;
+        if ((quad.flags & QUAD_FLAG_BILLBOARD_MIN_SCREEN_SIZE_BIT) != 0u && quad.half_extents.y > 0.0) {
+            // Pixels per world unit at the quad's depth. A world-space y offset moves the clip
+            // space y by projection[1][1] times as much, NDC divides that by w and spans 2 units
+            // across the viewport height. w is the view depth in perspective and 1 in orthographic
+            // projections. The quad is scaled up uniformly so its y half-extent is at least the
+            // minimum in half_extents.z.
+            let pixels_per_unit = 0.5 * view.viewport.w * view.projection[1][1] / position_to_clip(quad.center).w;
+            scale = max(1.0, quad.half_extents.z / (quad.half_extents.y * pixels_per_unit));
+        }
+        // Calculate the world-space offset in the right and up directions
+        relative_pos = (right * corner_offset.x + up * corner_offset.y) * scale;
+        // Apply the world-space offset and transform to clip space
+        out.clip_position = offset_depth(position_to_clip(quad.center + relative_pos), quad.detail.w);
+        out.world_position = vec4<f32>(quad.center + relative_pos + camera, 1.0);
+    } else if ((quad.flags & (QUAD_FLAG_BILLBOARD_FIXED_SCREEN_SIZE_BIT | QUAD_FLAG_BILLBOARD_VIEWPORT_FRACTION_BIT)) != 0u) {
+        // Transform the quad center position to clip space
+        out.clip_position = offset_depth(position_to_clip(quad.center), quad.detail.w);
+        var pixel_offset = corner_offset;
+#ifdef DEPTH_OF_FIELD
+        if ((quad.flags & QUAD_FLAG_BILLBOARD_COC_SCALED_BIT) != 0u && quad.half_extents.y > 0.0) {
+            // Scaled up uniformly until the y half-extent grew by the blur radius. w is the view
+            // depth in perspective projections.
+            pixel_offset *= 1.0 + blur_radius(out.clip_position.w) / quad.half_extents.y;
+        }
+#endif
+        // Clip to normalized device coordinate space
+        out.clip_position = out.clip_position / out.clip_position.w;
+
+        var ndc_offset: vec2<f32>;
+        if ((quad.flags & QUAD_FLAG_BILLBOARD_VIEWPORT_FRACTION_BIT) != 0u) {
+            // half_extents are fractions of the viewport height in this mode. NDC spans 2 units
+            // across the viewport and x is scaled by the inverse aspect ratio to keep it square.
+            ndc_offset = 2.0 * corner_offset * vec2<f32>(view.viewport.w / view.viewport.z, 1.0);
+        } else {
+            // half_extents are in screen pixels in this mode. NDC spans 2 units across the
+            // viewport.
+            ndc_offset = 2.0 * pixel_offset / view.viewport.zw;
+        }
+        out.clip_position.x = out.clip_position.x + ndc_offset.x;
+        out.clip_position.y = out.clip_position.y + ndc_offset.y;
+
+        // Transform back to world coordinates
+        out.world_position = view.inverse_view_proj * out.clip_position;
+        out.world_position = out.world_position / out.world_position.w;
+        // The world-space normal points from the quad center to the camera
+        out.world_normal = normalize(view.world_position - camera - quad.center);
+    } else {
+        // No billboarding so the world-space normal points along +z
+        out.world_normal = vec3<f32>(0.0, 0.0, 1.0);
+
+        // Calculate the world-space offset
+        relative_pos = vec3<f32>(corner_offset, 0.0);
+        // Apply the world-space offset and transform to clip space
+        out.clip_position = offset_depth(position_to_clip(quad.center + relative_pos), quad.detail.w);
+        out.world_position = vec4<f32>(quad.center + relative_pos + camera, 1.0);
+    }
+
+    if ((quad.flags & QUAD_FLAG_FOREGROUND_BIT) != 0u) {
+        // Remap the depth in clip space so it stays correct after the perspective divide and
+        // foreground quads keep sorting among themselves
+        out.clip_position.z = mix(out.clip_position.w, out.clip_position.z, FOREGROUND_DEPTH_SLICE);
+    }
+
+
+    return out;
+}
@@ -0,0 +1,118 @@
+#define_import_path bevy_vertex_pulling::quads::bindings
+
+#import bevy_vertex_pulling::quads::types Quads
+
+@group(1) @binding(0)
+var<storage> quads: Quads;
+@group(1) @binding(1)
+var quads_texture: texture_2d<f32>;
+@group(1) @binding(2)
+var quads_sampler: sampler;
+@group(1) @binding(3)
+var detail_texture: texture_2d<f32>;
+@group(1) @binding(4)
+var detail_sampler: sampler;
+
+struct NearFade {
+    start: f32,
+    end: f32,
+}
+
+@group(1) @binding(5)
+var<uniform> near_fade: NearFade;
+
+// The quads origin split into a single precision value and the remainder
+struct Origin {
+    high: vec3<f32>,
+    low: vec3<f32>,
+}
+
+@group(1) @binding(6)
+var<uniform> origin: Origin;
+
+// Colors replacing the color of every quad in a group, entries with a negative alpha keep the
+// quads' own colors. Group 0 is never recolored.
+struct GroupColors {
+    colors: array<vec4<f32>, 256>,
+}
+
+@group(1) @binding(7)
+var<uniform> group_colors: GroupColors;
+
+@group(1) @binding(8)
+var flow_field_texture: texture_3d<f32>;
+@group(1) @binding(9)
+var flow_field_sampler: sampler;
+
+struct FlowField {
+    scroll: vec3<f32>,
+    strength: f32,
+    scale: f32,
+}
+
+@group(1) @binding(10)
+var<uniform> flow_field: FlowField;
+
+// NOTE: Must match QUAD_CURVE_MAX_KEYS and the QuadsAnimationMode discriminants in animation.rs
+const QUAD_CURVE_MAX_KEYS: u32 = 8u;
+const ANIMATION_MODE_LOOP: u32 = 0u;
+const ANIMATION_MODE_PING_PONG: u32 = 1u;
+
+struct BatchAnimation {
+    // xy are the time and value of every key, the scale, alpha, hue shift and dissolve tracks are
+    // QUAD_CURVE_MAX_KEYS keys apart
+    keys: array<vec4<f32>, 32>,
+    // Number of keys of every track, 0 for tracks that are not animated
+    key_counts: vec4<u32>,
+    mode: u32,
+    start_time: f32,
+    duration: f32,
+    premultiplied: u32,
+}
+
+@group(1) @binding(11)
+var<uniform> batch_animation: BatchAnimation;
+
+// Multiplies the alpha of every quad, see QuadsGlobalAlpha
+struct GlobalAlpha {
+    alpha: f32,
+}
+
+@group(1) @binding(12)
+var<uniform> global_alpha: GlobalAlpha;
+
+@group(1) @binding(13)
+var dissolve_noise_texture: texture_2d<f32>;
+@group(1) @binding(14)
+var dissolve_sampler: sampler;
+
+// See QuadsDissolve
+struct Dissolve {
+    edge_color: vec4<f32>,
+    edge_width: f32,
+    noise_scale: f32,
+}
+
+@group(1) @binding(15)
+var<uniform> dissolve: Dissolve;
+
+// NOTE: Must match the DEPTH_OF_FIELD_MODE constants in depth_of_field.rs
+const DEPTH_OF_FIELD_MODE_BOKEH: u32 = 0u;
+const DEPTH_OF_FIELD_MODE_GAUSSIAN: u32 = 1u;
+
+// The lens of the view, see QuadsDepthOfField. The aperture is its diameter in meters.
+struct DepthOfField {
+    focal_distance: f32,
+    focal_length: f32,
+    aperture: f32,
+    sensor_height: f32,
+    min_circle_of_confusion: f32,
+    max_circle_of_confusion: f32,
+    mode: u32,
+}
+
+#ifdef DEPTH_OF_FIELD
+// Only bound in pipelines specialized for views with a depth of field
+@group(2) @binding(0)
+var<uniform> depth_of_field: DepthOfField;
+#endif
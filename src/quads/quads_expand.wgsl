@@ -0,0 +1,31 @@
+#import bevy_vertex_pulling::quads::types Quad
+
+struct QuadPoint {
+    position: vec3<f32>,
+    kind: u32,
+}
+
+@group(0) @binding(0)
+var<storage> points: array<QuadPoint>;
+@group(0) @binding(1)
+var<storage> kinds: array<Quad>;
+@group(0) @binding(2)
+var<storage, read_write> quads: array<Quad>;
+
+// NOTE: The workgroup size must match WORKGROUP_SIZE in generate.rs
+@compute @workgroup_size(64)
+fn expand(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // Large point counts are dispatched as multiple rows of workgroups
+    let index = global_id.x + global_id.y * num_workgroups.x * 64u;
+    if (index >= arrayLength(&points)) {
+        return;
+    }
+    let point = points[index];
+    var quad = kinds[min(point.kind, arrayLength(&kinds) - 1u)];
+    // The kind's center is an offset from the point position
+    quad.center = quad.center + point.position;
+    quads[index] = quad;
+}
@@ -0,0 +1,247 @@
+#define_import_path bevy_vertex_pulling::quads::shading
+
+#import bevy_pbr::mesh_view_bindings view, lights, point_lights, globals
+#import bevy_pbr::mesh_view_types POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE
+#import bevy_pbr::clustered_forward fragment_cluster_index, unpack_offset_and_counts, get_light_id
+#import bevy_pbr::lighting getDistanceAttenuation
+#import bevy_pbr::utils PI
+#import bevy_core_pipeline::tonemapping screen_space_dither, powsafe, tone_mapping
+#import bevy_vertex_pulling::quads::types FragmentInput, FragmentOutput, QUAD_FLAG_LIT_BIT, QUAD_FLAG_UV_WRAP_BIT
+#import bevy_vertex_pulling::quads::bindings detail_texture, detail_sampler, global_alpha, dissolve_noise_texture, dissolve_sampler, dissolve
+
+#ifdef HSV_ADJUST
+fn rgb_to_hsv(rgb: vec3<f32>) -> vec3<f32> {
+    let max_channel = max(rgb.r, max(rgb.g, rgb.b));
+    let min_channel = min(rgb.r, min(rgb.g, rgb.b));
+    let chroma = max_channel - min_channel;
+    var hue = 0.0;
+    if chroma > 0.0 {
+        if max_channel == rgb.r {
+            hue = (rgb.g - rgb.b) / chroma;
+        } else if max_channel == rgb.g {
+            hue = (rgb.b - rgb.r) / chroma + 2.0;
+        } else {
+            hue = (rgb.r - rgb.g) / chroma + 4.0;
+        }
+    }
+    var saturation = 0.0;
+    if max_channel > 0.0 {
+        saturation = chroma / max_channel;
+    }
+    // The hue is in turns, red hues below zero wrap around to just under 1
+    return vec3<f32>(fract(hue / 6.0), saturation, max_channel);
+}
+
+fn hsv_to_rgb(hsv: vec3<f32>) -> vec3<f32> {
+    let k = fract(hsv.x + vec3<f32>(1.0, 2.0 / 3.0, 1.0 / 3.0)) * 6.0;
+    return hsv.z * mix(vec3<f32>(1.0), saturate(abs(k - 3.0) - 1.0), hsv.y);
+}
+
+// Shifts the hue in turns, wrapping around, and offsets the saturation and value
+fn adjust_hsv(rgb: vec3<f32>, shift: vec3<f32>) -> vec3<f32> {
+    let hsv = rgb_to_hsv(rgb);
+    return hsv_to_rgb(vec3<f32>(fract(hsv.x + shift.x), saturate(hsv.y + shift.y), max(hsv.z + shift.z, 0.0)));
+}
+#endif
+
+// Lambertian diffuse plus diffuse plus an optional Blinn-Phong highlight for a light arriving from direction L
+// with the given radiance. specular.rgb is the highlight color and specular.a the power, zero
+// disables the highlight. With TRANSLUCENCY, the translucency is the fraction of the light
+// arriving at the back that shines through the quad.
+fn blinn_phong(N: vec3<f32>, V: vec3<f32>, L: vec3<f32>, radiance: vec3<f32>, albedo: vec3<f32>, specular: vec4<f32>, translucency: f32) -> vec3<f32> {
+    let NoL = saturate(dot(N, L));
+    var response = albedo * (NoL / PI);
+    if (specular.a > 0.0) {
+        let H = normalize(L + V);
+        response += specular.rgb * (pow(saturate(dot(N, H)), specular.a) * NoL);
+    }
+#ifdef TRANSLUCENCY
+    // Thin surfaces like leaves scatter the light passing through them diffusely to the front
+    response += albedo * (translucency * saturate(-dot(N, L)) / PI);
+#endif
+    return radiance * response;
+}
+
+// Response to a point or spot light from the clustered light list
+fn point_light(world_position: vec3<f32>, light_id: u32, N: vec3<f32>, V: vec3<f32>, albedo: vec3<f32>, specular: vec4<f32>, translucency: f32) -> vec3<f32> {
+    let light = &point_lights.data[light_id];
+    let light_to_frag = (*light).position_radius.xyz - world_position;
+    let distance_square = dot(light_to_frag, light_to_frag);
+    let range_attenuation = getDistanceAttenuation(distance_square, (*light).color_inverse_square_range.w);
+    let radiance = (*light).color_inverse_square_range.rgb * range_attenuation;
+    return blinn_phong(N, V, normalize(light_to_frag), radiance, albedo, specular, translucency);
+}
+
+fn spot_light(world_position: vec3<f32>, light_id: u32, N: vec3<f32>, V: vec3<f32>, albedo: vec3<f32>, specular: vec4<f32>, translucency: f32) -> vec3<f32> {
+    let point_light = point_light(world_position, light_id, N, V, albedo, specular, translucency);
+
+    let light = &point_lights.data[light_id];
+    // Reconstruct the spot direction from x/z and the y-direction flag
+    var spot_dir = vec3<f32>((*light).light_custom_data.x, 0.0, (*light).light_custom_data.y);
+    spot_dir.y = sqrt(max(0.0, 1.0 - spot_dir.x * spot_dir.x - spot_dir.z * spot_dir.z));
+    if ((*light).flags & POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE) != 0u {
+        spot_dir.y = -spot_dir.y;
+    }
+    let light_to_frag = (*light).position_radius.xyz - world_position;
+    // spot_scale and spot_offset are precomputed in light_custom_data.zw
+    let cd = dot(-spot_dir, normalize(light_to_frag));
+    let attenuation = saturate(cd * (*light).light_custom_data.z + (*light).light_custom_data.w);
+    return point_light * attenuation * attenuation;
+}
+
+fn shade(in: FragmentInput, albedo: vec3<f32>) -> vec3<f32> {
+#ifdef DOUBLE_SIDED
+    // Seen from behind, a quad is lit as if it faced the other way
+    let N = select(-1.0, 1.0, in.is_front) * normalize(in.world_normal);
+#else
+    let N = normalize(in.world_normal);
+#endif
+    let V = normalize(view.world_position - in.world_position.xyz);
+#ifdef TRANSLUCENCY
+    let translucency = in.translucency;
+#else
+    let translucency = 0.0;
+#endif
+    var light = lights.ambient_color.rgb * albedo;
+
+    for (var i: u32 = 0u; i < lights.n_directional_lights; i = i + 1u) {
+        let directional_light = &lights.directional_lights[i];
+        light += blinn_phong(N, V, (*directional_light).direction_to_light, (*directional_light).color.rgb, albedo, in.specular, translucency);
+    }
+
+    let view_z = dot(vec4<f32>(
+        view.inverse_view[0].z,
+        view.inverse_view[1].z,
+        view.inverse_view[2].z,
+        view.inverse_view[3].z
+    ), in.world_position);
+    let is_orthographic = view.projection[3].w == 1.0;
+#ifdef HALF_RESOLUTION
+    // The clusters are laid out over the full resolution view
+    let frag_coord = in.frag_coord.xy * 2.0;
+#else
+    let frag_coord = in.frag_coord.xy;
+#endif
+    let cluster_index = fragment_cluster_index(frag_coord, view_z, is_orthographic);
+    // x is the offset into the light index list, y the point light count, z the spot light count
+    let offset_and_counts = unpack_offset_and_counts(cluster_index);
+    for (var i: u32 = offset_and_counts[0]; i < offset_and_counts[0] + offset_and_counts[1]; i = i + 1u) {
+        light += point_light(in.world_position.xyz, get_light_id(i), N, V, albedo, in.specular, translucency);
+    }
+    let spot_start = offset_and_counts[0] + offset_and_counts[1];
+    for (var i: u32 = spot_start; i < spot_start + offset_and_counts[2]; i = i + 1u) {
+        light += spot_light(in.world_position.xyz, get_light_id(i), N, V, albedo, in.specular, translucency);
+    }
+
+    return light;
+}
+
+// Rotate the uv coordinates around the quad center
+fn rotate_uv(uv: vec2<f32>, rotation: f32) -> vec2<f32> {
+    let c = cos(rotation);
+    let s = sin(rotation);
+    return mat2x2<f32>(c, s, -s, c) * (uv - 0.5) + 0.5;
+}
+
+// Texture coordinates after the quad's rotation, tiling and scrolling
+fn texture_uv(in: FragmentInput) -> vec2<f32> {
+    return rotate_uv(in.uv, in.uv_rotation) * in.uv_transform.xy + in.uv_transform.zw;
+}
+
+// NOTE: Tiled and scrolling coordinates are wrapped here like the detail texture's, other quads
+// keep their coordinates so rotated textures still clamp at the edges
+fn wrap_texture_uv(flags: u32, uv: vec2<f32>) -> vec2<f32> {
+    return select(uv, fract(uv), (flags & QUAD_FLAG_UV_WRAP_BIT) != 0u);
+}
+
+#ifdef DETAIL_TEXTURE
+// Blends the scrolling detail texture over the base color by the quad's detail weight
+fn blend_detail(base: vec3<f32>, uv: vec2<f32>, detail: vec4<f32>) -> vec3<f32> {
+    let detail_uv = uv + detail.xy * globals.time;
+    // NOTE: The coordinates are wrapped here so the detail image's sampler does not need to
+    // repeat. The gradients of the unwrapped coordinates avoid mip seams at the wrap.
+    let sample = textureSampleGrad(detail_texture, detail_sampler, fract(detail_uv), dpdx(detail_uv), dpdy(detail_uv)).rgb;
+#ifdef DETAIL_OVERLAY
+    let blended = select(
+        1.0 - 2.0 * (1.0 - base) * (1.0 - sample),
+        2.0 * base * sample,
+        base < vec3<f32>(0.5)
+    );
+#else
+    let blended = base * sample;
+#endif
+    return mix(base, blended, detail.z);
+}
+#endif
+
+#ifdef DISSOLVE
+// Discards the fragment where the noise is below the quad's dissolve threshold and returns how
+// far into the edge band above the threshold it is, from 1 at the threshold to 0 past the band
+fn dissolve_fragment(in: FragmentInput) -> f32 {
+    let threshold = in.dissolve.x;
+    // NOTE: Sampled without mips, which would blur the noise toward grey and leave distant quads
+    // without a pattern to dissolve along
+    let noise = textureSampleLevel(dissolve_noise_texture, dissolve_sampler, in.uv * dissolve.noise_scale + in.dissolve.yz, 0.0).r;
+    // The threshold is stretched over the edge band, so the edge appears from a threshold of 0
+    // on and has burnt away at 1
+    let cut = threshold * (1.0 + dissolve.edge_width) - dissolve.edge_width;
+    if noise < cut || threshold >= 1.0 {
+        discard;
+    }
+    if dissolve.edge_width <= 0.0 {
+        return 0.0;
+    }
+    return 1.0 - saturate((noise - cut) / dissolve.edge_width);
+}
+#endif
+
+// Dissolves the quad, lights its color if it is lit, applies the in-shader tonemapping,
+// premultiplies blended straight alpha quads, applies the QuadsGlobalAlpha, and marks the texels
+// of half resolution batches as covered
+fn finish_color(in: FragmentInput, color: vec4<f32>) -> vec4<f32> {
+#ifdef DISSOLVE
+    let dissolve_edge = dissolve_fragment(in);
+#endif
+    var output_color = color;
+    if ((in.flags & QUAD_FLAG_LIT_BIT) != 0u) {
+        output_color = vec4<f32>(shade(in, color.rgb), color.a);
+    }
+#ifdef DISSOLVE
+    // The edge glows, so it is not lit
+    output_color = vec4<f32>(mix(output_color.rgb, dissolve.edge_color.rgb, dissolve_edge), output_color.a);
+#endif
+#ifdef TONEMAP_IN_SHADER
+    output_color = tone_mapping(output_color, view.color_grading);
+#ifdef DEBAND_DITHER
+    var output_rgb = output_color.rgb;
+    output_rgb = powsafe(output_rgb, 1.0 / 2.2);
+    output_rgb = output_rgb + screen_space_dither(in.frag_coord.xy);
+    // The output texture format is sRGB so convert back to linear space
+    output_rgb = powsafe(output_rgb, 2.2);
+    output_color = vec4<f32>(output_rgb, output_color.a);
+#endif
+#endif
+#ifdef PREMULTIPLY_OUTPUT
+    // Straight alpha quads with a blend constant are blended as premultiplied, see
+    // Quads::blend_constant. The color is premultiplied after the image was filtered, which
+    // blends it like straight alpha.
+    output_color = vec4<f32>(output_color.rgb * output_color.a, output_color.a);
+#endif
+    output_color.a *= global_alpha.alpha;
+#ifdef HALF_RESOLUTION
+    // The alpha of the half resolution target marks the texels covered by quads for the
+    // composite, see half_res.wgsl
+    output_color.a = 1.0;
+#endif
+    return output_color;
+}
+
+// The output of the fragment shader, including the quad's mask while a QuadsMask is written
+fn fragment_output(in: FragmentInput, color: vec4<f32>) -> FragmentOutput {
+    var out: FragmentOutput;
+    out.color = color;
+#ifdef QUADS_MASK
+    out.mask = in.mask;
+#endif
+    return out;
+}
@@ -0,0 +1,117 @@
+#define_import_path bevy_vertex_pulling::quads::types
+
+// NOTE: Must match the GpuQuad layout in mod.rs
+struct Quad {
+    center: vec3<f32>,
+    flags: u32,
+    // xyz are the half-extents, w is the uv rotation in radians
+    half_extents: vec4<f32>,
+    color: vec4<f32>,
+    // rgb is the specular color, a the Blinn-Phong power
+    specular: vec4<f32>,
+    // xy is the detail texture scroll speed, z the detail weight, w the depth offset
+    detail: vec4<f32>,
+    // xy is the texture tiling, zw the texture scroll speed
+    uv: vec4<f32>,
+    // x is the spin in radians per second, yzw the up axis of OrientedY quads
+    motion: vec4<f32>,
+    // xy are pairs of half floats, the hue shift in turns and the saturation shift in x and the
+    // value shift and the scale minus 1 in y, z is the mask in the low byte and the translucency in
+    // the next, and w the dissolve threshold as a half float in the low half and the noise offset
+    // in the high half
+    adjust: vec4<u32>,
+}
+
+struct Quads {
+    data: array<Quad>,
+}
+
+const QUAD_FLAG_BILLBOARD_BIT: u32 = 1u;
+const QUAD_FLAG_BILLBOARD_WORLD_Y_BIT: u32 = 2u;
+const QUAD_FLAG_BILLBOARD_FIXED_SCREEN_SIZE_BIT: u32 = 4u;
+const QUAD_FLAG_LIT_BIT: u32 = 8u;
+const QUAD_FLAG_BILLBOARD_VIEWPORT_FRACTION_BIT: u32 = 16u;
+const QUAD_FLAG_FLIP_X_BIT: u32 = 32u;
+const QUAD_FLAG_FLIP_Y_BIT: u32 = 64u;
+const QUAD_FLAG_CLIP_SPACE_BIT: u32 = 128u;
+const QUAD_FLAG_FOREGROUND_BIT: u32 = 256u;
+const QUAD_FLAG_UV_WRAP_BIT: u32 = 512u;
+const QUAD_FLAG_BILLBOARD_MIN_SCREEN_SIZE_BIT: u32 = 1024u;
+const QUAD_FLAG_BILLBOARD_AXIS_BIT: u32 = 2048u;
+const QUAD_FLAG_HIDDEN_BIT: u32 = 4096u;
+const QUAD_FLAG_BILLBOARD_COC_SCALED_BIT: u32 = 8192u;
+// The quad's group is stored in the top byte of the flags
+const QUAD_GROUP_SHIFT: u32 = 24u;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+    @location(3) color: vec4<f32>,
+    @location(4) @interpolate(flat) flags: u32,
+    @location(5) @interpolate(flat) uv_rotation: f32,
+    @location(6) @interpolate(flat) specular: vec4<f32>,
+#ifdef DETAIL_TEXTURE
+    @location(7) @interpolate(flat) detail: vec4<f32>,
+#endif
+#ifdef DEBUG_INSTANCE_INDEX
+    @location(8) @interpolate(flat) instance_index: u32,
+#endif
+    // xy is the texture tiling, zw the wrapped texture scroll offset
+    @location(9) @interpolate(flat) uv_transform: vec4<f32>,
+#ifdef HSV_ADJUST
+    // The hue shift in turns and the saturation and value shifts
+    @location(10) @interpolate(flat) hsv_shift: vec3<f32>,
+#endif
+#ifdef QUADS_MASK
+    @location(11) @interpolate(flat) mask: u32,
+#endif
+#ifdef DISSOLVE
+    // x is the dissolve threshold, yz the offset of the noise
+    @location(12) @interpolate(flat) dissolve: vec3<f32>,
+#endif
+#ifdef TRANSLUCENCY
+    // Fraction of the light arriving at the back of the quad that shines through it
+    @location(13) @interpolate(flat) translucency: f32,
+#endif
+};
+
+struct FragmentInput {
+    @builtin(front_facing) is_front: bool,
+    @builtin(position) frag_coord: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+    @location(3) color: vec4<f32>,
+    @location(4) @interpolate(flat) flags: u32,
+    @location(5) @interpolate(flat) uv_rotation: f32,
+    @location(6) @interpolate(flat) specular: vec4<f32>,
+#ifdef DETAIL_TEXTURE
+    @location(7) @interpolate(flat) detail: vec4<f32>,
+#endif
+#ifdef DEBUG_INSTANCE_INDEX
+    @location(8) @interpolate(flat) instance_index: u32,
+#endif
+    @location(9) @interpolate(flat) uv_transform: vec4<f32>,
+#ifdef HSV_ADJUST
+    @location(10) @interpolate(flat) hsv_shift: vec3<f32>,
+#endif
+#ifdef QUADS_MASK
+    @location(11) @interpolate(flat) mask: u32,
+#endif
+#ifdef DISSOLVE
+    @location(12) @interpolate(flat) dissolve: vec3<f32>,
+#endif
+#ifdef TRANSLUCENCY
+    @location(13) @interpolate(flat) translucency: f32,
+#endif
+};
+
+struct FragmentOutput {
+    @location(0) color: vec4<f32>,
+#ifdef QUADS_MASK
+    // See QuadsMaskTexture
+    @location(1) mask: u32,
+#endif
+}
@@ -0,0 +1,101 @@
+#define_import_path bevy_vertex_pulling::quads::unpack
+
+#import bevy_pbr::mesh_view_bindings globals
+#import bevy_vertex_pulling::quads::types Quad, QUAD_GROUP_SHIFT
+#import bevy_vertex_pulling::quads::bindings quads, group_colors, batch_animation, QUAD_CURVE_MAX_KEYS, ANIMATION_MODE_LOOP, ANIMATION_MODE_PING_PONG
+
+// The scale is stored minus 1, so zeroed instance data is unscaled
+fn unpack_scale(quad: Quad) -> f32 {
+    return unpack2x16float(quad.adjust.y).y + 1.0;
+}
+
+// The hue shift in turns and the saturation and value shifts
+fn unpack_hsv_shift(quad: Quad) -> vec3<f32> {
+    return vec3<f32>(unpack2x16float(quad.adjust.x), unpack2x16float(quad.adjust.y).x);
+}
+
+// The dissolve threshold and the offset of the dissolve noise
+fn unpack_dissolve(quad: Quad) -> vec3<f32> {
+    let offset = vec2<f32>(f32((quad.adjust.w >> 16u) & 0xffu), f32(quad.adjust.w >> 24u)) / 256.0;
+    return vec3<f32>(unpack2x16float(quad.adjust.w).x, offset);
+}
+
+fn unpack_mask(quad: Quad) -> u32 {
+    return quad.adjust.z & 0xffu;
+}
+
+fn unpack_translucency(quad: Quad) -> f32 {
+    return f32((quad.adjust.z >> 8u) & 0xffu) / 255.0;
+}
+
+fn unpack_group(quad: Quad) -> u32 {
+    return quad.flags >> QUAD_GROUP_SHIFT;
+}
+
+// Time since the start of the batch animation, wrapped according to its mode
+fn batch_animation_time() -> f32 {
+    let time = max(globals.time - batch_animation.start_time, 0.0);
+    let duration = batch_animation.duration;
+    if duration <= 0.0 {
+        return 0.0;
+    }
+    if batch_animation.mode == ANIMATION_MODE_LOOP {
+        return time % duration;
+    }
+    if batch_animation.mode == ANIMATION_MODE_PING_PONG {
+        return duration - abs(time % (2.0 * duration) - duration);
+    }
+    return min(time, duration);
+}
+
+// Linearly interpolates between the keys of a track, holding the first and last values outside of
+// them. Tracks without keys are `default_value`.
+fn sample_batch_animation(track: u32, time: f32, default_value: f32) -> f32 {
+    let count = batch_animation.key_counts[track];
+    if count == 0u {
+        return default_value;
+    }
+    let first = track * QUAD_CURVE_MAX_KEYS;
+    var previous = batch_animation.keys[first].xy;
+    if time <= previous.x {
+        return previous.y;
+    }
+    for (var i = 1u; i < count; i += 1u) {
+        let key = batch_animation.keys[first + i].xy;
+        if time <= key.x {
+            return mix(previous.y, key.y, (time - previous.x) / (key.x - previous.x));
+        }
+        previous = key;
+    }
+    return previous.y;
+}
+
+// Rotates the color around the grey axis by `angle` radians
+fn hue_shift(color: vec3<f32>, angle: f32) -> vec3<f32> {
+    let axis = vec3<f32>(0.57735);
+    let c = cos(angle);
+    return color * c + cross(axis, color) * sin(angle) + axis * dot(axis, color) * (1.0 - c);
+}
+
+// The quad at `instance_index` with its scale, group color and the batch animation applied
+fn load_quad(instance_index: u32) -> Quad {
+    var quad = quads.data[instance_index];
+    quad.half_extents = vec4<f32>(quad.half_extents.xyz * unpack_scale(quad), quad.half_extents.w);
+    let group = unpack_group(quad);
+    if (group != 0u && group_colors.colors[group].a >= 0.0) {
+        quad.color = group_colors.colors[group];
+    }
+#ifdef BATCH_ANIMATION
+    let animation_time = batch_animation_time();
+    quad.half_extents = vec4<f32>(quad.half_extents.xyz * sample_batch_animation(0u, animation_time, 1.0), quad.half_extents.w);
+    let alpha = sample_batch_animation(1u, animation_time, 1.0);
+    var rgb = hue_shift(quad.color.rgb, sample_batch_animation(2u, animation_time, 0.0));
+    if batch_animation.premultiplied != 0u {
+        rgb *= alpha;
+    }
+    quad.color = vec4<f32>(rgb, quad.color.a * alpha);
+    let dissolve = saturate(unpack_dissolve(quad).x + sample_batch_animation(3u, animation_time, 0.0));
+    quad.adjust.w = (quad.adjust.w & 0xffff0000u) | (pack2x16float(vec2<f32>(dissolve, 0.0)) & 0xffffu);
+#endif
+    return quad;
+}
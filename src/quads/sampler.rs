@@ -0,0 +1,104 @@
+//! Per-batch samplers for the quads texture, e.g. nearest filtering for pixel art in one batch and
+//! anisotropic filtering for ground decals in another, both sampling the same image.
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{AddressMode, FilterMode, Sampler, SamplerDescriptor},
+        renderer::RenderDevice,
+    },
+    utils::HashMap,
+};
+
+/// Sampler for the texture of one batch of quads, see [`Quads::sampler`](super::Quads::sampler)
+/// and [`QuadsPoints::sampler`](super::QuadsPoints::sampler). Overrides the sampler of the image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct QuadsSamplerDesc {
+    pub mag_filter: FilterMode,
+    pub min_filter: FilterMode,
+    pub mipmap_filter: FilterMode,
+    pub address_mode_u: AddressMode,
+    pub address_mode_v: AddressMode,
+    /// Maximum anisotropy from 1 to 16, 1 disables anisotropic filtering. Only used when all
+    /// filters are linear.
+    pub anisotropy_clamp: u16,
+}
+
+impl Default for QuadsSamplerDesc {
+    /// Linear filtering and clamped texture coordinates
+    fn default() -> Self {
+        Self {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Linear,
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            anisotropy_clamp: 1,
+        }
+    }
+}
+
+impl QuadsSamplerDesc {
+    /// Nearest filtering, keeping the texels of pixel art sharp
+    pub fn nearest() -> Self {
+        Self {
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Nearest,
+            mipmap_filter: FilterMode::Nearest,
+            ..default()
+        }
+    }
+
+    /// Linear filtering with up to `anisotropy_clamp` samples along the direction the texture is
+    /// stretched in, for quads seen at grazing angles
+    pub fn anisotropic(anisotropy_clamp: u16) -> Self {
+        Self {
+            anisotropy_clamp,
+            ..default()
+        }
+    }
+
+    pub fn with_address_mode(mut self, address_mode: AddressMode) -> Self {
+        self.address_mode_u = address_mode;
+        self.address_mode_v = address_mode;
+        self
+    }
+
+    fn descriptor(&self) -> SamplerDescriptor<'static> {
+        let linear = self.mag_filter == FilterMode::Linear
+            && self.min_filter == FilterMode::Linear
+            && self.mipmap_filter == FilterMode::Linear;
+        SamplerDescriptor {
+            label: Some("quads_sampler"),
+            address_mode_u: self.address_mode_u,
+            address_mode_v: self.address_mode_v,
+            mag_filter: self.mag_filter,
+            min_filter: self.min_filter,
+            mipmap_filter: self.mipmap_filter,
+            // NOTE: wgpu rejects anisotropic samplers unless every filter is linear
+            anisotropy_clamp: if linear {
+                self.anisotropy_clamp.clamp(1, 16)
+            } else {
+                1
+            },
+            ..default()
+        }
+    }
+}
+
+/// Samplers created for [`QuadsSamplerDesc`]s, shared by every batch using the same descriptor
+#[derive(Resource, Default)]
+pub(crate) struct QuadSamplers(HashMap<QuadsSamplerDesc, Sampler>);
+
+impl QuadSamplers {
+    pub(crate) fn get_or_create(
+        &mut self,
+        render_device: &RenderDevice,
+        desc: &QuadsSamplerDesc,
+    ) -> Sampler {
+        self.0
+            .entry(*desc)
+            .or_insert_with(|| render_device.create_sampler(&desc.descriptor()))
+            .clone()
+    }
+}
@@ -0,0 +1,358 @@
+//! Scrubbing non-finite values from the instance data before it is uploaded, see
+//! [`QuadsPlugin::sanitize`].
+//!
+//! The render world checks the quads while it converts them into instance data and shares what
+//! it found with the main world, which records it as the [`QuadsPlugin::INVALID_QUADS`]
+//! diagnostic and logs the indices of the first invalid quads.
+
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    diagnostic::{Diagnostic, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+    render::RenderApp,
+};
+
+use super::{GpuQuad, QuadsPlugin};
+
+/// Largest finite half float, which HDR render targets can still hold
+const HALF_MAX: f32 = 65504.0;
+/// Number of invalid quads whose indices are logged
+const LOGGED_INDICES: usize = 8;
+
+/// Whether both half floats packed into `packed` are finite
+fn finite_halves(packed: u32) -> bool {
+    packed & 0x7c00 != 0x7c00 && packed & 0x7c00_0000 != 0x7c00_0000
+}
+
+/// Replaces the non-finite half floats packed into `packed` with 0
+fn scrub_halves(packed: u32) -> u32 {
+    let low = if packed & 0x7c00 == 0x7c00 {
+        0
+    } else {
+        packed & 0xffff
+    };
+    let high = if packed & 0x7c00_0000 == 0x7c00_0000 {
+        0
+    } else {
+        packed & 0xffff_0000
+    };
+    high | low
+}
+
+fn scrub(value: Vec4) -> Vec4 {
+    // NOTE: Comparisons with NaN are false
+    Vec4::select(
+        value.abs().cmplt(Vec4::splat(f32::INFINITY)),
+        value,
+        Vec4::ZERO,
+    )
+}
+
+impl GpuQuad {
+    /// Whether all values of the quad are finite, including its half floats
+    pub(crate) fn is_finite(&self) -> bool {
+        self.center.is_finite()
+            && self.half_extents.is_finite()
+            && Vec4::from(self.color).is_finite()
+            && self.specular.is_finite()
+            && self.detail.is_finite()
+            && self.uv.is_finite()
+            && self.motion.is_finite()
+            && finite_halves(self.adjust.x)
+            && finite_halves(self.adjust.y)
+            // NOTE: Only the low half of w is a half float, the high half is the noise offset
+            && finite_halves(self.adjust.w & 0xffff)
+    }
+
+    /// Hides quads without a finite center or half-extents and replaces the other non-finite
+    /// values. Infinite colors become the largest half float and every other non-finite value
+    /// becomes 0. Returns whether the quad was hidden.
+    pub(crate) fn sanitize(&mut self) -> bool {
+        if !self.center.is_finite() || !self.half_extents.truncate().is_finite() {
+            // NOTE: Hidden quads are skipped by the vertex shader before it reads anything else
+            *self = GpuQuad::hidden();
+            return true;
+        }
+        self.half_extents = scrub(self.half_extents);
+        self.color = self.color.map(|channel| {
+            if channel.is_nan() {
+                0.0
+            } else if channel.is_infinite() {
+                HALF_MAX.copysign(channel)
+            } else {
+                channel
+            }
+        });
+        self.specular = scrub(self.specular);
+        self.detail = scrub(self.detail);
+        self.uv = scrub(self.uv);
+        self.motion = scrub(self.motion);
+        self.adjust.x = scrub_halves(self.adjust.x);
+        self.adjust.y = scrub_halves(self.adjust.y);
+        self.adjust.w = self.adjust.w & 0xffff_0000 | scrub_halves(self.adjust.w & 0xffff);
+        false
+    }
+}
+
+/// What the last upload found, written by the render world and read by the main world
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SanitizeReport {
+    /// Quads hidden for a non-finite center or half-extents
+    hidden: usize,
+    /// Quads uploaded with their other non-finite values replaced
+    scrubbed: usize,
+    /// Indices of the first invalid quads
+    indices: Vec<usize>,
+    /// Set by every upload until the main world read the report
+    unread: bool,
+}
+
+impl SanitizeReport {
+    /// Sanitizes the converted instances of the quads of an upload from index `first` on, which
+    /// lets uploads in several chunks report into one report
+    pub(crate) fn sanitize(&mut self, instances: &mut [GpuQuad], first: usize) {
+        for (i, instance) in instances.iter_mut().enumerate() {
+            if instance.is_finite() {
+                continue;
+            }
+            if instance.sanitize() {
+                self.hidden += 1;
+            } else {
+                self.scrubbed += 1;
+            }
+            if self.indices.len() < LOGGED_INDICES {
+                self.indices.push(first + i);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Resource)]
+pub(crate) struct SharedSanitizeReport(Arc<Mutex<SanitizeReport>>);
+
+impl SharedSanitizeReport {
+    /// Shares what the sanitizing of an upload found with the main world
+    pub(crate) fn publish(&self, report: SanitizeReport) {
+        *self.0.lock().unwrap() = SanitizeReport {
+            unread: true,
+            ..report
+        };
+    }
+}
+
+pub(crate) struct SanitizePlugin;
+
+impl Plugin for SanitizePlugin {
+    fn build(&self, app: &mut App) {
+        let shared = SharedSanitizeReport::default();
+        app.insert_resource(shared.clone())
+            .register_diagnostic(Diagnostic::new(
+                QuadsPlugin::INVALID_QUADS,
+                "invalid_quads",
+                20,
+            ))
+            .add_systems(PostUpdate, diagnose_invalid_quads);
+        app.sub_app_mut(RenderApp).insert_resource(shared);
+    }
+}
+
+/// Records [`QuadsPlugin::INVALID_QUADS`] and logs the invalid quads of an upload, unless the
+/// upload before had as many
+fn diagnose_invalid_quads(
+    shared: Res<SharedSanitizeReport>,
+    mut diagnostics: Diagnostics,
+    mut logged: Local<(usize, usize)>,
+) {
+    let mut report = shared.0.lock().unwrap();
+    let invalid = report.hidden + report.scrubbed;
+    diagnostics.add_measurement(QuadsPlugin::INVALID_QUADS, || invalid as f64);
+    if !std::mem::take(&mut report.unread) || *logged == (report.hidden, report.scrubbed) {
+        return;
+    }
+    *logged = (report.hidden, report.scrubbed);
+    if invalid > 0 {
+        warn!(
+            "{} quads have non-finite values, {} of them were hidden for their center or \
+            half-extents and the values of the others were replaced. The first are at indices \
+            {:?}",
+            invalid, report.hidden, report.indices
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+    use crate::quads::{Billboard, Quad};
+
+    struct XorShift(u32);
+
+    impl XorShift {
+        /// Uniformly distributed in `0..1`
+        fn next(&mut self) -> f32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            (self.0 >> 8) as f32 / (1 << 24) as f32
+        }
+    }
+
+    /// A value for [`check_sanitize`], non-finite or too large for a half float half of the time
+    fn poisoned(random: &mut XorShift, value: f32) -> f32 {
+        match (random.next() * 8.0) as u32 {
+            0 => f32::NAN,
+            1 => f32::INFINITY,
+            2 => f32::NEG_INFINITY,
+            3 => f32::MAX,
+            _ => value,
+        }
+    }
+
+    /// Converts `count` pseudo-random quads into instance data, with about every other field replaced
+    /// by NaN, an infinity or a value too large for the half floats, and sanitizes them like
+    /// [`QuadsPlugin::sanitize`]. Returns the number of quads that were hidden and that had their
+    /// values replaced, or a description of the first quad whose sanitized instance data is wrong.
+    ///
+    /// The sanitized instance data has to be finite, and quads have to be hidden exactly when their
+    /// center or half-extents are not finite. The other quads keep every finite value, and quads
+    /// without non-finite values and quads sanitized before stay unchanged.
+    fn check_sanitize(count: usize) -> Result<(usize, usize), String> {
+        /// Indices of the `u32` words of the instance data that hold half floats or no floats
+        const FLAGS_WORD: usize = 3;
+        const ADJUST_WORDS: std::ops::Range<usize> = 28..32;
+        let hidden_bits = bytemuck::cast::<_, [u32; 32]>(GpuQuad::from(&Quad {
+            hidden: true,
+            ..default()
+        }))[FLAGS_WORD];
+
+        let mut random = XorShift(0x2f6b_5c1d);
+        let (mut hidden, mut scrubbed) = (0, 0);
+        for i in 0..count {
+            let mut value = |scale: f32| {
+                let value = (random.next() * 2.0 - 1.0) * scale;
+                // NOTE: Every quad gets a few fields poisoned, the others stay valid
+                if random.next() < 0.1 {
+                    poisoned(&mut random, value)
+                } else {
+                    value
+                }
+            };
+            let quad = Quad {
+                color: Color::rgba(value(2.0), value(2.0), value(2.0), value(1.0)),
+                center: Vec3::new(value(100.0), value(100.0), value(100.0)),
+                half_extents: Vec3::new(value(2.0), value(2.0), value(2.0)),
+                scale: value(2.0),
+                billboard: match i % 3 {
+                    0 => Billboard::ViewY,
+                    1 => Billboard::ViewYMinScreenSize {
+                        min_half_extent: value(20.0),
+                    },
+                    _ => Billboard::OrientedY {
+                        orientation: Quat::from_xyzw(
+                            value(1.0),
+                            value(1.0),
+                            value(1.0),
+                            value(1.0),
+                        ),
+                    },
+                },
+                uv_rotation: value(PI),
+                spin: value(PI),
+                specular_color: Color::rgb(value(1.0), value(1.0), value(1.0)),
+                specular_power: value(64.0),
+                translucency: value(1.0),
+                seed: i as u32,
+                detail_weight: value(1.0),
+                detail_scroll: Vec2::new(value(1.0), value(1.0)),
+                uv_scroll: Vec2::new(value(1.0), value(1.0)),
+                uv_tile: Vec2::new(value(4.0), value(4.0)),
+                depth_offset: value(1.0),
+                hsv_shift: Vec3::new(value(360.0), value(1.0), value(1.0)),
+                dissolve: value(1.0),
+                ..default()
+            };
+
+            let original = GpuQuad::from(&quad);
+            let mut sanitized = original;
+            let was_finite = original.is_finite();
+            let was_hidden = !was_finite && sanitized.sanitize();
+            let words = bytemuck::cast::<_, [u32; 32]>(original);
+            let sanitized_words = bytemuck::cast::<_, [u32; 32]>(sanitized);
+            let error = |problem: &str| Err(format!("quad {i} {problem}: {quad:?}"));
+
+            if !sanitized.is_finite() {
+                return error("is not finite after sanitizing");
+            }
+            let mut resanitized = sanitized;
+            if resanitized.sanitize()
+                || bytemuck::bytes_of(&resanitized) != bytemuck::bytes_of(&sanitized)
+            {
+                return error("changed when it was sanitized again");
+            }
+            if was_finite {
+                if words != sanitized_words {
+                    return error("has finite values but was changed");
+                }
+                continue;
+            }
+            let placed = words[0..3]
+                .iter()
+                .chain(&words[4..7])
+                .all(|&word| f32::from_bits(word).is_finite());
+            if was_hidden == placed {
+                return error(if placed {
+                    "was hidden with a finite center and half-extents"
+                } else {
+                    "was not hidden without a finite center or half-extents"
+                });
+            }
+            if was_hidden {
+                if sanitized_words[FLAGS_WORD] & hidden_bits == 0 {
+                    return error("is not flagged as hidden");
+                }
+                hidden += 1;
+                continue;
+            }
+            for (word, (&before, &after)) in words.iter().zip(&sanitized_words).enumerate() {
+                let kept = if word == FLAGS_WORD {
+                    true
+                } else if ADJUST_WORDS.contains(&word) {
+                    // NOTE: The mask and the noise offset are not half floats
+                    let halves = match word {
+                        30 => 0,
+                        31 => 0xffff,
+                        _ => 0xffff_ffff,
+                    };
+                    let finite = |half: u32| half & 0x7c00 != 0x7c00;
+                    [0, 16].iter().all(|&shift| {
+                        let half = (before >> shift) & 0xffff;
+                        halves >> shift & 0xffff == 0
+                            || !finite(half)
+                            || (after >> shift) & 0xffff == half
+                    }) && (before & !halves) == (after & !halves)
+                } else {
+                    !f32::from_bits(before).is_finite() || before == after
+                };
+                if !kept {
+                    return error(&format!(
+                        "lost the finite value in word {word} of its instance data"
+                    ));
+                }
+            }
+            scrubbed += 1;
+        }
+        Ok((hidden, scrubbed))
+    }
+
+    #[test]
+    fn sanitizes_poisoned_quads() {
+        // NOTE: Enough quads for every field, including the ones packed into half floats, to be
+        // poisoned with each non-finite value many times over
+        let (hidden, scrubbed) = check_sanitize(100_000).unwrap_or_else(|err| panic!("{err}"));
+        assert!(hidden > 0, "no quad was hidden");
+        assert!(scrubbed > 0, "no quad was scrubbed and kept");
+    }
+}
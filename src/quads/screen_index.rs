@@ -0,0 +1,489 @@
+//! Screen-space queries on the quads, like hover picking, box selection and label decluttering.
+//!
+//! [`QuadsScreenIndex`] projects the quads of the [`Quads`] resource for every active 3D camera
+//! on the CPU at the end of each frame. Every quad on screen is bounded by a rect in the camera's
+//! viewport, and the rects are sorted into a grid of [`QuadsScreenIndex::CELL_SIZE`] cells.
+
+use bevy::{
+    prelude::*,
+    render::camera::{CameraUpdateSystem, Projection},
+    transform::TransformSystem,
+    utils::{HashMap, HashSet},
+};
+
+use super::{
+    declutter::declutter_quads, Billboard, Quad, QuadId, Quads, QuadsCoordinateConvention,
+    QuadsDeclutter, QuadsDepthOfField, QuadsOrigin,
+};
+
+/// A quad found by a [`QuadsScreenIndex`] query
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScreenQuad {
+    /// Index of the quad in [`Quads::data`] when the quads were last projected
+    pub index: usize,
+    /// Id of quads inserted with [`Quads::insert`], which stays valid when other quads are
+    /// removed before the quads are projected again
+    pub id: Option<QuadId>,
+    /// Bounds of the quad in logical viewport pixels, with the origin in the top left corner like
+    /// [`Camera::world_to_viewport`]. Spinning and rotated quads are bounded by the circle around
+    /// their corners, so the rect may be larger than the quad.
+    pub rect: Rect,
+    /// Reverse-Z depth of the quad's center, larger values are closer to the camera
+    pub depth: f32,
+}
+
+/// The quads on screen of one camera
+#[derive(Debug, Default)]
+struct ViewScreenIndex {
+    quads: Vec<ScreenQuad>,
+    /// Indices into `quads` of the quads overlapping each cell, row by row
+    cells: Vec<Vec<u32>>,
+    columns: usize,
+    rows: usize,
+    viewport_size: Vec2,
+    /// Physical pixels per logical pixel, pixel sized quads change size on screen with it
+    scale_factor: f32,
+    /// The view projection the quads were projected with
+    view_proj: Mat4,
+    /// Corners of the box around the world-space quad centers, to measure how far the quads
+    /// moved on screen since they were projected
+    bounds: Option<[Vec3; 8]>,
+}
+
+/// Screen-space rects of the [`Quads`] resource's quads for every active 3D camera. Insert the
+/// resource to enable it, the rects are projected in `PostUpdate` after the cameras were updated.
+///
+/// The quads are projected like the vertex shader places them, with [`Quads::variation`],
+/// [`Quads::convention`], [`Quad::scale`] and the [`QuadsOrigin`] applied. Quads behind the camera
+/// or off screen are left out, and so are quads whose center is behind the camera even if a part
+/// of them is visible. [`Quads::baked`] quads and the [`QuadsFlowField`](super::QuadsFlowField)
+/// displacement are not indexed. [`Quad::hidden`] quads are, so that [`QuadsDeclutter`] can show
+/// them again.
+///
+/// The rects are only projected again when the quads change, the viewport is resized or its
+/// scale factor changes, the camera's [`QuadsDepthOfField`] changes or the camera moves the quads by more than
+/// [`QuadsScreenIndex::rebuild_threshold`] pixels, so a
+/// camera drifting slowly over many frames keeps the index until the drift adds up. The resource
+/// is only flagged as changed when the rects of a camera were projected again.
+#[derive(Debug, Resource)]
+pub struct QuadsScreenIndex {
+    /// Distance in logical pixels the quads may move on screen before they are projected again,
+    /// measured at the corners of the box around their centers. 0 projects them again whenever
+    /// the camera moves.
+    pub rebuild_threshold: f32,
+    views: HashMap<Entity, ViewScreenIndex>,
+}
+
+impl Default for QuadsScreenIndex {
+    fn default() -> Self {
+        Self {
+            rebuild_threshold: 0.5,
+            views: HashMap::default(),
+        }
+    }
+}
+
+impl QuadsScreenIndex {
+    /// Width and height of the grid cells in logical pixels
+    pub const CELL_SIZE: f32 = 64.0;
+
+    /// The quads whose rect overlaps `rect` in the viewport of the camera `view`, each once and in
+    /// no particular order. Only the part of `rect` inside the viewport is queried. Empty for
+    /// cameras that are not indexed.
+    pub fn query_rect(&self, view: Entity, rect: Rect) -> impl Iterator<Item = &ScreenQuad> + '_ {
+        let query = self.views.get(&view).and_then(|index| {
+            let viewport = index.viewport();
+            (overlaps(rect, viewport) && !index.cells.is_empty())
+                .then(|| (index, rect.intersect(viewport)))
+        });
+        query.into_iter().flat_map(|(index, rect)| {
+            let (min_column, min_row) = index.cell(rect.min);
+            let (max_column, max_row) = index.cell(rect.max);
+            (min_row..=max_row)
+                .flat_map(move |row| (min_column..=max_column).map(move |column| (column, row)))
+                .flat_map(move |(column, row)| {
+                    index.cells[row * index.columns + column]
+                        .iter()
+                        .map(|&quad| &index.quads[quad as usize])
+                        .filter(move |quad| {
+                            // NOTE: Quads overlapping several cells of the query are only
+                            // reported by the first of them
+                            overlaps(quad.rect, rect)
+                                && index.cell(quad.rect.min.max(rect.min)) == (column, row)
+                        })
+                })
+        })
+    }
+
+    /// The quads whose rect contains `point`, see [`QuadsScreenIndex::query_rect`]. The closest
+    /// one has the largest [`ScreenQuad::depth`].
+    pub fn query_point(&self, view: Entity, point: Vec2) -> impl Iterator<Item = &ScreenQuad> + '_ {
+        self.views
+            .get(&view)
+            .filter(|index| !index.cells.is_empty() && index.viewport().contains(point))
+            .into_iter()
+            .flat_map(move |index| {
+                let (column, row) = index.cell(point);
+                index.cells[row * index.columns + column]
+                    .iter()
+                    .map(|&quad| &index.quads[quad as usize])
+                    .filter(move |quad| quad.rect.contains(point))
+            })
+    }
+
+    /// All quads on screen of the camera `view`, in the order of [`Quads::data`]
+    pub fn quads(&self, view: Entity) -> &[ScreenQuad] {
+        self.views
+            .get(&view)
+            .map_or(&[], |index| index.quads.as_slice())
+    }
+}
+
+impl ViewScreenIndex {
+    fn cell(&self, point: Vec2) -> (usize, usize) {
+        let cell = (point / QuadsScreenIndex::CELL_SIZE)
+            .floor()
+            .max(Vec2::ZERO);
+        (
+            (cell.x as usize).min(self.columns - 1),
+            (cell.y as usize).min(self.rows - 1),
+        )
+    }
+
+    fn viewport(&self) -> Rect {
+        Rect::from_corners(Vec2::ZERO, self.viewport_size)
+    }
+}
+
+/// The camera's depth of field and vertical field of view, for [`Billboard::CocScaled`] quads
+type Lens<'a> = Option<(&'a QuadsDepthOfField, f32)>;
+
+/// Bounds of a quad on screen, `None` if it is off screen or its center is behind the camera
+fn screen_rect(
+    quad: &Quad,
+    origin: Vec3,
+    view_proj: &Mat4,
+    projection: &Mat4,
+    viewport_size: Vec2,
+    scale_factor: f32,
+    lens: Lens,
+) -> Option<(Rect, f32)> {
+    let half_extents = quad.half_extents.abs().truncate() * quad.scale.max(0.0);
+    // NOTE: The radius of the circle around the corners bounds spinning quads too
+    let radius = half_extents.length();
+    let ndc_to_pixels = |ndc: Vec2| Vec2::new(ndc.x + 1.0, 1.0 - ndc.y) * 0.5 * viewport_size;
+    let (center, depth, pixel_radius) = if let Billboard::ClipSpace = quad.billboard {
+        let size = half_extents * 0.5 * viewport_size;
+        (
+            ndc_to_pixels(quad.center.truncate()),
+            quad.center.z,
+            Vec2::splat(size.length()),
+        )
+    } else {
+        let clip = *view_proj * (origin + quad.center).extend(1.0);
+        if clip.w <= 0.0 {
+            return None;
+        }
+        let ndc = clip.truncate() / clip.w;
+        // NOTE: Sizes in world units shrink with the distance, w for perspective projections and
+        // 1 for orthographic ones. The circle around the corners has the same size on screen
+        // however the camera is rotated, so only the projection scales it.
+        let world_pixels =
+            Vec2::new(projection.x_axis.x, projection.y_axis.y).abs() * 0.5 * viewport_size
+                / clip.w;
+        let pixel_radius = match quad.billboard {
+            // NOTE: Pixel sizes are in physical pixels like the view's viewport in the shader
+            Billboard::FixedScreenSize => Vec2::splat(radius / scale_factor),
+            // NOTE: Scaled up uniformly until the y half-extent grew by the blur radius, like the
+            // shader does. The circle of confusion is in physical pixels too.
+            Billboard::CocScaled => {
+                let scale = match lens {
+                    Some((depth_of_field, fov)) if half_extents.y > 0.0 => {
+                        let coc = depth_of_field.circle_of_confusion(
+                            fov,
+                            viewport_size.y * scale_factor,
+                            clip.w,
+                        );
+                        1.0 + depth_of_field.blur_radius(coc) / half_extents.y
+                    }
+                    _ => 1.0,
+                };
+                Vec2::splat(radius * scale / scale_factor)
+            }
+            Billboard::ViewportFraction => Vec2::splat(radius * viewport_size.y),
+            // NOTE: Scaled up uniformly until the y half-extent reaches the minimum, like the
+            // shader does
+            Billboard::ViewYMinScreenSize { min_half_extent } if half_extents.y > 0.0 => {
+                let min_half_extent = min_half_extent * quad.scale.max(0.0) / scale_factor;
+                let scale = (min_half_extent / (half_extents.y * world_pixels.y)).max(1.0);
+                world_pixels * radius * scale
+            }
+            _ => world_pixels * radius,
+        };
+        (ndc_to_pixels(ndc.truncate()), ndc.z, pixel_radius)
+    };
+    let rect = Rect::from_center_half_size(center, pixel_radius);
+    overlaps(rect, Rect::from_corners(Vec2::ZERO, viewport_size)).then_some((rect, depth))
+}
+
+/// Whether two rects overlap, including rects that only touch, so zero sized query rects find
+/// the quads under them
+fn overlaps(a: Rect, b: Rect) -> bool {
+    a.min.cmple(b.max).all() && b.min.cmple(a.max).all()
+}
+
+/// Largest distance in pixels the corners of `bounds` moved between the two projections, `None`
+/// if a corner is behind the camera in either
+fn screen_motion(bounds: &[Vec3; 8], from: &Mat4, to: &Mat4, viewport_size: Vec2) -> Option<f32> {
+    let mut motion = 0.0f32;
+    for corner in bounds {
+        let a = *from * corner.extend(1.0);
+        let b = *to * corner.extend(1.0);
+        if a.w <= 0.0 || b.w <= 0.0 {
+            return None;
+        }
+        let delta = (b.truncate().truncate() / b.w - a.truncate().truncate() / a.w) * 0.5;
+        motion = motion.max((delta * viewport_size).length());
+    }
+    Some(motion)
+}
+
+pub(crate) struct ScreenIndexPlugin;
+
+impl Plugin for ScreenIndexPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                update_screen_index,
+                declutter_quads.run_if(resource_exists::<QuadsDeclutter>()),
+            )
+                .chain()
+                .run_if(resource_exists::<QuadsScreenIndex>())
+                .after(CameraUpdateSystem)
+                .after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_screen_index(
+    mut screen_index: ResMut<QuadsScreenIndex>,
+    quads: Option<Res<Quads>>,
+    origin: Option<Res<QuadsOrigin>>,
+    cameras: Query<
+        (
+            Entity,
+            &Camera,
+            &GlobalTransform,
+            &Projection,
+            Option<Ref<QuadsDepthOfField>>,
+        ),
+        With<Camera3d>,
+    >,
+) {
+    let index = screen_index.bypass_change_detection();
+    let mut active = HashSet::new();
+    let mut rebuilt = false;
+    let quads_changed = !matches!(&quads, Some(quads) if !quads.is_changed());
+    let origin_changed = origin.as_ref().is_some_and(|origin| origin.is_changed());
+    let origin = origin.map_or(Vec3::ZERO, |origin| origin.translation.as_vec3());
+    for (entity, camera, transform, projection, depth_of_field) in &cameras {
+        let Some(viewport_size) = camera.logical_viewport_size() else {
+            continue;
+        };
+        if !camera.is_active || viewport_size.cmple(Vec2::ZERO).any() {
+            continue;
+        }
+        active.insert(entity);
+        let scale_factor = camera
+            .physical_viewport_size()
+            .map_or(1.0, |size| size.y as f32 / viewport_size.y);
+        let projection_matrix = camera.projection_matrix();
+        let view_proj = projection_matrix * transform.compute_matrix().inverse();
+        let view_index = index.views.entry(entity).or_default();
+        let moved = view_index.bounds.as_ref().map_or(Some(0.0), |bounds| {
+            screen_motion(bounds, &view_index.view_proj, &view_proj, viewport_size)
+        });
+        let lens = match (projection, &depth_of_field) {
+            (Projection::Perspective(perspective), Some(depth_of_field)) => {
+                Some((&**depth_of_field, perspective.fov))
+            }
+            _ => None,
+        };
+        if !quads_changed
+            && !origin_changed
+            && !depth_of_field.as_ref().is_some_and(|d| d.is_changed())
+            && view_index.viewport_size == viewport_size
+            && view_index.scale_factor == scale_factor
+            && moved.is_some_and(|moved| moved <= index.rebuild_threshold)
+        {
+            continue;
+        }
+
+        rebuilt = true;
+        view_index.quads.clear();
+        view_index.columns = (viewport_size.x / QuadsScreenIndex::CELL_SIZE).ceil() as usize;
+        view_index.rows = (viewport_size.y / QuadsScreenIndex::CELL_SIZE).ceil() as usize;
+        view_index.cells.clear();
+        view_index
+            .cells
+            .resize_with(view_index.columns * view_index.rows, Vec::new);
+        view_index.viewport_size = viewport_size;
+        view_index.scale_factor = scale_factor;
+        view_index.view_proj = view_proj;
+        view_index.bounds = None;
+        let Some(quads) = quads.as_deref() else {
+            continue;
+        };
+
+        let mut min = Vec3::splat(f32::INFINITY);
+        let mut max = Vec3::splat(f32::NEG_INFINITY);
+        for (i, quad) in quads.data.iter().enumerate() {
+            // NOTE: Converted like the quads are uploaded
+            let converted;
+            let quad = match &quads.variation {
+                None if quads.convention == QuadsCoordinateConvention::YUpRightHanded => quad,
+                None => {
+                    converted = quads.convention.quad_to_bevy(quad);
+                    &converted
+                }
+                Some(variation) => {
+                    converted = quads.convention.quad_to_bevy(&variation.apply(quad));
+                    &converted
+                }
+            };
+            if !matches!(quad.billboard, Billboard::ClipSpace) {
+                min = min.min(origin + quad.center);
+                max = max.max(origin + quad.center);
+            }
+            let Some((rect, depth)) = screen_rect(
+                quad,
+                origin,
+                &view_proj,
+                &projection_matrix,
+                viewport_size,
+                scale_factor,
+                lens,
+            ) else {
+                continue;
+            };
+            view_index.quads.push(ScreenQuad {
+                index: i,
+                id: quads.id(i),
+                rect,
+                depth,
+            });
+        }
+        if min.cmple(max).all() {
+            view_index.bounds = Some(std::array::from_fn(|corner| {
+                Vec3::select(
+                    BVec3::new(corner & 1 != 0, corner & 2 != 0, corner & 4 != 0),
+                    max,
+                    min,
+                )
+            }));
+        }
+
+        for (i, quad) in view_index.quads.iter().enumerate() {
+            let rect = quad.rect.intersect(view_index.viewport());
+            let (min_column, min_row) = view_index.cell(rect.min);
+            let (max_column, max_row) = view_index.cell(rect.max);
+            for row in min_row..=max_row {
+                for column in min_column..=max_column {
+                    view_index.cells[row * view_index.columns + column].push(i as u32);
+                }
+            }
+        }
+    }
+    let views = index.views.len();
+    index.views.retain(|entity, _| active.contains(entity));
+    // NOTE: Only flagged as changed when a view was projected again, so systems reading the index
+    // can skip the frames in between
+    if rebuilt || index.views.len() != views {
+        screen_index.set_changed();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        asset::AssetPlugin,
+        render::camera::CameraPlugin,
+        window::{ExitCondition, PrimaryWindow, WindowResized, WindowResolution},
+    };
+
+    use super::*;
+
+    /// An app with a window and the screen index, but without rendering
+    fn app() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            WindowPlugin {
+                primary_window: Some(Window {
+                    resolution: WindowResolution::new(400.0, 300.0).with_scale_factor_override(1.0),
+                    ..default()
+                }),
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+            },
+            AssetPlugin::default(),
+        ))
+        .add_asset::<Image>()
+        .add_plugins((CameraPlugin, ScreenIndexPlugin))
+        .insert_resource(QuadsScreenIndex::default());
+        let camera = app
+            .world
+            .spawn(Camera3dBundle {
+                transform: Transform::from_xyz(0.0, 0.0, 10.0),
+                ..default()
+            })
+            .id();
+        (app, camera)
+    }
+
+    #[test]
+    fn scale_factor_changes_rebuild_the_index() {
+        let (mut app, camera) = app();
+        let mut quads = Quads::default();
+        quads.insert(Quad {
+            half_extents: Vec3::new(16.0, 16.0, 0.0),
+            billboard: Billboard::FixedScreenSize,
+            ..default()
+        });
+        app.insert_resource(quads);
+        app.update();
+        let radius = Vec2::splat(16.0).length();
+        let rect = app.world.resource::<QuadsScreenIndex>().quads(camera)[0].rect;
+        assert!((rect.half_size() - radius).abs().max_element() < 1e-3);
+
+        // NOTE: Like moving the window to a monitor with twice the scale factor, its logical size
+        // stays and winit reports it as resized
+        let window = app
+            .world
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .single(&app.world);
+        let mut window_component = app.world.get_mut::<Window>(window).unwrap();
+        window_component
+            .resolution
+            .set_scale_factor_override(Some(2.0));
+        window_component
+            .resolution
+            .set_physical_resolution(800, 600);
+        app.world.send_event(WindowResized {
+            window,
+            width: 400.0,
+            height: 300.0,
+        });
+        app.update();
+
+        let rect = app.world.resource::<QuadsScreenIndex>().quads(camera)[0].rect;
+        assert!(
+            (rect.half_size() - radius / 2.0).abs().max_element() < 1e-3,
+            "the rect {rect:?} kept the size of the old scale factor"
+        );
+        assert!(rect.center().abs_diff_eq(Vec2::new(200.0, 150.0), 1e-3));
+    }
+}
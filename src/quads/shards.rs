@@ -0,0 +1,178 @@
+//! Filling [`Quads`] from many threads at once.
+//!
+//! A [`QuadsShards`] splits the quads into shards that are locked separately, so producers that
+//! each write to their own shard never wait for one another. [`Quads::append_shards`] then merges
+//! the shards in shard order, which keeps the result independent of how the threads were
+//! scheduled.
+
+use std::sync::{Mutex, MutexGuard};
+
+use super::{Quad, Quads};
+
+/// Quads collected from many threads, appended to [`Quads`] with [`Quads::append_shards`].
+///
+/// Each producer should write to its own shard, e.g. the index of its chunk of the input, as
+/// threads writing to the same shard take turns. Within a shard the quads keep the order they
+/// were pushed in.
+#[derive(Debug)]
+pub struct QuadsShards {
+    shards: Box<[Mutex<Vec<Quad>>]>,
+}
+
+impl QuadsShards {
+    /// Creates `count` empty shards, at least one
+    pub fn new(count: usize) -> Self {
+        Self {
+            shards: (0..count.max(1)).map(|_| Mutex::default()).collect(),
+        }
+    }
+
+    /// Number of shards
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Locks shard `index` wrapped to the number of shards, to push many quads with one lock
+    pub fn shard(&self, index: usize) -> MutexGuard<'_, Vec<Quad>> {
+        // NOTE: A producer that panicked leaves its shard as far as it got
+        self.shards[index % self.shards.len()]
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Appends a quad to shard `index`, see [`QuadsShards::shard`]
+    pub fn push(&self, index: usize, quad: Quad) {
+        self.shard(index).push(quad);
+    }
+
+    /// Number of quads in all shards
+    pub fn len(&self) -> usize {
+        (0..self.shards.len())
+            .map(|index| self.shard(index).len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Quads {
+    /// Appends the quads of all shards to `data`, shard by shard in index order. The quads are
+    /// pushed without [`QuadId`](super::QuadId)s, like quads pushed to `data` directly.
+    pub fn append_shards(&mut self, shards: QuadsShards) {
+        let shards = Vec::from(shards.shards)
+            .into_iter()
+            .map(|shard| shard.into_inner().unwrap_or_else(|err| err.into_inner()))
+            .collect::<Vec<_>>();
+        self.data
+            .reserve(shards.iter().map(|shard| shard.len()).sum::<usize>());
+        for mut shard in shards {
+            self.data.append(&mut shard);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+
+    fn quad(shard: usize, index: usize) -> Quad {
+        Quad {
+            center: Vec3::new(shard as f32, index as f32, 0.0),
+            ..default()
+        }
+    }
+
+    fn positions(quads: &Quads) -> Vec<(usize, usize)> {
+        quads
+            .data
+            .iter()
+            .map(|quad| (quad.center.x as usize, quad.center.y as usize))
+            .collect()
+    }
+
+    #[test]
+    fn appends_shard_by_shard() {
+        let shards = QuadsShards::new(3);
+        for (shard, index) in [(2, 0), (0, 0), (1, 0), (2, 1), (0, 1)] {
+            shards.push(shard, quad(shard, index));
+        }
+        assert_eq!(shards.len(), 5);
+
+        let mut quads = Quads::default();
+        quads.append_shards(shards);
+        assert_eq!(positions(&quads), [(0, 0), (0, 1), (1, 0), (2, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn keeps_the_order_within_shards_from_many_threads() {
+        const THREADS: usize = 8;
+        const QUADS_PER_THREAD: usize = 10_000;
+
+        let shards = QuadsShards::new(THREADS);
+        std::thread::scope(|scope| {
+            for thread in 0..THREADS {
+                let shards = &shards;
+                scope.spawn(move || {
+                    for index in 0..QUADS_PER_THREAD {
+                        // NOTE: Pushing one by one interleaves the threads as much as possible
+                        shards.push(thread, quad(thread, index));
+                    }
+                });
+            }
+        });
+
+        let mut quads = Quads::default();
+        quads.insert(quad(THREADS, 0));
+        quads.append_shards(shards);
+        let expected: Vec<_> = std::iter::once((THREADS, 0))
+            .chain((0..THREADS).flat_map(|thread| (0..QUADS_PER_THREAD).map(move |i| (thread, i))))
+            .collect();
+        assert_eq!(positions(&quads), expected);
+    }
+
+    #[test]
+    fn appended_quads_have_no_ids() {
+        let mut quads = Quads::default();
+        let id = quads.insert(quad(0, 0));
+        let shards = QuadsShards::new(1);
+        shards.push(0, quad(1, 0));
+        quads.append_shards(shards);
+
+        assert_eq!(quads.id(0), Some(id));
+        assert_eq!(quads.id(1), None);
+    }
+
+    #[test]
+    fn wraps_shard_indices() {
+        assert_eq!(QuadsShards::new(0).shard_count(), 1);
+
+        let shards = QuadsShards::new(2);
+        shards.push(3, quad(1, 0));
+        shards.push(2, quad(0, 0));
+        assert_eq!(shards.shard(1).len(), 1);
+        assert_eq!(shards.shard(0).len(), 1);
+    }
+
+    #[test]
+    fn keeps_the_quads_of_panicked_producers() {
+        let shards = QuadsShards::new(2);
+        std::thread::scope(|scope| {
+            let producer = scope.spawn(|| {
+                let mut shard = shards.shard(1);
+                shard.push(quad(1, 0));
+                panic!("the producer failed while holding its shard");
+            });
+            assert!(producer.join().is_err());
+        });
+        shards.push(0, quad(0, 0));
+        shards.push(1, quad(1, 1));
+
+        let mut quads = Quads::default();
+        quads.append_shards(shards);
+        assert_eq!(positions(&quads), [(0, 0), (1, 0), (1, 1)]);
+    }
+}
@@ -0,0 +1,188 @@
+//! Stamping reusable groups of quads authored in local space into [`Quads`].
+//!
+//! A [`QuadCluster`], e.g. a bush made of a dozen quads, is baked into world space with a
+//! [`Transform`] by [`Quads::stamp`]. Each stamp is appended to [`Quads::data`] as one contiguous
+//! range and can be removed as a unit with [`Quads::remove_stamp`].
+
+use bevy::prelude::*;
+
+use super::{Billboard, Quad, QuadId, Quads};
+
+/// Quads in a local space, stamped into [`Quads`] any number of times with [`Quads::stamp`]
+#[derive(Clone, Debug, Default)]
+pub struct QuadCluster {
+    pub quads: Vec<Quad>,
+}
+
+/// The quads added by one [`Quads::stamp`], for removing them again with [`Quads::remove_stamp`]
+#[derive(Clone, Debug)]
+pub struct QuadStampId {
+    ids: Box<[QuadId]>,
+}
+
+impl QuadStampId {
+    /// Ids of the stamped quads, in cluster order
+    pub fn ids(&self) -> &[QuadId] {
+        &self.ids
+    }
+}
+
+impl QuadCluster {
+    /// The cluster's quads moved into world space by `transform`.
+    ///
+    /// Quads have no orientation of their own, so the rotation only moves their centers and turns
+    /// the orientation of [`Billboard::OrientedY`] quads.
+    /// Half-extents of quads sized in world units are multiplied by the scale, a non-uniform scale
+    /// stretches each quad along its own x and y axes regardless of the rotation. Quads sized in
+    /// screen space keep their half-extents, and [`Billboard::ClipSpace`] quads are copied as-is.
+    pub fn baked(&self, transform: &Transform) -> impl Iterator<Item = Quad> + '_ {
+        let transform = *transform;
+        self.quads.iter().map(move |quad| {
+            let mut quad = quad.clone();
+            match quad.billboard {
+                Billboard::None
+                | Billboard::ViewY
+                | Billboard::WorldY
+                | Billboard::ViewYMinScreenSize { .. } => {
+                    quad.center = transform.transform_point(quad.center);
+                    quad.half_extents *= transform.scale;
+                }
+                Billboard::OrientedY {
+                    ref mut orientation,
+                } => {
+                    quad.center = transform.transform_point(quad.center);
+                    quad.half_extents *= transform.scale;
+                    *orientation = transform.rotation * *orientation;
+                }
+                Billboard::FixedScreenSize | Billboard::ViewportFraction | Billboard::CocScaled => {
+                    quad.center = transform.transform_point(quad.center);
+                }
+                Billboard::ClipSpace => {}
+            }
+            quad
+        })
+    }
+}
+
+impl Quads {
+    /// Appends the quads of `cluster`, baked into world space by `transform` as described in
+    /// [`QuadCluster::baked`]
+    pub fn stamp(&mut self, cluster: &QuadCluster, transform: &Transform) -> QuadStampId {
+        self.data.reserve(cluster.quads.len());
+        QuadStampId {
+            ids: cluster
+                .baked(transform)
+                .map(|quad| self.insert(quad))
+                .collect(),
+        }
+    }
+
+    /// Removes the quads of a stamp, returning how many were still present
+    pub fn remove_stamp(&mut self, stamp: QuadStampId) -> usize {
+        stamp
+            .ids
+            .iter()
+            .filter(|&&id| self.remove(id).is_some())
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    fn quad(center: Vec3, billboard: Billboard) -> Quad {
+        Quad {
+            center,
+            half_extents: Vec3::new(1.0, 2.0, 0.0),
+            billboard,
+            ..default()
+        }
+    }
+
+    fn transform() -> Transform {
+        Transform::from_xyz(10.0, 0.0, 0.0)
+            .with_rotation(Quat::from_rotation_y(FRAC_PI_2))
+            .with_scale(Vec3::new(2.0, 3.0, 1.0))
+    }
+
+    #[test]
+    fn bakes_world_sized_quads() {
+        let orientation = Quat::from_rotation_x(0.5);
+        let cluster = QuadCluster {
+            quads: vec![
+                quad(Vec3::X, Billboard::None),
+                quad(Vec3::X, Billboard::OrientedY { orientation }),
+            ],
+        };
+        let transform = transform();
+        let baked: Vec<_> = cluster.baked(&transform).collect();
+        for quad in &baked {
+            assert!(quad
+                .center
+                .abs_diff_eq(transform.transform_point(Vec3::X), 1e-5));
+            assert_eq!(quad.half_extents, Vec3::new(2.0, 6.0, 0.0));
+        }
+        let Billboard::OrientedY {
+            orientation: baked_orientation,
+        } = baked[1].billboard
+        else {
+            panic!("the billboard mode changed");
+        };
+        assert!(baked_orientation.abs_diff_eq(transform.rotation * orientation, 1e-5));
+    }
+
+    #[test]
+    fn keeps_screen_sized_quads() {
+        let cluster = QuadCluster {
+            quads: vec![
+                quad(Vec3::X, Billboard::FixedScreenSize),
+                quad(Vec3::X, Billboard::ViewportFraction),
+                quad(Vec3::X, Billboard::ClipSpace),
+            ],
+        };
+        let transform = transform();
+        let baked: Vec<_> = cluster.baked(&transform).collect();
+        for quad in &baked[..2] {
+            assert!(quad
+                .center
+                .abs_diff_eq(transform.transform_point(Vec3::X), 1e-5));
+            assert_eq!(quad.half_extents, Vec3::new(1.0, 2.0, 0.0));
+        }
+        // Clip space quads are not in the world at all
+        assert_eq!(baked[2].center, Vec3::X);
+        assert_eq!(baked[2].half_extents, Vec3::new(1.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn stamps_are_removed_as_a_unit() {
+        let cluster = QuadCluster {
+            quads: (0..3)
+                .map(|i| quad(Vec3::new(i as f32, 0.0, 0.0), Billboard::None))
+                .collect(),
+        };
+        let mut quads = Quads::default();
+        let first = quads.stamp(&cluster, &Transform::IDENTITY);
+        let second = quads.stamp(&cluster, &Transform::from_xyz(0.0, 5.0, 0.0));
+        assert_eq!(quads.data.len(), 6);
+        // Each stamp is one contiguous range in cluster order
+        for (stamp, offset) in [(&first, 0), (&second, 3)] {
+            for (i, &id) in stamp.ids().iter().enumerate() {
+                assert_eq!(quads.index(id), Some(offset + i));
+                assert_eq!(quads.get(id).unwrap().center.x, i as f32);
+            }
+        }
+
+        quads.remove(first.ids()[1]);
+        assert_eq!(quads.remove_stamp(first), 2);
+        assert_eq!(quads.data.len(), 3);
+        for (i, &id) in second.ids().iter().enumerate() {
+            assert_eq!(quads.get(id).unwrap().center, Vec3::new(i as f32, 5.0, 0.0));
+        }
+        assert_eq!(quads.remove_stamp(second.clone()), 3);
+        assert!(quads.data.is_empty());
+        assert_eq!(quads.remove_stamp(second), 0);
+    }
+}
@@ -0,0 +1,133 @@
+//! Drawing custom work inside the quads pass, see [`QuadsSubpassDraws`].
+
+use bevy::{
+    prelude::*,
+    render::{render_phase::TrackedRenderPass, RenderApp},
+};
+
+/// Where in the quads pass a [`QuadsSubpassDraw`] runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum QuadsSubpassAnchor {
+    /// After the batches drawn at full resolution, before the half resolution batches are
+    /// composited over them
+    AfterOpaque,
+    /// At the end of the pass, after everything the quads draw
+    AfterTransparent,
+}
+
+/// Custom draws issued inside the quads pass, sharing its attachments instead of loading and
+/// storing them in a pass of their own. Registered with [`QuadsSubpassDraws::add`].
+///
+/// The pass draws into the view target and has the view's depth texture attached, with the
+/// [`QuadsMaskTexture`](super::QuadsMaskTexture) as a second color attachment in views with a
+/// mask. Pipelines must match these attachments, specializing them with
+/// [`QuadsPipelineKey::from_msaa_samples`](super::QuadsPipelineKey::from_msaa_samples),
+/// [`QuadsPipelineKey::from_hdr`](super::QuadsPipelineKey::from_hdr) and
+/// [`QuadsPipelineKey::MASK`](super::QuadsPipelineKey::MASK) gives the formats and sample count.
+/// The depth texture is `Depth32Float` with reverse-Z. The camera's viewport is set.
+///
+/// Views rendering the [`QuadsHeatmap`](super::QuadsHeatmap) draw into the heatmap instead and
+/// skip the hooks.
+pub trait QuadsSubpassDraw: Send + Sync + 'static {
+    /// Records the draws for the view `view_entity` of the render world `world`. The pipeline,
+    /// bind groups and buffers set by the quads and by earlier hooks are undefined.
+    fn draw<'w>(
+        &'w self,
+        render_pass: &mut TrackedRenderPass<'w>,
+        world: &'w World,
+        view_entity: Entity,
+    );
+}
+
+struct RegisteredSubpassDraw {
+    anchor: QuadsSubpassAnchor,
+    order: i32,
+    label: &'static str,
+    draw: Box<dyn QuadsSubpassDraw>,
+}
+
+/// The [`QuadsSubpassDraw`] hooks of the quads pass, a render world resource
+///
+/// Hooks at the same anchor run in ascending order of the order they were added with, which
+/// must be unique per anchor, so the order never depends on which plugin happened to add its
+/// hook first.
+#[derive(Default, Resource)]
+pub struct QuadsSubpassDraws {
+    draws: Vec<RegisteredSubpassDraw>,
+}
+
+impl QuadsSubpassDraws {
+    /// Runs `draw` at `anchor` of every quads pass, wrapped in a debug group named `label`
+    ///
+    /// # Panics
+    ///
+    /// If another hook was added at `anchor` with the same `order`
+    pub fn add(
+        &mut self,
+        anchor: QuadsSubpassAnchor,
+        order: i32,
+        label: &'static str,
+        draw: impl QuadsSubpassDraw,
+    ) {
+        let index = match self
+            .draws
+            .binary_search_by_key(&(anchor as u8, order), |draw| {
+                (draw.anchor as u8, draw.order)
+            }) {
+            Ok(index) => panic!(
+                "Quads subpass draw {label} has the same order {order} at {anchor:?} as {}",
+                self.draws[index].label
+            ),
+            Err(index) => index,
+        };
+        self.draws.insert(
+            index,
+            RegisteredSubpassDraw {
+                anchor,
+                order,
+                label,
+                draw: Box::new(draw),
+            },
+        );
+    }
+
+    /// Removes the hook added at `anchor` with `order`, returns whether there was one
+    pub fn remove(&mut self, anchor: QuadsSubpassAnchor, order: i32) -> bool {
+        let len = self.draws.len();
+        self.draws
+            .retain(|draw| (draw.anchor, draw.order) != (anchor, order));
+        self.draws.len() < len
+    }
+
+    /// Labels of the hooks at `anchor` in the order they run
+    pub fn labels(&self, anchor: QuadsSubpassAnchor) -> impl Iterator<Item = &'static str> + '_ {
+        self.draws
+            .iter()
+            .filter(move |draw| draw.anchor == anchor)
+            .map(|draw| draw.label)
+    }
+
+    pub(crate) fn draw<'w>(
+        &'w self,
+        anchor: QuadsSubpassAnchor,
+        render_pass: &mut TrackedRenderPass<'w>,
+        world: &'w World,
+        view_entity: Entity,
+    ) {
+        for draw in self.draws.iter().filter(|draw| draw.anchor == anchor) {
+            render_pass.push_debug_group(draw.label);
+            draw.draw.draw(render_pass, world, view_entity);
+            render_pass.pop_debug_group();
+        }
+    }
+}
+
+pub(crate) struct SubpassPlugin;
+
+impl Plugin for SubpassPlugin {
+    fn build(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<QuadsSubpassDraws>();
+    }
+}
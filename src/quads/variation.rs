@@ -0,0 +1,68 @@
+//! Deterministic per-quad variation applied when quads are uploaded.
+//!
+//! The variation is derived from [`Quad::seed`] only, so converting the same quad again always
+//! gives the same result and the authored [`Quads::data`](super::Quads) is never modified.
+
+use bevy::prelude::*;
+
+use super::Quad;
+
+/// Random jitter applied to every quad of a [`Quads`](super::Quads) batch. Each value is the
+/// maximum deviation in either direction, zero disables that kind of variation.
+#[derive(Clone, Debug, Default)]
+pub struct QuadsVariation {
+    /// Hue jitter in degrees
+    pub hue: f32,
+    /// Saturation jitter, added to the 0 to 1 saturation
+    pub saturation: f32,
+    /// Lightness jitter, added to the 0 to 1 lightness
+    pub lightness: f32,
+    /// Half-extents jitter as a fraction of the half-extents, e.g. 0.2 for ±20%
+    pub extent: f32,
+    /// Texture rotation jitter in radians
+    pub uv_rotation: f32,
+    /// Spin jitter in radians per second, e.g. to spin particles at random speeds in both
+    /// directions from a spin of zero
+    pub spin: f32,
+}
+
+/// Hashes the seed and a per-property salt into a value in [-1, 1]
+fn signed_unit(seed: u32, salt: u32) -> f32 {
+    // NOTE: This is the lowbias32 integer hash
+    let mut x = seed ^ salt.wrapping_mul(0x9e37_79b9);
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^= x >> 16;
+    (x >> 8) as f32 / (1u32 << 23) as f32 - 1.0
+}
+
+impl QuadsVariation {
+    /// Returns a copy of `quad` with the variation for its seed applied
+    pub fn apply(&self, quad: &Quad) -> Quad {
+        let mut quad = quad.clone();
+        let seed = quad.seed;
+
+        if self.hue != 0.0 || self.saturation != 0.0 || self.lightness != 0.0 {
+            let [hue, saturation, lightness, alpha] = quad.color.as_hsla_f32();
+            quad.color = Color::hsla(
+                (hue + self.hue * signed_unit(seed, 0)).rem_euclid(360.0),
+                (saturation + self.saturation * signed_unit(seed, 1)).clamp(0.0, 1.0),
+                (lightness + self.lightness * signed_unit(seed, 2)).clamp(0.0, 1.0),
+                alpha,
+            )
+            .as_rgba();
+        }
+        if self.extent != 0.0 {
+            quad.half_extents *= 1.0 + self.extent * signed_unit(seed, 3);
+        }
+        if self.uv_rotation != 0.0 {
+            quad.uv_rotation += self.uv_rotation * signed_unit(seed, 4);
+        }
+        if self.spin != 0.0 {
+            quad.spin += self.spin * signed_unit(seed, 5);
+        }
+        quad
+    }
+}
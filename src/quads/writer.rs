@@ -0,0 +1,55 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use super::{Quad, QuadId, Quads};
+
+/// Adds, changes and removes quads of the [`Quads`] resource from gameplay systems, e.g. to place
+/// markers. The quads persist until they are removed.
+///
+/// Every change to [`Quads`] uploads all quads again, so the writer only marks the resource as
+/// changed when a call actually modifies a quad. Calls with ids of removed quads leave it
+/// untouched, unlike going through [`ResMut<Quads>`] where any mutable access counts as a change.
+///
+/// Like [`ResMut<Quads>`], systems using the writer panic if the [`Quads`] resource was not
+/// inserted.
+#[derive(SystemParam)]
+pub struct QuadsWriter<'w> {
+    quads: ResMut<'w, Quads>,
+}
+
+impl<'w> QuadsWriter<'w> {
+    /// Adds a quad, the returned id stays valid until the quad is removed
+    pub fn insert(&mut self, quad: Quad) -> QuadId {
+        self.quads.insert(quad)
+    }
+
+    /// Removes a quad, returning `None` if it was already removed
+    pub fn remove(&mut self, id: QuadId) -> Option<Quad> {
+        if !self.quads.contains(id) {
+            return None;
+        }
+        self.quads.remove(id)
+    }
+
+    /// Replaces a quad, returning `false` if it was removed
+    pub fn set(&mut self, id: QuadId, quad: Quad) -> bool {
+        self.update(id, |slot| *slot = quad)
+    }
+
+    /// Calls `f` to change a quad in place, returning `false` without calling it if the quad was
+    /// removed
+    pub fn update(&mut self, id: QuadId, f: impl FnOnce(&mut Quad)) -> bool {
+        if !self.quads.contains(id) {
+            return false;
+        }
+        f(self.quads.get_mut(id).unwrap());
+        true
+    }
+
+    pub fn get(&self, id: QuadId) -> Option<&Quad> {
+        self.quads.get(id)
+    }
+
+    pub fn contains(&self, id: QuadId) -> bool {
+        self.quads.contains(id)
+    }
+}
@@ -0,0 +1,1577 @@
+//! Helpers for golden-image tests of quads rendering, enabled by the `test_support` feature.
+//!
+//! [`render_once`] renders a headless app into an offscreen image and reads it back.
+//! [`render_frames`] keeps rendering it for a number of frames after that, and [`render_resized`]
+//! resizes the image in between frames.
+//! [`pixel`] reads single pixels and [`assert_image_matches`] compares a rendered image with a
+//! golden PNG, writing the actual and diff images next to it when they differ.
+//! [`compare_images`] compares images within an [`ImageTolerance`] that can leave out the edges
+//! where MSAA resolves differ between GPUs, as the `regression_runner` example does for a whole
+//! suite of scenes.
+//!
+//! Shader specializations are checked without a GPU by [`QuadsShaderComposer`], which composes
+//! and validates `quads.wgsl` for the keys of [`quads_pipeline_keys`], and
+//! [`assert_shader_snapshot`] compares the composed module with a snapshot.
+//!
+//! The billboard math of the vertex shader is mirrored on the CPU by [`billboard_corners`] and
+//! [`billboard_uvs`].
+//! [`check_billboard`] checks the invariants of the corners for the views and quads of
+//! [`billboard_test_cases`], and [`assert_billboard_rendered`] compares it with a rendered quad.
+//! [`assert_corners_covered`] checks predicted corners against any rendered image.
+//!
+//! The adapter is chosen like in any other bevy app, so the `WGPU_BACKEND` environment variable
+//! selects the backend. `WGPU_POWER_PREF` (`low` or `high`) additionally selects between an
+//! integrated and a discrete GPU, which is useful to pin tests to a software adapter in CI.
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy_vertex_pulling::{quads::*, test_support::*};
+//!
+//! let image = render_once(
+//!     |app| {
+//!         app.add_plugins(QuadsPlugin::default());
+//!         app.world.spawn(Camera3dBundle {
+//!             transform: Transform::from_xyz(0.0, 0.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+//!             ..default()
+//!         });
+//!         let mut quads = Quads::default();
+//!         quads.insert(Quad {
+//!             color: Color::RED,
+//!             half_extents: Vec3::ONE,
+//!             ..default()
+//!         });
+//!         app.insert_resource(quads);
+//!     },
+//!     UVec2::new(64, 64),
+//! );
+//! assert_eq!(pixel(&image, 32, 32), Color::RED);
+//! assert_image_matches(&image, "tests/golden/red_quad.png", 2.0 / 255.0);
+//! ```
+
+use std::{
+    collections::HashMap,
+    f32::consts::{FRAC_PI_2, PI},
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use bevy::{
+    core_pipeline::tonemapping::Tonemapping,
+    prelude::*,
+    render::{
+        camera::{CameraProjection, CameraUpdateSystem, RenderTarget},
+        main_graph::node::CAMERA_DRIVER,
+        render_asset::RenderAssets,
+        render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext},
+        render_resource::{
+            Buffer, BufferDescriptor, BufferUsages, CachedPipelineState, Extent3d, ImageCopyBuffer,
+            ImageDataLayout, MapMode, PipelineCache, PipelineCacheError, Shader, ShaderDefVal,
+            ShaderImport, TextureDimension, TextureFormat, TextureUsages,
+        },
+        renderer::{RenderContext, RenderDevice},
+        settings::WgpuSettings,
+        texture::{CompressedImageFormats, ImageType},
+        Render, RenderApp, RenderPlugin, RenderSet,
+    },
+    window::ExitCondition,
+    winit::WinitPlugin,
+};
+use naga::{
+    back::wgsl::WriterFlags,
+    valid::{Capabilities, ValidationFlags, Validator},
+};
+use naga_oil::compose::{Composer, NagaModuleDescriptor, ShaderDefValue};
+
+use crate::quads::{
+    Billboard, GpuQuad, GpuQuadFlags, Quad, Quads, QuadsDebugView, QuadsDetailBlend,
+    QuadsPipelineKey, QuadsPlugin, MAX_QUAD_CORNERS,
+};
+
+/// Format of the images returned by [`render_once`]
+pub const RENDER_TARGET_FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+
+/// Environment variable that makes [`assert_image_matches`] overwrite the golden images with the
+/// rendered ones instead of comparing them
+pub const UPDATE_GOLDEN_ENV: &str = "BEVY_VERTEX_PULLING_UPDATE_GOLDEN";
+
+/// Environment variable with the directory [`assert_image_matches`] writes the actual and diff
+/// images to on a mismatch. Defaults to the directory of the golden image.
+pub const ARTIFACTS_DIR_ENV: &str = "BEVY_VERTEX_PULLING_TEST_ARTIFACTS";
+
+/// Frames rendered before giving up on the pipelines becoming ready
+const MAX_FRAMES: usize = 120;
+/// Frames rendered at least, so that resources prepared one frame late are included
+const MIN_FRAMES: usize = 3;
+
+const READBACK_NODE: &str = "quads_test_readback";
+
+const QUADS_SHADER_SOURCE: &str = include_str!("quads/quads.wgsl");
+
+/// The modules `quads.wgsl` is composed from, registered by the `QuadsPlugin` in an app
+const QUADS_SHADER_MODULES: [(&str, &str); 5] = [
+    (include_str!("quads/quads_types.wgsl"), "quads_types.wgsl"),
+    (
+        include_str!("quads/quads_bindings.wgsl"),
+        "quads_bindings.wgsl",
+    ),
+    (include_str!("quads/quads_unpack.wgsl"), "quads_unpack.wgsl"),
+    (
+        include_str!("quads/quads_billboard.wgsl"),
+        "quads_billboard.wgsl",
+    ),
+    (
+        include_str!("quads/quads_shading.wgsl"),
+        "quads_shading.wgsl",
+    ),
+];
+
+/// Builds a headless app, lets `app_setup` add its plugins and scene, and returns the frame
+/// rendered once all render pipelines are compiled.
+///
+/// Every camera is redirected to an offscreen [`RENDER_TARGET_FORMAT`] image of `size` pixels.
+/// MSAA is resolved and HDR cameras are tonemapped into that image as they would be into a
+/// window. Assets loaded through the `AssetServer` are not waited for, add them to their
+/// `Assets` collection directly instead.
+///
+/// # Panics
+///
+/// Panics if no adapter is available, a pipeline fails to compile or the pipelines are not ready
+/// after a few seconds worth of frames.
+pub fn render_once(app_setup: impl FnOnce(&mut App), size: UVec2) -> Image {
+    let mut headless = HeadlessApp::new(app_setup, size);
+    let data = headless.render_until_ready();
+    Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        RENDER_TARGET_FORMAT,
+    )
+}
+
+/// Like [`render_once`], but renders `frames` more frames once all render pipelines are compiled
+/// and returns the last one. Systems added by `app_setup` see every frame, so they can change the
+/// scene or record render world state while it is steady.
+///
+/// # Panics
+///
+/// Panics like [`render_once`].
+pub fn render_frames(app_setup: impl FnOnce(&mut App), size: UVec2, frames: usize) -> Image {
+    let mut headless = HeadlessApp::new(app_setup, size);
+    let mut data = headless.render_until_ready();
+    for _ in 0..frames {
+        headless.app.update();
+        data = headless.take_frame();
+    }
+    Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        RENDER_TARGET_FORMAT,
+    )
+}
+
+/// Like [`render_once`], but renders one more frame at each of `sizes` once all render pipelines
+/// are compiled at the first size, resizing the offscreen image in between, and returns those
+/// frames. Nothing is rendered for no sizes.
+///
+/// # Panics
+///
+/// Panics like [`render_once`], or if a frame is not read back at its new size.
+pub fn render_resized(app_setup: impl FnOnce(&mut App), sizes: &[UVec2]) -> Vec<Image> {
+    let Some(&first) = sizes.first() else {
+        return Vec::new();
+    };
+    let mut headless = HeadlessApp::new(app_setup, first);
+    headless.render_until_ready();
+    sizes
+        .iter()
+        .map(|&size| headless.render_resized(size))
+        .collect()
+}
+
+/// An app rendering every camera into an offscreen image that is read back after each frame
+struct HeadlessApp {
+    app: App,
+    target: Handle<Image>,
+    data: Arc<Mutex<Option<Vec<u8>>>>,
+}
+
+impl HeadlessApp {
+    fn new(app_setup: impl FnOnce(&mut App), size: UVec2) -> Self {
+        let mut wgpu_settings = WgpuSettings::default();
+        if let Some(power_preference) = wgpu::util::power_preference_from_env() {
+            wgpu_settings.power_preference = power_preference;
+        }
+
+        let mut app = App::new();
+        app.add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                })
+                .set(RenderPlugin { wgpu_settings })
+                // NOTE: Tests run several apps in one process, which would try to install the global
+                // logger more than once
+                .disable::<bevy::log::LogPlugin>()
+                .disable::<WinitPlugin>(),
+        );
+
+        let mut target = Image::new_fill(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 0],
+            RENDER_TARGET_FORMAT,
+        );
+        target.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT
+            | TextureUsages::COPY_SRC
+            | TextureUsages::TEXTURE_BINDING;
+        let target = app.world.resource_mut::<Assets<Image>>().add(target);
+        let data = Arc::new(Mutex::new(None));
+        app.add_plugins(ReadbackPlugin {
+            target: target.clone(),
+            data: data.clone(),
+        });
+
+        app_setup(&mut app);
+
+        while !app.ready() {
+            bevy::tasks::tick_global_task_pools_on_main_thread();
+        }
+        app.finish();
+        app.cleanup();
+
+        Self { app, target, data }
+    }
+
+    /// Renders frames until all render pipelines are compiled and returns the last frame's pixels
+    fn render_until_ready(&mut self) -> Vec<u8> {
+        let app = &mut self.app;
+        let mut settled_frames = 0;
+        let mut n_pipelines = 0;
+        for frame in 0..MAX_FRAMES {
+            app.update();
+
+            let pipeline_cache = app.sub_app(RenderApp).world.resource::<PipelineCache>();
+            let mut ready = true;
+            for pipeline in pipeline_cache.pipelines() {
+                match &pipeline.state {
+                    CachedPipelineState::Ok(_) => {}
+                    CachedPipelineState::Queued
+                    | CachedPipelineState::Err(
+                        PipelineCacheError::ShaderNotLoaded(_)
+                        | PipelineCacheError::ShaderImportNotYetAvailable,
+                    ) => ready = false,
+                    CachedPipelineState::Err(err) => panic!("a render pipeline failed: {err}"),
+                }
+            }
+            // NOTE: A pipeline specialized during this frame is only used from the next one
+            let count = pipeline_cache.pipelines().count();
+            if ready && count == n_pipelines {
+                settled_frames += 1;
+            } else {
+                settled_frames = 0;
+            }
+            n_pipelines = count;
+
+            if settled_frames >= 2 && frame + 1 >= MIN_FRAMES {
+                return self.take_frame();
+            }
+        }
+        panic!("the render pipelines were not ready after {MAX_FRAMES} frames");
+    }
+
+    /// Resizes the render target, renders a single frame and returns it
+    fn render_resized(&mut self, size: UVec2) -> Image {
+        self.app
+            .world
+            .resource_mut::<Assets<Image>>()
+            .get_mut(&self.target)
+            .unwrap()
+            .resize(Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            });
+        self.app.update();
+        let data = self.take_frame();
+        assert_eq!(
+            data.len(),
+            4 * (size.x * size.y) as usize,
+            "the render target was not read back at its new size"
+        );
+        Image::new(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            RENDER_TARGET_FORMAT,
+        )
+    }
+
+    fn take_frame(&mut self) -> Vec<u8> {
+        self.data
+            .lock()
+            .unwrap()
+            .take()
+            .expect("the render target was not read back")
+    }
+}
+
+/// Returns the color of the pixel at `x`, `y` with the origin in the top left corner. sRGB
+/// formats are decoded, the returned color is always in the [`Color::Rgba`] space.
+///
+/// # Panics
+///
+/// Panics if the pixel is out of bounds or the image format is not supported.
+pub fn pixel(image: &Image, x: u32, y: u32) -> Color {
+    let size = image.texture_descriptor.size;
+    assert!(
+        x < size.width && y < size.height,
+        "pixel ({x}, {y}) is outside of the {}x{} image",
+        size.width,
+        size.height
+    );
+    let index = (y * size.width + x) as usize;
+    let format = image.texture_descriptor.format;
+    match format {
+        TextureFormat::Rgba8UnormSrgb
+        | TextureFormat::Rgba8Unorm
+        | TextureFormat::Bgra8UnormSrgb
+        | TextureFormat::Bgra8Unorm => {
+            let bytes = &image.data[4 * index..4 * index + 4];
+            let [r, g, b, a] = match format {
+                TextureFormat::Bgra8UnormSrgb | TextureFormat::Bgra8Unorm => {
+                    [bytes[2], bytes[1], bytes[0], bytes[3]]
+                }
+                _ => [bytes[0], bytes[1], bytes[2], bytes[3]],
+            };
+            match format {
+                TextureFormat::Rgba8UnormSrgb | TextureFormat::Bgra8UnormSrgb => {
+                    Color::rgba_u8(r, g, b, a)
+                }
+                _ => Color::rgba_linear(
+                    r as f32 / 255.0,
+                    g as f32 / 255.0,
+                    b as f32 / 255.0,
+                    a as f32 / 255.0,
+                )
+                .as_rgba(),
+            }
+        }
+        TextureFormat::Rgba32Float => {
+            let [r, g, b, a]: [f32; 4] =
+                bytemuck::pod_read_unaligned(&image.data[16 * index..16 * index + 16]);
+            Color::rgba_linear(r, g, b, a).as_rgba()
+        }
+        _ => panic!("reading pixels of {format:?} images is not supported"),
+    }
+}
+
+/// Result of [`diff_images`] and [`compare_images`]
+pub struct ImageDiff {
+    /// Largest difference of any channel of any pixel, from 0 to 1 in sRGB space
+    pub max_difference: f32,
+    /// Number of pixels with a channel differing by more than the tolerance
+    pub mismatched_pixels: usize,
+    /// Number of pixels differing by more than the tolerance on edges ignored by
+    /// [`ImageTolerance::ignore_edges`], not counted as mismatched
+    pub ignored_pixels: usize,
+    /// The expected image darkened, with the mismatched pixels in red and the ignored ones in
+    /// yellow
+    pub image: Image,
+}
+
+/// How far a rendered image may be from the expected one, see [`compare_images`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImageTolerance {
+    /// Largest difference of a channel, from 0 to 1 in sRGB space, for a pixel to match
+    pub channel: f32,
+    /// Number of mismatched pixels accepted
+    pub max_mismatched_pixels: usize,
+    /// Ignore mismatches on the edges of the expected image, pixels with a neighbor differing by
+    /// more than `channel`. The coverage of MSAA resolved edges varies between GPUs and drivers.
+    pub ignore_edges: bool,
+}
+
+impl Default for ImageTolerance {
+    fn default() -> Self {
+        Self {
+            channel: 2.0 / 255.0,
+            max_mismatched_pixels: 0,
+            ignore_edges: true,
+        }
+    }
+}
+
+impl ImageTolerance {
+    /// Whether `diff` is within the tolerance
+    pub fn accepts(&self, diff: &ImageDiff) -> bool {
+        diff.mismatched_pixels <= self.max_mismatched_pixels
+    }
+}
+
+/// Compares two images of the same size channel by channel in sRGB space. Channels differing by
+/// at most `tolerance`, from 0 to 1, are considered equal.
+///
+/// # Panics
+///
+/// Panics if the sizes differ or a format is not supported by [`pixel`].
+pub fn diff_images(actual: &Image, expected: &Image, tolerance: f32) -> ImageDiff {
+    compare_images(
+        actual,
+        expected,
+        &ImageTolerance {
+            channel: tolerance,
+            max_mismatched_pixels: 0,
+            ignore_edges: false,
+        },
+    )
+}
+
+/// Like [`diff_images`], but leaves out the edges of the expected image if
+/// [`ImageTolerance::ignore_edges`] is set. Whether the result is acceptable is up to
+/// [`ImageTolerance::accepts`].
+///
+/// # Panics
+///
+/// Panics if the sizes differ or a format is not supported by [`pixel`].
+pub fn compare_images(actual: &Image, expected: &Image, tolerance: &ImageTolerance) -> ImageDiff {
+    let size = actual.texture_descriptor.size;
+    let expected_size = expected.texture_descriptor.size;
+    assert!(
+        size.width == expected_size.width && size.height == expected_size.height,
+        "the actual image is {}x{} but the expected one is {}x{}",
+        size.width,
+        size.height,
+        expected_size.width,
+        expected_size.height
+    );
+
+    let difference = |a: [f32; 4], b: [f32; 4]| {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f32::max)
+    };
+    let expected_pixels: Vec<[f32; 4]> = (0..size.height)
+        .flat_map(|y| (0..size.width).map(move |x| pixel(expected, x, y).as_rgba_f32()))
+        .collect();
+    let is_edge = |x: u32, y: u32| {
+        let e = expected_pixels[(y * size.width + x) as usize];
+        let xs = x.saturating_sub(1)..=(x + 1).min(size.width - 1);
+        xs.flat_map(|nx| {
+            (y.saturating_sub(1)..=(y + 1).min(size.height - 1)).map(move |ny| (nx, ny))
+        })
+        .any(|(nx, ny)| {
+            difference(e, expected_pixels[(ny * size.width + nx) as usize]) > tolerance.channel
+        })
+    };
+
+    let mut max_difference = 0.0f32;
+    let mut mismatched_pixels = 0;
+    let mut ignored_pixels = 0;
+    let mut data = Vec::with_capacity(4 * (size.width * size.height) as usize);
+    for y in 0..size.height {
+        for x in 0..size.width {
+            let a = pixel(actual, x, y).as_rgba_f32();
+            let e = expected_pixels[(y * size.width + x) as usize];
+            let difference = difference(a, e);
+            max_difference = max_difference.max(difference);
+            if difference > tolerance.channel && tolerance.ignore_edges && is_edge(x, y) {
+                ignored_pixels += 1;
+                data.extend_from_slice(&[255, 255, 0, 255]);
+            } else if difference > tolerance.channel {
+                mismatched_pixels += 1;
+                data.extend_from_slice(&[255, 0, 0, 255]);
+            } else {
+                let gray = (255.0 * 0.25 * (e[0] + e[1] + e[2]) / 3.0) as u8;
+                data.extend_from_slice(&[gray, gray, gray, 255]);
+            }
+        }
+    }
+
+    ImageDiff {
+        max_difference,
+        mismatched_pixels,
+        ignored_pixels,
+        image: Image::new(
+            Extent3d {
+                width: size.width,
+                height: size.height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            RENDER_TARGET_FORMAT,
+        ),
+    }
+}
+
+/// Loads a PNG file as an sRGB image
+pub fn load_png(path: impl AsRef<Path>) -> Image {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", path.display()));
+    Image::from_buffer(
+        &bytes,
+        ImageType::Extension("png"),
+        CompressedImageFormats::NONE,
+        true,
+    )
+    .unwrap_or_else(|err| panic!("failed to decode {}: {err}", path.display()))
+}
+
+/// Saves an 8-bit image as a PNG file, creating the parent directories
+pub fn save_png(image: &Image, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .unwrap_or_else(|err| panic!("failed to create {}: {err}", parent.display()));
+    }
+    image
+        .clone()
+        .try_into_dynamic()
+        .and_then(|image| Ok(image.save(path)?))
+        .unwrap_or_else(|err| panic!("failed to write {}: {err}", path.display()));
+}
+
+/// Writes `<name>.actual.png` and `<name>.diff.png` for a mismatch with the golden PNG at
+/// `golden` to the [`ARTIFACTS_DIR_ENV`] directory or next to the golden image, and returns
+/// their paths
+pub fn save_diff_artifacts(
+    actual: &Image,
+    diff: &ImageDiff,
+    golden: impl AsRef<Path>,
+) -> (PathBuf, PathBuf) {
+    let golden = golden.as_ref();
+    let artifacts_dir = std::env::var_os(ARTIFACTS_DIR_ENV)
+        .map(PathBuf::from)
+        .or_else(|| golden.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+    let name = golden
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let actual_path = artifacts_dir.join(format!("{name}.actual.png"));
+    let diff_path = artifacts_dir.join(format!("{name}.diff.png"));
+    save_png(actual, &actual_path);
+    save_png(&diff.image, &diff_path);
+    (actual_path, diff_path)
+}
+
+/// Asserts that `actual` matches the golden PNG at `golden` within `tolerance`, see
+/// [`diff_images`].
+///
+/// A missing golden image is created from `actual`, as are all golden images when the
+/// [`UPDATE_GOLDEN_ENV`] environment variable is set. On a mismatch `<name>.actual.png` and
+/// `<name>.diff.png` are written to the [`ARTIFACTS_DIR_ENV`] directory or next to the golden
+/// image before panicking.
+pub fn assert_image_matches(actual: &Image, golden: impl AsRef<Path>, tolerance: f32) {
+    let golden = golden.as_ref();
+    if std::env::var_os(UPDATE_GOLDEN_ENV).is_some() || !golden.exists() {
+        save_png(actual, golden);
+        return;
+    }
+
+    let diff = diff_images(actual, &load_png(golden), tolerance);
+    if diff.mismatched_pixels == 0 {
+        return;
+    }
+
+    let (actual_path, diff_path) = save_diff_artifacts(actual, &diff, golden);
+    panic!(
+        "{} pixels differ from {} by up to {} (tolerance {tolerance}), see {} and {}",
+        diff.mismatched_pixels,
+        golden.display(),
+        diff.max_difference,
+        actual_path.display(),
+        diff_path.display()
+    );
+}
+
+/// Shader defs the `PipelineCache` adds to every shader, with the limits of the default wgpu
+/// device
+fn pipeline_cache_shader_defs() -> Vec<ShaderDefVal> {
+    vec![ShaderDefVal::UInt(
+        "AVAILABLE_STORAGE_BUFFER_BINDINGS".into(),
+        wgpu::Limits::default().max_storage_buffers_per_shader_stage,
+    )]
+}
+
+/// Composes `quads.wgsl` for pipeline keys the way the `PipelineCache` does, but without a GPU.
+///
+/// The imported bevy shaders are collected from an app with the default plugins and no render
+/// backend, next to the crate's own modules. Composed shaders are validated by naga without any
+/// optional capabilities, so a specialization that only works on some adapters fails here as well.
+///
+/// ```no_run
+/// use bevy_vertex_pulling::{quads::QuadsPipelineKey, test_support::*};
+///
+/// let mut composer = QuadsShaderComposer::new();
+/// for key in quads_pipeline_keys() {
+///     composer.compose(key).unwrap_or_else(|err| panic!("{err}"));
+/// }
+/// let wgsl = composer.compose(QuadsPipelineKey::from_msaa_samples(4)).unwrap();
+/// assert_shader_snapshot(&wgsl, "tests/shaders/quads_default.wgsl");
+/// ```
+pub struct QuadsShaderComposer {
+    composer: Composer,
+    shaders: HashMap<ShaderImport, Shader>,
+}
+
+/// A specialization of `quads.wgsl` that failed to compose or validate
+#[derive(Debug)]
+pub struct QuadsShaderError {
+    pub key: QuadsPipelineKey,
+    /// All shader defs the shader was composed with, including the ones the `PipelineCache`
+    /// adds
+    pub shader_defs: Vec<ShaderDefVal>,
+    /// The error as reported by naga_oil or naga
+    pub message: String,
+}
+
+impl fmt::Display for QuadsShaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "quads.wgsl failed for {:?} with shader defs {:?}:\n{}",
+            self.key, self.shader_defs, self.message
+        )
+    }
+}
+
+impl std::error::Error for QuadsShaderError {}
+
+impl Default for QuadsShaderComposer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QuadsShaderComposer {
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                })
+                .set(RenderPlugin {
+                    wgpu_settings: WgpuSettings {
+                        backends: None,
+                        ..default()
+                    },
+                })
+                .disable::<bevy::log::LogPlugin>()
+                .disable::<WinitPlugin>(),
+        );
+        let shaders = app
+            .world
+            .resource::<Assets<Shader>>()
+            .iter()
+            .map(|(_, shader)| shader.clone())
+            .chain(
+                QUADS_SHADER_MODULES
+                    .iter()
+                    .map(|(source, path)| Shader::from_wgsl(*source, *path)),
+            )
+            .map(|shader| (shader.import_path().clone(), shader))
+            .collect();
+
+        Self {
+            composer: Composer::default().with_capabilities(Capabilities::empty()),
+            shaders,
+        }
+    }
+
+    /// Composes and validates `quads.wgsl` with the shader defs of `key`, returning the composed
+    /// module, imports included, as written back by naga.
+    ///
+    /// naga_oil adds the imported declarations in hash map order, so the top-level declarations
+    /// are sorted by their source to keep the output stable between runs.
+    pub fn compose(&mut self, key: QuadsPipelineKey) -> Result<String, QuadsShaderError> {
+        let shader = Shader::from_wgsl(QUADS_SHADER_SOURCE, "quads.wgsl");
+        let mut shader_defs = key.shader_defs();
+        shader_defs.extend(pipeline_cache_shader_defs());
+        let error = |message| QuadsShaderError {
+            key,
+            shader_defs: shader_defs.clone(),
+            message,
+        };
+        let naga_shader_defs: HashMap<_, _> = shader_defs
+            .iter()
+            .map(|def| match def.clone() {
+                ShaderDefVal::Bool(name, value) => (name, ShaderDefValue::Bool(value)),
+                ShaderDefVal::Int(name, value) => (name, ShaderDefValue::Int(value)),
+                ShaderDefVal::UInt(name, value) => (name, ShaderDefValue::UInt(value)),
+            })
+            .collect();
+
+        for import in shader.imports() {
+            self.add_import(import).map_err(error)?;
+        }
+        let module = self
+            .composer
+            .make_naga_module(NagaModuleDescriptor {
+                shader_defs: naga_shader_defs,
+                ..(&shader).into()
+            })
+            .map_err(|err| error(err.emit_to_string(&self.composer)))?;
+        let info = Validator::new(ValidationFlags::all(), Capabilities::empty())
+            .validate(&module)
+            .map_err(|err| error(format!("{err:?}")))?;
+        let wgsl = naga::back::wgsl::write_string(&module, &info, WriterFlags::empty())
+            .map_err(|err| error(err.to_string()))?;
+        Ok(sort_declarations(&wgsl))
+    }
+
+    /// Adds an imported module and its own imports to the composer, like the `PipelineCache`.
+    /// Errors are emitted as naga_oil reports them.
+    fn add_import(&mut self, import: &ShaderImport) -> Result<(), String> {
+        if self.composer.contains_module(&import.module_name()) {
+            return Ok(());
+        }
+        // NOTE: A missing module is reported by the composer when the importing shader is added
+        let Some(shader) = self.shaders.get(import).cloned() else {
+            return Ok(());
+        };
+        for import in shader.imports() {
+            self.add_import(import)?;
+        }
+        match self.composer.add_composable_module((&shader).into()) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(err.emit_to_string(&self.composer)),
+        }
+    }
+}
+
+/// Sorts the top-level declarations of WGSL written by naga. A declaration starts at an
+/// unindented line, unless that line closes a body or follows the declaration's attributes.
+/// Empty lines are dropped, declarations are separated by one.
+fn sort_declarations(wgsl: &str) -> String {
+    let mut declarations: Vec<Vec<&str>> = Vec::new();
+    let mut attributes = false;
+    for line in wgsl.lines() {
+        if line.is_empty() {
+            continue;
+        }
+        match declarations.last_mut() {
+            Some(declaration) if attributes || line == "}" || line.starts_with(' ') => {
+                declaration.push(line);
+            }
+            _ => declarations.push(vec![line]),
+        }
+        attributes = line.starts_with('@') && !line.trim_end().ends_with([';', '{']);
+    }
+    declarations.sort_unstable();
+    let declarations: Vec<_> = declarations
+        .iter()
+        .map(|declaration| declaration.join("\n"))
+        .collect();
+    declarations.join("\n\n") + "\n"
+}
+
+/// A curated matrix of pipeline keys for [`QuadsShaderComposer`].
+///
+/// Covers every combination of MSAA, debug view, detail texture blend, near fade, camera-relative
+/// positions, the flow field and the heatmap, every tonemapping method with and without deband
+/// dithering on top of the plain multisampled key, polygons with 8 and [`MAX_QUAD_CORNERS`]
+/// corners in every debug view, batch animations, HSV adjustments, masks, double-sided quads with
+/// and without translucency, dissolves and depth of field in every debug view,
+/// HSV adjustments with a detail texture, in-shader tonemapping and the heatmap, and half
+/// resolution batches with and without in-shader tonemapping. HDR only changes the target format and is left
+/// out.
+pub fn quads_pipeline_keys() -> Vec<QuadsPipelineKey> {
+    let debug_views = [
+        QuadsDebugView::Off,
+        QuadsDebugView::Wireframe,
+        QuadsDebugView::Overdraw,
+        QuadsDebugView::InstanceIndex,
+        QuadsDebugView::Normal,
+        QuadsDebugView::Uv,
+    ];
+    let detail_blends = [
+        None,
+        Some(QuadsDetailBlend::Multiply),
+        Some(QuadsDetailBlend::Overlay),
+    ];
+    let toggles = [
+        QuadsPipelineKey::NEAR_FADE,
+        QuadsPipelineKey::CAMERA_RELATIVE,
+        QuadsPipelineKey::FLOW_FIELD,
+        QuadsPipelineKey::HEATMAP,
+    ];
+
+    let mut keys = Vec::new();
+    for msaa_samples in [1, 4] {
+        for debug_view in debug_views {
+            for detail_blend in detail_blends {
+                for enabled in 0..1 << toggles.len() {
+                    let mut key = QuadsPipelineKey::from_msaa_samples(msaa_samples)
+                        | QuadsPipelineKey::from_debug_view(debug_view);
+                    if let Some(blend) = detail_blend {
+                        key |= QuadsPipelineKey::from_detail_blend(blend);
+                    }
+                    for (i, toggle) in toggles.iter().enumerate() {
+                        if enabled & (1 << i) != 0 {
+                            key |= *toggle;
+                        }
+                    }
+                    keys.push(key);
+                }
+            }
+        }
+    }
+
+    for tonemapping in [
+        Tonemapping::None,
+        Tonemapping::Reinhard,
+        Tonemapping::ReinhardLuminance,
+        Tonemapping::AcesFitted,
+        Tonemapping::AgX,
+        Tonemapping::SomewhatBoringDisplayTransform,
+        Tonemapping::TonyMcMapface,
+        Tonemapping::BlenderFilmic,
+    ] {
+        for deband_dither in [QuadsPipelineKey::empty(), QuadsPipelineKey::DEBAND_DITHER] {
+            keys.push(
+                QuadsPipelineKey::from_msaa_samples(4)
+                    | QuadsPipelineKey::TONEMAP_IN_SHADER
+                    | QuadsPipelineKey::from_tonemapping(tonemapping)
+                    | deband_dither,
+            );
+        }
+    }
+
+    for corners in [8, MAX_QUAD_CORNERS] {
+        for debug_view in debug_views {
+            keys.push(
+                QuadsPipelineKey::from_msaa_samples(4)
+                    | QuadsPipelineKey::from_corners(corners)
+                    | QuadsPipelineKey::from_debug_view(debug_view),
+            );
+        }
+    }
+
+    for extra in [
+        QuadsPipelineKey::BATCH_ANIMATION,
+        QuadsPipelineKey::HSV_ADJUST,
+        QuadsPipelineKey::MASK,
+        QuadsPipelineKey::DOUBLE_SIDED,
+        QuadsPipelineKey::DOUBLE_SIDED | QuadsPipelineKey::TRANSLUCENCY,
+        QuadsPipelineKey::PREMULTIPLIED_ALPHA,
+        QuadsPipelineKey::BLEND_CONSTANT,
+        QuadsPipelineKey::PREMULTIPLIED_ALPHA | QuadsPipelineKey::BLEND_CONSTANT,
+        QuadsPipelineKey::DISSOLVE,
+        QuadsPipelineKey::DEPTH_OF_FIELD,
+    ] {
+        for debug_view in debug_views {
+            keys.push(
+                QuadsPipelineKey::from_msaa_samples(4)
+                    | extra
+                    | QuadsPipelineKey::from_debug_view(debug_view),
+            );
+        }
+    }
+    for extra in [
+        QuadsPipelineKey::from_detail_blend(QuadsDetailBlend::Overlay),
+        QuadsPipelineKey::TONEMAP_IN_SHADER
+            | QuadsPipelineKey::from_tonemapping(Tonemapping::TonyMcMapface),
+        QuadsPipelineKey::HEATMAP,
+        QuadsPipelineKey::DISSOLVE | QuadsPipelineKey::BATCH_ANIMATION | QuadsPipelineKey::MASK,
+    ] {
+        keys.push(QuadsPipelineKey::from_msaa_samples(4) | QuadsPipelineKey::HSV_ADJUST | extra);
+    }
+
+    // NOTE: Half resolution batches are never drawn in a debug view or the heatmap
+    for msaa_samples in [1, 4] {
+        for tonemapping in [
+            QuadsPipelineKey::empty(),
+            QuadsPipelineKey::TONEMAP_IN_SHADER
+                | QuadsPipelineKey::from_tonemapping(Tonemapping::TonyMcMapface)
+                | QuadsPipelineKey::DEBAND_DITHER,
+        ] {
+            keys.push(
+                QuadsPipelineKey::from_msaa_samples(msaa_samples)
+                    | QuadsPipelineKey::HALF_RESOLUTION
+                    | tonemapping,
+            );
+        }
+    }
+    keys
+}
+
+/// Asserts that `wgsl` matches the snapshot file at `snapshot`.
+///
+/// Snapshots are only written when the [`UPDATE_GOLDEN_ENV`] environment variable is set, a
+/// missing snapshot fails like a mismatch. On a mismatch `<name>.actual.wgsl` is written to the
+/// [`ARTIFACTS_DIR_ENV`] directory or next to the snapshot before panicking.
+pub fn assert_shader_snapshot(wgsl: &str, snapshot: impl AsRef<Path>) {
+    let snapshot = snapshot.as_ref();
+    if std::env::var_os(UPDATE_GOLDEN_ENV).is_some() {
+        write_file(snapshot, wgsl);
+        return;
+    }
+    if !snapshot.exists() {
+        panic!(
+            "the shader snapshot {} is missing, set {UPDATE_GOLDEN_ENV} to create it",
+            snapshot.display()
+        );
+    }
+
+    let expected = std::fs::read_to_string(snapshot)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", snapshot.display()));
+    if wgsl == expected {
+        return;
+    }
+    let line = wgsl
+        .lines()
+        .zip(expected.lines())
+        .take_while(|(actual, expected)| actual == expected)
+        .count();
+
+    let artifacts_dir = std::env::var_os(ARTIFACTS_DIR_ENV)
+        .map(PathBuf::from)
+        .or_else(|| snapshot.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+    let name = snapshot
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let actual_path = artifacts_dir.join(format!("{name}.actual.wgsl"));
+    write_file(&actual_path, wgsl);
+    panic!(
+        "the shader differs from {} from line {}, see {}",
+        snapshot.display(),
+        line + 1,
+        actual_path.display()
+    );
+}
+
+/// Writes a text file, creating the parent directories
+fn write_file(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .unwrap_or_else(|err| panic!("failed to create {}: {err}", parent.display()));
+    }
+    std::fs::write(path, contents)
+        .unwrap_or_else(|err| panic!("failed to write {}: {err}", path.display()));
+}
+
+/// The view a quad is billboarded for, holding the values of bevy's view uniform that the quads
+/// shader reads
+#[derive(Clone, Debug)]
+pub struct BillboardView {
+    /// World transform of the camera, `view.view` in the shader
+    pub view: Mat4,
+    pub projection: Mat4,
+    /// Size of the viewport in physical pixels
+    pub viewport_size: Vec2,
+}
+
+impl BillboardView {
+    /// The view of a camera at `transform` with `projection` rendering into a target of `size`
+    /// pixels, like a `Camera3dBundle` in [`render_once`]
+    pub fn new(transform: &Transform, projection: &PerspectiveProjection, size: UVec2) -> Self {
+        let mut projection = projection.clone();
+        projection.update(size.x as f32, size.y as f32);
+        Self {
+            view: transform.compute_matrix(),
+            projection: projection.get_projection_matrix(),
+            viewport_size: size.as_vec2(),
+        }
+    }
+
+    pub fn view_proj(&self) -> Mat4 {
+        self.projection * self.view.inverse()
+    }
+
+    /// Pixel coordinates of a world position, with the origin in the top left corner like
+    /// [`pixel`]
+    pub fn world_to_pixel(&self, position: Vec3) -> Vec2 {
+        let ndc = self.view_proj().project_point3(position);
+        Vec2::new(ndc.x + 1.0, 1.0 - ndc.y) * 0.5 * self.viewport_size
+    }
+
+    fn ndc_to_world(&self, ndc: Vec3) -> Vec3 {
+        self.view_proj().inverse().project_point3(ndc)
+    }
+}
+
+/// World-space right, up and normal directions that the quads shader builds for a quad at `center`
+/// billboarded with `billboard`, mirroring the shader's math including its fallbacks for
+/// degenerate views. `None` for quads sized in screen space, which are offset in normalized device
+/// coordinates instead.
+pub fn billboard_basis(
+    view: &BillboardView,
+    billboard: &Billboard,
+    center: Vec3,
+) -> Option<[Vec3; 3]> {
+    let view_right = view.view.x_axis.truncate().normalize();
+    let view_up = view.view.y_axis.truncate().normalize();
+    let to_camera = view.view.w_axis.truncate() - center;
+    match billboard {
+        Billboard::None => Some([Vec3::X, Vec3::Y, Vec3::Z]),
+        Billboard::ViewY | Billboard::ViewYMinScreenSize { .. } => {
+            let normal = if to_camera.length_squared() > 1e-12 {
+                to_camera.normalize()
+            } else {
+                view.view.z_axis.truncate().normalize()
+            };
+            Some([view_right, view_up, normal])
+        }
+        Billboard::WorldY => {
+            let horizontal_right = view_right * Vec3::new(1.0, 0.0, 1.0);
+            let right = if horizontal_right.length_squared() > 1e-12 {
+                horizontal_right.normalize()
+            } else {
+                Vec3::Y.cross(to_camera).normalize()
+            };
+            let right = if right.cross(Vec3::Y).dot(to_camera) < 0.0 {
+                -right
+            } else {
+                right
+            };
+            Some([right, Vec3::Y, right.cross(Vec3::Y)])
+        }
+        Billboard::OrientedY { orientation } => {
+            let up = orientation.normalize() * Vec3::Y;
+            let axis_right = up.cross(to_camera);
+            let right = if axis_right.length_squared() > 1e-12 {
+                axis_right.normalize()
+            } else {
+                (view_right - up * view_right.dot(up)).normalize()
+            };
+            Some([right, up, right.cross(up)])
+        }
+        Billboard::FixedScreenSize
+        | Billboard::ViewportFraction
+        | Billboard::CocScaled
+        | Billboard::ClipSpace => None,
+    }
+}
+
+/// World-space corners of `quad` as the quads shader places them for `view`, in vertex order:
+/// bottom left, bottom right, top left and top right in the quad's own axes. Corners of quads
+/// sized in screen space are unprojected from normalized device coordinates.
+///
+/// Only the four corner quad is covered. Spin, depth offsets, [`QuadsOrigin`], the near fade, the
+/// flow field and batch animations are not applied, and [`Billboard::CocScaled`] quads are placed
+/// in focus.
+///
+/// [`QuadsOrigin`]: crate::quads::QuadsOrigin
+pub fn billboard_corners(view: &BillboardView, quad: &Quad) -> [Vec3; 4] {
+    let quad_scale = quad.scale.max(0.0);
+    let half_extents = quad.half_extents.abs().truncate() * quad_scale;
+    let offsets = [
+        Vec2::new(-1.0, -1.0),
+        Vec2::new(1.0, -1.0),
+        Vec2::new(-1.0, 1.0),
+        Vec2::new(1.0, 1.0),
+    ]
+    .map(|unit| unit * half_extents);
+    match &quad.billboard {
+        Billboard::FixedScreenSize | Billboard::ViewportFraction | Billboard::CocScaled => {
+            let center = view.view_proj().project_point3(quad.center);
+            let scale = match quad.billboard {
+                Billboard::ViewportFraction => {
+                    2.0 * Vec2::new(view.viewport_size.y / view.viewport_size.x, 1.0)
+                }
+                _ => 2.0 / view.viewport_size,
+            };
+            offsets.map(|offset| view.ndc_to_world(center + (offset * scale).extend(0.0)))
+        }
+        Billboard::ClipSpace => {
+            offsets.map(|offset| view.ndc_to_world(quad.center + offset.extend(0.0)))
+        }
+        billboard => {
+            let [right, up, _] = billboard_basis(view, billboard, quad.center).unwrap();
+            let mut scale = 1.0;
+            if let Billboard::ViewYMinScreenSize { min_half_extent } = billboard {
+                if half_extents.y > 0.0 {
+                    let w = (view.view_proj() * quad.center.extend(1.0)).w;
+                    let pixels_per_unit = 0.5 * view.viewport_size.y * view.projection.y_axis.y / w;
+                    scale = f32::max(
+                        1.0,
+                        min_half_extent.max(0.0) * quad_scale / (half_extents.y * pixels_per_unit),
+                    );
+                }
+            }
+            offsets.map(|offset| quad.center + (right * offset.x + up * offset.y) * scale)
+        }
+    }
+}
+
+/// Texture coordinates the quads shader gives the corners of `quad` in the order of
+/// [`billboard_corners`], before the quad's uv rotation, tiling and scrolling. Like in the shader
+/// they are mirrored by the flags the upload sets for negative half-extents.
+pub fn billboard_uvs(quad: &Quad) -> [Vec2; 4] {
+    let flags =
+        GpuQuadFlags::from_bits_truncate(bytemuck::cast::<_, [u32; 32]>(GpuQuad::from(quad))[3]);
+    [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(0.0, 1.0),
+        Vec2::new(1.0, 1.0),
+    ]
+    .map(|xy| {
+        // NOTE: Texture v coordinates point down while the quad's y points up
+        let mut uv = Vec2::new(xy.x, 1.0 - xy.y);
+        if flags.contains(GpuQuadFlags::FLIP_X) {
+            uv.x = 1.0 - uv.x;
+        }
+        if flags.contains(GpuQuadFlags::FLIP_Y) {
+            uv.y = 1.0 - uv.y;
+        }
+        uv
+    })
+}
+
+/// Checks the invariants of the corners [`billboard_corners`] predicts for a quad in front of the
+/// camera, and returns a description of the first one that is violated:
+///
+/// - the corners are finite
+/// - they form a planar rectangle
+/// - its edges have the lengths the half-extents ask for, measured in world units or in pixels
+///   depending on the billboard mode, and [`Billboard::ViewYMinScreenSize`] quads are scaled up
+///   to exactly their minimum height on screen when they would be smaller
+/// - quads of every mode except [`Billboard::None`] are wound counter-clockwise on screen, so
+///   back-face culling keeps them
+///
+/// The expected sizes are measured by projecting the corners, independently of the formulas the
+/// shader uses to place them.
+pub fn check_billboard(view: &BillboardView, quad: &Quad) -> Result<(), String> {
+    let corners = billboard_corners(view, quad);
+    if !corners.iter().all(|corner| corner.is_finite()) {
+        return Err(format!("the corners {corners:?} are not finite"));
+    }
+
+    let x_edge = corners[1] - corners[0];
+    let y_edge = corners[2] - corners[0];
+    let size = x_edge.length() + y_edge.length();
+    // NOTE: Single precision positions are only exact to a few ulps of their magnitude, which
+    // dominates for tiny quads far from the origin
+    let tolerance = 1e-3 * size + 1e-5 * corners[0].length().max(1.0);
+    if (corners[3] - corners[1] - y_edge).length() > tolerance {
+        return Err(format!(
+            "the corners {corners:?} do not form a planar parallelogram"
+        ));
+    }
+    if x_edge.dot(y_edge).abs() > 1e-3 * x_edge.length() * y_edge.length() + tolerance * size {
+        return Err(format!(
+            "the edges {x_edge} and {y_edge} are not perpendicular"
+        ));
+    }
+
+    let pixels = corners.map(|corner| view.world_to_pixel(corner));
+    let pixel_edges = Vec2::new(pixels[1].distance(pixels[0]), pixels[2].distance(pixels[0]));
+    let quad_scale = quad.scale.max(0.0);
+    let half_extents = quad.half_extents.abs().truncate() * quad_scale;
+    let (actual, expected, unit) = match &quad.billboard {
+        Billboard::None | Billboard::ViewY | Billboard::WorldY | Billboard::OrientedY { .. } => (
+            Vec2::new(x_edge.length(), y_edge.length()),
+            2.0 * half_extents,
+            "world units",
+        ),
+        Billboard::ViewYMinScreenSize { min_half_extent } => {
+            // NOTE: The quad is parallel to the image plane, so its height on screen is exact
+            let [_, up, _] = billboard_basis(view, &quad.billboard, quad.center).unwrap();
+            let height = view
+                .world_to_pixel(quad.center + up * half_extents.y)
+                .distance(view.world_to_pixel(quad.center - up * half_extents.y));
+            let scale = if height > 0.0 {
+                f32::max(1.0, 2.0 * min_half_extent.max(0.0) * quad_scale / height)
+            } else {
+                1.0
+            };
+            (
+                Vec2::new(x_edge.length(), y_edge.length()),
+                2.0 * half_extents * scale,
+                "world units",
+            )
+        }
+        Billboard::FixedScreenSize | Billboard::CocScaled => {
+            (pixel_edges, 2.0 * half_extents, "pixels")
+        }
+        Billboard::ViewportFraction => (
+            pixel_edges,
+            2.0 * half_extents * view.viewport_size.y,
+            "pixels",
+        ),
+        Billboard::ClipSpace => (pixel_edges, half_extents * view.viewport_size, "pixels"),
+    };
+    if (actual - expected).abs().max_element() > 1e-3 * expected.max_element() + 1e-3 {
+        return Err(format!(
+            "the edges are {actual} {unit} long instead of {expected}"
+        ));
+    }
+
+    if !matches!(quad.billboard, Billboard::None) {
+        let ndc = corners.map(|corner| view.view_proj().project_point3(corner).truncate());
+        let area = (ndc[0] - ndc[2]).perp_dot(ndc[1] - ndc[2]);
+        if area < -1e-6 {
+            return Err(format!(
+                "the corners {pixels:?} are wound clockwise on screen and back-face culled"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Views and quads to run [`check_billboard`] on. The edge cases of the billboard math come first:
+/// cameras looking straight down, rolled on their side or straight along an [`OrientedY`] axis, and
+/// zero half-extents. They are followed by `count` pseudo-random views and quads of every mode,
+/// the same ones on every call.
+///
+/// [`OrientedY`]: Billboard::OrientedY
+pub fn billboard_test_cases(count: usize) -> Vec<(BillboardView, Quad)> {
+    let size = UVec2::new(1280, 720);
+    let projection = PerspectiveProjection::default();
+    let view = |transform: Transform| BillboardView::new(&transform, &projection, size);
+    let tilted = Quat::from_rotation_z(0.4) * Quat::from_rotation_x(0.3);
+    let quad = |billboard: Billboard, half_extents: Vec3| Quad {
+        half_extents,
+        billboard,
+        ..default()
+    };
+
+    let straight_down =
+        Transform::from_xyz(0.0, 10.0, 0.0).with_rotation(Quat::from_rotation_x(-FRAC_PI_2));
+    let on_its_side =
+        Transform::from_xyz(0.0, 0.0, 10.0).with_rotation(Quat::from_rotation_z(FRAC_PI_2));
+    let mut cases = Vec::new();
+    for transform in [straight_down, on_its_side] {
+        for billboard in [
+            Billboard::ViewY,
+            Billboard::WorldY,
+            Billboard::OrientedY {
+                orientation: tilted,
+            },
+            Billboard::ViewYMinScreenSize {
+                min_half_extent: 20.0,
+            },
+        ] {
+            cases.push((view(transform), quad(billboard, Vec3::new(1.0, 0.5, 0.0))));
+        }
+        cases.push((
+            view(transform),
+            quad(Billboard::FixedScreenSize, Vec3::new(16.0, 8.0, 0.0)),
+        ));
+    }
+    cases.push((
+        view(Transform::from_translation(tilted * Vec3::Y * 10.0).looking_at(Vec3::ZERO, Vec3::X)),
+        quad(
+            Billboard::OrientedY {
+                orientation: tilted,
+            },
+            Vec3::new(1.0, 0.5, 0.0),
+        ),
+    ));
+    cases.push((
+        view(Transform::from_xyz(0.0, 0.0, 10.0)),
+        quad(Billboard::ViewY, Vec3::ZERO),
+    ));
+
+    // NOTE: A small xorshift generator keeps the cases reproducible without a dependency on rand
+    let mut random = XorShift(0x2545_f491);
+    for _ in 0..count {
+        let center = random.vec3(5.0);
+        let eye = center + random.vec3(20.0);
+        let roll = Quat::from_rotation_z(random.vec3(PI).x);
+        let billboard = match (random.next() * 8.0) as u32 {
+            0 => Billboard::None,
+            1 => Billboard::ViewY,
+            2 => Billboard::WorldY,
+            3 => Billboard::OrientedY {
+                orientation: Quat::from_scaled_axis(random.vec3(PI)),
+            },
+            4 => Billboard::ViewYMinScreenSize {
+                min_half_extent: 40.0 * random.next(),
+            },
+            5 => Billboard::FixedScreenSize,
+            6 => Billboard::ViewportFraction,
+            _ => Billboard::ClipSpace,
+        };
+        let half_extents = match billboard {
+            Billboard::FixedScreenSize => random.vec3(64.0),
+            Billboard::ViewportFraction | Billboard::ClipSpace => random.vec3(0.5),
+            _ => random.vec3(3.0),
+        };
+        let center = match billboard {
+            Billboard::ClipSpace => Vec3::new(random.next() - 0.5, random.next() - 0.5, 0.5),
+            _ => center,
+        };
+        let mut transform = Transform::from_translation(eye).looking_at(center, Vec3::Y);
+        transform.rotation *= roll;
+        // NOTE: Quads facing away from the camera are culled, and so are the others seen from
+        // behind
+        if matches!(billboard, Billboard::None) && eye.z < center.z {
+            continue;
+        }
+        // NOTE: Every other quad is scaled, which has to give the same result as larger
+        // half-extents
+        let scale = if cases.len() % 2 == 0 {
+            1.0
+        } else {
+            0.25 + 2.0 * random.next()
+        };
+        // NOTE: A quad in world units reaching past the camera has corners behind it, which no
+        // billboard mode handles
+        let world_units = !matches!(
+            billboard,
+            Billboard::FixedScreenSize | Billboard::ViewportFraction | Billboard::ClipSpace
+        );
+        if world_units && eye.distance(center) <= half_extents.truncate().length() * scale {
+            continue;
+        }
+        cases.push((
+            view(transform),
+            Quad {
+                center,
+                scale,
+                ..quad(billboard, half_extents)
+            },
+        ));
+    }
+    cases
+}
+
+struct XorShift(u32);
+
+impl XorShift {
+    /// Uniformly distributed in `0..1`
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 >> 8) as f32 / (1 << 24) as f32
+    }
+
+    /// Uniformly distributed in a cube with a half size of `scale`
+    fn vec3(&mut self, scale: f32) -> Vec3 {
+        (Vec3::new(self.next(), self.next(), self.next()) * 2.0 - 1.0) * scale
+    }
+}
+
+/// Renders `quad` alone in white with [`render_once`], seen from a camera at `camera` with the
+/// default perspective projection, and asserts that it covers exactly the area
+/// [`billboard_corners`] predicts. The pixels `tolerance` pixels inside every corner along the
+/// diagonals have to be covered and the pixels as far outside of them must not be. The quad has to
+/// be a few times larger than the tolerance on screen.
+pub fn assert_billboard_rendered(camera: Transform, quad: &Quad, size: UVec2, tolerance: f32) {
+    let view = BillboardView::new(&camera, &PerspectiveProjection::default(), size);
+    let corners = billboard_corners(&view, quad).map(|corner| view.world_to_pixel(corner));
+    let quad = Quad {
+        color: Color::WHITE,
+        lit: false,
+        ..quad.clone()
+    };
+    let image = render_once(
+        |app| {
+            app.insert_resource(ClearColor(Color::BLACK))
+                .add_plugins(QuadsPlugin::default());
+            app.world.spawn(Camera3dBundle {
+                transform: camera,
+                tonemapping: Tonemapping::None,
+                ..default()
+            });
+            let mut quads = Quads::default();
+            quads.insert(quad);
+            app.insert_resource(quads);
+        },
+        size,
+    );
+
+    assert_corners_covered(&image, corners, Vec2::ZERO, size.as_vec2(), tolerance);
+}
+
+/// Asserts that the pixels `tolerance` pixels inside the `corners` of a quad are covered and the
+/// pixels as far outside of them are not, skipping those outside of `min` to `max`. The corners
+/// are in pixels like [`BillboardView::world_to_pixel`] returns them, and covered pixels have a
+/// red channel above 0.5.
+pub fn assert_corners_covered(
+    image: &Image,
+    corners: [Vec2; 4],
+    min: Vec2,
+    max: Vec2,
+    tolerance: f32,
+) {
+    let center = corners.iter().sum::<Vec2>() / 4.0;
+    for corner in corners {
+        let inward = (center - corner).normalize_or_zero() * tolerance;
+        for (position, expected) in [(corner + inward, true), (corner - inward, false)] {
+            if position.cmplt(min).any() || position.cmpge(max).any() {
+                continue;
+            }
+            let covered = pixel(image, position.x as u32, position.y as u32).r() > 0.5;
+            assert!(
+                covered == expected,
+                "the pixel at {position} {} covered by the quad, the predicted corners are {corners:?}",
+                if covered { "is" } else { "is not" }
+            );
+        }
+    }
+}
+
+/// Redirects all cameras to `target` and copies it into `data` after every frame
+struct ReadbackPlugin {
+    target: Handle<Image>,
+    data: Arc<Mutex<Option<Vec<u8>>>>,
+}
+
+#[derive(Clone, Resource)]
+struct ReadbackTarget(Handle<Image>);
+
+#[derive(Clone, Resource)]
+struct ReadbackData(Arc<Mutex<Option<Vec<u8>>>>);
+
+#[derive(Resource)]
+struct ReadbackBuffer {
+    buffer: Buffer,
+    size: UVec2,
+    padded_bytes_per_row: u32,
+}
+
+impl Plugin for ReadbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ReadbackTarget(self.target.clone()))
+            .add_systems(PostUpdate, target_image.before(CameraUpdateSystem));
+
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app
+            .insert_resource(ReadbackTarget(self.target.clone()))
+            .insert_resource(ReadbackData(self.data.clone()))
+            .add_systems(
+                Render,
+                (
+                    prepare_readback_buffer.in_set(RenderSet::Prepare),
+                    read_back.in_set(RenderSet::Cleanup),
+                ),
+            );
+
+        let mut graph = render_app.world.resource_mut::<RenderGraph>();
+        graph.add_node(READBACK_NODE, ReadbackNode);
+        graph.add_node_edge(CAMERA_DRIVER, READBACK_NODE);
+    }
+}
+
+fn target_image(target: Res<ReadbackTarget>, mut cameras: Query<&mut Camera, Added<Camera>>) {
+    for mut camera in &mut cameras {
+        camera.target = RenderTarget::Image(target.0.clone());
+    }
+}
+
+fn prepare_readback_buffer(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    target: Res<ReadbackTarget>,
+    images: Res<RenderAssets<Image>>,
+    buffer: Option<Res<ReadbackBuffer>>,
+) {
+    let Some(image) = images.get(&target.0) else {
+        return;
+    };
+    let size = image.size.as_uvec2();
+    if buffer.is_some_and(|buffer| buffer.size == size) {
+        return;
+    }
+    // NOTE: Rows copied into a buffer must be aligned to 256 bytes
+    let padded_bytes_per_row = (4 * size.x).next_multiple_of(256);
+    commands.insert_resource(ReadbackBuffer {
+        buffer: render_device.create_buffer(&BufferDescriptor {
+            label: Some("quads_test_readback_buffer"),
+            size: (padded_bytes_per_row * size.y) as u64,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        }),
+        size,
+        padded_bytes_per_row,
+    });
+}
+
+struct ReadbackNode;
+
+impl Node for ReadbackNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Some(buffer) = world.get_resource::<ReadbackBuffer>() else {
+            return Ok(());
+        };
+        let target = world.resource::<ReadbackTarget>();
+        let Some(image) = world.resource::<RenderAssets<Image>>().get(&target.0) else {
+            return Ok(());
+        };
+        if image.size.as_uvec2() != buffer.size {
+            return Ok(());
+        }
+
+        render_context.command_encoder().copy_texture_to_buffer(
+            image.texture.as_image_copy(),
+            ImageCopyBuffer {
+                buffer: &buffer.buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(buffer.padded_bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            Extent3d {
+                width: buffer.size.x,
+                height: buffer.size.y,
+                depth_or_array_layers: 1,
+            },
+        );
+        Ok(())
+    }
+}
+
+/// Runs after the frame was submitted and waits for the copy to finish
+fn read_back(
+    render_device: Res<RenderDevice>,
+    buffer: Option<Res<ReadbackBuffer>>,
+    data: Res<ReadbackData>,
+) {
+    let Some(buffer) = buffer else {
+        return;
+    };
+    let slice = buffer.buffer.slice(..);
+    slice.map_async(MapMode::Read, |result| {
+        result.expect("failed to map the readback buffer");
+    });
+    render_device.poll(wgpu::Maintain::Wait);
+
+    let row_bytes = 4 * buffer.size.x as usize;
+    let mut pixels = Vec::with_capacity(row_bytes * buffer.size.y as usize);
+    {
+        let mapped = slice.get_mapped_range();
+        for row in mapped.chunks_exact(buffer.padded_bytes_per_row as usize) {
+            pixels.extend_from_slice(&row[..row_bytes]);
+        }
+    }
+    buffer.buffer.unmap();
+    *data.0.lock().unwrap() = Some(pixels);
+}
@@ -0,0 +1,107 @@
+//! Tests of the buffer allocation decisions of the quads
+
+use bevy_vertex_pulling::quads::{
+    QuadsAllocatorConfig, QuadsAllocatorEventKind, QuadsBuffer, QuadsShrinkPolicy,
+};
+
+/// Sizes in elements a batch changes through: growing in small and large steps, shrinking,
+/// emptying and staying the same
+const SIZES: [usize; 12] = [0, 10, 11, 100, 50, 101, 0, 1000, 3, 3, 200, 150];
+
+/// Runs the allocation decisions of `config` for a buffer of elements of `element_size` bytes
+/// whose contents change through the scripted `sizes` in elements, starting out without a
+/// buffer. Returns the events of every decision, or a description of the first one that is wrong.
+///
+/// Every upload has to fit into the capacity and only drop the elements beyond the budget of
+/// [`QuadsAllocatorConfig::max_retained_bytes`], which the capacity stays within. A buffer is
+/// reallocated only when its contents don't fit or, with [`QuadsShrinkPolicy::Release`], they fit
+/// into a smaller buffer with room to grow, and it is reallocated with
+/// [`QuadsAllocatorConfig::growth_factor`] times the size of its contents.
+fn check_allocator(
+    config: &QuadsAllocatorConfig,
+    element_size: u64,
+    sizes: &[usize],
+) -> Result<Vec<QuadsAllocatorEventKind>, String> {
+    let max_len = config
+        .max_retained_bytes
+        .map_or(u64::MAX, |bytes| bytes / element_size.max(1));
+    let mut capacity = 0;
+    let mut events = Vec::new();
+    for (step, &required) in sizes.iter().enumerate() {
+        let allocation = config.allocate(element_size, capacity, required);
+        let error = |problem: &str| {
+            Err(format!(
+                "step {step} from {capacity} to {required} elements {problem}: {allocation:?}"
+            ))
+        };
+        let len = (required as u64).min(max_len) as usize;
+        let grown = ((len as f64 * config.growth_factor.max(1.0) as f64).ceil() as u64)
+            .clamp(len as u64, max_len) as usize;
+        if allocation.len != len {
+            return error(&format!(
+                "uploaded {} elements instead of {len}",
+                allocation.len
+            ));
+        }
+        if allocation.capacity < allocation.len {
+            return error("has less capacity than it uploads");
+        }
+        if allocation.capacity as u64 > max_len {
+            return error("exceeds the budget");
+        }
+        let release = config.shrink_policy == QuadsShrinkPolicy::Release && len > 0;
+        let reallocate = capacity < len || (release && grown < capacity);
+        if allocation.reallocates() != reallocate {
+            return error(if reallocate {
+                "keeps a buffer it should reallocate"
+            } else {
+                "reallocates a buffer it could keep"
+            });
+        }
+        if allocation.reallocates() && allocation.capacity != grown {
+            return error(&format!("was not reallocated for {grown} elements"));
+        }
+        events.extend(
+            allocation
+                .events(QuadsBuffer::Instances)
+                .map(|event| event.kind),
+        );
+        capacity = allocation.capacity;
+    }
+    Ok(events)
+}
+
+#[test]
+fn allocations_follow_the_config() {
+    for growth_factor in [1.0, 1.5, 2.0, 0.5, f32::NAN] {
+        for max_retained_bytes in [None, Some(128 * 100), Some(0)] {
+            for shrink_policy in [QuadsShrinkPolicy::Retain, QuadsShrinkPolicy::Release] {
+                let config = QuadsAllocatorConfig {
+                    growth_factor,
+                    max_retained_bytes,
+                    shrink_policy,
+                };
+                if let Err(err) = check_allocator(&config, 128, &SIZES) {
+                    panic!("{config:?}: {err}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn release_reallocates_less_often_with_room_to_grow() {
+    let reallocations = |growth_factor| {
+        let config = QuadsAllocatorConfig {
+            growth_factor,
+            shrink_policy: QuadsShrinkPolicy::Release,
+            ..Default::default()
+        };
+        let events = check_allocator(&config, 128, &SIZES).unwrap_or_else(|err| panic!("{err}"));
+        events
+            .iter()
+            .filter(|event| !matches!(event, QuadsAllocatorEventKind::Overflow { .. }))
+            .count()
+    };
+    assert!(reallocations(2.0) < reallocations(1.0));
+}
@@ -0,0 +1,108 @@
+//! Tests of the CPU reference of the quads shader's billboarding and of rendered billboards, run
+//! with `cargo test --features test_support`
+
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::prelude::*;
+use bevy_vertex_pulling::{prelude::*, test_support::*};
+
+#[test]
+fn billboards_keep_their_invariants() {
+    let errors: Vec<_> = billboard_test_cases(1000)
+        .into_iter()
+        .filter_map(|(view, quad)| {
+            let err = check_billboard(&view, &quad).err()?;
+            Some(format!("{quad:?} seen from {:?}: {err}", view.view))
+        })
+        .collect();
+    assert!(errors.is_empty(), "{}", errors.join("\n"));
+}
+
+#[test]
+fn rendered_billboards_match_the_reference() {
+    let size = UVec2::new(160, 120);
+    // NOTE: Rolled and looking down at the quads, so that every mode turns them differently
+    let mut camera = Transform::from_xyz(2.0, 3.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y);
+    camera.rotation *= Quat::from_rotation_z(0.3);
+    for (billboard, half_extents) in [
+        (Billboard::None, Vec3::new(1.0, 0.6, 0.0)),
+        (Billboard::ViewY, Vec3::new(1.0, 0.6, 0.0)),
+        (Billboard::WorldY, Vec3::new(1.0, 0.6, 0.0)),
+        (
+            Billboard::OrientedY {
+                orientation: Quat::from_rotation_x(FRAC_PI_4),
+            },
+            Vec3::new(1.0, 0.6, 0.0),
+        ),
+        (
+            Billboard::ViewYMinScreenSize {
+                min_half_extent: 20.0,
+            },
+            Vec3::new(0.2, 0.1, 0.0),
+        ),
+        (Billboard::FixedScreenSize, Vec3::new(30.0, 20.0, 0.0)),
+        (Billboard::ViewportFraction, Vec3::new(0.3, 0.2, 0.0)),
+        (Billboard::ClipSpace, Vec3::new(0.4, 0.3, 0.0)),
+    ] {
+        let center = match billboard {
+            Billboard::ClipSpace => Vec3::new(0.1, -0.2, 0.5),
+            _ => Vec3::ZERO,
+        };
+        let quad = Quad {
+            center,
+            half_extents,
+            billboard,
+            ..default()
+        };
+        assert_billboard_rendered(camera, &quad, size, 2.0);
+    }
+}
+
+/// Every combination of signs of the three half-extents
+fn sign_combinations() -> impl Iterator<Item = Vec3> {
+    (0..8).map(|bits| {
+        Vec3::select(
+            BVec3::new(bits & 1 != 0, bits & 2 != 0, bits & 4 != 0),
+            Vec3::NEG_ONE,
+            Vec3::ONE,
+        )
+    })
+}
+
+#[test]
+fn negative_half_extents_mirror_the_texture() {
+    for (view, quad) in billboard_test_cases(200) {
+        let unsigned = Quad {
+            half_extents: quad.half_extents.abs(),
+            ..quad
+        };
+        let corners = billboard_corners(&view, &unsigned);
+        let uvs = billboard_uvs(&unsigned);
+        for signs in sign_combinations() {
+            let mirrored = Quad {
+                half_extents: unsigned.half_extents * signs,
+                ..unsigned.clone()
+            };
+            // NOTE: The quad keeps its facing and size, so back-face culling keeps it as well
+            assert_eq!(
+                billboard_corners(&view, &mirrored),
+                corners,
+                "{signs} mirrored {mirrored:?} moved its corners"
+            );
+            if let Err(err) = check_billboard(&view, &mirrored) {
+                panic!("{signs} mirrored {mirrored:?}: {err}");
+            }
+            let expected = uvs.map(|uv| {
+                Vec2::new(
+                    if signs.x < 0.0 { 1.0 - uv.x } else { uv.x },
+                    if signs.y < 0.0 { 1.0 - uv.y } else { uv.y },
+                )
+            });
+            assert_eq!(
+                billboard_uvs(&mirrored),
+                expected,
+                "{signs} mirrored {mirrored:?} has the wrong texture coordinates"
+            );
+        }
+    }
+}
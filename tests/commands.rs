@@ -0,0 +1,163 @@
+//! Stress test of applying quad commands pushed from many threads
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_vertex_pulling::quads::{
+    GpuQuad, Quad, QuadCommand, QuadCommandKeys, Quads, QuadsCommandQueue,
+};
+
+const THREADS: usize = 4;
+const COMMANDS_PER_THREAD: usize = 1_000_000;
+const COMMANDS_PER_FRAME: usize = 100_000;
+
+struct XorShift(u32);
+
+impl XorShift {
+    /// Uniformly distributed in `0..1`
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 >> 8) as f32 / (1 << 24) as f32
+    }
+
+    /// Uniformly distributed in a cube with a half size of `scale`
+    fn vec3(&mut self, scale: f32) -> Vec3 {
+        (Vec3::new(self.next(), self.next(), self.next()) * 2.0 - 1.0) * scale
+    }
+}
+
+/// Keys every producer of [`check_quad_commands`] works on
+const COMMAND_KEYS_PER_THREAD: u64 = 1024;
+
+/// Pushes `commands_per_thread` pseudo-random [`QuadCommand`]s from each of `threads` threads
+/// into a [`QuadsCommandQueue::bounded`] to `commands_per_frame`, while another thread applies the
+/// queue like the frames of an app. Returns the number of frames it took, or a description of the
+/// first quad that differs from applying the commands of every thread on their own.
+///
+/// Every thread works on keys of its own, so the result does not depend on how the threads were
+/// interleaved, only on the commands of each thread being applied in order. A final
+/// [`QuadCommand::Clear`] has to remove all quads.
+fn check_quad_commands(
+    threads: usize,
+    commands_per_thread: usize,
+    commands_per_frame: usize,
+) -> Result<usize, String> {
+    let queue = QuadsCommandQueue::bounded(commands_per_frame);
+    let producers = (0..threads as u64)
+        .map(|thread| {
+            let queue = queue.clone();
+            std::thread::spawn(move || {
+                let mut random = XorShift(0x9e37_79b9 ^ (thread as u32 + 1));
+                let mut expected = HashMap::new();
+                for step in 0..commands_per_thread {
+                    let key = thread * COMMAND_KEYS_PER_THREAD
+                        + (random.next() * COMMAND_KEYS_PER_THREAD as f32) as u64;
+                    let quad = Quad {
+                        color: Color::rgb(random.next(), random.next(), random.next()),
+                        center: Vec3::new(key as f32, step as f32, 0.0),
+                        half_extents: random.vec3(1.0),
+                        ..default()
+                    };
+                    let command = match (random.next() * 5.0) as u32 {
+                        0 => {
+                            expected.insert(key, quad.clone());
+                            QuadCommand::Insert { key, quad }
+                        }
+                        1 => {
+                            if let Some(expected) = expected.get_mut(&key) {
+                                *expected = quad.clone();
+                            }
+                            QuadCommand::Set { key, quad }
+                        }
+                        2 => {
+                            expected.remove(&key);
+                            QuadCommand::Remove { key }
+                        }
+                        3 => {
+                            if let Some(expected) = expected.get_mut(&key) {
+                                expected.color = quad.color;
+                            }
+                            QuadCommand::SetColor {
+                                key,
+                                color: quad.color,
+                            }
+                        }
+                        _ => {
+                            if let Some(expected) = expected.get_mut(&key) {
+                                expected.center = quad.center;
+                            }
+                            QuadCommand::SetCenter {
+                                key,
+                                center: quad.center,
+                            }
+                        }
+                    };
+                    queue.push(command);
+                }
+                expected
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut quads = Quads::default();
+    let mut keys = QuadCommandKeys::default();
+    let mut frames = 0;
+    while !producers.iter().all(|producer| producer.is_finished()) || !queue.is_empty() {
+        if queue.is_empty() {
+            std::thread::yield_now();
+            continue;
+        }
+        queue.apply(&mut quads, &mut keys);
+        frames += 1;
+    }
+    let mut expected_count = 0;
+    for producer in producers {
+        let expected = producer
+            .join()
+            .map_err(|_| "a producer thread panicked".to_string())?;
+        expected_count += expected.len();
+        for (key, expected) in expected {
+            let Some(quad) = keys.get(key).and_then(|id| quads.get(id)) else {
+                return Err(format!("the quad of key {key} is missing"));
+            };
+            if bytemuck::bytes_of(&GpuQuad::from(quad))
+                != bytemuck::bytes_of(&GpuQuad::from(&expected))
+            {
+                return Err(format!(
+                    "the quad of key {key} is {quad:?} instead of {expected:?}"
+                ));
+            }
+        }
+    }
+    if keys.len() != expected_count || quads.data.len() != expected_count {
+        return Err(format!(
+            "there are {} keys and {} quads instead of {expected_count}",
+            keys.len(),
+            quads.data.len()
+        ));
+    }
+
+    queue.push(QuadCommand::Clear);
+    queue.apply(&mut quads, &mut keys);
+    if !quads.data.is_empty() || !keys.is_empty() {
+        return Err(format!(
+            "{} quads and {} keys are left after clearing",
+            quads.data.len(),
+            keys.len()
+        ));
+    }
+    Ok(frames)
+}
+
+#[test]
+fn commands_from_many_threads_apply_in_order() {
+    let frames = check_quad_commands(THREADS, COMMANDS_PER_THREAD, COMMANDS_PER_FRAME)
+        .unwrap_or_else(|err| panic!("{err}"));
+    // NOTE: A bounded queue never holds more than a frame's worth of commands
+    assert!(
+        frames >= THREADS * COMMANDS_PER_THREAD / COMMANDS_PER_FRAME,
+        "{frames} frames applied more than {COMMANDS_PER_FRAME} commands each"
+    );
+}
@@ -0,0 +1,683 @@
+//! Rendered tests of the quads plugin, run with `cargo test --features test_support`
+
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    core_pipeline::{clear_color::ClearColorConfig, tonemapping::Tonemapping},
+    prelude::*,
+    render::{
+        camera::Viewport,
+        render_phase::RenderPhase,
+        render_resource::{CachedPipelineState, PipelineCache, PipelineDescriptor, TextureFormat},
+        renderer::{RenderAdapterInfo, RenderInstance},
+        Render, RenderApp, RenderSet,
+    },
+    window::{PrimaryWindow, WindowResolution},
+};
+use bevy_vertex_pulling::{
+    prelude::*,
+    quads::{QuadsHeatmap, QuadsMask, QuadsPhaseItem},
+    test_support::*,
+};
+use wgpu::Backend;
+
+const SIZE: UVec2 = UVec2::new(128, 64);
+
+/// A quad covering the whole view
+fn fullscreen_quad(color: Color) -> Quad {
+    Quad {
+        color,
+        center: Vec3::new(0.0, 0.0, 0.5),
+        half_extents: Vec3::new(1.0, 1.0, 0.0),
+        billboard: Billboard::ClipSpace,
+        ..default()
+    }
+}
+
+/// A camera drawing into `viewport` of the target, which is left or right half
+fn half_camera(order: isize, right: bool, hdr: bool) -> Camera3dBundle {
+    Camera3dBundle {
+        camera: Camera {
+            order,
+            hdr,
+            viewport: Some(Viewport {
+                physical_position: UVec2::new(if right { SIZE.x / 2 } else { 0 }, 0),
+                physical_size: UVec2::new(SIZE.x / 2, SIZE.y),
+                ..default()
+            }),
+            ..default()
+        },
+        camera_3d: Camera3d {
+            // NOTE: Like in any split screen, only the first camera clears the target
+            clear_color: if order == 0 {
+                ClearColorConfig::Custom(Color::BLACK)
+            } else {
+                ClearColorConfig::None
+            },
+            ..default()
+        },
+        tonemapping: Tonemapping::None,
+        ..default()
+    }
+}
+
+/// Records the color target formats of the compiled quads pipelines every frame
+fn record_quads_pipeline_formats(app: &mut App) -> Arc<Mutex<Vec<TextureFormat>>> {
+    let formats = Arc::new(Mutex::new(Vec::new()));
+    let recorded = formats.clone();
+    app.sub_app_mut(RenderApp).add_systems(
+        Render,
+        (move |pipeline_cache: Res<PipelineCache>| {
+            *recorded.lock().unwrap() = pipeline_cache
+                .pipelines()
+                .filter(|pipeline| matches!(pipeline.state, CachedPipelineState::Ok(_)))
+                .filter_map(|pipeline| match &pipeline.descriptor {
+                    PipelineDescriptor::RenderPipelineDescriptor(descriptor)
+                        if descriptor.label.as_deref() == Some("quads_pipeline") =>
+                    {
+                        Some(descriptor.fragment.as_ref()?.targets[0].as_ref()?.format)
+                    }
+                    _ => None,
+                })
+                .collect();
+        })
+        .in_set(RenderSet::Cleanup),
+    );
+    formats
+}
+
+#[test]
+fn specializes_per_view_target_format() {
+    let mut formats = None;
+    let image = render_once(
+        |app| {
+            app.add_plugins(QuadsPlugin::default());
+            formats = Some(record_quads_pipeline_formats(app));
+            // NOTE: Like two windows with different surface formats, the HDR camera renders
+            // into an Rgba16Float main texture and the other one into an sRGB texture
+            app.world.spawn(half_camera(0, false, false));
+            app.world.spawn(half_camera(1, true, true));
+            let mut quads = Quads::default();
+            quads.insert(fullscreen_quad(Color::RED));
+            app.insert_resource(quads);
+        },
+        SIZE,
+    );
+
+    // NOTE: Cameras with different main texture formats on one target each write their whole
+    // main texture to it, so only the view of the last camera is left to check
+    assert_eq!(pixel(&image, 3 * SIZE.x / 4, SIZE.y / 2), Color::RED);
+    let formats = formats.unwrap().lock().unwrap().clone();
+    assert!(
+        formats.contains(&TextureFormat::Rgba16Float),
+        "no quads pipeline for the HDR view: {formats:?}"
+    );
+    assert!(
+        formats.iter().any(|format| format.is_srgb()),
+        "no quads pipeline for the LDR view: {formats:?}"
+    );
+}
+
+/// Renders a red quad covering the view with a blue UI panel over its left half
+fn render_quad_under_panel(plugin: QuadsPlugin, msaa: Msaa) -> Image {
+    render_once(
+        |app| {
+            app.insert_resource(msaa)
+                .insert_resource(ClearColor(Color::BLACK))
+                .add_plugins(plugin);
+            // NOTE: The UI is laid out for the primary window, which is not rendered to without
+            // the winit plugin
+            app.world.spawn((
+                Window {
+                    resolution: WindowResolution::new(SIZE.x as f32, SIZE.y as f32)
+                        .with_scale_factor_override(1.0),
+                    ..default()
+                },
+                PrimaryWindow,
+            ));
+            app.world.spawn(Camera3dBundle {
+                tonemapping: Tonemapping::None,
+                ..default()
+            });
+            let mut quads = Quads::default();
+            quads.insert(fullscreen_quad(Color::RED));
+            app.insert_resource(quads);
+            // A panel over the left half of the quad
+            app.world.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Percent(50.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: Color::BLUE.into(),
+                ..default()
+            });
+        },
+        SIZE,
+    )
+}
+
+#[test]
+fn draws_ui_over_quads() {
+    let image = render_quad_under_panel(QuadsPlugin::default(), Msaa::default());
+    assert_eq!(pixel(&image, SIZE.x / 4, SIZE.y / 2), Color::BLUE);
+    assert_eq!(pixel(&image, 3 * SIZE.x / 4, SIZE.y / 2), Color::RED);
+}
+
+#[test]
+fn draws_quads_over_ui() {
+    let over_ui = QuadsPlugin {
+        over_ui: true,
+        ..default()
+    };
+    let image = render_quad_under_panel(over_ui, Msaa::Off);
+    assert_eq!(pixel(&image, SIZE.x / 4, SIZE.y / 2), Color::RED);
+    assert_eq!(pixel(&image, 3 * SIZE.x / 4, SIZE.y / 2), Color::RED);
+
+    // NOTE: A multisampled pass would overwrite the UI, the quads are skipped instead
+    let over_ui = QuadsPlugin {
+        over_ui: true,
+        ..default()
+    };
+    let image = render_quad_under_panel(over_ui, Msaa::Sample4);
+    assert_eq!(pixel(&image, SIZE.x / 4, SIZE.y / 2), Color::BLUE);
+    assert_eq!(pixel(&image, 3 * SIZE.x / 4, SIZE.y / 2), Color::BLACK);
+}
+
+/// Renders `quads` filled with one screen-filling `quad` over a blue background and returns the
+/// linear color in the middle of the target
+fn render_fullscreen_quad(quad: Quad, quads: Quads) -> Vec4 {
+    let image = render_once(
+        |app| {
+            app.insert_resource(ClearColor(Color::rgb_linear(0.0, 0.0, 1.0)))
+                .add_plugins(QuadsPlugin::default());
+            app.world.spawn(Camera3dBundle {
+                tonemapping: Tonemapping::None,
+                ..default()
+            });
+            let mut quads = quads;
+            quads.insert(quad);
+            app.insert_resource(quads);
+        },
+        SIZE,
+    );
+    Vec4::from(pixel(&image, SIZE.x / 2, SIZE.y / 2).as_linear_rgba_f32())
+}
+
+fn assert_color_near(actual: Vec4, expected: Vec4) {
+    // NOTE: The sRGB target quantizes dark values more coarsely than bright ones
+    assert!(
+        actual.abs_diff_eq(expected, 0.01),
+        "the color is {actual} instead of {expected}"
+    );
+}
+
+#[test]
+fn premultiplies_colors_on_upload() {
+    let straight = Vec4::new(1.0, 0.5, 0.25, 0.5);
+    // NOTE: The components of quad colors are written to the target as they are
+    let quad = Quad {
+        color: Color::rgba(straight.x, straight.y, straight.z, straight.w),
+        lit: false,
+        ..fullscreen_quad(Color::NONE)
+    };
+
+    // Drawn opaque, the premultiplied color replaces the background
+    let premultiplied = render_fullscreen_quad(
+        quad.clone(),
+        Quads {
+            alpha_mode: QuadsAlphaMode::PremultiplyOnUpload,
+            ..default()
+        },
+    );
+    assert_color_near(
+        premultiplied,
+        (straight.truncate() * straight.w).extend(straight.w),
+    );
+    let unchanged = render_fullscreen_quad(quad.clone(), Quads::default());
+    assert_color_near(unchanged, straight);
+
+    // Blended with premultiplied alpha, the result is the straight alpha blend of the color
+    let blended = render_fullscreen_quad(
+        quad,
+        Quads {
+            alpha_mode: QuadsAlphaMode::PremultipliedBlend,
+            ..default()
+        },
+    );
+    let background = Vec3::new(0.0, 0.0, 1.0);
+    let expected = straight.truncate() * straight.w + background * (1.0 - straight.w);
+    assert_color_near(blended.truncate().extend(1.0), expected.extend(1.0));
+}
+
+#[test]
+fn idle_views_get_no_quads_phase() {
+    // NOTE: Before render_once stops at the third frame, as an idle app has no pipelines to wait
+    // for
+    const ACTIVATION_FRAME: usize = 2;
+    let mut phases = None;
+    let image = render_once(
+        |app| {
+            app.add_plugins(QuadsPlugin::default());
+            app.world.spawn(Camera3dBundle {
+                tonemapping: Tonemapping::None,
+                ..default()
+            });
+            // NOTE: The render world's frames extract the main world's frames in order
+            let recorded = Arc::new(Mutex::new(Vec::new()));
+            phases = Some(recorded.clone());
+            app.sub_app_mut(RenderApp).add_systems(
+                Render,
+                (move |views: Query<(), With<RenderPhase<QuadsPhaseItem>>>| {
+                    recorded.lock().unwrap().push(views.iter().count());
+                })
+                .in_set(RenderSet::PhaseSort),
+            );
+            app.insert_resource(Quads::default()).add_systems(
+                Update,
+                |mut quads: ResMut<Quads>, mut frame: Local<usize>| {
+                    if *frame == ACTIVATION_FRAME {
+                        quads.insert(fullscreen_quad(Color::RED));
+                    }
+                    *frame += 1;
+                },
+            );
+        },
+        SIZE,
+    );
+
+    let phases = phases.unwrap().lock().unwrap().clone();
+    assert!(
+        phases[..ACTIVATION_FRAME].iter().all(|&phases| phases == 0),
+        "the views got a quads phase without quads: {phases:?}"
+    );
+    assert_eq!(
+        phases[ACTIVATION_FRAME], 1,
+        "the view did not get a quads phase in the frame the quads were added: {phases:?}"
+    );
+    assert_eq!(pixel(&image, SIZE.x / 2, SIZE.y / 2), Color::RED);
+}
+
+/// Renders `quads` in white with two cameras side by side, `cameras[0]` in the left and
+/// `cameras[1]` in the right half of the `size` target, and asserts like
+/// [`assert_billboard_rendered`] that every quad covers the area [`billboard_corners`] predicts
+/// for each camera's own viewport. `clear` is passed on to [`QuadsPlugin::clear`] and has to be
+/// dark, the right camera must not clear what the left camera drew.
+fn assert_split_screen_rendered(
+    cameras: [Transform; 2],
+    quads: &[Quad],
+    size: UVec2,
+    clear: Option<QuadsClear>,
+    tolerance: f32,
+) {
+    let viewport_size = UVec2::new(size.x / 2, size.y);
+    let image = render_once(
+        |app| {
+            app.insert_resource(ClearColor(Color::BLACK))
+                .add_plugins(QuadsPlugin { clear, ..default() });
+            for (order, transform) in cameras.into_iter().enumerate() {
+                app.world.spawn(Camera3dBundle {
+                    camera: Camera {
+                        viewport: Some(Viewport {
+                            physical_position: UVec2::new(order as u32 * viewport_size.x, 0),
+                            physical_size: viewport_size,
+                            ..default()
+                        }),
+                        order: order as isize,
+                        ..default()
+                    },
+                    camera_3d: Camera3d {
+                        // NOTE: Like in any split screen, only the first camera clears the target
+                        clear_color: if order == 0 {
+                            ClearColorConfig::Default
+                        } else {
+                            ClearColorConfig::None
+                        },
+                        ..default()
+                    },
+                    transform,
+                    tonemapping: Tonemapping::None,
+                    ..default()
+                });
+            }
+            let mut white_quads = Quads::default();
+            for quad in quads {
+                white_quads.insert(Quad {
+                    color: Color::WHITE,
+                    lit: false,
+                    ..quad.clone()
+                });
+            }
+            app.insert_resource(white_quads);
+        },
+        size,
+    );
+
+    for (order, transform) in cameras.iter().enumerate() {
+        let view = BillboardView::new(transform, &PerspectiveProjection::default(), viewport_size);
+        let offset = Vec2::new((order as u32 * viewport_size.x) as f32, 0.0);
+        for quad in quads {
+            let corners =
+                billboard_corners(&view, quad).map(|corner| view.world_to_pixel(corner) + offset);
+            assert_corners_covered(
+                &image,
+                corners,
+                offset,
+                offset + viewport_size.as_vec2(),
+                tolerance,
+            );
+        }
+    }
+}
+
+#[test]
+fn quads_clear_keeps_to_the_viewport() {
+    let cameras = [
+        Transform::from_xyz(0.0, 1.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y),
+        Transform::from_xyz(4.0, 2.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ];
+    let quads = [
+        Quad {
+            center: Vec3::new(-1.0, 0.0, 0.0),
+            half_extents: Vec3::new(0.6, 0.6, 0.0),
+            billboard: Billboard::ViewY,
+            ..default()
+        },
+        Quad {
+            center: Vec3::new(1.0, 0.0, 0.0),
+            half_extents: Vec3::new(8.0, 8.0, 0.0),
+            billboard: Billboard::FixedScreenSize,
+            ..default()
+        },
+    ];
+    // NOTE: Clearing the whole target for the right camera would erase the left camera's quads
+    for clear in [None, Some(QuadsClear::default())] {
+        assert_split_screen_rendered(cameras, &quads, UVec2::new(256, 96), clear, 2.0);
+    }
+}
+
+#[test]
+fn fixed_screen_size_quads_follow_viewport_changes() {
+    const HALF_EXTENT: f32 = 10.0;
+    let viewports = [
+        Viewport {
+            physical_position: UVec2::ZERO,
+            physical_size: SIZE,
+            ..default()
+        },
+        Viewport {
+            physical_position: UVec2::ZERO,
+            physical_size: SIZE / 2,
+            ..default()
+        },
+    ];
+    let image = render_frames(
+        |app| {
+            app.insert_resource(ClearColor(Color::BLACK))
+                .add_plugins(QuadsPlugin::default());
+            app.world.spawn(Camera3dBundle {
+                transform: Transform::from_xyz(0.0, 0.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+                tonemapping: Tonemapping::None,
+                ..default()
+            });
+            let mut quads = Quads::default();
+            quads.insert(Quad {
+                color: Color::WHITE,
+                half_extents: Vec3::new(HALF_EXTENT, HALF_EXTENT, 0.0),
+                billboard: Billboard::FixedScreenSize,
+                lit: false,
+                ..default()
+            });
+            app.insert_resource(quads);
+            // NOTE: Every frame is rendered with another viewport than the one before it
+            let viewports = viewports.clone();
+            app.add_systems(
+                Update,
+                move |mut cameras: Query<&mut Camera>, mut frame: Local<usize>| {
+                    cameras.single_mut().viewport = Some(viewports[*frame % 2].clone());
+                    *frame += 1;
+                },
+            );
+        },
+        SIZE,
+        5,
+    );
+
+    let covered: Vec<_> = (0..SIZE.y)
+        .flat_map(|y| (0..SIZE.x).map(move |x| UVec2::new(x, y)))
+        .filter(|&position| pixel(&image, position.x, position.y).r() > 0.5)
+        .collect();
+    let min = covered
+        .iter()
+        .fold(UVec2::MAX, |min, &position| min.min(position));
+    let max = covered
+        .iter()
+        .fold(UVec2::ZERO, |max, &position| max.max(position));
+    let size = (max + 1 - min).as_vec2();
+    assert!(
+        (size - 2.0 * HALF_EXTENT).abs().max_element() <= 1.0,
+        "the quad is {size} pixels large instead of {}",
+        2.0 * HALF_EXTENT
+    );
+    let center = (min + max + 1).as_vec2() / 2.0;
+    assert!(
+        viewports.iter().any(|viewport| {
+            let viewport_center =
+                (viewport.physical_position + viewport.physical_size / 2).as_vec2();
+            center.distance(viewport_center) <= 1.0
+        }),
+        "the quad at {center} is in the center of neither viewport"
+    );
+}
+
+/// Renders a white [`Billboard::FixedScreenSize`] quad with `half_extent` pixel half-extents in
+/// the center of the target, resizes the target to each of `sizes` in turn and asserts that the
+/// quad is `2 * half_extent` pixels wide and high, within a pixel, in the first frame rendered at
+/// every size. Resizes in between frames must not leave the quad at the size of the old viewport.
+fn assert_resized_target_rendered(sizes: &[UVec2], half_extent: f32) {
+    let images = render_resized(
+        |app| {
+            app.insert_resource(ClearColor(Color::BLACK))
+                .add_plugins(QuadsPlugin::default());
+            app.world.spawn(Camera3dBundle {
+                transform: Transform::from_xyz(0.0, 0.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+                tonemapping: Tonemapping::None,
+                ..default()
+            });
+            let mut quads = Quads::default();
+            quads.insert(Quad {
+                color: Color::WHITE,
+                half_extents: Vec3::new(half_extent, half_extent, 0.0),
+                billboard: Billboard::FixedScreenSize,
+                lit: false,
+                ..default()
+            });
+            app.insert_resource(quads);
+        },
+        sizes,
+    );
+
+    let expected = 2.0 * half_extent;
+    for (&size, image) in sizes.iter().zip(&images) {
+        let center = size / 2;
+        let width = (0..size.x)
+            .filter(|&x| pixel(image, x, center.y).r() > 0.5)
+            .count();
+        let height = (0..size.y)
+            .filter(|&y| pixel(image, center.x, y).r() > 0.5)
+            .count();
+        for (axis, measured) in [("wide", width), ("high", height)] {
+            assert!(
+                (measured as f32 - expected).abs() <= 1.0,
+                "the quad is {measured} pixels {axis} instead of {expected} in the first frame \
+                rendered at {size}"
+            );
+        }
+    }
+}
+
+#[test]
+fn resized_targets_keep_fixed_screen_size_quads() {
+    assert_resized_target_rendered(
+        &[
+            UVec2::new(128, 64),
+            UVec2::new(256, 192),
+            UVec2::new(64, 64),
+            UVec2::new(300, 100),
+        ],
+        16.0,
+    );
+}
+
+#[test]
+fn draws_half_resolution_quads() {
+    let color = Vec4::new(1.0, 0.5, 0.25, 1.0);
+    let quad = Quad {
+        color: Color::rgba(color.x, color.y, color.z, color.w),
+        lit: false,
+        ..fullscreen_quad(Color::NONE)
+    };
+    let half = render_fullscreen_quad(
+        quad,
+        Quads {
+            resolution: QuadsResolution::Half,
+            ..default()
+        },
+    );
+    assert_color_near(half, color);
+}
+
+/// Number of buffers and textures alive on the device of the adapter in use
+fn gpu_resource_counts(instance: &RenderInstance, adapter: &RenderAdapterInfo) -> (usize, usize) {
+    let report = instance.generate_report();
+    let hub = match adapter.backend {
+        #[cfg(any(
+            windows,
+            all(
+                unix,
+                not(target_os = "emscripten"),
+                not(target_os = "ios"),
+                not(target_os = "macos")
+            )
+        ))]
+        Backend::Vulkan => report.vulkan,
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        Backend::Metal => report.metal,
+        #[cfg(windows)]
+        Backend::Dx12 => report.dx12,
+        #[cfg(windows)]
+        Backend::Dx11 => report.dx11,
+        Backend::Gl => report.gl,
+        backend => panic!("no resource report for the {backend:?} backend"),
+    }
+    .expect("the backend in use has no resource report");
+    (hub.buffers.num_occupied, hub.textures.num_occupied)
+}
+
+/// A camera despawned once the churn reaches `despawn_frame`
+#[derive(Component)]
+struct ChurnedCamera {
+    despawn_frame: usize,
+}
+
+/// Spawns a camera every 10 frames that lives for 20, 50 cameras over 500 frames, and asserts
+/// that the buffers and textures on the device stay the same
+fn assert_camera_churn_is_stable(setup: impl FnOnce(&mut App)) {
+    /// Frames between two spawned cameras, each lives for two periods so they overlap
+    const PERIOD: usize = 10;
+    const CAMERAS: usize = 50;
+    const FRAMES: usize = PERIOD * CAMERAS;
+
+    let counts = Arc::new(Mutex::new(Vec::new()));
+    let recorded = counts.clone();
+    render_frames(
+        |app| {
+            app.add_plugins(QuadsPlugin::default());
+            setup(app);
+            app.world
+                .resource_mut::<Quads>()
+                .insert(fullscreen_quad(Color::RED));
+            app.world.spawn((
+                Camera3dBundle::default(),
+                ChurnedCamera { despawn_frame: 0 },
+            ));
+            app.add_systems(
+                Update,
+                |mut commands: Commands,
+                 cameras: Query<(Entity, &ChurnedCamera)>,
+                 mut frame: Local<usize>| {
+                    for (entity, camera) in &cameras {
+                        if camera.despawn_frame == *frame {
+                            commands.entity(entity).despawn();
+                        }
+                    }
+                    // NOTE: Alternating HDR also churns the view target formats
+                    if frame.is_multiple_of(PERIOD) {
+                        commands.spawn((
+                            Camera3dBundle {
+                                camera: Camera {
+                                    order: (*frame / PERIOD) as isize,
+                                    hdr: *frame / PERIOD % 2 == 1,
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            ChurnedCamera {
+                                despawn_frame: *frame + 2 * PERIOD,
+                            },
+                        ));
+                    }
+                    *frame += 1;
+                },
+            );
+            app.sub_app_mut(RenderApp).add_systems(
+                Render,
+                (move |instance: Res<RenderInstance>, adapter: Res<RenderAdapterInfo>| {
+                    recorded
+                        .lock()
+                        .unwrap()
+                        .push(gpu_resource_counts(&instance, &adapter));
+                })
+                .in_set(RenderSet::Cleanup),
+            );
+        },
+        SIZE,
+        FRAMES,
+    );
+
+    // NOTE: Compared between windows of whole periods, as the counts change with the cameras
+    // alive within one
+    let counts = counts.lock().unwrap();
+    let churned = &counts[counts.len() - FRAMES..];
+    let max = |frames: &[(usize, usize)]| {
+        frames.iter().fold((0, 0), |(buffers, textures), &counts| {
+            (buffers.max(counts.0), textures.max(counts.1))
+        })
+    };
+    let early = max(&churned[FRAMES / 5..FRAMES / 5 + 10 * PERIOD]);
+    let late = max(&churned[FRAMES - 10 * PERIOD..]);
+    assert!(
+        late.0 <= early.0 && late.1 <= early.1,
+        "the buffers and textures grew from {early:?} to {late:?} while {CAMERAS} cameras were \
+        spawned and despawned"
+    );
+}
+
+#[test]
+fn camera_churn_frees_half_resolution_and_mask_textures() {
+    assert_camera_churn_is_stable(|app| {
+        app.insert_resource(QuadsMask).insert_resource(Quads {
+            resolution: QuadsResolution::Half,
+            ..default()
+        });
+    });
+}
+
+// NOTE: The heatmap replaces the mask and the half resolution batches, so it is churned on its own
+#[test]
+fn camera_churn_frees_heatmap_textures() {
+    assert_camera_churn_is_stable(|app| {
+        app.insert_resource(QuadsHeatmap::default())
+            .insert_resource(Quads::default());
+    });
+}
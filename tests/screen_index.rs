@@ -0,0 +1,175 @@
+//! Tests of the screen-space queries on the quads, run with `cargo test --features test_support`
+
+use std::{
+    f32::consts::{FRAC_PI_2, FRAC_PI_4},
+    sync::{Arc, Mutex},
+};
+
+use bevy::prelude::*;
+use bevy_vertex_pulling::{
+    prelude::*,
+    quads::{QuadsDeclutter, QuadsScreenIndex},
+    test_support::*,
+};
+
+const SIZE: UVec2 = UVec2::new(160, 120);
+
+/// Cameras rolled around their view axis, which turns the world's x and y axes on screen
+fn rolled_cameras() -> [Transform; 2] {
+    let mut straight = Transform::from_xyz(0.0, 0.0, 10.0);
+    straight.rotation *= Quat::from_rotation_z(FRAC_PI_2);
+    let mut oblique = Transform::from_xyz(6.0, 4.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y);
+    oblique.rotation *= Quat::from_rotation_z(FRAC_PI_4);
+    [straight, oblique]
+}
+
+/// Renders `quads` seen from `camera` and returns what `record` read from the world in the last
+/// frame, after the screen index was updated
+fn record_last_frame<T: Send + 'static>(
+    camera: Transform,
+    quads: Quads,
+    setup: impl FnOnce(&mut App, Entity),
+    record: impl FnMut(&mut World) -> T + Send + Sync + 'static,
+) -> T {
+    let recorded = Arc::new(Mutex::new(None));
+    let last = recorded.clone();
+    let record = Mutex::new(record);
+    render_once(
+        |app| {
+            app.add_plugins(QuadsPlugin::default())
+                .insert_resource(QuadsScreenIndex::default())
+                .insert_resource(quads);
+            let camera = app
+                .world
+                .spawn(Camera3dBundle {
+                    transform: camera,
+                    ..default()
+                })
+                .id();
+            setup(app, camera);
+            app.add_systems(Last, move |world: &mut World| {
+                *last.lock().unwrap() = Some((record.lock().unwrap())(world));
+            });
+        },
+        SIZE,
+    );
+    let last = recorded.lock().unwrap().take();
+    last.expect("no frame was recorded")
+}
+
+#[test]
+fn queries_find_quads_under_rolled_cameras() {
+    let centers: Vec<_> = (-2..=2)
+        .map(|i| Vec3::new(i as f32 * 2.0, i as f32 * 0.5, 0.0))
+        .collect();
+    let half_extents = Vec3::new(0.5, 0.5, 0.0);
+    for camera in rolled_cameras() {
+        let mut quads = Quads::default();
+        for &center in &centers {
+            quads.insert(Quad {
+                center,
+                half_extents,
+                billboard: Billboard::ViewY,
+                ..default()
+            });
+        }
+        let centers = centers.clone();
+        let results = record_last_frame(
+            camera,
+            quads,
+            |_, _| {},
+            move |world| {
+                let (entity, camera, transform) = world
+                    .query::<(Entity, &Camera, &GlobalTransform)>()
+                    .single(world);
+                let index = world.resource::<QuadsScreenIndex>();
+                centers
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &center)| {
+                        // NOTE: ViewY quads face the camera, so their corners are as far from their
+                        // center on screen as a point that far to the camera's right
+                        let radius = half_extents.truncate().length();
+                        let pixel = camera.world_to_viewport(transform, center).unwrap();
+                        let edge = camera
+                            .world_to_viewport(transform, center + transform.right() * radius)
+                            .unwrap();
+                        let hits: Vec<_> = index
+                            .query_point(entity, pixel)
+                            .map(|quad| quad.index)
+                            .collect();
+                        let rect = index.quads(entity).iter().find(|quad| quad.index == i);
+                        (
+                            pixel,
+                            pixel.distance(edge),
+                            hits,
+                            rect.map(|quad| quad.rect),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            },
+        );
+
+        for (i, (pixel, radius, hits, rect)) in results.into_iter().enumerate() {
+            assert!(
+                hits.contains(&i),
+                "the quad {i} at {pixel} was not found from {camera:?}, found {hits:?}"
+            );
+            let rect = rect.unwrap();
+            assert!(
+                rect.center().distance(pixel) < 0.5,
+                "the rect {rect:?} of quad {i} is not centered at {pixel}"
+            );
+            let half_size = rect.half_size();
+            assert!(
+                (half_size - radius).abs().max_element() < 0.1 * radius,
+                "the rect {rect:?} of quad {i} from {camera:?} has a half size of {half_size} \
+                instead of {radius}"
+            );
+        }
+    }
+}
+
+#[test]
+fn declutters_under_rolled_cameras() {
+    for camera in rolled_cameras() {
+        let mut quads = Quads::default();
+        for (center, priority) in [
+            (Vec3::ZERO, 3),
+            // Overlapping the first quad on screen
+            (Vec3::new(0.4, 0.3, 0.0), 2),
+            (Vec3::new(3.0, -2.0, 0.0), 1),
+        ] {
+            quads.insert(Quad {
+                center,
+                half_extents: Vec3::new(0.5, 0.5, 0.0),
+                billboard: Billboard::ViewY,
+                priority,
+                ..default()
+            });
+        }
+        let (kept, hidden, flags) = record_last_frame(
+            camera,
+            quads,
+            |app, camera| {
+                app.insert_resource(QuadsDeclutter::new(camera));
+            },
+            |world| {
+                let declutter = world.resource::<QuadsDeclutter>();
+                let flags: Vec<_> = world
+                    .resource::<Quads>()
+                    .data
+                    .iter()
+                    .map(|quad| quad.hidden)
+                    .collect();
+                (declutter.kept(), declutter.hidden(), flags)
+            },
+        );
+        assert_eq!(
+            (kept, hidden),
+            (2, 1),
+            "{kept} quads were kept and {hidden} hidden from {camera:?}"
+        );
+        assert_eq!(flags, [false, true, false], "from {camera:?}");
+    }
+}
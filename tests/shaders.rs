@@ -0,0 +1,101 @@
+//! Composes and validates every curated specialization of the quads shader without a GPU, run
+//! with `cargo test --features test_support`
+
+use std::{collections::HashMap, path::Path};
+
+use bevy::core_pipeline::tonemapping::Tonemapping;
+use bevy_vertex_pulling::{
+    quads::{QuadsDebugView, QuadsDetailBlend, QuadsPipelineKey, MAX_QUAD_CORNERS},
+    test_support::*,
+};
+
+#[test]
+fn composes_every_pipeline_key() {
+    let mut composer = QuadsShaderComposer::new();
+    let errors: Vec<_> = quads_pipeline_keys()
+        .into_iter()
+        .filter_map(|key| composer.compose(key).err())
+        .map(|err| err.to_string())
+        .collect();
+    assert!(errors.is_empty(), "{}", errors.join("\n\n"));
+}
+
+/// One specialization per shader def on top of the MSAA default, so a snapshot diff points at
+/// the feature it changes
+fn snapshot_keys() -> Vec<(&'static str, QuadsPipelineKey)> {
+    let base = QuadsPipelineKey::from_msaa_samples(4);
+    vec![
+        ("default", base),
+        ("msaa_off", QuadsPipelineKey::from_msaa_samples(1)),
+        (
+            "debug_wireframe",
+            base | QuadsPipelineKey::from_debug_view(QuadsDebugView::Wireframe),
+        ),
+        (
+            "debug_overdraw",
+            base | QuadsPipelineKey::from_debug_view(QuadsDebugView::Overdraw),
+        ),
+        (
+            "debug_instance_index",
+            base | QuadsPipelineKey::from_debug_view(QuadsDebugView::InstanceIndex),
+        ),
+        (
+            "debug_normal",
+            base | QuadsPipelineKey::from_debug_view(QuadsDebugView::Normal),
+        ),
+        (
+            "debug_uv",
+            base | QuadsPipelineKey::from_debug_view(QuadsDebugView::Uv),
+        ),
+        (
+            "detail_multiply",
+            base | QuadsPipelineKey::from_detail_blend(QuadsDetailBlend::Multiply),
+        ),
+        (
+            "detail_overlay",
+            base | QuadsPipelineKey::from_detail_blend(QuadsDetailBlend::Overlay),
+        ),
+        ("near_fade", base | QuadsPipelineKey::NEAR_FADE),
+        ("camera_relative", base | QuadsPipelineKey::CAMERA_RELATIVE),
+        ("flow_field", base | QuadsPipelineKey::FLOW_FIELD),
+        ("heatmap", base | QuadsPipelineKey::HEATMAP),
+        (
+            "tonemap_deband_dither",
+            base | QuadsPipelineKey::TONEMAP_IN_SHADER
+                | QuadsPipelineKey::from_tonemapping(Tonemapping::TonyMcMapface)
+                | QuadsPipelineKey::DEBAND_DITHER,
+        ),
+        (
+            "corners",
+            base | QuadsPipelineKey::from_corners(MAX_QUAD_CORNERS),
+        ),
+        ("batch_animation", base | QuadsPipelineKey::BATCH_ANIMATION),
+        ("hsv_adjust", base | QuadsPipelineKey::HSV_ADJUST),
+        ("mask", base | QuadsPipelineKey::MASK),
+        (
+            "double_sided_translucency",
+            base | QuadsPipelineKey::DOUBLE_SIDED | QuadsPipelineKey::TRANSLUCENCY,
+        ),
+        ("blend_constant", base | QuadsPipelineKey::BLEND_CONSTANT),
+        ("dissolve", base | QuadsPipelineKey::DISSOLVE),
+        ("depth_of_field", base | QuadsPipelineKey::DEPTH_OF_FIELD),
+        (
+            "half_resolution",
+            QuadsPipelineKey::from_msaa_samples(1) | QuadsPipelineKey::HALF_RESOLUTION,
+        ),
+    ]
+}
+
+#[test]
+fn matches_the_shader_snapshots() {
+    let snapshots = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/shaders");
+    let mut composer = QuadsShaderComposer::new();
+    let mut names_by_source = HashMap::new();
+    for (name, key) in snapshot_keys() {
+        let wgsl = composer.compose(key).unwrap_or_else(|err| panic!("{err}"));
+        assert_shader_snapshot(&wgsl, snapshots.join(format!("quads_{name}.wgsl")));
+        if let Some(other) = names_by_source.insert(wgsl, name) {
+            panic!("the {name} snapshot is identical to the {other} snapshot");
+        }
+    }
+}
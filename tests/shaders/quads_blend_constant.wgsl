@@ -0,0 +1,922 @@
+@fragment 
+fn fragment(in: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberFragmentInput) -> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberFragmentOutput {
+    let _e1 = quad_color(in);
+    let _e2 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberfragment_output(in, _e1);
+    return _e2;
+}
+
+@group(0) @binding(0) 
+var<uniform> _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview: _naga_oil_mod_MJSXM6K7OJSW4ZDFOI5DU5TJMV3Q_memberView;
+
+@group(0) @binding(1) 
+var<uniform> _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberlights: _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberLights;
+
+@group(0) @binding(10) 
+var<uniform> _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberfog: _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberFog;
+
+@group(0) @binding(11) 
+var _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberscreen_space_ambient_occlusion_texture: texture_2d<f32>;
+
+@group(0) @binding(12) 
+var _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberenvironment_map_diffuse: texture_cube<f32>;
+
+@group(0) @binding(13) 
+var _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberenvironment_map_specular: texture_cube<f32>;
+
+@group(0) @binding(14) 
+var _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberenvironment_map_sampler: sampler;
+
+@group(0) @binding(15) 
+var _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberdt_lut_texture: texture_3d<f32>;
+
+@group(0) @binding(16) 
+var _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberdt_lut_sampler: sampler;
+
+@group(0) @binding(17) 
+var _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberdepth_prepass_texture: texture_depth_multisampled_2d;
+
+@group(0) @binding(18) 
+var _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_membernormal_prepass_texture: texture_multisampled_2d<f32>;
+
+@group(0) @binding(19) 
+var _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_membermotion_vector_prepass_texture: texture_multisampled_2d<f32>;
+
+@group(0) @binding(2) 
+var _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberpoint_shadow_textures: texture_depth_cube_array;
+
+@group(0) @binding(3) 
+var _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberpoint_shadow_textures_sampler: sampler_comparison;
+
+@group(0) @binding(4) 
+var _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberdirectional_shadow_textures: texture_depth_2d_array;
+
+@group(0) @binding(5) 
+var _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberdirectional_shadow_textures_sampler: sampler_comparison;
+
+@group(0) @binding(6) 
+var<storage> _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberpoint_lights: _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberPointLights;
+
+@group(0) @binding(7) 
+var<storage> _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_membercluster_light_index_lists: _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberClusterLightIndexLists;
+
+@group(0) @binding(8) 
+var<storage> _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_membercluster_offsets_and_counts: _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberClusterOffsetsAndCounts;
+
+@group(0) @binding(9) 
+var<uniform> _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberglobals: _naga_oil_mod_MJSXM6K7OJSW4ZDFOI5DUZ3MN5RGC3DT_memberGlobals;
+
+@group(1) @binding(0) 
+var<storage> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberquads: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuads;
+
+@group(1) @binding(1) 
+var _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberquads_texture: texture_2d<f32>;
+
+@group(1) @binding(10) 
+var<uniform> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberflow_field: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberFlowField;
+
+@group(1) @binding(11) 
+var<uniform> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberbatch_animation: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberBatchAnimation;
+
+@group(1) @binding(12) 
+var<uniform> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberglobal_alpha: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberGlobalAlpha;
+
+@group(1) @binding(13) 
+var _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberdissolve_noise_texture: texture_2d<f32>;
+
+@group(1) @binding(14) 
+var _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberdissolve_sampler: sampler;
+
+@group(1) @binding(15) 
+var<uniform> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberdissolve: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberDissolve;
+
+@group(1) @binding(2) 
+var _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberquads_sampler: sampler;
+
+@group(1) @binding(3) 
+var _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberdetail_texture: texture_2d<f32>;
+
+@group(1) @binding(4) 
+var _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberdetail_sampler: sampler;
+
+@group(1) @binding(5) 
+var<uniform> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_membernear_fade: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberNearFade;
+
+@group(1) @binding(6) 
+var<uniform> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberorigin: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberOrigin;
+
+@group(1) @binding(7) 
+var<uniform> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_membergroup_colors: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberGroupColors;
+
+@group(1) @binding(8) 
+var _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberflow_field_texture: texture_3d<f32>;
+
+@group(1) @binding(9) 
+var _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberflow_field_sampler: sampler;
+
+@vertex 
+fn vertex(@builtin(vertex_index) vertex_index: u32) -> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberVertexOutput {
+    var out: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberVertexOutput;
+    var quad: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuad;
+    let instance_index_1 = (vertex_index >> 2u);
+    let _e3 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5LOOBQWG2Y_memberload_quad(instance_index_1);
+    quad = _e3;
+    let _e6 = quad.flags;
+    if ((_e6 & _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_HIDDEN_BIT) != 0u) {
+        out.clip_position = vec4<f32>(0.0);
+        let _e15 = out;
+        return _e15;
+    }
+    let _e16 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_membercamera_origin();
+    let _e18 = quad.flags;
+    if ((_e18 & _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_CLIP_SPACE_BIT) == 0u) {
+        let _e26 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberorigin.high;
+        let _e30 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberorigin.low;
+        let _e33 = quad.center;
+        quad.center = (((_e26 - _e16) + _e30) + _e33);
+    }
+    let xyz = vec3<f32>(f32((vertex_index & 1u)), f32(((vertex_index & 2u) >> 1u)), 0.5);
+    out.uv = vec2<f32>(xyz.x, (1.0 - xyz.y));
+    let _e52 = quad.flags;
+    if ((_e52 & _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_FLIP_X_BIT) != 0u) {
+        let _e62 = out.uv.x;
+        out.uv.x = (1.0 - _e62);
+    }
+    let _e65 = quad.flags;
+    if ((_e65 & _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_FLIP_Y_BIT) != 0u) {
+        let _e75 = out.uv.y;
+        out.uv.y = (1.0 - _e75);
+    }
+    let relative_pos_unit = ((xyz * 2.0) - vec3<f32>(1.0));
+    let _e84 = quad.motion.x;
+    let _e87 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberglobals.time;
+    let spin = ((fract(((_e84 * _e87) / (2.0 * _naga_oil_mod_MJSXM6K7OBRHEOR2OV2GS3DT_memberPI))) * 2.0) * _naga_oil_mod_MJSXM6K7OBRHEOR2OV2GS3DT_memberPI);
+    let _e108 = quad.half_extents;
+    let corner_offset_1 = (mat2x2<f32>(vec2<f32>(cos(spin), sin(spin)), vec2<f32>(-(sin(spin)), cos(spin))) * (relative_pos_unit.xy * _e108.xy));
+    let _e112 = quad;
+    let _e113 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberbillboard(_e112, corner_offset_1, _e16);
+    out.clip_position = _e113.clip_position;
+    out.world_position = _e113.world_position;
+    out.world_normal = _e113.world_normal;
+    let _e122 = quad.color;
+    out.color = _e122;
+    let _e125 = quad.flags;
+    out.flags = _e125;
+    let _e129 = quad.half_extents.w;
+    out.uv_rotation = _e129;
+    let _e132 = quad.uv;
+    let _e135 = quad.uv;
+    let _e139 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberglobals.time;
+    out.uv_transform = vec4<f32>(_e132.xy, fract((_e135.zw * _e139)));
+    let _e145 = quad.specular;
+    out.specular = _e145;
+    let _e146 = out;
+    return _e146;
+}
+
+const DEBUG_OVERDRAW_WEIGHT: f32 = 0.05000000074505806;
+
+const DEBUG_WIREFRAME_WIDTH: f32 = 1.5;
+
+const _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberDIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32 = 1u;
+
+const _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberFOG_MODE_ATMOSPHERIC: u32 = 4u;
+
+const _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberFOG_MODE_EXPONENTIAL: u32 = 2u;
+
+const _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberFOG_MODE_EXPONENTIAL_SQUARED: u32 = 3u;
+
+const _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberFOG_MODE_LINEAR: u32 = 1u;
+
+const _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberFOG_MODE_OFF: u32 = 0u;
+
+const _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberPOINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32 = 1u;
+
+const _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberPOINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE: u32 = 2u;
+
+const _naga_oil_mod_MJSXM6K7OBRHEOR2OV2GS3DT_memberPI: f32 = 3.1415927410125732;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_AXIS_BIT: u32 = 2048u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_BIT: u32 = 1u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_COC_SCALED_BIT: u32 = 8192u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_FIXED_SCREEN_SIZE_BIT: u32 = 4u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_MIN_SCREEN_SIZE_BIT: u32 = 1024u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_VIEWPORT_FRACTION_BIT: u32 = 16u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_WORLD_Y_BIT: u32 = 2u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_CLIP_SPACE_BIT: u32 = 128u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_FLIP_X_BIT: u32 = 32u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_FLIP_Y_BIT: u32 = 64u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_FOREGROUND_BIT: u32 = 256u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_HIDDEN_BIT: u32 = 4096u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_LIT_BIT: u32 = 8u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_UV_WRAP_BIT: u32 = 512u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_GROUP_SHIFT: u32 = 24u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberFOREGROUND_DEPTH_SLICE: f32 = 0.10000000149011612;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberANIMATION_MODE_LOOP: u32 = 0u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberANIMATION_MODE_PING_PONG: u32 = 1u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberDEPTH_OF_FIELD_MODE_GAUSSIAN: u32 = 1u;
+
+const _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberQUAD_CURVE_MAX_KEYS: u32 = 8u;
+
+fn _naga_oil_mod_MJSXM6K7MNXXEZK7OBUXAZLMNFXGKOR2ORXW4ZLNMFYHA2LOM4_memberpowsafe(color: vec3<f32>, power: f32) -> vec3<f32> {
+    return (pow(abs(color), vec3<f32>(power)) * sign(color));
+}
+
+fn _naga_oil_mod_MJSXM6K7MNXXEZK7OBUXAZLMNFXGKOR2ORXW4ZLNMFYHA2LOM4_membersaturation(color_1: vec3<f32>, saturationAmount: f32) -> vec3<f32> {
+    let _e1 = _naga_oil_mod_MJSXM6K7MNXXEZK7OBUXAZLMNFXGKOR2ORXW4ZLNMFYHA2LOM4_membertonemapping_luminance(color_1);
+    return mix(vec3<f32>(_e1), color_1, vec3<f32>(saturationAmount));
+}
+
+fn _naga_oil_mod_MJSXM6K7MNXXEZK7OBUXAZLMNFXGKOR2ORXW4ZLNMFYHA2LOM4_memberscreen_space_dither(frag_coord_1: vec2<f32>) -> vec3<f32> {
+    var dither: vec3<f32>;
+    dither = vec3<f32>(dot(vec2<f32>(171.0, 231.0), frag_coord_1)).xxx;
+    let _e8 = dither;
+    dither = fract((_e8.xyz / vec3<f32>(103.0, 71.0, 97.0)));
+    let _e16 = dither;
+    return ((_e16 - vec3<f32>(0.5)) / vec3<f32>(255.0));
+}
+
+fn _naga_oil_mod_MJSXM6K7MNXXEZK7OBUXAZLMNFXGKOR2ORXW4ZLNMFYHA2LOM4_membertone_mapping(in_1: vec4<f32>, color_grading: _naga_oil_mod_MJSXM6K7OJSW4ZDFOI5DU5TJMV3Q_memberColorGrading) -> vec4<f32> {
+    var color_2: vec3<f32>;
+    color_2 = max(in_1.xyz, vec3<f32>(0.0));
+    let _e7 = color_2;
+    let _e9 = _naga_oil_mod_MJSXM6K7MNXXEZK7OBUXAZLMNFXGKOR2ORXW4ZLNMFYHA2LOM4_membersaturation(_e7, color_grading.pre_saturation);
+    color_2 = _e9;
+    let _e10 = color_2;
+    let _e12 = _naga_oil_mod_MJSXM6K7MNXXEZK7OBUXAZLMNFXGKOR2ORXW4ZLNMFYHA2LOM4_memberpowsafe(_e10, color_grading.gamma);
+    color_2 = _e12;
+    let _e13 = color_2;
+    let _e17 = _naga_oil_mod_MJSXM6K7MNXXEZK7OBUXAZLMNFXGKOR2ORXW4ZLNMFYHA2LOM4_memberpowsafe(vec3<f32>(2.0), color_grading.exposure);
+    color_2 = (_e13 * _e17);
+    let _e19 = color_2;
+    color_2 = max(_e19, vec3<f32>(0.0));
+    let _e23 = color_2;
+    let _e25 = _naga_oil_mod_MJSXM6K7MNXXEZK7OBUXAZLMNFXGKOR2ORXW4ZLNMFYHA2LOM4_membersaturation(_e23, color_grading.post_saturation);
+    color_2 = _e25;
+    let _e26 = color_2;
+    return vec4<f32>(_e26, in_1.w);
+}
+
+fn _naga_oil_mod_MJSXM6K7MNXXEZK7OBUXAZLMNFXGKOR2ORXW4ZLNMFYHA2LOM4_membertonemapping_luminance(v: vec3<f32>) -> f32 {
+    return dot(v, vec3<f32>(0.2125999927520752, 0.7152000069618225, 0.0722000002861023));
+}
+
+fn _naga_oil_mod_MJSXM6K7OBRHEOR2MNWHK43UMVZGKZC7MZXXE53BOJSA_memberfragment_cluster_index(frag_coord: vec2<f32>, view_z_1: f32, is_orthographic_1: bool) -> u32 {
+    let _e3 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.viewport;
+    let _e8 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberlights.cluster_factors;
+    let xy = vec2<u32>(floor(((frag_coord - _e3.xy) * _e8.xy)));
+    let _e15 = _naga_oil_mod_MJSXM6K7OBRHEOR2MNWHK43UMVZGKZC7MZXXE53BOJSA_memberview_z_to_z_slice(view_z_1, is_orthographic_1);
+    let _e20 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberlights.cluster_dimensions.x;
+    let _e27 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberlights.cluster_dimensions.z;
+    let _e33 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberlights.cluster_dimensions.w;
+    return min(((((xy.y * _e20) + xy.x) * _e27) + _e15), (_e33 - 1u));
+}
+
+fn _naga_oil_mod_MJSXM6K7OBRHEOR2MNWHK43UMVZGKZC7MZXXE53BOJSA_memberget_light_id(index: u32) -> u32 {
+    let _e4 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_membercluster_light_index_lists.data[index];
+    return _e4;
+}
+
+fn _naga_oil_mod_MJSXM6K7OBRHEOR2MNWHK43UMVZGKZC7MZXXE53BOJSA_memberunpack_offset_and_counts(cluster_index: u32) -> vec3<u32> {
+    let _e4 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_membercluster_offsets_and_counts.data[cluster_index];
+    return _e4.xyz;
+}
+
+fn _naga_oil_mod_MJSXM6K7OBRHEOR2MNWHK43UMVZGKZC7MZXXE53BOJSA_memberview_z_to_z_slice(view_z: f32, is_orthographic: bool) -> u32 {
+    var z_slice: u32;
+    z_slice = 0u;
+    if is_orthographic {
+        let _e7 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberlights.cluster_factors.z;
+        let _e12 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberlights.cluster_factors.w;
+        z_slice = u32(floor(((view_z - _e7) * _e12)));
+    } else {
+        let _e21 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberlights.cluster_factors.z;
+        let _e26 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberlights.cluster_factors.w;
+        z_slice = u32((((log(-(view_z)) * _e21) - _e26) + 1.0));
+    }
+    let _e31 = z_slice;
+    let _e35 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberlights.cluster_dimensions.z;
+    return min(_e31, (_e35 - 1u));
+}
+
+fn _naga_oil_mod_MJSXM6K7OBRHEOR2NRUWO2DUNFXGO_membergetDistanceAttenuation(distanceSquare: f32, inverseRangeSquared: f32) -> f32 {
+    let factor = (distanceSquare * inverseRangeSquared);
+    let smoothFactor = saturate((1.0 - (factor * factor)));
+    let attenuation = (smoothFactor * smoothFactor);
+    return ((attenuation * 1.0) / max(distanceSquare, 9.999999747378752e-5));
+}
+
+fn _naga_oil_mod_MJSXM6K7OBRHEOR2OV2GS3DT_memberhsv2rgb(hue: f32, saturation: f32, value: f32) -> vec3<f32> {
+    let rgb = clamp((abs((((vec3<f32>((hue * 6.0)) + vec3<f32>(0.0, 4.0, 2.0)) % vec3<f32>(6.0)) - vec3<f32>(3.0))) - vec3<f32>(1.0)), vec3<f32>(0.0), vec3<f32>(1.0));
+    return (value * mix(vec3<f32>(1.0), rgb, vec3<f32>(saturation)));
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberblinn_phong(N: vec3<f32>, V: vec3<f32>, L: vec3<f32>, radiance: vec3<f32>, albedo: vec3<f32>, specular: vec4<f32>, translucency: f32) -> vec3<f32> {
+    var response: vec3<f32>;
+    let NoL = saturate(dot(N, L));
+    response = (albedo * (NoL / _naga_oil_mod_MJSXM6K7OBRHEOR2OV2GS3DT_memberPI));
+    if (specular.w > 0.0) {
+        let H = normalize((L + V));
+        let _e23 = response;
+        response = (_e23 + (specular.xyz * (pow(saturate(dot(N, H)), specular.w) * NoL)));
+    }
+    let _e26 = response;
+    return (radiance * _e26);
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberfinish_color(in_4: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberFragmentInput, color_3: vec4<f32>) -> vec4<f32> {
+    var output_color: vec4<f32>;
+    output_color = color_3;
+    if ((in_4.flags & _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_LIT_BIT) != 0u) {
+        let _e9 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_membershade(in_4, color_3.xyz);
+        output_color = vec4<f32>(_e9, color_3.w);
+    }
+    let _e12 = output_color;
+    let _e15 = output_color.w;
+    let _e18 = output_color.w;
+    output_color = vec4<f32>((_e12.xyz * _e15), _e18);
+    let _e23 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberglobal_alpha.alpha;
+    let _e24 = output_color.w;
+    output_color.w = (_e24 * _e23);
+    let _e26 = output_color;
+    return _e26;
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberfragment_output(in_5: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberFragmentInput, color_4: vec4<f32>) -> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberFragmentOutput {
+    var out_2: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberFragmentOutput;
+    out_2.color = color_4;
+    let _e3 = out_2;
+    return _e3;
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberpoint_light(world_position: vec3<f32>, light_id: u32, N_1: vec3<f32>, V_1: vec3<f32>, albedo_1: vec3<f32>, specular_1: vec4<f32>, translucency_1: f32) -> vec3<f32> {
+    let light_1 = (&_naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberpoint_lights.data[light_id]);
+    let _e6 = (*light_1).position_radius;
+    let light_to_frag = (_e6.xyz - world_position);
+    let distance_square = dot(light_to_frag, light_to_frag);
+    let _e12 = (*light_1).color_inverse_square_range.w;
+    let _e13 = _naga_oil_mod_MJSXM6K7OBRHEOR2NRUWO2DUNFXGO_membergetDistanceAttenuation(distance_square, _e12);
+    let _e15 = (*light_1).color_inverse_square_range;
+    let radiance_1 = (_e15.xyz * _e13);
+    let _e24 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberblinn_phong(N_1, V_1, normalize(light_to_frag), radiance_1, albedo_1, specular_1, translucency_1);
+    return _e24;
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberrotate_uv(uv: vec2<f32>, rotation: f32) -> vec2<f32> {
+    let c = cos(rotation);
+    let s = sin(rotation);
+    return ((mat2x2<f32>(vec2<f32>(c, s), vec2<f32>(-(s), c)) * (uv - vec2<f32>(0.5))) + vec2<f32>(0.5));
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_membershade(in_3: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberFragmentInput, albedo_3: vec3<f32>) -> vec3<f32> {
+    var light: vec3<f32>;
+    var i: u32;
+    var i_1: u32;
+    var i_2: u32;
+    let N_3 = normalize(in_3.world_normal);
+    let _e5 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.world_position;
+    let V_3 = normalize((_e5 - in_3.world_position.xyz));
+    let _e13 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberlights.ambient_color;
+    light = (_e13.xyz * albedo_3);
+    i = 0u;
+    loop {
+        let _e19 = i;
+        let _e22 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberlights.n_directional_lights;
+        if (_e19 < _e22) {
+        } else {
+            break;
+        }
+        {
+            let _e26 = i;
+            let directional_light = (&_naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberlights.directional_lights[_e26]);
+            let _e29 = (*directional_light).direction_to_light;
+            let _e31 = (*directional_light).color;
+            let _e35 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberblinn_phong(N_3, V_3, _e29, _e31.xyz, albedo_3, in_3.specular, 0.0);
+            let _e36 = light;
+            light = (_e36 + _e35);
+        }
+        continuing {
+            let _e38 = i;
+            i = (_e38 + 1u);
+        }
+    }
+    let _e45 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.inverse_view[0][2];
+    let _e50 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.inverse_view[1][2];
+    let _e55 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.inverse_view[2][2];
+    let _e60 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.inverse_view[3][2];
+    let view_z_2 = dot(vec4<f32>(_e45, _e50, _e55, _e60), in_3.world_position);
+    let _e68 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.projection[3][3];
+    let is_orthographic_2 = (_e68 == 1.0);
+    let frag_coord_2 = in_3.frag_coord.xy;
+    let _e73 = _naga_oil_mod_MJSXM6K7OBRHEOR2MNWHK43UMVZGKZC7MZXXE53BOJSA_memberfragment_cluster_index(frag_coord_2, view_z_2, is_orthographic_2);
+    let _e74 = _naga_oil_mod_MJSXM6K7OBRHEOR2MNWHK43UMVZGKZC7MZXXE53BOJSA_memberunpack_offset_and_counts(_e73);
+    i_1 = _e74.x;
+    loop {
+        let _e77 = i_1;
+        if (_e77 < (_e74.x + _e74.y)) {
+        } else {
+            break;
+        }
+        {
+            let _e84 = i_1;
+            let _e85 = _naga_oil_mod_MJSXM6K7OBRHEOR2MNWHK43UMVZGKZC7MZXXE53BOJSA_memberget_light_id(_e84);
+            let _e87 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberpoint_light(in_3.world_position.xyz, _e85, N_3, V_3, albedo_3, in_3.specular, 0.0);
+            let _e88 = light;
+            light = (_e88 + _e87);
+        }
+        continuing {
+            let _e90 = i_1;
+            i_1 = (_e90 + 1u);
+        }
+    }
+    let spot_start = (_e74.x + _e74.y);
+    i_2 = spot_start;
+    loop {
+        let _e97 = i_2;
+        if (_e97 < (spot_start + _e74.z)) {
+        } else {
+            break;
+        }
+        {
+            let _e103 = i_2;
+            let _e104 = _naga_oil_mod_MJSXM6K7OBRHEOR2MNWHK43UMVZGKZC7MZXXE53BOJSA_memberget_light_id(_e103);
+            let _e106 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberspot_light(in_3.world_position.xyz, _e104, N_3, V_3, albedo_3, in_3.specular, 0.0);
+            let _e107 = light;
+            light = (_e107 + _e106);
+        }
+        continuing {
+            let _e109 = i_2;
+            i_2 = (_e109 + 1u);
+        }
+    }
+    let _e112 = light;
+    return _e112;
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberspot_light(world_position_1: vec3<f32>, light_id_1: u32, N_2: vec3<f32>, V_2: vec3<f32>, albedo_2: vec3<f32>, specular_2: vec4<f32>, translucency_2: f32) -> vec3<f32> {
+    var spot_dir: vec3<f32>;
+    let _e7 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberpoint_light(world_position_1, light_id_1, N_2, V_2, albedo_2, specular_2, translucency_2);
+    let light_2 = (&_naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberpoint_lights.data[light_id_1]);
+    let _e13 = (*light_2).light_custom_data.x;
+    let _e17 = (*light_2).light_custom_data.y;
+    spot_dir = vec3<f32>(_e13, 0.0, _e17);
+    let _e24 = spot_dir.x;
+    let _e26 = spot_dir.x;
+    let _e30 = spot_dir.z;
+    let _e32 = spot_dir.z;
+    spot_dir.y = sqrt(max(0.0, ((1.0 - (_e24 * _e26)) - (_e30 * _e32))));
+    let _e38 = (*light_2).flags;
+    if ((_e38 & _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberPOINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE) != 0u) {
+        let _e45 = spot_dir.y;
+        spot_dir.y = -(_e45);
+    }
+    let _e48 = (*light_2).position_radius;
+    let light_to_frag_1 = (_e48.xyz - world_position_1);
+    let _e51 = spot_dir;
+    let cd = dot(-(_e51), normalize(light_to_frag_1));
+    let _e57 = (*light_2).light_custom_data.z;
+    let _e61 = (*light_2).light_custom_data.w;
+    let attenuation_1 = saturate(((cd * _e57) + _e61));
+    return ((_e7 * attenuation_1) * attenuation_1);
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_membertexture_uv(in_2: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberFragmentInput) -> vec2<f32> {
+    let _e3 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberrotate_uv(in_2.uv, in_2.uv_rotation);
+    return ((_e3 * in_2.uv_transform.xy) + in_2.uv_transform.zw);
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberwrap_texture_uv(flags: u32, uv_1: vec2<f32>) -> vec2<f32> {
+    return select(uv_1, fract(uv_1), ((flags & _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_UV_WRAP_BIT) != 0u));
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5LOOBQWG2Y_memberload_quad(instance_index: u32) -> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuad {
+    var quad_7: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuad;
+    let _e4 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberquads.data[instance_index];
+    quad_7 = _e4;
+    let _e8 = quad_7.half_extents;
+    let _e10 = quad_7;
+    let _e11 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5LOOBQWG2Y_memberunpack_scale(_e10);
+    let _e15 = quad_7.half_extents.w;
+    quad_7.half_extents = vec4<f32>((_e8.xyz * _e11), _e15);
+    let _e17 = quad_7;
+    let _e18 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5LOOBQWG2Y_memberunpack_group(_e17);
+    let _e25 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_membergroup_colors.colors[_e18].w;
+    if ((_e18 != 0u) && (_e25 >= 0.0)) {
+        let _e33 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_membergroup_colors.colors[_e18];
+        quad_7.color = _e33;
+    }
+    let _e34 = quad_7;
+    return _e34;
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5LOOBQWG2Y_memberunpack_dissolve(quad_2: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuad) -> vec3<f32> {
+    let offset_1 = (vec2<f32>(f32(((quad_2.adjust.w >> 16u) & 255u)), f32((quad_2.adjust.w >> 24u))) / vec2<f32>(256.0));
+    return vec3<f32>(unpack2x16float(quad_2.adjust.w).x, offset_1);
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5LOOBQWG2Y_memberunpack_group(quad_6: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuad) -> u32 {
+    return (quad_6.flags >> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_GROUP_SHIFT);
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5LOOBQWG2Y_memberunpack_hsv_shift(quad_1: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuad) -> vec3<f32> {
+    return vec3<f32>(unpack2x16float(quad_1.adjust.x), unpack2x16float(quad_1.adjust.y).x);
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5LOOBQWG2Y_memberunpack_mask(quad_3: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuad) -> u32 {
+    return (quad_3.adjust.z & 255u);
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5LOOBQWG2Y_memberunpack_scale(quad_5: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuad) -> f32 {
+    return (unpack2x16float(quad_5.adjust.y).y + 1.0);
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5LOOBQWG2Y_memberunpack_translucency(quad_4: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuad) -> f32 {
+    return (f32(((quad_4.adjust.z >> 8u) & 255u)) / 255.0);
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberbillboard(quad_8: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuad, corner_offset: vec2<f32>, camera: vec3<f32>) -> _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberBillboardVertex {
+    var out_1: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberBillboardVertex;
+    var relative_pos: vec3<f32>;
+    var right: vec3<f32>;
+    var up: vec3<f32>;
+    var scale: f32;
+    var pixel_offset: vec2<f32>;
+    var ndc_offset: vec2<f32>;
+    if ((quad_8.flags & _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_CLIP_SPACE_BIT) != 0u) {
+        out_1.clip_position = vec4<f32>((quad_8.center.xy + corner_offset), quad_8.center.z, 1.0);
+        let _e19 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.inverse_view_proj;
+        let _e21 = out_1.clip_position;
+        out_1.world_position = (_e19 * _e21);
+        let _e25 = out_1.world_position;
+        let _e28 = out_1.world_position.w;
+        out_1.world_position = (_e25 / vec4<f32>(_e28));
+        let _e35 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.view[2];
+        out_1.world_normal = normalize(_e35.xyz);
+    } else {
+        if ((quad_8.flags & _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_BIT) != 0u) {
+            let _e46 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.view[0];
+            right = normalize(_e46.xyz);
+            if ((quad_8.flags & _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_AXIS_BIT) != 0u) {
+                up = quad_8.motion.yzw;
+                let _e58 = up;
+                let _e62 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.world_position;
+                let axis_right = cross(_e58, ((_e62 - camera) - quad_8.center));
+                if (dot(axis_right, axis_right) > 9.999999960041972e-13) {
+                    right = normalize(axis_right);
+                } else {
+                    let _e71 = right;
+                    let _e72 = up;
+                    let _e73 = right;
+                    let _e74 = up;
+                    right = normalize((_e71 - (_e72 * dot(_e73, _e74))));
+                }
+                let _e80 = right;
+                let _e81 = up;
+                out_1.world_normal = cross(_e80, _e81);
+            } else {
+                if ((quad_8.flags & _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_WORLD_Y_BIT) != 0u) {
+                    up = vec3<f32>(0.0, 1.0, 0.0);
+                    let _e94 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.world_position;
+                    let to_camera = ((_e94 - camera) - quad_8.center);
+                    let _e99 = right.x;
+                    let _e102 = right.z;
+                    let horizontal_right = vec3<f32>(_e99, 0.0, _e102);
+                    if (dot(horizontal_right, horizontal_right) > 9.999999960041972e-13) {
+                        right = normalize(horizontal_right);
+                    } else {
+                        let _e108 = up;
+                        right = normalize(cross(_e108, to_camera));
+                    }
+                    let _e112 = right;
+                    let _e113 = up;
+                    out_1.world_normal = cross(_e112, _e113);
+                    let _e116 = out_1.world_normal;
+                    if (dot(_e116, to_camera) < 0.0) {
+                        let _e120 = right;
+                        right = -(_e120);
+                        let _e124 = out_1.world_normal;
+                        out_1.world_normal = -(_e124);
+                    }
+                } else {
+                    let _e128 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.world_position;
+                    let to_camera_1 = ((_e128 - camera) - quad_8.center);
+                    let _e136 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.view[2];
+                    out_1.world_normal = select(normalize(_e136.xyz), normalize(to_camera_1), (dot(to_camera_1, to_camera_1) > 9.999999960041972e-13));
+                    let _e147 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.view[1];
+                    up = normalize(_e147.xyz);
+                }
+            }
+            scale = 1.0;
+            if (((quad_8.flags & _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_MIN_SCREEN_SIZE_BIT) != 0u) && (quad_8.half_extents.y > 0.0)) {
+                let _e166 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.viewport.w;
+                let _e172 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.projection[1][1];
+                let _e175 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberposition_to_clip(quad_8.center);
+                let pixels_per_unit = (((0.5 * _e166) * _e172) / _e175.w);
+                scale = max(1.0, (quad_8.half_extents.z / (quad_8.half_extents.y * pixels_per_unit)));
+            }
+            let _e186 = right;
+            let _e189 = up;
+            let _e193 = scale;
+            relative_pos = (((_e186 * corner_offset.x) + (_e189 * corner_offset.y)) * _e193);
+            let _e198 = relative_pos;
+            let _e200 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberposition_to_clip((quad_8.center + _e198));
+            let _e203 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberoffset_depth(_e200, quad_8.detail.w);
+            out_1.clip_position = _e203;
+            let _e206 = relative_pos;
+            out_1.world_position = vec4<f32>(((quad_8.center + _e206) + camera), 1.0);
+        } else {
+            if ((quad_8.flags & (_naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_FIXED_SCREEN_SIZE_BIT | _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_VIEWPORT_FRACTION_BIT)) != 0u) {
+                let _e220 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberposition_to_clip(quad_8.center);
+                let _e223 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberoffset_depth(_e220, quad_8.detail.w);
+                out_1.clip_position = _e223;
+                pixel_offset = corner_offset;
+                let _e227 = out_1.clip_position;
+                let _e230 = out_1.clip_position.w;
+                out_1.clip_position = (_e227 / vec4<f32>(_e230));
+                if ((quad_8.flags & _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_BILLBOARD_VIEWPORT_FRACTION_BIT) != 0u) {
+                    let _e243 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.viewport.w;
+                    let _e247 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.viewport.z;
+                    ndc_offset = ((2.0 * corner_offset) * vec2<f32>((_e243 / _e247), 1.0));
+                } else {
+                    let _e254 = pixel_offset;
+                    let _e258 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.viewport;
+                    ndc_offset = ((2.0 * _e254) / _e258.zw);
+                }
+                let _e265 = out_1.clip_position.x;
+                let _e267 = ndc_offset.x;
+                out_1.clip_position.x = (_e265 + _e267);
+                let _e273 = out_1.clip_position.y;
+                let _e275 = ndc_offset.y;
+                out_1.clip_position.y = (_e273 + _e275);
+                let _e280 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.inverse_view_proj;
+                let _e282 = out_1.clip_position;
+                out_1.world_position = (_e280 * _e282);
+                let _e286 = out_1.world_position;
+                let _e289 = out_1.world_position.w;
+                out_1.world_position = (_e286 / vec4<f32>(_e289));
+                let _e295 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.world_position;
+                out_1.world_normal = normalize(((_e295 - camera) - quad_8.center));
+            } else {
+                out_1.world_normal = vec3<f32>(0.0, 0.0, 1.0);
+                relative_pos = vec3<f32>(corner_offset, 0.0);
+                let _e309 = relative_pos;
+                let _e311 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberposition_to_clip((quad_8.center + _e309));
+                let _e314 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberoffset_depth(_e311, quad_8.detail.w);
+                out_1.clip_position = _e314;
+                let _e317 = relative_pos;
+                out_1.world_position = vec4<f32>(((quad_8.center + _e317) + camera), 1.0);
+            }
+        }
+    }
+    if ((quad_8.flags & _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQUAD_FLAG_FOREGROUND_BIT) != 0u) {
+        let _e331 = out_1.clip_position.w;
+        let _e334 = out_1.clip_position.z;
+        out_1.clip_position.z = mix(_e331, _e334, _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberFOREGROUND_DEPTH_SLICE);
+    }
+    let _e337 = out_1;
+    return _e337;
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_membercamera_origin() -> vec3<f32> {
+    return vec3<f32>(0.0);
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberoffset_depth(clip_position: vec4<f32>, offset: f32) -> vec4<f32> {
+    let _e5 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.projection[2];
+    let nudged = (clip_position + (_e5 * offset));
+    return vec4<f32>(clip_position.xy, ((nudged.z / nudged.w) * clip_position.w), clip_position.w);
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberposition_to_clip(position_1: vec3<f32>) -> vec4<f32> {
+    let _e2 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.view_proj;
+    return (_e2 * vec4<f32>(position_1, 1.0));
+}
+
+fn _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberposition_to_view(position: vec3<f32>) -> vec3<f32> {
+    let _e2 = _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527MJUW4ZDJNZTXG_memberview.inverse_view;
+    return (_e2 * vec4<f32>(position, 1.0)).xyz;
+}
+
+fn hash_color(value_1: u32) -> vec3<f32> {
+    var x: u32;
+    x = value_1;
+    let _e2 = x;
+    let _e5 = x;
+    x = (((_e2 >> 16u) ^ _e5) * 73244475u);
+    let _e9 = x;
+    let _e12 = x;
+    x = (((_e9 >> 16u) ^ _e12) * 73244475u);
+    let _e16 = x;
+    let _e19 = x;
+    x = ((_e16 >> 16u) ^ _e19);
+    let _e21 = x;
+    let _e25 = x;
+    let _e31 = x;
+    return (vec3<f32>(0.20000000298023224) + ((0.800000011920929 * vec3<f32>(f32((_e21 & 255u)), f32(((_e25 >> 8u) & 255u)), f32(((_e31 >> 16u) & 255u)))) / vec3<f32>(255.0)));
+}
+
+fn quad_color(in_6: _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberFragmentInput) -> vec4<f32> {
+    var color_5: vec4<f32>;
+    let _e1 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_membertexture_uv(in_6);
+    let _e4 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberwrap_texture_uv(in_6.flags, _e1);
+    let _e7 = dpdx(_e1);
+    let _e8 = dpdy(_e1);
+    let _e9 = textureSampleGrad(_naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberquads_texture, _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberquads_sampler, _e4, _e7, _e8);
+    color_5 = (in_6.color * _e9);
+    let _e12 = color_5;
+    let _e13 = _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU43IMFSGS3TH_memberfinish_color(in_6, _e12);
+    return _e13;
+}
+
+struct _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberClusterLightIndexLists {
+    data: array<u32>,
+}
+
+struct _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberClusterOffsetsAndCounts {
+    data: array<vec4<u32>>,
+}
+
+struct _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberDirectionalCascade {
+    view_projection: mat4x4<f32>,
+    texel_size: f32,
+    far_bound: f32,
+}
+
+struct _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberDirectionalLight {
+    cascades: array<_naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberDirectionalCascade,4>,
+    color: vec4<f32>,
+    direction_to_light: vec3<f32>,
+    flags: u32,
+    shadow_depth_bias: f32,
+    shadow_normal_bias: f32,
+    num_cascades: u32,
+    cascades_overlap_proportion: f32,
+    depth_texture_base_index: u32,
+}
+
+struct _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberFog {
+    base_color: vec4<f32>,
+    directional_light_color: vec4<f32>,
+    be: vec3<f32>,
+    directional_light_exponent: f32,
+    bi: vec3<f32>,
+    mode: u32,
+}
+
+struct _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberLights {
+    directional_lights: array<_naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberDirectionalLight,10u>,
+    ambient_color: vec4<f32>,
+    cluster_dimensions: vec4<u32>,
+    cluster_factors: vec4<f32>,
+    n_directional_lights: u32,
+    spot_light_shadowmap_offset: i32,
+    environment_map_smallest_specular_mip_level: u32,
+}
+
+struct _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberPointLight {
+    light_custom_data: vec4<f32>,
+    color_inverse_square_range: vec4<f32>,
+    position_radius: vec4<f32>,
+    flags: u32,
+    shadow_depth_bias: f32,
+    shadow_normal_bias: f32,
+    spot_light_tan_angle: f32,
+}
+
+struct _naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberPointLights {
+    data: array<_naga_oil_mod_MJSXM6K7OBRHEOR2NVSXG2C7OZUWK527OR4XAZLT_memberPointLight>,
+}
+
+struct _naga_oil_mod_MJSXM6K7OJSW4ZDFOI5DU5TJMV3Q_memberColorGrading {
+    exposure: f32,
+    gamma: f32,
+    pre_saturation: f32,
+    post_saturation: f32,
+}
+
+struct _naga_oil_mod_MJSXM6K7OJSW4ZDFOI5DU5TJMV3Q_memberView {
+    view_proj: mat4x4<f32>,
+    unjittered_view_proj: mat4x4<f32>,
+    inverse_view_proj: mat4x4<f32>,
+    view: mat4x4<f32>,
+    inverse_view: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    inverse_projection: mat4x4<f32>,
+    world_position: vec3<f32>,
+    viewport: vec4<f32>,
+    color_grading: _naga_oil_mod_MJSXM6K7OJSW4ZDFOI5DU5TJMV3Q_memberColorGrading,
+    mip_bias: f32,
+}
+
+struct _naga_oil_mod_MJSXM6K7OJSW4ZDFOI5DUZ3MN5RGC3DT_memberGlobals {
+    time: f32,
+    delta_time: f32,
+    frame_count: u32,
+}
+
+struct _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberFragmentInput {
+    @builtin(front_facing) is_front: bool,
+    @builtin(position) frag_coord: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+    @location(3) color: vec4<f32>,
+    @location(4) @interpolate(flat) flags: u32,
+    @location(5) @interpolate(flat) uv_rotation: f32,
+    @location(6) @interpolate(flat) specular: vec4<f32>,
+    @location(9) @interpolate(flat) uv_transform: vec4<f32>,
+}
+
+struct _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberFragmentOutput {
+    @location(0) color: vec4<f32>,
+}
+
+struct _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuad {
+    center: vec3<f32>,
+    flags: u32,
+    half_extents: vec4<f32>,
+    color: vec4<f32>,
+    specular: vec4<f32>,
+    detail: vec4<f32>,
+    uv: vec4<f32>,
+    motion: vec4<f32>,
+    adjust: vec4<u32>,
+}
+
+struct _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuads {
+    data: array<_naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberQuad>,
+}
+
+struct _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DU5DZOBSXG_memberVertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+    @location(3) color: vec4<f32>,
+    @location(4) @interpolate(flat) flags: u32,
+    @location(5) @interpolate(flat) uv_rotation: f32,
+    @location(6) @interpolate(flat) specular: vec4<f32>,
+    @location(9) @interpolate(flat) uv_transform: vec4<f32>,
+}
+
+struct _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNRWGE33BOJSA_memberBillboardVertex {
+    clip_position: vec4<f32>,
+    world_position: vec4<f32>,
+    world_normal: vec3<f32>,
+}
+
+struct _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberBatchAnimation {
+    keys: array<vec4<f32>,32>,
+    key_counts: vec4<u32>,
+    mode: u32,
+    start_time: f32,
+    duration: f32,
+    premultiplied: u32,
+}
+
+struct _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberDissolve {
+    edge_color: vec4<f32>,
+    edge_width: f32,
+    noise_scale: f32,
+}
+
+struct _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberFlowField {
+    scroll: vec3<f32>,
+    strength: f32,
+    scale: f32,
+}
+
+struct _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberGlobalAlpha {
+    alpha: f32,
+}
+
+struct _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberGroupColors {
+    colors: array<vec4<f32>,256>,
+}
+
+struct _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberNearFade {
+    start: f32,
+    end: f32,
+}
+
+struct _naga_oil_mod_MJSXM6K7OZSXE5DFPBPXA5LMNRUW4ZZ2HJYXKYLEOM5DUYTJNZSGS3THOM_memberOrigin {
+    high: vec3<f32>,
+    low: vec3<f32>,
+}